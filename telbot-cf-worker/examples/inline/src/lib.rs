@@ -0,0 +1,52 @@
+use telbot_cf_worker::inline::InlineAnswerWriter;
+use telbot_cf_worker::types::query::{InlineQueryResultKind, InputMessageContent};
+use telbot_cf_worker::types::update::{Update, UpdateKind};
+use worker::*;
+
+mod utils;
+
+#[event(fetch)]
+pub async fn main(req: Request, env: Env) -> Result<Response> {
+    utils::set_panic_hook();
+
+    let router = Router::new();
+
+    router
+        .post_async("/", |mut req, _| async move {
+            let update = req.json::<Update>().await.unwrap();
+            if let UpdateKind::InlineQuery { inline_query } = update.kind {
+                let start: usize = inline_query.offset.parse().unwrap_or(0);
+                let mut answer = InlineAnswerWriter::new(&inline_query.id)
+                    .unwrap()
+                    .with_next_offset((start + 50).to_string());
+                // Results are serialized one by one, so they don't pile up in memory.
+                for index in start.. {
+                    let text = format!("{} #{}", inline_query.query, index);
+                    let result = InlineQueryResultKind::Article {
+                        title: text.clone(),
+                        input_message_content: InputMessageContent::Text {
+                            message_text: text,
+                            parse_mode: None,
+                            entities: None,
+                            disable_web_page_preview: None,
+                        },
+                        url: None,
+                        hide_url: None,
+                        description: None,
+                        thumb_url: None,
+                        thumb_width: None,
+                        thumb_height: None,
+                    }
+                    .with_id(index.to_string());
+                    if !answer.push(&result).unwrap() {
+                        break;
+                    }
+                }
+                // Answer the query in the webhook response.
+                return answer.finish().map_err(|e| format!("{:?}", e).into());
+            }
+            Response::empty()
+        })
+        .run(req, env)
+        .await
+}
@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+
+use telbot_types::query::{InlineQueryResult, MAX_INLINE_QUERY_RESULTS};
+use worker::Response;
+
+use crate::Result;
+
+/// Builds an `answerInlineQuery` webhook response, serializing results one at a time.
+///
+/// Each result is serialized as soon as it is pushed, so it can be dropped right away
+/// and the work can be stopped early when the worker is about to exceed its CPU limit.
+/// At most [`MAX_INLINE_QUERY_RESULTS`] results are written.
+pub struct InlineAnswerWriter {
+    body: String,
+    ids: HashSet<String>,
+    cache_time: Option<u32>,
+    is_personal: bool,
+    next_offset: Option<String>,
+}
+
+impl InlineAnswerWriter {
+    /// Creates a new writer answering to the given inline query.
+    pub fn new(query_id: impl AsRef<str>) -> Result<Self> {
+        let query_id =
+            serde_json::to_string(query_id.as_ref()).map_err(Into::<worker::Error>::into)?;
+        Ok(Self {
+            body: format!(
+                r#"{{"method":"answerInlineQuery","inline_query_id":{},"results":["#,
+                query_id
+            ),
+            ids: HashSet::new(),
+            cache_time: None,
+            is_personal: false,
+            next_offset: None,
+        })
+    }
+
+    /// Sets cache time.
+    pub fn with_cache_time(self, cache_time: u32) -> Self {
+        Self {
+            cache_time: Some(cache_time),
+            ..self
+        }
+    }
+
+    /// Sets the results to be cached on the server side only for the user.
+    pub fn personal(self) -> Self {
+        Self {
+            is_personal: true,
+            ..self
+        }
+    }
+
+    /// Sets next offset string.
+    pub fn with_next_offset(self, offset: impl Into<String>) -> Self {
+        Self {
+            next_offset: Some(offset.into()),
+            ..self
+        }
+    }
+
+    /// Serializes a result into the response body.
+    ///
+    /// Returns `false` without writing if the answer is already full,
+    /// or a result with the same identifier is already written.
+    pub fn push(&mut self, result: &InlineQueryResult) -> Result<bool> {
+        if self.is_full() || self.ids.contains(&result.id) {
+            return Ok(false);
+        }
+        let serialized = serde_json::to_string(result).map_err(Into::<worker::Error>::into)?;
+        if !self.ids.is_empty() {
+            self.body.push(',');
+        }
+        self.body.push_str(&serialized);
+        self.ids.insert(result.id.clone());
+        Ok(true)
+    }
+
+    /// `true` if no more results can be written.
+    pub fn is_full(&self) -> bool {
+        self.ids.len() >= MAX_INLINE_QUERY_RESULTS
+    }
+
+    /// Gets the number of written results.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// `true` if no result is written.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Finishes the answer and creates a webhook response carrying it.
+    pub fn finish(mut self) -> Result<Response> {
+        self.body.push(']');
+        if let Some(cache_time) = self.cache_time {
+            self.body
+                .push_str(&format!(r#","cache_time":{}"#, cache_time));
+        }
+        if self.is_personal {
+            self.body.push_str(r#","is_personal":true"#);
+        }
+        if let Some(next_offset) = self.next_offset {
+            let next_offset =
+                serde_json::to_string(&next_offset).map_err(Into::<worker::Error>::into)?;
+            self.body
+                .push_str(&format!(r#","next_offset":{}"#, next_offset));
+        }
+        self.body.push('}');
+
+        let mut response = Response::ok(self.body)?;
+        response
+            .headers_mut()
+            .set("Content-Type", "application/json")?;
+        Ok(response)
+    }
+}
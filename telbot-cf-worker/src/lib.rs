@@ -1,21 +1,82 @@
+#[cfg(feature = "inline")]
+pub mod inline;
+
 use std::io::Read;
+use std::time::Duration;
 
 use multipart::client::lazy::Multipart;
+use serde::de::DeserializeOwned;
 pub use telbot_types as types;
-use telbot_types::{ApiResponse, FileMethod, JsonMethod, TelegramError, TelegramMethod};
+use telbot_types::bot::BotToken;
+use telbot_types::chat::ChatPhoto;
+use telbot_types::file::{DownloadCheck, File, GetFile, IntegrityError};
+use telbot_types::form::{to_form_fields, FormValue};
+use telbot_types::webhook::WebhookReply;
+use telbot_types::{
+    ApiErrorKind, ApiResponse, FileMethod, JsonMethod, RequestOptions, RetryPolicy, TelegramClient,
+    TelegramError,
+};
+use worker::js_sys::{Function, Promise, Reflect};
 use worker::wasm_bindgen::JsValue;
+use worker::wasm_bindgen_futures::JsFuture;
 use worker::{Fetch, Headers, Request, RequestInit, Response};
 
 #[derive(Clone)]
 pub struct Api {
     base_url: String,
+    file_base_url: String,
+    default_headers: Vec<(String, String)>,
+    retry: Option<RetryPolicy>,
 }
 
 impl Api {
-    pub fn new(token: impl AsRef<str>) -> Self {
+    pub fn new(token: impl Into<BotToken>) -> Self {
+        Self::new_with_server(token, "https://api.telegram.org")
+    }
+
+    /// Creates a new API requester which sends requests to the server, like `http://localhost:8081`,
+    /// e.g. a [local Bot API server](https://github.com/tdlib/telegram-bot-api).
+    pub fn new_with_server(token: impl Into<BotToken>, server: &str) -> Self {
+        let token = token.into();
+        let server = server.trim_end_matches('/');
+        Self {
+            base_url: format!("{}/bot{}/", server, token.as_str()),
+            file_base_url: format!("{}/file/bot{}/", server, token.as_str()),
+            default_headers: vec![],
+            retry: None,
+        }
+    }
+
+    /// Sets HTTP headers sent with every request, including file downloads.
+    pub fn with_default_headers<K: Into<String>, V: Into<String>>(
+        self,
+        headers: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        Self {
+            default_headers: headers
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Retries requests which failed with flood control or network errors, following the policy.
+    ///
+    /// Keep the delays short, since the worker is billed and limited by its running time.
+    pub fn with_retry(self, policy: RetryPolicy) -> Self {
         Self {
-            base_url: format!("https://api.telegram.org/bot{}/", token.as_ref()),
+            retry: Some(policy),
+            ..self
+        }
+    }
+
+    fn headers(&self, options: &RequestOptions) -> Result<Headers> {
+        let mut headers = Headers::new();
+        for (name, value) in self.default_headers.iter().chain(&options.headers) {
+            headers.set(name, value)?;
         }
+        Ok(headers)
     }
 }
 
@@ -24,6 +85,7 @@ pub enum Error {
     TelegramError(TelegramError),
     Worker(worker::Error),
     Io(std::io::Error),
+    Integrity(IntegrityError),
 }
 
 impl From<worker::Error> for Error {
@@ -40,10 +102,44 @@ impl From<std::io::Error> for Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Creates a webhook response performing the given method.
+///
+/// The result of the method is not available, but a round trip to Telegram is saved.
+pub fn respond_with<Method: JsonMethod>(method: &Method) -> Result<Response> {
+    let body = WebhookReply::new(method)
+        .and_then(|reply| serde_json::to_string(&reply))
+        .map_err(Into::<worker::Error>::into)?;
+    let mut response = Response::ok(body)?;
+    response
+        .headers_mut()
+        .set("Content-Type", "application/json")?;
+    Ok(response)
+}
+
+impl Error {
+    /// Gets the kind of the error returned by Telegram, if any.
+    pub fn api_error_kind(&self) -> Option<ApiErrorKind> {
+        match self {
+            Self::TelegramError(error) => Some(error.kind()),
+            _ => None,
+        }
+    }
+}
+
 impl Api {
     /// Send a JSON-serializable API request
     pub async fn send_json<Method: JsonMethod>(&self, method: &Method) -> Result<Method::Response> {
-        let mut headers = Headers::new();
+        self.send_json_with(method, &RequestOptions::default())
+            .await
+    }
+
+    /// Send a JSON-serializable API request with extra headers and query parameters.
+    pub async fn send_json_with<Method: JsonMethod>(
+        &self,
+        method: &Method,
+        options: &RequestOptions,
+    ) -> Result<Method::Response> {
+        let mut headers = self.headers(options)?;
         headers.set("Content-Type", "application/json")?;
         let mut request = RequestInit::new();
         let payload = serde_json::to_string(&method).map_err(Into::<worker::Error>::into)?;
@@ -52,35 +148,40 @@ impl Api {
             .with_body(Some(JsValue::from_str(&payload)))
             .with_headers(headers);
 
-        let response = Fetch::Request(Request::new_with_init(
-            &format!("{}{}", self.base_url, Method::name()),
+        self.send_request(
+            &options.url_with_query(&format!("{}{}", self.base_url, Method::name())),
             &request,
-        )?)
-        .send()
-        .await?;
-
-        Self::parse_response::<Method>(response).await
+        )
+        .await
     }
 
     /// Send a JSON-serializable API request with files.
     pub async fn send_file<Method: FileMethod>(&self, method: &Method) -> Result<Method::Response> {
+        self.send_file_with(method, &RequestOptions::default())
+            .await
+    }
+
+    /// Send a JSON-serializable API request with files, extra headers and query parameters.
+    pub async fn send_file_with<Method: FileMethod>(
+        &self,
+        method: &Method,
+        options: &RequestOptions,
+    ) -> Result<Method::Response> {
         let mut request = RequestInit::new();
-        let value = serde_json::to_value(method).map_err(Into::<worker::Error>::into)?;
-        let files = method.files();
+        let fields = to_form_fields(method).map_err(Into::<worker::Error>::into)?;
         let mut multipart = Multipart::new();
-        for (key, value) in value.as_object().unwrap() {
-            if let Some(file) = files.as_ref().and_then(|map| map.get(key.as_str())) {
-                multipart.add_stream(
-                    key,
-                    &file.data[..],
-                    Some(&file.name),
-                    Some(file.mime.parse().unwrap()),
-                );
-            } else {
-                if let Some(str) = value.as_str() {
-                    multipart.add_text(key, str);
-                } else {
-                    multipart.add_text(key, value.to_string());
+        for (key, value) in &fields {
+            match value {
+                FormValue::Text(text) => {
+                    multipart.add_text(key.as_str(), text.as_str());
+                }
+                FormValue::File(file) => {
+                    multipart.add_stream(
+                        key.as_str(),
+                        &file.data[..],
+                        Some(file.name.as_str()),
+                        Some(file.mime.parse().unwrap()),
+                    );
                 }
             }
         }
@@ -88,7 +189,7 @@ impl Api {
         let mut buf = vec![];
         payload.read_to_end(&mut buf)?;
 
-        let mut headers = Headers::new();
+        let mut headers = self.headers(options)?;
         headers.set(
             "Content-Type",
             &format!("multipart/form-data; boundary={}", payload.boundary()),
@@ -99,19 +200,116 @@ impl Api {
             .with_body(Some(worker::js_sys::Uint8Array::from(&buf[..]).into()))
             .with_headers(headers);
 
-        let response = Fetch::Request(Request::new_with_init(
-            &format!("{}{}", self.base_url, Method::name()),
+        self.send_request(
+            &options.url_with_query(&format!("{}{}", self.base_url, Method::name())),
+            &request,
+        )
+        .await
+    }
+
+    /// Download the small (160x160) chat photo.
+    pub async fn download_chat_photo_small(&self, photo: &ChatPhoto) -> Result<Vec<u8>> {
+        self.download(&photo.get_small()).await
+    }
+
+    /// Download the big (640x640) chat photo.
+    pub async fn download_chat_photo_big(&self, photo: &ChatPhoto) -> Result<Vec<u8>> {
+        self.download(&photo.get_big()).await
+    }
+
+    /// Downloads the file.
+    ///
+    /// If the path of the file is unknown, e.g. the file is from a message, it is requested with [`GetFile`] first.
+    /// Bots can download files of up to 20 MB in size.
+    pub async fn download_file(&self, file: &File) -> Result<Vec<u8>> {
+        let mut response = self.download_file_response(file).await?;
+        Ok(response.bytes().await?)
+    }
+
+    /// Downloads the file, returning the response whose body is not read yet.
+    ///
+    /// The response can be returned from the worker as is to pass the file through to the client
+    /// without keeping the whole file in memory.
+    pub async fn download_file_response(&self, file: &File) -> Result<Response> {
+        let file_path = match &file.file_path {
+            Some(file_path) => file_path.clone(),
+            None => self
+                .send_json(&GetFile::new(&file.file_id))
+                .await?
+                .file_path
+                .ok_or_else(|| {
+                    Error::TelegramError(TelegramError {
+                        error_code: 0,
+                        description: "File is not available for download".to_string(),
+                        parameters: None,
+                    })
+                })?,
+        };
+
+        let mut request = RequestInit::new();
+        request
+            .with_method(worker::Method::Get)
+            .with_headers(self.headers(&RequestOptions::default())?);
+        let mut response = Fetch::Request(Request::new_with_init(
+            &format!("{}{}", self.file_base_url, file_path),
             &request,
         )?)
         .send()
         .await?;
 
-        Self::parse_response::<Method>(response).await
+        if (200..300).contains(&response.status_code()) {
+            Ok(response)
+        } else {
+            Err(Error::TelegramError(response.json().await?))
+        }
     }
 
-    async fn parse_response<Method: TelegramMethod>(
-        mut response: Response,
-    ) -> Result<Method::Response> {
+    /// Downloads the file and verifies the content, e.g. to detect truncated downloads.
+    pub async fn download_file_checked(
+        &self,
+        file: &File,
+        check: &DownloadCheck,
+    ) -> Result<Vec<u8>> {
+        let data = self.download_file(file).await?;
+        check.verify(file, &data).map_err(Error::Integrity)?;
+        Ok(data)
+    }
+
+    async fn download(&self, get_file: &GetFile) -> Result<Vec<u8>> {
+        let file = self.send_json(get_file).await?;
+        self.download_file(&file).await
+    }
+
+    async fn send_request<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        request: &RequestInit,
+    ) -> Result<T> {
+        let mut retries = 0;
+        loop {
+            let fetch = Fetch::Request(Request::new_with_init(url, request)?);
+            let (result, is_network_error) = match fetch.send().await {
+                Ok(response) => (Self::parse_response(response).await, false),
+                Err(error) => (Err(error.into()), true),
+            };
+            let policy = match &self.retry {
+                Some(policy) => policy,
+                None => return result,
+            };
+            let delay = match &result {
+                Err(Error::TelegramError(error)) => policy.delay_for(retries, error),
+                Err(_) if is_network_error => policy.delay(retries, None),
+                _ => None,
+            };
+            match delay {
+                Some(delay) => sleep(delay).await?,
+                None => return result,
+            }
+            retries += 1;
+        }
+    }
+
+    async fn parse_response<T: DeserializeOwned>(mut response: Response) -> Result<T> {
         let tg_response: ApiResponse<_> = response.json().await?;
         match tg_response {
             ApiResponse::Ok { result } => Ok(result),
@@ -119,3 +317,32 @@ impl Api {
         }
     }
 }
+
+/// Lets bot logic written against [`TelegramClient`] send requests with this requester.
+impl TelegramClient for Api {
+    type Error = Error;
+
+    async fn execute_json<M: JsonMethod>(&self, method: &M) -> Result<M::Response> {
+        self.send_json(method).await
+    }
+
+    async fn execute_file<M: FileMethod>(&self, method: &M) -> Result<M::Response> {
+        self.send_file(method).await
+    }
+}
+
+/// Waits for the duration with `setTimeout`, since workers have no timer API in Rust.
+async fn sleep(duration: Duration) -> Result<()> {
+    let set_timeout: Function = Reflect::get(&worker::js_sys::global(), &"setTimeout".into())
+        .map_err(worker::Error::from)?
+        .into();
+    let promise = Promise::new(&mut |resolve, _| {
+        let _ = set_timeout.call2(
+            &JsValue::NULL,
+            &resolve,
+            &(duration.as_millis() as f64).into(),
+        );
+    });
+    JsFuture::from(promise).await.map_err(worker::Error::from)?;
+    Ok(())
+}
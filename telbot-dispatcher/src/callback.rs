@@ -0,0 +1,170 @@
+//! Automatic answers to callback queries which handlers did not answer.
+//!
+//! Telegram clients display a progress bar until a callback query is answered.
+//! With [`Dispatcher::with_callback_answer`](crate::Dispatcher::with_callback_answer),
+//! the dispatcher sends an empty answer when the handler finishes, or when the window passes,
+//! unless the handler answered the query with its [`CallbackAnswer`].
+//! Queries which no route handles are answered right away.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use telbot_types::query::AnswerCallbackQuery;
+use telbot_types::update::{Update, UpdateKind};
+use telbot_types::TelegramClient;
+
+use crate::extract::{Extraction, FromUpdate};
+use crate::guard::SleepFuture;
+use crate::handler::HandlerFuture;
+
+/// Answers the callback query of the update, disabling the automatic answer of the dispatcher.
+///
+/// Handlers which answer the query should answer it with this extractor;
+/// otherwise the dispatcher sends an empty answer as well, which Telegram rejects.
+///
+/// ```
+/// # use telbot_dispatcher::{CallbackAnswer, State};
+/// # use telbot_types::TelegramClient;
+/// // dispatcher.on_callback_data("order:", order)
+/// async fn order<Api: TelegramClient>(answer: CallbackAnswer, State(api): State<Api>) {
+///     let request = answer.request().with_text("One coffee coming up!");
+///     let _ = answer.answer(&api, &request).await;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CallbackAnswer {
+    query_id: String,
+    answered: Arc<AtomicBool>,
+}
+
+/// Extracted outside of a dispatcher, the answer does not disable any automatic answer.
+impl<S> FromUpdate<S> for CallbackAnswer {
+    fn from_update(update: &Update, state: &S) -> Option<Self> {
+        Self::from_extraction(&Extraction::new(update, state))
+    }
+
+    fn from_extraction(extraction: &Extraction<'_, S>) -> Option<Self> {
+        match &extraction.update().kind {
+            UpdateKind::CallbackQuery { callback_query } => Some(Self {
+                query_id: callback_query.id.clone(),
+                answered: Arc::clone(&extraction.callback_answered),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl CallbackAnswer {
+    /// Gets the identifier of the query.
+    pub fn query_id(&self) -> &str {
+        &self.query_id
+    }
+
+    /// Creates an empty answer to the query, which can be filled in with its `with_*` methods.
+    pub fn request(&self) -> AnswerCallbackQuery {
+        AnswerCallbackQuery::new(self.query_id.clone())
+    }
+
+    /// Answers the query with the API requester.
+    pub async fn answer<C: TelegramClient>(
+        self,
+        api: &C,
+        answer: &AnswerCallbackQuery,
+    ) -> Result<bool, C::Error> {
+        self.answered.store(true, Ordering::Relaxed);
+        api.execute_json(answer).await
+    }
+
+    /// Disables the automatic answer, e.g. when the query was answered elsewhere.
+    pub fn dismiss(self) {
+        self.answered.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Runs the future of a handler, and the answer once the handler finishes or the window passes,
+/// if the query was not answered yet.
+///
+/// Without the window and the answer, only runs the future of the handler.
+pub(crate) struct Answering<F: Future, A> {
+    handler: F,
+    output: Option<F::Output>,
+    window: Option<SleepFuture>,
+    answer: Answer<A>,
+    answered: Arc<AtomicBool>,
+}
+
+enum Answer<A> {
+    NotStarted(A),
+    Running(HandlerFuture),
+    Done,
+}
+
+impl<F: Future + Unpin, A: FnOnce() -> HandlerFuture> Answering<F, A> {
+    pub(crate) fn new(
+        handler: F,
+        auto_answer: Option<(SleepFuture, A)>,
+        answered: Arc<AtomicBool>,
+    ) -> Self {
+        let (window, answer) = match auto_answer {
+            Some((window, start)) => (Some(window), Answer::NotStarted(start)),
+            None => (None, Answer::Done),
+        };
+        Self {
+            handler,
+            output: None,
+            window,
+            answer,
+            answered,
+        }
+    }
+
+    fn start_answer(&mut self) {
+        if let Answer::NotStarted(_) = self.answer {
+            if let Answer::NotStarted(start) = std::mem::replace(&mut self.answer, Answer::Done) {
+                if !self.answered.swap(true, Ordering::Relaxed) {
+                    self.answer = Answer::Running(start());
+                }
+            }
+        }
+    }
+}
+
+impl<F: Future + Unpin, A: FnOnce() -> HandlerFuture + Unpin> Future for Answering<F, A>
+where
+    F::Output: Unpin,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.output.is_none() {
+            if let Poll::Ready(output) = Pin::new(&mut this.handler).poll(cx) {
+                this.output = Some(output);
+                this.window = None;
+                this.start_answer();
+            }
+        }
+        if let Some(window) = &mut this.window {
+            if window.as_mut().poll(cx).is_ready() {
+                this.window = None;
+                this.start_answer();
+            }
+        }
+        if let Answer::Running(answer) = &mut this.answer {
+            if answer.as_mut().poll(cx).is_ready() {
+                this.answer = Answer::Done;
+            }
+        }
+        let answer_pending = matches!(this.answer, Answer::Running(_));
+        match this.output.take() {
+            Some(output) if !answer_pending => Poll::Ready(output),
+            output => {
+                this.output = output;
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! A handler argument bundling the API requester, the update and the state of the bot.
+
+use telbot_types::chat::Chat;
+use telbot_types::message::{Message, SendMessage};
+use telbot_types::update::Update;
+use telbot_types::user::User;
+use telbot_types::{JsonMethod, TelegramClient};
+
+use crate::extract::FromUpdate;
+
+/// State of the dispatcher which provides the API requester to [`Context`].
+///
+/// Implemented for API requesters themselves, like the `Api` of any backend.
+/// Bots keeping more in the state, like storage handles or the bot itself, implement it for their state.
+///
+/// ```
+/// # use std::sync::{Arc, Mutex};
+/// # use std::collections::HashMap;
+/// # use telbot_dispatcher::context::BotState;
+/// # use telbot_types::user::User;
+/// # use telbot_types::TelegramClient;
+/// #[derive(Clone)]
+/// struct Bot<Api> {
+///     api: Api,
+///     me: User,
+///     scores: Arc<Mutex<HashMap<i64, u32>>>,
+/// }
+///
+/// impl<Api: TelegramClient + Clone> BotState for Bot<Api> {
+///     type Api = Api;
+///
+///     fn api(&self) -> &Api {
+///         &self.api
+///     }
+///
+///     fn me(&self) -> Option<&User> {
+///         Some(&self.me)
+///     }
+/// }
+/// ```
+pub trait BotState: Clone {
+    /// The API requester.
+    type Api: TelegramClient;
+
+    /// Gets the API requester.
+    fn api(&self) -> &Self::Api;
+
+    /// Gets the bot itself, as returned by [`GetMe`](telbot_types::bot::GetMe), if known.
+    fn me(&self) -> Option<&User> {
+        None
+    }
+}
+
+impl<A: TelegramClient + Clone> BotState for A {
+    type Api = A;
+
+    fn api(&self) -> &A {
+        self
+    }
+}
+
+/// The update with the state of the dispatcher, for handlers which need more than one part of the update.
+///
+/// ```
+/// # use telbot_dispatcher::context::Context;
+/// # use telbot_types::TelegramClient;
+/// // dispatcher.on_command("whoami", whoami)
+/// async fn whoami<Api: TelegramClient + Clone>(ctx: Context<Api>) {
+///     let name = match ctx.user() {
+///         Some(user) => user.first_name.clone(),
+///         None => "anonymous".to_string(),
+///     };
+///     let _ = ctx.reply(format!("You are {}", name)).await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Context<S> {
+    state: S,
+    update: Update,
+}
+
+impl<S: BotState> FromUpdate<S> for Context<S> {
+    fn from_update(update: &Update, state: &S) -> Option<Self> {
+        Some(Self {
+            state: state.clone(),
+            update: update.clone(),
+        })
+    }
+}
+
+impl<S: BotState> Context<S> {
+    /// Gets the API requester.
+    pub fn api(&self) -> &S::Api {
+        self.state.api()
+    }
+
+    /// Gets the state of the dispatcher.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Gets the update.
+    pub fn update(&self) -> &Update {
+        &self.update
+    }
+
+    /// Gets the bot itself, if the state knows it.
+    pub fn me(&self) -> Option<&User> {
+        self.state.me()
+    }
+
+    /// Gets the chat the update happened in, if any.
+    pub fn chat(&self) -> Option<&Chat> {
+        self.update.kind.effective_chat()
+    }
+
+    /// Gets the user who caused the update, if any.
+    pub fn user(&self) -> Option<&User> {
+        self.update.kind.effective_user()
+    }
+
+    /// Gets the message, edited message, channel post or edited channel post, if any.
+    pub fn message(&self) -> Option<&Message> {
+        self.update.kind.any_message()
+    }
+
+    /// Sends the request with the API requester.
+    pub async fn send<M: JsonMethod>(
+        &self,
+        method: &M,
+    ) -> Result<M::Response, <S::Api as TelegramClient>::Error> {
+        self.api().execute_json(method).await
+    }
+
+    /// Replies to the message of the update, or sends the text to the chat of the update.
+    ///
+    /// Returns `None` if the update has no chat, like inline queries.
+    pub async fn reply(
+        &self,
+        text: impl Into<String>,
+    ) -> Result<Option<Message>, <S::Api as TelegramClient>::Error> {
+        let request = match (self.message(), self.chat()) {
+            (Some(message), _) => message.reply_text(text),
+            (None, Some(chat)) => SendMessage::new(chat.id, text),
+            (None, None) => return Ok(None),
+        };
+        self.send(&request).await.map(Some)
+    }
+}
@@ -0,0 +1,155 @@
+//! Arguments of handlers, extracted from updates.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use telbot_types::chat::{ChatJoinRequest, ChatMemberUpdated};
+use telbot_types::message::Message;
+use telbot_types::query::{CallbackQuery, InlineQuery};
+use telbot_types::update::{Update, UpdateKind};
+
+/// A type which can be extracted from an update, to be passed to handlers as an argument.
+///
+/// If any argument of a handler cannot be extracted, the handler is skipped and the next route is tried.
+pub trait FromUpdate<S>: Sized {
+    /// Extracts the value from the update and the state of the dispatcher.
+    fn from_update(update: &Update, state: &S) -> Option<Self>;
+
+    /// Extracts the value from the update being dispatched.
+    ///
+    /// Defaults to [`FromUpdate::from_update`].
+    /// Arguments which work together with the dispatcher, like [`CallbackAnswer`](crate::CallbackAnswer),
+    /// get what they need from the [`Extraction`].
+    fn from_extraction(extraction: &Extraction<'_, S>) -> Option<Self> {
+        Self::from_update(extraction.update(), extraction.state())
+    }
+}
+
+/// The update being dispatched and the state of the dispatcher, from which handler arguments are extracted.
+#[derive(Debug)]
+pub struct Extraction<'a, S> {
+    update: &'a Update,
+    state: &'a S,
+    /// Set once the callback query is answered, to disable the automatic answer of the dispatcher.
+    pub(crate) callback_answered: Arc<AtomicBool>,
+}
+
+impl<'a, S> Extraction<'a, S> {
+    /// Creates a new extraction from the update and the state, e.g. to call a [`Handler`](crate::Handler).
+    pub fn new(update: &'a Update, state: &'a S) -> Self {
+        Self {
+            update,
+            state,
+            callback_answered: Arc::default(),
+        }
+    }
+
+    /// Gets the update.
+    pub fn update(&self) -> &'a Update {
+        self.update
+    }
+
+    /// Gets the state of the dispatcher.
+    pub fn state(&self) -> &'a S {
+        self.state
+    }
+}
+
+/// The state given to [`Dispatcher::dispatch`](crate::Dispatcher::dispatch), such as an API requester.
+///
+/// ```
+/// # use telbot_dispatcher::State;
+/// # #[derive(Clone)]
+/// # struct Api;
+/// async fn handler(State(api): State<Api>) {
+///     // api.send_json(...)
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct State<S>(pub S);
+
+impl<S: Clone> FromUpdate<S> for State<S> {
+    fn from_update(_update: &Update, state: &S) -> Option<Self> {
+        Some(Self(state.clone()))
+    }
+}
+
+/// The forum topic of the message, given as [`Message::topic_id`].
+///
+/// Handlers taking it are skipped for messages outside forum topics.
+///
+/// ```
+/// # use telbot_dispatcher::extract::Topic;
+/// # use telbot_types::message::Message;
+/// async fn handler(message: Message, Topic(topic_id): Topic) {
+///     let reply = message.reply_text("Hello!");
+///     assert_eq!(reply.message_thread_id, Some(topic_id));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Topic(pub i64);
+
+impl<S> FromUpdate<S> for Topic {
+    fn from_update(update: &Update, _state: &S) -> Option<Self> {
+        update.kind.any_message()?.topic_id().map(Self)
+    }
+}
+
+impl<S> FromUpdate<S> for Update {
+    fn from_update(update: &Update, _state: &S) -> Option<Self> {
+        Some(update.clone())
+    }
+}
+
+/// The message, edited message, channel post or edited channel post.
+impl<S> FromUpdate<S> for Message {
+    fn from_update(update: &Update, _state: &S) -> Option<Self> {
+        update.kind.any_message().cloned()
+    }
+}
+
+impl<S> FromUpdate<S> for CallbackQuery {
+    fn from_update(update: &Update, _state: &S) -> Option<Self> {
+        match &update.kind {
+            UpdateKind::CallbackQuery { callback_query } => Some(callback_query.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<S> FromUpdate<S> for InlineQuery {
+    fn from_update(update: &Update, _state: &S) -> Option<Self> {
+        match &update.kind {
+            UpdateKind::InlineQuery { inline_query } => Some(inline_query.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The change of the bot's own member status, or of another member.
+impl<S> FromUpdate<S> for ChatMemberUpdated {
+    fn from_update(update: &Update, _state: &S) -> Option<Self> {
+        match &update.kind {
+            UpdateKind::MyChatMemberUpdated { my_chat_member: u }
+            | UpdateKind::ChatMemberUpdated { chat_member: u } => Some(u.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<S> FromUpdate<S> for ChatJoinRequest {
+    fn from_update(update: &Update, _state: &S) -> Option<Self> {
+        update.kind.chat_join_request().cloned()
+    }
+}
+
+/// Optional arguments, which never make the handler skipped.
+impl<S, T: FromUpdate<S>> FromUpdate<S> for Option<T> {
+    fn from_update(update: &Update, state: &S) -> Option<Self> {
+        Some(T::from_update(update, state))
+    }
+
+    fn from_extraction(extraction: &Extraction<'_, S>) -> Option<Self> {
+        Some(T::from_extraction(extraction))
+    }
+}
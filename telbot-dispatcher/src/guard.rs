@@ -0,0 +1,97 @@
+//! Isolation of handlers which panic or take too long, so that they don't stop other updates from being handled.
+
+use std::any::Any;
+use std::fmt;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::handler::HandlerFuture;
+
+/// A boxed future returned by the sleep function given to
+/// [`Dispatcher::with_timeout`](crate::Dispatcher::with_timeout).
+pub(crate) type SleepFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+pub(crate) type SleepFn = Box<dyn Fn(Duration) -> SleepFuture + Send + Sync>;
+
+/// Failure of a handler, reported to the hook set with [`Dispatcher::on_error`](crate::Dispatcher::on_error).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandlerError {
+    /// The handler panicked, with the panic message.
+    Panicked(String),
+    /// The handler did not finish within the timeout, and was dropped.
+    TimedOut(Duration),
+}
+
+impl fmt::Display for HandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panicked(message) => write!(f, "handler panicked: {}", message),
+            Self::TimedOut(timeout) => write!(f, "handler timed out after {:?}", timeout),
+        }
+    }
+}
+
+impl std::error::Error for HandlerError {}
+
+/// Runs the future of a handler, catching panics and stopping at the timeout.
+pub(crate) struct Guarded {
+    future: Result<HandlerFuture, Option<HandlerError>>,
+    timeout: Option<(Duration, SleepFuture)>,
+}
+
+impl Guarded {
+    /// Guards the future, or fails at once if building the future failed.
+    pub(crate) fn new(
+        future: Result<HandlerFuture, HandlerError>,
+        timeout: Option<(Duration, SleepFuture)>,
+    ) -> Self {
+        Self {
+            future: future.map_err(Some),
+            timeout,
+        }
+    }
+}
+
+impl Future for Guarded {
+    type Output = Result<(), HandlerError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let future = match &mut this.future {
+            Ok(future) => future,
+            Err(error) => {
+                return Poll::Ready(Err(error.take().expect("polled after completion")));
+            }
+        };
+        match catch_panic(|| future.as_mut().poll(cx)) {
+            Ok(Poll::Ready(())) => return Poll::Ready(Ok(())),
+            Ok(Poll::Pending) => {}
+            Err(error) => return Poll::Ready(Err(error)),
+        }
+        if let Some((timeout, sleep)) = &mut this.timeout {
+            if sleep.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Err(HandlerError::TimedOut(*timeout)));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// Runs `f`, reporting a panic as [`HandlerError::Panicked`].
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, HandlerError> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .map_err(|payload| HandlerError::Panicked(panic_message(payload)))
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Box<dyn Any>".to_string(),
+        },
+    }
+}
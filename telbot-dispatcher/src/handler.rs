@@ -0,0 +1,44 @@
+//! Async functions which handle updates.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::extract::{Extraction, FromUpdate};
+
+/// A boxed future returned by handlers.
+pub type HandlerFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// An async function taking arguments which implement [`FromUpdate`].
+///
+/// Implemented for functions and closures with up to 6 arguments.
+/// `Args` is the tuple of the argument types, which only exists to tell the implementations apart.
+pub trait Handler<Args, S> {
+    /// Calls the handler with the arguments extracted from the update,
+    /// or returns `None` if any argument cannot be extracted.
+    fn call(&self, extraction: &Extraction<'_, S>) -> Option<HandlerFuture>;
+}
+
+macro_rules! impl_handler {
+    ($($arg:ident),*) => {
+        impl<F, Fut, S, $($arg,)*> Handler<($($arg,)*), S> for F
+        where
+            F: Fn($($arg),*) -> Fut,
+            Fut: Future<Output = ()> + Send + 'static,
+            $($arg: FromUpdate<S>,)*
+        {
+            #[allow(non_snake_case, unused_variables)]
+            fn call(&self, extraction: &Extraction<'_, S>) -> Option<HandlerFuture> {
+                $(let $arg = $arg::from_extraction(extraction)?;)*
+                Some(Box::pin(self($($arg),*)))
+            }
+        }
+    };
+}
+
+impl_handler!();
+impl_handler!(A);
+impl_handler!(A, B);
+impl_handler!(A, B, C);
+impl_handler!(A, B, C, D);
+impl_handler!(A, B, C, D, E);
+impl_handler!(A, B, C, D, E, G);
@@ -0,0 +1,895 @@
+//! Router-style dispatcher of Telegram updates, independent of the API backend.
+//!
+//! Handlers are async functions whose arguments are extracted from the update,
+//! like [`Message`](telbot_types::message::Message) or [`CallbackQuery`](telbot_types::query::CallbackQuery),
+//! or from the state given to the dispatcher with [`State`], like the API requester of any backend.
+//!
+//! ```
+//! use telbot_dispatcher::{Dispatcher, State};
+//! use telbot_types::message::Message;
+//! use telbot_types::query::CallbackQuery;
+//!
+//! # #[derive(Clone)]
+//! # struct Api;
+//! let dispatcher = Dispatcher::new()
+//!     .on_command("start", |message: Message, State(api): State<Api>| async move {
+//!         // api.send_json(&message.reply_text("Hello!")).await;
+//!     })
+//!     .on_callback_query(|query: CallbackQuery| async move {
+//!         // ...
+//!     });
+//!
+//! # let update = serde_json::from_str(r#"{
+//! #     "update_id": 1,
+//! #     "message": {"message_id": 1, "date": 0, "chat": {"id": 1, "type": "private"}, "text": "/start",
+//! #         "entities": [{"type": "bot_command", "offset": 0, "length": 6}]}
+//! # }"#).unwrap();
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! // for update in polling { ... }
+//! let handled = dispatcher.dispatch(&update, &Api).await;
+//! # assert!(handled);
+//! # });
+//! ```
+//!
+//! Handlers, filters and the futures of handlers are [`Send`], so that a dispatcher shared with [`Arc`]
+//! can handle updates in tasks spawned with `tokio::spawn`, or in the webhook server of `telbot-hyper`.
+//!
+//! A handler which panics does not stop the dispatcher, even if the panic comes from a filter
+//! or from extracting the arguments of the handler, and handlers can be stopped after a timeout
+//! with [`Dispatcher::with_timeout`]. Such failures are reported to the hook set with [`Dispatcher::on_error`].
+//!
+//! [`Arc`]: std::sync::Arc
+
+pub mod callback;
+pub mod context;
+pub mod extract;
+pub mod guard;
+pub mod handler;
+pub mod locale;
+pub mod metrics;
+
+pub use callback::CallbackAnswer;
+pub use context::{BotState, Context};
+pub use extract::{Extraction, FromUpdate, State, Topic};
+pub use guard::HandlerError;
+pub use handler::Handler;
+
+use telbot_types::command::BotCommandParser;
+use telbot_types::filter::{self, Filter};
+use telbot_types::message::Message;
+use telbot_types::query::AnswerCallbackQuery;
+use telbot_types::update::{Update, UpdateKind};
+
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use callback::Answering;
+use guard::{Guarded, SleepFn};
+use handler::HandlerFuture;
+use metrics::Metrics;
+
+type BoxedFilter = Box<dyn Fn(&Update) -> bool + Send + Sync>;
+type BoxedHandler<S> = Box<dyn Fn(&Extraction<'_, S>) -> Option<HandlerFuture> + Send + Sync>;
+type ErrorHook = Box<dyn Fn(&str, &HandlerError) + Send + Sync>;
+type AnswerFn<S> = Box<dyn Fn(&S, AnswerCallbackQuery) -> HandlerFuture + Send + Sync>;
+
+struct AutoAnswer<S> {
+    window: Duration,
+    sleep: SleepFn,
+    answer: AnswerFn<S>,
+}
+
+struct Route<S> {
+    name: String,
+    filter: BoxedFilter,
+    handler: BoxedHandler<S>,
+}
+
+/// Routes updates to the first matching handler.
+///
+/// Routes are tried in the order they are registered.
+/// A route matches if its condition is satisfied and all arguments of its handler can be extracted.
+pub struct Dispatcher<S> {
+    routes: Vec<Route<S>>,
+    fallback: Option<BoxedHandler<S>>,
+    metrics: Option<Box<dyn Metrics + Send + Sync>>,
+    slow_threshold: Option<Duration>,
+    timeout: Option<(Duration, SleepFn)>,
+    error_hook: Option<ErrorHook>,
+    auto_answer: Option<AutoAnswer<S>>,
+    command_parser: Arc<Mutex<BotCommandParser>>,
+}
+
+impl<S> Default for Dispatcher<S> {
+    fn default() -> Self {
+        Self {
+            routes: vec![],
+            fallback: None,
+            metrics: None,
+            slow_threshold: None,
+            timeout: None,
+            error_hook: None,
+            auto_answer: None,
+            command_parser: Arc::default(),
+        }
+    }
+}
+
+/// Name of the fallback handler reported to [`Metrics`].
+pub const FALLBACK_ROUTE: &str = "fallback";
+
+impl<S: 'static> Dispatcher<S> {
+    /// Creates a new dispatcher without routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handles updates satisfying the condition.
+    ///
+    /// The route is named `#<index>`, which can be changed with [`Dispatcher::named`].
+    pub fn on<Args: 'static>(
+        self,
+        filter: impl Fn(&Update) -> bool + Send + Sync + 'static,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        let name = format!("#{}", self.routes.len());
+        self.route(name, filter, handler)
+    }
+
+    fn route<Args: 'static>(
+        mut self,
+        name: String,
+        filter: impl Fn(&Update) -> bool + Send + Sync + 'static,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.routes.push(Route {
+            name,
+            filter: Box::new(filter),
+            handler: Box::new(move |extraction| handler.call(extraction)),
+        });
+        self
+    }
+
+    /// Names the last registered route, to tell its handler apart in [`Metrics`].
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        if let Some(route) = self.routes.last_mut() {
+            route.name = name.into();
+        }
+        self
+    }
+
+    /// Reports the time taken by each handler to the metrics.
+    ///
+    /// Handlers are timed with [`Instant`], which is not available on `wasm32-unknown-unknown`,
+    /// so metrics cannot be used with backends like `telbot-cf-worker`.
+    pub fn with_metrics(self, metrics: impl Metrics + Send + Sync + 'static) -> Self {
+        Self {
+            metrics: Some(Box::new(metrics)),
+            ..self
+        }
+    }
+
+    /// Reports handlers taking longer than the threshold with [`Metrics::slow`].
+    pub fn with_slow_threshold(self, threshold: Duration) -> Self {
+        Self {
+            slow_threshold: Some(threshold),
+            ..self
+        }
+    }
+
+    /// Stops handlers which take longer than the timeout, reporting them with [`HandlerError::TimedOut`].
+    ///
+    /// The dispatcher does not depend on an async runtime, so it waits with the given function,
+    /// like `tokio::time::sleep`.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use telbot_dispatcher::{Dispatcher, HandlerError};
+    /// let dispatcher = Dispatcher::<()>::new()
+    ///     .on_command("report", || async { /* ... */ })
+    ///     .with_timeout(Duration::from_secs(30), tokio::time::sleep)
+    ///     .on_error(|route, error| eprintln!("{}: {}", route, error));
+    /// ```
+    pub fn with_timeout<F>(
+        self,
+        timeout: Duration,
+        sleep: impl Fn(Duration) -> F + Send + Sync + 'static,
+    ) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let sleep: SleepFn = Box::new(move |timeout| Box::pin(sleep(timeout)));
+        Self {
+            timeout: Some((timeout, sleep)),
+            ..self
+        }
+    }
+
+    /// Sets the hook called with the route name when a handler panics or times out.
+    ///
+    /// Panics of handlers, their filters and the extraction of their arguments are caught either way,
+    /// so that the next updates are still handled.
+    pub fn on_error(self, hook: impl Fn(&str, &HandlerError) + Send + Sync + 'static) -> Self {
+        Self {
+            error_hook: Some(Box::new(hook)),
+            ..self
+        }
+    }
+
+    /// Answers callback queries which the handler did not answer with [`CallbackAnswer`],
+    /// when the handler finishes or after the window, whichever comes first,
+    /// so that clients stop showing a progress bar even if the handler is slow or fails.
+    ///
+    /// The dispatcher waits for the window with `sleep`, like `tokio::time::sleep`,
+    /// and sends the empty answer with `answer`, since it does not know how to send requests with the state.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use telbot_dispatcher::{CallbackAnswer, Dispatcher};
+    /// # #[derive(Clone)]
+    /// # struct Api;
+    /// # impl Api {
+    /// #     async fn send_json<M>(&self, _: &M) {}
+    /// # }
+    /// let dispatcher = Dispatcher::new()
+    ///     .on_callback_data("order:", |answer: CallbackAnswer| async move {
+    ///         // answer.answer(&api, &answer.request().with_text("Ordered!")).await;
+    ///         # answer.dismiss();
+    ///     })
+    ///     .with_callback_answer(Duration::from_secs(5), tokio::time::sleep, |api: &Api, answer| {
+    ///         let api = api.clone();
+    ///         async move {
+    ///             let _ = api.send_json(&answer).await;
+    ///         }
+    ///     });
+    /// ```
+    pub fn with_callback_answer<F, A>(
+        self,
+        window: Duration,
+        sleep: impl Fn(Duration) -> F + Send + Sync + 'static,
+        answer: impl Fn(&S, AnswerCallbackQuery) -> A + Send + Sync + 'static,
+    ) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+        A: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            auto_answer: Some(AutoAnswer {
+                window,
+                sleep: Box::new(move |window| Box::pin(sleep(window))),
+                answer: Box::new(move |state, request| Box::pin(answer(state, request))),
+            }),
+            ..self
+        }
+    }
+
+    /// Ignores commands addressed to other bots, like `/start@OtherBot` in groups.
+    ///
+    /// Applies to all routes of [`Dispatcher::on_command`], including those registered earlier.
+    /// Until the username is set, commands addressed to any bot are handled.
+    ///
+    /// ```
+    /// # use telbot_dispatcher::Dispatcher;
+    /// # use telbot_types::message::Message;
+    /// # let update = |text: &str| -> telbot_types::update::Update {
+    /// #     serde_json::from_value(serde_json::json!({"update_id": 1, "message": {
+    /// #         "message_id": 1, "date": 0, "chat": {"id": -1, "type": "group"}, "text": text,
+    /// #         "entities": [{"type": "bot_command", "offset": 0, "length": text.len()}]
+    /// #     }})).unwrap()
+    /// # };
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// // username = api.me()?.username
+    /// let dispatcher = Dispatcher::new()
+    ///     .on_command("start", |message: Message| async move {})
+    ///     .with_bot_username("MyBot");
+    /// assert!(dispatcher.dispatch(&update("/start@mybot"), &()).await);
+    /// assert!(!dispatcher.dispatch(&update("/start@OtherBot"), &()).await);
+    /// # });
+    /// ```
+    pub fn with_bot_username(self, username: impl Into<String>) -> Self {
+        self.set_bot_username(username);
+        self
+    }
+
+    /// Sets the username of the bot after the dispatcher is built, like [`Dispatcher::with_bot_username`].
+    pub fn set_bot_username(&self, username: impl Into<String>) {
+        *self
+            .command_parser
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) =
+            BotCommandParser::new().with_username(username);
+    }
+
+    /// Handles new messages, excluding edits and channel posts.
+    pub fn on_message<Args: 'static>(
+        self,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.route(
+            "message".to_string(),
+            |update| matches!(update.kind, UpdateKind::Message { .. }),
+            handler,
+        )
+    }
+
+    /// Handles new messages satisfying the [`Filter`].
+    pub fn on_message_where<Args: 'static>(
+        self,
+        filter: impl Filter + Send + Sync + 'static,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.on(
+            move |update| match &update.kind {
+                UpdateKind::Message { message } => filter.matches(message),
+                _ => false,
+            },
+            handler,
+        )
+    }
+
+    /// Handles new messages starting with the command, like `/start` or `/start@MyBot`, without the slash.
+    ///
+    /// Commands addressed to other bots are ignored once the username of the bot is set
+    /// with [`Dispatcher::with_bot_username`].
+    ///
+    /// The route is named after the command, like `/start`.
+    pub fn on_command<Args: 'static>(
+        self,
+        command: impl Into<String>,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        let command = command.into();
+        let name = format!("/{}", command);
+        let parser = Arc::clone(&self.command_parser);
+        self.on_message_where(
+            move |message: &Message| {
+                let parser = parser.lock().unwrap_or_else(PoisonError::into_inner);
+                matches!(parser.parse_message(message), Some(parsed) if parsed.name == command)
+            },
+            handler,
+        )
+        .named(name)
+    }
+
+    /// Handles new messages sent to the forum topic of the chat.
+    ///
+    /// Replies created with methods like [`Message::reply_text`] are sent to the same topic.
+    /// Handlers can also take the [`Topic`] of any message as an argument, to behave differently per topic.
+    ///
+    /// The route is named after the chat and the topic, like `topic -100123/42`.
+    pub fn on_topic<Args: 'static>(
+        self,
+        chat_id: i64,
+        topic_id: i64,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_message_where(filter::in_topic(chat_id, topic_id), handler)
+            .named(format!("topic {}/{}", chat_id, topic_id))
+    }
+
+    /// Handles new text messages whose text satisfies the predicate.
+    pub fn on_text<Args: 'static>(
+        self,
+        predicate: impl Fn(&str) -> bool + Send + Sync + 'static,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_message_where(
+            move |message: &Message| matches!(message.kind.text(), Some(text) if predicate(text)),
+            handler,
+        )
+    }
+
+    /// Handles callback queries.
+    pub fn on_callback_query<Args: 'static>(
+        self,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.route(
+            "callback_query".to_string(),
+            |update| matches!(update.kind, UpdateKind::CallbackQuery { .. }),
+            handler,
+        )
+    }
+
+    /// Handles callback queries whose data starts with the prefix.
+    pub fn on_callback_data<Args: 'static>(
+        self,
+        prefix: impl Into<String>,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        let prefix = prefix.into();
+        let name = format!("callback_query {}", prefix);
+        self.route(
+            name,
+            move |update| match &update.kind {
+                UpdateKind::CallbackQuery { callback_query } => {
+                    matches!(&callback_query.data, Some(data) if data.starts_with(prefix.as_str()))
+                }
+                _ => false,
+            },
+            handler,
+        )
+    }
+
+    /// Handles inline queries.
+    pub fn on_inline_query<Args: 'static>(
+        self,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.route(
+            "inline_query".to_string(),
+            |update| matches!(update.kind, UpdateKind::InlineQuery { .. }),
+            handler,
+        )
+    }
+
+    /// Handles changes of chat members, including the bot itself.
+    pub fn on_chat_member<Args: 'static>(
+        self,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.route(
+            "chat_member".to_string(),
+            |update| {
+                matches!(
+                    update.kind,
+                    UpdateKind::MyChatMemberUpdated { .. } | UpdateKind::ChatMemberUpdated { .. }
+                )
+            },
+            handler,
+        )
+    }
+
+    /// Handles requests to join chats.
+    ///
+    /// The handler can take the [`ChatJoinRequest`](telbot_types::chat::ChatJoinRequest) as an argument,
+    /// and answer it with its `approve` or `decline` method.
+    pub fn on_chat_join_request<Args: 'static>(
+        self,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.route(
+            "chat_join_request".to_string(),
+            |update| matches!(update.kind, UpdateKind::ChatJoinRequest { .. }),
+            handler,
+        )
+    }
+
+    /// Handles updates which match no route.
+    ///
+    /// The handler is reported to [`Metrics`] as [`FALLBACK_ROUTE`].
+    pub fn fallback<Args: 'static>(
+        mut self,
+        handler: impl Handler<Args, S> + Send + Sync + 'static,
+    ) -> Self {
+        self.fallback = Some(Box::new(move |extraction| handler.call(extraction)));
+        self
+    }
+
+    /// Runs the handler of the first matching route, or the fallback handler.
+    ///
+    /// Returns `false` if no handler was run, and `true` even if the handler failed.
+    /// Failures are reported to the hook set with [`Dispatcher::on_error`].
+    pub async fn dispatch(&self, update: &Update, state: &S) -> bool {
+        let extraction = Extraction::new(update, state);
+        let auto_answer = match (&self.auto_answer, &update.kind) {
+            (Some(auto_answer), UpdateKind::CallbackQuery { callback_query }) => {
+                let answer = AnswerCallbackQuery::new(callback_query.id.as_str());
+                let start = move || (auto_answer.answer)(state, answer);
+                Some(((auto_answer.sleep)(auto_answer.window), start))
+            }
+            _ => None,
+        };
+        let answered = Arc::clone(&extraction.callback_answered);
+        let (name, future) = match self.find_handler(&extraction) {
+            Some(handled) => handled,
+            None => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.unhandled();
+                }
+                // Clients keep waiting for queries which no route handles as well.
+                Answering::new(std::future::ready(()), auto_answer, answered).await;
+                return false;
+            }
+        };
+        let timeout = self
+            .timeout
+            .as_ref()
+            .map(|(timeout, sleep)| (*timeout, sleep(*timeout)));
+        let future = Answering::new(Guarded::new(future, timeout), auto_answer, answered);
+        let result = match &self.metrics {
+            Some(metrics) => {
+                let start = Instant::now();
+                let result = future.await;
+                let elapsed = start.elapsed();
+                metrics.handled(name, elapsed);
+                if matches!(self.slow_threshold, Some(threshold) if elapsed > threshold) {
+                    metrics.slow(name, elapsed);
+                }
+                result
+            }
+            None => future.await,
+        };
+        if let (Err(error), Some(hook)) = (result, &self.error_hook) {
+            hook(name, &error);
+        }
+        true
+    }
+
+    /// Finds the handler of the first matching route, or the fallback handler, and calls it.
+    ///
+    /// A panic in a filter, in an extractor or in the call is returned as the failure of the route.
+    fn find_handler(
+        &self,
+        extraction: &Extraction<'_, S>,
+    ) -> Option<(&str, Result<HandlerFuture, HandlerError>)> {
+        for route in &self.routes {
+            let called = guard::catch_panic(|| match (route.filter)(extraction.update()) {
+                true => (route.handler)(extraction),
+                false => None,
+            });
+            match called {
+                Ok(None) => {}
+                Ok(Some(future)) => return Some((route.name.as_str(), Ok(future))),
+                Err(error) => return Some((route.name.as_str(), Err(error))),
+            }
+        }
+        let fallback = self.fallback.as_ref()?;
+        match guard::catch_panic(|| fallback(extraction)) {
+            Ok(future) => Some((FALLBACK_ROUTE, Ok(future?))),
+            Err(error) => Some((FALLBACK_ROUTE, Err(error))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::json;
+    use telbot_types::query::CallbackQuery;
+    use telbot_types::update::Update;
+
+    use super::*;
+    use crate::metrics::HandlerStats;
+
+    /// Names of the handlers run so far.
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    fn record(name: &'static str) -> impl Fn(State<Log>) -> std::future::Ready<()> + Clone {
+        move |State(log): State<Log>| {
+            log.lock().unwrap().push(name);
+            std::future::ready(())
+        }
+    }
+
+    fn text_update(text: &str) -> Update {
+        let entities = match text.starts_with('/') {
+            true => {
+                let length = text.find(' ').unwrap_or(text.len());
+                json!([{"type": "bot_command", "offset": 0, "length": length}])
+            }
+            false => json!([]),
+        };
+        serde_json::from_value(json!({"update_id": 1, "message": {
+            "message_id": 1, "date": 0, "chat": {"id": -1, "type": "group"},
+            "text": text, "entities": entities
+        }}))
+        .unwrap()
+    }
+
+    fn callback_update(data: &str) -> Update {
+        serde_json::from_value(json!({"update_id": 1, "callback_query": {
+            "id": "1", "chat_instance": "1", "data": data,
+            "from": {"id": 1, "is_bot": false, "first_name": "Kiwi"}
+        }}))
+        .unwrap()
+    }
+
+    async fn run(dispatcher: &Dispatcher<Log>, update: &Update) -> Vec<&'static str> {
+        let log = Log::default();
+        dispatcher.dispatch(update, &log).await;
+        let ran = log.lock().unwrap().clone();
+        ran
+    }
+
+    #[tokio::test]
+    async fn runs_first_matching_route() {
+        let dispatcher = Dispatcher::new()
+            .on_callback_query(record("callback"))
+            .on_message(record("first"))
+            .on_message(record("second"));
+        assert_eq!(run(&dispatcher, &text_update("hi")).await, ["first"]);
+        assert_eq!(run(&dispatcher, &callback_update("a")).await, ["callback"]);
+    }
+
+    #[tokio::test]
+    async fn skips_routes_whose_arguments_cannot_be_extracted() {
+        let dispatcher = Dispatcher::new()
+            .on(
+                |_| true,
+                |_: CallbackQuery, State(log): State<Log>| async move {
+                    log.lock().unwrap().push("callback");
+                },
+            )
+            .on_message(record("message"));
+        assert_eq!(run(&dispatcher, &text_update("hi")).await, ["message"]);
+    }
+
+    #[tokio::test]
+    async fn runs_fallback_when_no_route_matches() {
+        let dispatcher = Dispatcher::new().on_callback_query(record("callback"));
+        let log = Log::default();
+        assert!(!dispatcher.dispatch(&text_update("hi"), &log).await);
+
+        let dispatcher = dispatcher.fallback(record("fallback"));
+        assert_eq!(run(&dispatcher, &text_update("hi")).await, ["fallback"]);
+    }
+
+    #[tokio::test]
+    async fn matches_filters() {
+        let dispatcher = Dispatcher::new()
+            .on_text(|text| text.contains("kiwi"), record("kiwi"))
+            .on_message_where(|message: &Message| message.chat.id < 0, record("group"))
+            .on_callback_data("menu:", record("menu"))
+            .on_callback_query(record("callback"));
+        assert_eq!(run(&dispatcher, &text_update("a kiwi")).await, ["kiwi"]);
+        assert_eq!(run(&dispatcher, &text_update("an apple")).await, ["group"]);
+        assert_eq!(
+            run(&dispatcher, &callback_update("menu:main")).await,
+            ["menu"]
+        );
+        assert_eq!(
+            run(&dispatcher, &callback_update("order:tea")).await,
+            ["callback"]
+        );
+    }
+
+    #[tokio::test]
+    async fn matches_commands() {
+        let dispatcher = Dispatcher::new()
+            .on_command("start", record("start"))
+            .fallback(record("fallback"));
+        assert_eq!(run(&dispatcher, &text_update("/start")).await, ["start"]);
+        assert_eq!(
+            run(&dispatcher, &text_update("/start now")).await,
+            ["start"]
+        );
+        assert_eq!(
+            run(&dispatcher, &text_update("/start@AnyBot")).await,
+            ["start"]
+        );
+        assert_eq!(
+            run(&dispatcher, &text_update("/starting")).await,
+            ["fallback"]
+        );
+        assert_eq!(run(&dispatcher, &text_update("start")).await, ["fallback"]);
+
+        dispatcher.set_bot_username("KiwiBot");
+        assert_eq!(
+            run(&dispatcher, &text_update("/start@kiwibot")).await,
+            ["start"]
+        );
+        assert_eq!(
+            run(&dispatcher, &text_update("/start@AnyBot")).await,
+            ["fallback"]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_named_routes() {
+        let stats = Arc::new(HandlerStats::new());
+        let dispatcher = Dispatcher::new()
+            .on_command("start", record("start"))
+            .on_message(record("message"))
+            .named("chat")
+            .with_metrics(stats.clone());
+        run(&dispatcher, &text_update("/start")).await;
+        run(&dispatcher, &text_update("hi")).await;
+        run(&dispatcher, &callback_update("a")).await;
+        assert_eq!(stats.get("/start").unwrap().count, 1);
+        assert_eq!(stats.get("chat").unwrap().count, 1);
+        assert_eq!(stats.unhandled_count(), 1);
+    }
+
+    fn topic_update(chat_id: i64, topic_id: i64) -> Update {
+        serde_json::from_value(json!({"update_id": 1, "message": {
+            "message_id": 2, "date": 0, "chat": {"id": chat_id, "type": "supergroup", "is_forum": true},
+            "message_thread_id": topic_id, "is_topic_message": true, "text": "hi"
+        }}))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn routes_by_topic() {
+        let dispatcher = Dispatcher::new()
+            .on_topic(-100, 42, record("announcements"))
+            .on(
+                |_| true,
+                |Topic(topic_id): Topic, State(log): State<Log>| async move {
+                    assert_eq!(topic_id, 7);
+                    log.lock().unwrap().push("other topic");
+                },
+            )
+            .on_message(record("message"));
+        assert_eq!(
+            run(&dispatcher, &topic_update(-100, 42)).await,
+            ["announcements"]
+        );
+        assert_eq!(
+            run(&dispatcher, &topic_update(-100, 7)).await,
+            ["other topic"]
+        );
+        assert_eq!(run(&dispatcher, &text_update("hi")).await, ["message"]);
+    }
+
+    #[tokio::test]
+    async fn isolates_panicking_handlers() {
+        let errors = Arc::new(Mutex::new(vec![]));
+        let dispatcher = Dispatcher::new()
+            .on_command("panic", |_: Message| async move { panic!("boom") })
+            .on_message(record("message"))
+            .on_error({
+                let errors = Arc::clone(&errors);
+                move |route: &str, error: &HandlerError| {
+                    errors
+                        .lock()
+                        .unwrap()
+                        .push((route.to_string(), error.clone()))
+                }
+            });
+        let log = Log::default();
+        assert!(dispatcher.dispatch(&text_update("/panic"), &log).await);
+        assert_eq!(
+            *errors.lock().unwrap(),
+            [(
+                "/panic".to_string(),
+                HandlerError::Panicked("boom".to_string())
+            )]
+        );
+        assert_eq!(run(&dispatcher, &text_update("hi")).await, ["message"]);
+    }
+
+    struct Panicking;
+
+    impl<S> FromUpdate<S> for Panicking {
+        fn from_update(_update: &Update, _state: &S) -> Option<Self> {
+            panic!("extractor")
+        }
+    }
+
+    #[tokio::test]
+    async fn isolates_panicking_filters_and_extractors() {
+        let errors = Arc::new(Mutex::new(vec![]));
+        let dispatcher = Dispatcher::new()
+            .on_text(
+                |text| text == "filter" && panic!("filter"),
+                record("filter"),
+            )
+            .named("filter")
+            .on_command("extract", |_: Panicking| async move {})
+            .on_message(record("message"))
+            .on_error({
+                let errors = Arc::clone(&errors);
+                move |route: &str, error: &HandlerError| {
+                    errors
+                        .lock()
+                        .unwrap()
+                        .push((route.to_string(), error.clone()))
+                }
+            });
+        let log = Log::default();
+        assert!(dispatcher.dispatch(&text_update("filter"), &log).await);
+        assert!(dispatcher.dispatch(&text_update("/extract"), &log).await);
+        assert!(log.lock().unwrap().is_empty());
+        assert_eq!(
+            *errors.lock().unwrap(),
+            [
+                (
+                    "filter".to_string(),
+                    HandlerError::Panicked("filter".to_string())
+                ),
+                (
+                    "/extract".to_string(),
+                    HandlerError::Panicked("extractor".to_string())
+                ),
+            ]
+        );
+        assert_eq!(run(&dispatcher, &text_update("hi")).await, ["message"]);
+    }
+
+    #[tokio::test]
+    async fn stops_handlers_at_timeout() {
+        let errors = Arc::new(Mutex::new(vec![]));
+        let timeout = Duration::from_millis(10);
+        let dispatcher = Dispatcher::<Log>::new()
+            .on_command("hang", std::future::pending)
+            .on_message(record("message"))
+            .with_timeout(timeout, tokio::time::sleep)
+            .on_error({
+                let errors = Arc::clone(&errors);
+                move |_: &str, error: &HandlerError| errors.lock().unwrap().push(error.clone())
+            });
+        assert!(run(&dispatcher, &text_update("/hang")).await.is_empty());
+        assert_eq!(*errors.lock().unwrap(), [HandlerError::TimedOut(timeout)]);
+        assert_eq!(run(&dispatcher, &text_update("hi")).await, ["message"]);
+        assert_eq!(errors.lock().unwrap().len(), 1);
+    }
+
+    fn answer_to_log(log: &Log, answer: AnswerCallbackQuery) -> std::future::Ready<()> {
+        assert_eq!(answer.callback_query_id, "1");
+        log.lock().unwrap().push("answer");
+        std::future::ready(())
+    }
+
+    #[tokio::test]
+    async fn answers_unanswered_callback_queries() {
+        let dispatcher = Dispatcher::new()
+            .on_callback_data("answered", |answer: CallbackAnswer| async move {
+                answer.dismiss();
+            })
+            .on_callback_query(record("callback"))
+            .on_message(record("message"))
+            .with_callback_answer(Duration::from_secs(5), tokio::time::sleep, answer_to_log);
+        assert_eq!(
+            run(&dispatcher, &callback_update("a")).await,
+            ["callback", "answer"]
+        );
+        assert!(run(&dispatcher, &callback_update("answered"))
+            .await
+            .is_empty());
+        assert_eq!(run(&dispatcher, &text_update("hi")).await, ["message"]);
+    }
+
+    #[tokio::test]
+    async fn answers_unmatched_callback_queries() {
+        let dispatcher = Dispatcher::new()
+            .on_callback_data("menu:", record("menu"))
+            .with_callback_answer(Duration::from_secs(5), tokio::time::sleep, answer_to_log);
+        let log = Log::default();
+        assert!(
+            !dispatcher
+                .dispatch(&callback_update("order:tea"), &log)
+                .await
+        );
+        assert_eq!(*log.lock().unwrap(), ["answer"]);
+    }
+
+    #[tokio::test]
+    async fn keeps_answers_made_in_other_tasks() {
+        let dispatcher = Dispatcher::new()
+            .on_callback_query(|answer: CallbackAnswer| async move {
+                tokio::spawn(async move { answer.dismiss() }).await.unwrap();
+            })
+            .with_callback_answer(Duration::from_secs(5), tokio::time::sleep, answer_to_log);
+        assert!(run(&dispatcher, &callback_update("a")).await.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn answers_slow_handlers_after_window() {
+        let dispatcher = Dispatcher::new()
+            .on_callback_query(|State(log): State<Log>| async move {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                log.lock().unwrap().push("callback");
+            })
+            .with_callback_answer(Duration::from_secs(1), tokio::time::sleep, answer_to_log);
+        assert_eq!(
+            run(&dispatcher, &callback_update("a")).await,
+            ["answer", "callback"]
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatches_in_spawned_tasks() {
+        let dispatcher = Arc::new(Dispatcher::new().on_message(record("message")));
+        let log = Log::default();
+        let task = tokio::spawn({
+            let dispatcher = Arc::clone(&dispatcher);
+            let log = Arc::clone(&log);
+            async move { dispatcher.dispatch(&text_update("hi"), &log).await }
+        });
+        assert!(task.await.unwrap());
+        assert_eq!(*log.lock().unwrap(), ["message"]);
+    }
+}
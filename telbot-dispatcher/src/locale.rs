@@ -0,0 +1,237 @@
+//! Negotiation of the language to respond in, for bots translated into several languages.
+//!
+//! The language is chosen for each update by a [`LanguageNegotiator`] in the state of the dispatcher,
+//! and given to handlers as a [`Locale`] argument, which translates texts with a [`Translator`].
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use telbot_dispatcher::locale::{LanguageNegotiator, Locale};
+//! use telbot_dispatcher::Dispatcher;
+//! use telbot_types::message::Message;
+//!
+//! struct Bot {
+//!     // api: Api,
+//!     languages: LanguageNegotiator,
+//! }
+//!
+//! impl AsRef<LanguageNegotiator> for Bot {
+//!     fn as_ref(&self) -> &LanguageNegotiator {
+//!         &self.languages
+//!     }
+//! }
+//!
+//! let mut texts: HashMap<String, HashMap<String, String>> = HashMap::new();
+//! texts.entry("en".into()).or_default().insert("hello".into(), "Hello!".into());
+//! texts.entry("pt".into()).or_default().insert("hello".into(), "Olá!".into());
+//! let bot = Bot {
+//!     languages: LanguageNegotiator::new(texts).with_fallback("en"),
+//! };
+//!
+//! let dispatcher = Dispatcher::new().on_command("start", |message: Message, locale: Locale| async move {
+//!     assert_eq!(locale.language(), Some("pt"));
+//!     let reply = message.reply_text(locale.translate("hello"));
+//!     assert_eq!(reply.text, "Olá!");
+//!     // api.send_json(&reply).await;
+//! });
+//! # let update = serde_json::from_value(serde_json::json!({"update_id": 1, "message": {
+//! #     "message_id": 1, "date": 0, "chat": {"id": 1234, "type": "private", "first_name": "Kiwi"},
+//! #     "from": {"id": 1234, "is_bot": false, "first_name": "Kiwi", "language_code": "pt-br"},
+//! #     "text": "/start", "entities": [{"type": "bot_command", "offset": 0, "length": 6}]
+//! # }})).unwrap();
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! assert!(dispatcher.dispatch(&update, &bot).await);
+//! # });
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use telbot_types::update::{Update, UpdateKind};
+use telbot_types::user::User;
+
+use crate::extract::FromUpdate;
+
+/// Texts of the bot translated into several languages.
+///
+/// Languages are given as lowercase [IETF language tags](https://en.wikipedia.org/wiki/IETF_language_tag),
+/// like `en` or `pt-br`, as in the `language_code` of users.
+pub trait Translator {
+    /// Checks if the texts are translated into the language.
+    fn supports(&self, language: &str) -> bool;
+
+    /// Gets the text of the key in the language, or `None` if it is not translated.
+    fn translate(&self, language: &str, key: &str) -> Option<String>;
+}
+
+/// Texts keyed by language, then by key.
+impl Translator for HashMap<String, HashMap<String, String>> {
+    fn supports(&self, language: &str) -> bool {
+        self.contains_key(language)
+    }
+
+    fn translate(&self, language: &str, key: &str) -> Option<String> {
+        self.get(language)?.get(key).cloned()
+    }
+}
+
+impl<T: Translator + ?Sized> Translator for Arc<T> {
+    fn supports(&self, language: &str) -> bool {
+        (**self).supports(language)
+    }
+
+    fn translate(&self, language: &str, key: &str) -> Option<String> {
+        (**self).translate(language, key)
+    }
+}
+
+type ChatLanguage = Box<dyn Fn(i64) -> Option<String> + Send + Sync>;
+
+/// Chooses the language to respond to each update in.
+///
+/// The first language supported by the [`Translator`] is chosen among:
+/// 1. the language set for the chat, with [`LanguageNegotiator::with_chat_language`],
+/// 2. the `language_code` of the user, like `pt-br`,
+/// 3. the primary language of the user, like `pt`,
+/// 4. the fallback languages, in the order they are added.
+///
+/// Handlers get the chosen language as a [`Locale`] argument,
+/// if the state of the dispatcher implements `AsRef<LanguageNegotiator>`.
+pub struct LanguageNegotiator {
+    translator: Arc<dyn Translator + Send + Sync>,
+    chat_language: Option<ChatLanguage>,
+    fallbacks: Arc<[String]>,
+}
+
+impl LanguageNegotiator {
+    /// Creates a new negotiator choosing among the languages of the translator.
+    pub fn new(translator: impl Translator + Send + Sync + 'static) -> Self {
+        Self {
+            translator: Arc::new(translator),
+            chat_language: None,
+            fallbacks: Arc::new([]),
+        }
+    }
+
+    /// Prefers the language set for each chat, like by a `/language` command.
+    ///
+    /// The function is called with the identifier of the chat,
+    /// or of the user if the update is not from a chat, like inline queries.
+    pub fn with_chat_language(
+        self,
+        chat_language: impl Fn(i64) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            chat_language: Some(Box::new(chat_language)),
+            ..self
+        }
+    }
+
+    /// Adds a language to fall back to, if no language of the user is supported.
+    ///
+    /// Texts not translated into the chosen language are also looked up in the fallback languages.
+    pub fn with_fallback(self, language: impl Into<String>) -> Self {
+        let mut fallbacks = self.fallbacks.to_vec();
+        fallbacks.push(language.into().to_lowercase());
+        Self {
+            fallbacks: fallbacks.into(),
+            ..self
+        }
+    }
+
+    /// Chooses the language to respond to the update in.
+    pub fn negotiate(&self, update: &Update) -> Locale {
+        let user = user(update);
+        let chat_language = self.chat_language.as_ref().and_then(|chat_language| {
+            let chat_id = chat_id(update).or_else(|| user.map(|user| user.id))?;
+            chat_language(chat_id)
+        });
+        let user_language = user.and_then(|user| user.language_code.as_deref());
+        let primary_language =
+            user_language.and_then(|language| Some(language.split_once('-')?.0.to_string()));
+
+        let language = chat_language
+            .into_iter()
+            .chain(user_language.map(str::to_string))
+            .chain(primary_language)
+            .map(|language| language.to_lowercase())
+            .chain(self.fallbacks.iter().cloned())
+            .find(|language| self.translator.supports(language));
+        Locale {
+            language,
+            translator: Arc::clone(&self.translator),
+            fallbacks: Arc::clone(&self.fallbacks),
+        }
+    }
+}
+
+impl AsRef<LanguageNegotiator> for LanguageNegotiator {
+    fn as_ref(&self) -> &LanguageNegotiator {
+        self
+    }
+}
+
+/// The user who caused the update, if any.
+fn user(update: &Update) -> Option<&User> {
+    match &update.kind {
+        UpdateKind::InlineQuery { inline_query } => Some(&inline_query.from),
+        UpdateKind::CallbackQuery { callback_query } => Some(&callback_query.from),
+        UpdateKind::MyChatMemberUpdated { my_chat_member: u }
+        | UpdateKind::ChatMemberUpdated { chat_member: u } => Some(&u.from),
+        UpdateKind::ChatJoinRequest { chat_join_request } => Some(&chat_join_request.from),
+        kind => kind.any_message()?.from.as_ref(),
+    }
+}
+
+/// The chat where the update happened, if any.
+fn chat_id(update: &Update) -> Option<i64> {
+    match &update.kind {
+        UpdateKind::CallbackQuery { callback_query } => {
+            Some(callback_query.message.as_ref()?.chat.id)
+        }
+        UpdateKind::MyChatMemberUpdated { my_chat_member: u }
+        | UpdateKind::ChatMemberUpdated { chat_member: u } => Some(u.chat.id),
+        UpdateKind::ChatJoinRequest { chat_join_request } => Some(chat_join_request.chat.id),
+        kind => Some(kind.any_message()?.chat.id),
+    }
+}
+
+/// The language chosen for the update by the [`LanguageNegotiator`] in the state of the dispatcher.
+#[derive(Clone)]
+pub struct Locale {
+    language: Option<String>,
+    translator: Arc<dyn Translator + Send + Sync>,
+    fallbacks: Arc<[String]>,
+}
+
+impl Locale {
+    /// Gets the chosen language, or `None` if no language is supported, not even the fallback languages.
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Gets the text of the key in the chosen language, or in the fallback languages if it is not translated.
+    ///
+    /// Returns the key itself if the text is not translated into any of the languages.
+    pub fn translate(&self, key: &str) -> String {
+        self.language
+            .iter()
+            .chain(self.fallbacks.iter())
+            .find_map(|language| self.translator.translate(language, key))
+            .unwrap_or_else(|| key.to_string())
+    }
+}
+
+impl std::fmt::Debug for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Locale")
+            .field("language", &self.language)
+            .field("fallbacks", &self.fallbacks)
+            .finish()
+    }
+}
+
+impl<S: AsRef<LanguageNegotiator>> FromUpdate<S> for Locale {
+    fn from_update(update: &Update, state: &S) -> Option<Self> {
+        Some(state.as_ref().negotiate(update))
+    }
+}
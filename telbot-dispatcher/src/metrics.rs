@@ -0,0 +1,146 @@
+//! Timing of handlers, to find which handler makes updates back up.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Receives the time taken by each handler run by the dispatcher.
+///
+/// Handlers are identified by the names of their routes, like `/start` for [`Dispatcher::on_command`]
+/// or the name given with [`Dispatcher::named`].
+///
+/// [`Dispatcher::on_command`]: crate::Dispatcher::on_command
+/// [`Dispatcher::named`]: crate::Dispatcher::named
+pub trait Metrics {
+    /// Called after a handler finishes.
+    fn handled(&self, route: &str, elapsed: Duration);
+
+    /// Called after a handler finishes, if it took longer than the threshold set with
+    /// [`Dispatcher::with_slow_threshold`](crate::Dispatcher::with_slow_threshold).
+    ///
+    /// Does nothing by default. Implement this to warn about slow handlers in your logger.
+    fn slow(&self, route: &str, elapsed: Duration) {
+        let _ = (route, elapsed);
+    }
+
+    /// Called when no handler is run for an update.
+    fn unhandled(&self) {}
+}
+
+impl<M: Metrics + ?Sized> Metrics for Arc<M> {
+    fn handled(&self, route: &str, elapsed: Duration) {
+        (**self).handled(route, elapsed)
+    }
+
+    fn slow(&self, route: &str, elapsed: Duration) {
+        (**self).slow(route, elapsed)
+    }
+
+    fn unhandled(&self) {
+        (**self).unhandled()
+    }
+}
+
+/// Counts and times of a handler, collected by [`HandlerStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HandlerStat {
+    /// Number of times the handler was run.
+    pub count: u64,
+    /// Number of times the handler was slower than the threshold.
+    pub slow_count: u64,
+    /// Total time taken by the handler.
+    pub total: Duration,
+    /// Longest time taken by the handler at once.
+    pub max: Duration,
+}
+
+impl HandlerStat {
+    /// Gets the average time taken by the handler.
+    pub fn average(&self) -> Duration {
+        match self.count {
+            0 => Duration::ZERO,
+            count => self.total / count.min(u32::MAX as u64) as u32,
+        }
+    }
+}
+
+/// Collects [`HandlerStat`]s of each route.
+///
+/// Share it with the dispatcher through [`Arc`] to read the stats while dispatching.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use std::time::Duration;
+/// # use telbot_dispatcher::Dispatcher;
+/// # use telbot_dispatcher::metrics::HandlerStats;
+/// let stats = Arc::new(HandlerStats::new());
+/// let dispatcher = Dispatcher::<()>::new()
+///     .on_command("report", || async { /* ... */ })
+///     .with_metrics(stats.clone())
+///     .with_slow_threshold(Duration::from_secs(1));
+/// // after dispatching updates:
+/// for (route, stat) in stats.slowest() {
+///     println!("{}: {} runs, {:?} on average", route, stat.count, stat.average());
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct HandlerStats {
+    routes: Mutex<HashMap<String, HandlerStat>>,
+    unhandled: Mutex<u64>,
+}
+
+impl HandlerStats {
+    /// Creates a new empty [`HandlerStats`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the stat of the route, if the handler of the route was ever run.
+    pub fn get(&self, route: &str) -> Option<HandlerStat> {
+        self.routes.lock().unwrap().get(route).copied()
+    }
+
+    /// Gets the stats of all routes, sorted by the total time taken in descending order.
+    pub fn slowest(&self) -> Vec<(String, HandlerStat)> {
+        let mut stats: Vec<_> = self
+            .routes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(route, stat)| (route.clone(), *stat))
+            .collect();
+        stats.sort_by_key(|(_, stat)| Reverse(stat.total));
+        stats
+    }
+
+    /// Gets the number of updates which no handler was run for.
+    pub fn unhandled_count(&self) -> u64 {
+        *self.unhandled.lock().unwrap()
+    }
+
+    /// Clears all stats.
+    pub fn reset(&self) {
+        self.routes.lock().unwrap().clear();
+        *self.unhandled.lock().unwrap() = 0;
+    }
+}
+
+impl Metrics for HandlerStats {
+    fn handled(&self, route: &str, elapsed: Duration) {
+        let mut routes = self.routes.lock().unwrap();
+        let stat = routes.entry(route.to_string()).or_default();
+        stat.count += 1;
+        stat.total += elapsed;
+        stat.max = stat.max.max(elapsed);
+    }
+
+    fn slow(&self, route: &str, _elapsed: Duration) {
+        let mut routes = self.routes.lock().unwrap();
+        routes.entry(route.to_string()).or_default().slow_count += 1;
+    }
+
+    fn unhandled(&self) {
+        *self.unhandled.lock().unwrap() += 1;
+    }
+}
@@ -0,0 +1,50 @@
+use std::thread;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use telbot_hyper::types::bot::GetMe;
+use telbot_hyper::{Api, ApiBuilder};
+use tiny_http::{Header, Response, Server};
+
+const ME: &str =
+    r#"{"ok":true,"result":{"id":1234,"is_bot":true,"first_name":"Kiwi","username":"KiwiBot"}}"#;
+
+/// Serves every request with the response of `getMe`, returning the address of the server.
+fn serve() -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", server.server_addr().to_ip().unwrap());
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+            let _ = request.respond(Response::from_string(ME).with_header(content_type));
+        }
+    });
+    address
+}
+
+fn bench_get_me(c: &mut Criterion, name: &str, builder: ApiBuilder) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let api = builder.build();
+    c.bench_function(name, |b| {
+        b.iter(|| runtime.block_on(api.send_json(&GetMe)).unwrap())
+    });
+}
+
+fn connection_reuse(c: &mut Criterion) {
+    let address = serve();
+    bench_get_me(
+        c,
+        "getMe with pooled connections",
+        Api::builder("1234:TOKEN").server(&address),
+    );
+    bench_get_me(
+        c,
+        "getMe without pooled connections",
+        Api::builder("1234:TOKEN").server(&address).pool_max_idle(0),
+    );
+}
+
+criterion_group!(benches, connection_reuse);
+criterion_main!(benches);
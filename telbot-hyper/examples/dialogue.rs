@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+
+use telbot_dispatcher::{Dispatcher, State};
+use telbot_hyper::types::message::Message;
+use telbot_hyper::types::update::GetUpdates;
+use telbot_hyper::Api;
+
+/// The step of the registration dialogue of each chat.
+enum Dialogue {
+    ReceiveName,
+    ReceiveAge { name: String },
+}
+
+/// The state shared by the handlers.
+#[derive(Clone)]
+struct Bot {
+    api: Api,
+    dialogues: Arc<Mutex<HashMap<i64, Dialogue>>>,
+}
+
+async fn start(message: Message, State(bot): State<Bot>) {
+    bot.dialogues
+        .lock()
+        .unwrap()
+        .insert(message.chat.id, Dialogue::ReceiveName);
+    let request = message.reply_text("What is your name?");
+    bot.api.send_json(&request).await.unwrap();
+}
+
+async fn cancel(message: Message, State(bot): State<Bot>) {
+    bot.dialogues.lock().unwrap().remove(&message.chat.id);
+    let request = message.reply_text("Cancelled.");
+    bot.api.send_json(&request).await.unwrap();
+}
+
+async fn receive(message: Message, State(bot): State<Bot>) {
+    let text = match message.kind.text() {
+        Some(text) => text,
+        None => return,
+    };
+    // The lock must be released before awaiting, as other handlers may lock the map meanwhile.
+    let dialogue = bot.dialogues.lock().unwrap().remove(&message.chat.id);
+    let (reply, next) = match dialogue {
+        None => return,
+        Some(Dialogue::ReceiveName) => (
+            format!("Nice to meet you, {}. How old are you?", text),
+            Some(Dialogue::ReceiveAge {
+                name: text.to_string(),
+            }),
+        ),
+        Some(Dialogue::ReceiveAge { name }) => match text.parse::<u8>() {
+            Ok(age) => (format!("Registered {}, aged {}.", name, age), None),
+            Err(_) => (
+                "Please send a number.".to_string(),
+                Some(Dialogue::ReceiveAge { name }),
+            ),
+        },
+    };
+    if let Some(next) = next {
+        bot.dialogues.lock().unwrap().insert(message.chat.id, next);
+    }
+    bot.api.send_json(&message.reply_text(reply)).await.unwrap();
+}
+
+#[tokio::main]
+async fn main() {
+    let bot = Bot {
+        api: Api::new(env::var("BOT_TOKEN").unwrap()),
+        dialogues: Arc::default(),
+    };
+
+    let dispatcher = Dispatcher::new()
+        .on_command("start", start)
+        .on_command("cancel", cancel)
+        .on_message(receive);
+
+    let mut offset = 0;
+    loop {
+        let get_updates = GetUpdates::new().with_offset(offset as i32).with_timeout(1);
+
+        let updates = bot.api.send_json(&get_updates).await.unwrap();
+        for update in updates {
+            dispatcher.dispatch(&update, &bot).await;
+            offset = offset.max(update.update_id + 1);
+        }
+    }
+}
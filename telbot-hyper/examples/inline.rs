@@ -0,0 +1,62 @@
+use std::env;
+
+use telbot_hyper::types::query::{InlineQueryResultKind, InputMessageContent};
+use telbot_hyper::types::update::GetUpdates;
+use telbot_hyper::Api;
+
+const FRUITS: &[(&str, &str)] = &[
+    ("Apple", "🍎"),
+    ("Banana", "🍌"),
+    ("Cherry", "🍒"),
+    ("Grape", "🍇"),
+    ("Kiwi", "🥝"),
+    ("Lemon", "🍋"),
+    ("Peach", "🍑"),
+    ("Strawberry", "🍓"),
+];
+
+#[tokio::main]
+async fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+
+    // Enable the inline mode with /setinline on @BotFather first.
+    let mut offset = 0;
+    loop {
+        let get_updates = GetUpdates::new().with_offset(offset as i32).with_timeout(1);
+
+        let updates = api.send_json(&get_updates).await.unwrap();
+        for update in updates {
+            if let Some(query) = update.kind.inline_query() {
+                let keyword = query.query.to_lowercase();
+                let matches: Vec<_> = FRUITS
+                    .iter()
+                    .filter(|(name, _)| name.to_lowercase().contains(&keyword))
+                    .collect();
+                // Answer 5 results at a time, loading more as the user scrolls.
+                let answer = query.answer_page(5, |start, count| {
+                    matches
+                        .iter()
+                        .skip(start)
+                        .take(count)
+                        .map(|(name, emoji)| {
+                            InlineQueryResultKind::article(*name, InputMessageContent::text(*emoji))
+                                .with_description(format!("Send {}", emoji))
+                                .with_stable_id(name)
+                        })
+                        .collect()
+                });
+                // Ignore queries whose offset was not set by this bot.
+                let answer = match answer {
+                    Ok(answer) => answer,
+                    Err(_) => continue,
+                };
+                let request = answer.with_cache_time(60);
+                let api = api.clone();
+                tokio::spawn(async move {
+                    api.send_json(&request).await.unwrap();
+                });
+            }
+            offset = offset.max(update.update_id + 1);
+        }
+    }
+}
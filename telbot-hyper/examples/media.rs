@@ -0,0 +1,65 @@
+use std::env;
+
+use telbot_hyper::types::file::{GetFile, InputFile};
+use telbot_hyper::types::message::Message;
+use telbot_hyper::types::update::GetUpdates;
+use telbot_hyper::Api;
+
+#[tokio::main]
+async fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+
+    let mut offset = 0;
+    loop {
+        let get_updates = GetUpdates::new().with_offset(offset as i32).with_timeout(1);
+
+        let updates = api.send_json(&get_updates).await.unwrap();
+        for update in updates {
+            if let Some(message) = update.kind.message() {
+                let message = message.clone();
+                let api = api.clone();
+                tokio::spawn(async move {
+                    send_back(&api, &message).await;
+                });
+            }
+            offset = offset.max(update.update_id + 1);
+        }
+    }
+}
+
+/// Sends photos back as documents, and documents back as they are.
+async fn send_back(api: &Api, message: &Message) {
+    let (file_id, name, mime) = if let Some(sizes) = message.kind.photo() {
+        // the last size is the largest one
+        let largest = sizes.last().unwrap();
+        (
+            largest.file_id.clone(),
+            "photo.jpg".to_string(),
+            "image/jpeg".to_string(),
+        )
+    } else if let Some(document) = message.kind.document() {
+        (
+            document.file_id.clone(),
+            document
+                .file_name
+                .clone()
+                .unwrap_or_else(|| "document".to_string()),
+            document
+                .mime_type
+                .clone()
+                .unwrap_or_else(|| "application/octet-stream".to_string()),
+        )
+    } else {
+        return;
+    };
+
+    // Download the file from Telegram servers...
+    let file = api.send_json(&GetFile::new(file_id)).await.unwrap();
+    let data = api.download_file(&file).await.unwrap();
+
+    // ...and upload it again.
+    let document = InputFile { name, data, mime };
+    api.send_file(&message.reply_document(document))
+        .await
+        .unwrap();
+}
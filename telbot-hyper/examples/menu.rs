@@ -0,0 +1,100 @@
+use std::env;
+use std::time::Duration;
+
+use telbot_dispatcher::{CallbackAnswer, Dispatcher, State};
+use telbot_hyper::types::markup::{
+    InlineKeyboardButtonKind, InlineKeyboardMarkup, InlineKeyboardRow,
+};
+use telbot_hyper::types::message::Message;
+use telbot_hyper::types::query::CallbackQuery;
+use telbot_hyper::types::update::GetUpdates;
+use telbot_hyper::Api;
+
+fn button(text: &str, data: &str) -> InlineKeyboardRow {
+    InlineKeyboardRow::new_emplace(
+        text,
+        InlineKeyboardButtonKind::Callback {
+            callback_data: data.to_string(),
+        },
+    )
+}
+
+fn main_menu() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new_with_row(button("Drinks", "menu:drinks"))
+        .with_row(button("Desserts", "menu:desserts"))
+}
+
+fn sub_menu(items: &[&str]) -> InlineKeyboardMarkup {
+    let mut markup = InlineKeyboardMarkup::new_with_row(button("« Back", "menu:main"));
+    for item in items {
+        markup = markup.with_row(button(item, &format!("order:{}", item)));
+    }
+    markup
+}
+
+async fn show_menu(message: Message, State(api): State<Api>) {
+    let request = message.reply_text("What would you like?");
+    api.send_json(&request.with_reply_markup(main_menu()))
+        .await
+        .unwrap();
+}
+
+async fn navigate(query: CallbackQuery, State(api): State<Api>) {
+    if let Some(message) = &query.message {
+        let edit = match query.data.as_deref() {
+            Some("menu:drinks") => message
+                .edit_text("Drinks")
+                .with_reply_markup(sub_menu(&["Coffee", "Tea", "Juice"])),
+            Some("menu:desserts") => message
+                .edit_text("Desserts")
+                .with_reply_markup(sub_menu(&["Cake", "Ice cream"])),
+            _ => message
+                .edit_text("What would you like?")
+                .with_reply_markup(main_menu()),
+        };
+        api.send_json(&edit).await.unwrap();
+    }
+    // The dispatcher stops the progress bar on the button with an empty answer.
+}
+
+async fn order(query: CallbackQuery, answer: CallbackAnswer, State(api): State<Api>) {
+    let item = query.data.as_deref().unwrap_or_default();
+    let text = format!("One {} coming up!", item.trim_start_matches("order:"));
+    let request = answer.request().with_text(text);
+    answer.answer(&api, &request).await.unwrap();
+}
+
+#[tokio::main]
+async fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+
+    let dispatcher = Dispatcher::new()
+        .on_command("menu", show_menu)
+        .on_callback_data("menu:", navigate)
+        .on_callback_data("order:", order)
+        .with_callback_answer(
+            Duration::from_secs(5),
+            tokio::time::sleep,
+            |api: &Api, answer| {
+                let api = api.clone();
+                async move {
+                    let _ = api.send_json(&answer).await;
+                }
+            },
+        );
+    // Ignore `/menu@OtherBot` in groups.
+    if let Some(username) = api.me().await.unwrap().username {
+        dispatcher.set_bot_username(username);
+    }
+
+    let mut offset = 0;
+    loop {
+        let get_updates = GetUpdates::new().with_offset(offset as i32).with_timeout(1);
+
+        let updates = api.send_json(&get_updates).await.unwrap();
+        for update in updates {
+            dispatcher.dispatch(&update, &api).await;
+            offset = offset.max(update.update_id + 1);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::env;
+
+use telbot_hyper::types::update::Update;
+use telbot_hyper::types::webhook::{SetWebhook, WebhookReply};
+use telbot_hyper::webhook::WebhookServer;
+use telbot_hyper::Api;
+
+#[tokio::main]
+async fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+    // The public HTTPS URL which forwards to this server, e.g. `https://example.com/telegram`
+    let url = env::var("WEBHOOK_URL").unwrap();
+    let secret_token = env::var("WEBHOOK_SECRET").unwrap();
+
+    api.send_file(&SetWebhook::new(url).with_secret_token(&secret_token))
+        .await
+        .unwrap();
+
+    WebhookServer::new("/telegram")
+        .with_secret_token(secret_token)
+        .on_rejection(|rejection| eprintln!("rejected an update: {}", rejection))
+        .serve_with_shutdown(
+            ([0, 0, 0, 0], 8080).into(),
+            |update: Update| async move {
+                // Echo in the response to the webhook request, saving a round trip.
+                let message = update.kind.message()?;
+                let text = message.kind.text()?;
+                WebhookReply::new(&message.reply_text(text)).ok()
+            },
+            async {
+                tokio::signal::ctrl_c().await.unwrap();
+            },
+        )
+        .await
+        .unwrap();
+}
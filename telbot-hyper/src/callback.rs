@@ -0,0 +1,52 @@
+use telbot_types::query::{AnswerCallbackQuery, CallbackQuery};
+
+use crate::{Api, Result};
+
+/// Answers a callback query automatically if it was not answered explicitly.
+///
+/// Telegram clients display a progress bar until the callback query is answered.
+/// Create this guard before handling a callback query;
+/// when the guard is dropped without [`CallbackAnswer::answer`] being called,
+/// an empty answer is sent in a task spawned with `tokio::spawn`, so the progress bar does not hang.
+///
+/// The empty answer is not sent if the guard is dropped outside of a Tokio runtime.
+pub struct CallbackAnswer {
+    api: Api,
+    query_id: Option<String>,
+}
+
+impl CallbackAnswer {
+    /// Creates a new guard for the given callback query.
+    pub fn new(api: &Api, query: &CallbackQuery) -> Self {
+        Self {
+            api: api.clone(),
+            query_id: Some(query.id.clone()),
+        }
+    }
+
+    /// Answers the query explicitly, disabling the automatic answer.
+    pub async fn answer(mut self, answer: &AnswerCallbackQuery) -> Result<bool> {
+        self.query_id = None;
+        self.api.send_json(answer).await
+    }
+
+    /// Disables the automatic answer, e.g. when the query was answered elsewhere.
+    pub fn dismiss(mut self) {
+        self.query_id = None;
+    }
+}
+
+impl Drop for CallbackAnswer {
+    fn drop(&mut self) {
+        let query_id = match self.query_id.take() {
+            Some(query_id) => query_id,
+            None => return,
+        };
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let api = self.api.clone();
+            runtime.spawn(async move {
+                let _ = api.send_json(&AnswerCallbackQuery::new(query_id)).await;
+            });
+        }
+    }
+}
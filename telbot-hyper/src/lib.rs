@@ -4,19 +4,145 @@
 //! send it to the Telegram server, and get a response.
 //! Sending request will be done with [`Api::send_json`] and [`Api::send_file`] methods.
 
+pub mod callback;
+mod connector;
+#[cfg(feature = "webhook")]
+pub mod run;
+#[cfg(feature = "self-signed")]
+pub mod tls;
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+use std::future::Future;
 use std::io::Cursor;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
 
-use hyper::{body::Buf, client::HttpConnector, Body, Client, Request, Response};
+#[cfg(feature = "simd-json")]
+use bytes::BytesMut;
+use connector::Connector;
+use hyper::body::Bytes;
+use hyper::{client::HttpConnector, Body, Client, Request, Response};
 use hyper_multipart_rfc7578::client::multipart::{self, Form};
-use hyper_tls::HttpsConnector;
+use serde::de::DeserializeOwned;
 pub use telbot_types as types;
-use types::{ApiResponse, FileMethod, JsonMethod, TelegramError, TelegramMethod};
+use types::bot::{BotToken, GetMe};
+use types::chat::ChatPhoto;
+use types::file::{DownloadCheck, File, GetFile, IntegrityError};
+use types::form::{to_form_fields, FormValue};
+use types::user::User;
+use types::webhook::WebhookReply;
+use types::{
+    ApiErrorKind, ApiResponse, FileMethod, JsonMethod, RequestOptions, RetryPolicy, TelegramClient,
+    TelegramError, TelegramMethod,
+};
 
 /// Telegram API requester.
 #[derive(Clone)]
 pub struct Api {
     base_url: String,
-    client: Client<HttpsConnector<HttpConnector>>,
+    file_base_url: String,
+    client: Client<Connector>,
+    default_headers: Vec<(String, String)>,
+    retry: Option<RetryPolicy>,
+    me: Arc<Mutex<Option<User>>>,
+}
+
+/// Builder of [`Api`] with HTTP client options.
+///
+/// Useful for bots with high request rates, which benefit from connection reuse.
+pub struct ApiBuilder {
+    token: BotToken,
+    server: String,
+    http2: bool,
+    http2_keep_alive_interval: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    tcp_keepalive: Option<Duration>,
+}
+
+impl ApiBuilder {
+    /// Sends requests to the server, like `http://localhost:8081`, instead of `https://api.telegram.org`,
+    /// e.g. a [local Bot API server](https://github.com/tdlib/telegram-bot-api).
+    pub fn server(self, server: impl Into<String>) -> Self {
+        let mut server = server.into();
+        while server.ends_with('/') {
+            server.pop();
+        }
+        Self { server, ..self }
+    }
+
+    /// Offers HTTP/2 during the TLS handshake, multiplexing requests over a single connection
+    /// if the server accepts it, like `api.telegram.org` does.
+    ///
+    /// Servers which do not accept it, or are reached without TLS, are requested with HTTP/1.1.
+    pub fn http2(self) -> Self {
+        Self {
+            http2: true,
+            ..self
+        }
+    }
+
+    /// Sets the interval of HTTP/2 ping frames which keep the connection alive.
+    ///
+    /// Takes effect only on connections using HTTP/2.
+    pub fn http2_keep_alive_interval(self, interval: Duration) -> Self {
+        Self {
+            http2_keep_alive_interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Sets the timeout after which idle pooled connections are closed.
+    ///
+    /// Defaults to 90 seconds.
+    pub fn pool_idle_timeout(self, timeout: Duration) -> Self {
+        Self {
+            pool_idle_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of idle pooled connections.
+    pub fn pool_max_idle(self, max: usize) -> Self {
+        Self {
+            pool_max_idle_per_host: Some(max),
+            ..self
+        }
+    }
+
+    /// Sets TCP keep-alive interval of connections.
+    pub fn tcp_keepalive(self, interval: Duration) -> Self {
+        Self {
+            tcp_keepalive: Some(interval),
+            ..self
+        }
+    }
+
+    /// Creates a new API requester.
+    pub fn build(self) -> Api {
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        http.set_keepalive(self.tcp_keepalive);
+
+        let mut client = Client::builder();
+        client.http2_keep_alive_interval(self.http2_keep_alive_interval);
+        if let Some(timeout) = self.pool_idle_timeout {
+            client.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            client.pool_max_idle_per_host(max);
+        }
+
+        Api {
+            base_url: format!("{}/bot{}/", self.server, self.token.as_str()),
+            file_base_url: format!("{}/file/bot{}/", self.server, self.token.as_str()),
+            client: client.build(Connector::new(http, self.http2)),
+            default_headers: vec![],
+            retry: None,
+            me: Arc::default(),
+        }
+    }
 }
 
 /// Error that can occur while requesting and responding to the server.
@@ -26,11 +152,34 @@ pub enum Error {
     Hyper(hyper::Error),
     Serde(serde_json::Error),
     Mime(mime::FromStrError),
+    Http(hyper::http::Error),
+    Integrity(IntegrityError),
 }
 
 /// Result having [`Error`] as error type.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Creates a webhook response performing the given method.
+///
+/// The result of the method is not available, but a round trip to Telegram is saved.
+pub fn respond_with<Method: JsonMethod>(method: &Method) -> Result<Response<Body>> {
+    let body = serde_json::to_vec(&WebhookReply::new(method)?)?;
+    Ok(Response::builder()
+        .header("Content-Type", "application/json")
+        .body(Body::from(body))
+        .unwrap())
+}
+
+impl Error {
+    /// Gets the kind of the error returned by Telegram, if any.
+    pub fn api_error_kind(&self) -> Option<ApiErrorKind> {
+        match self {
+            Self::Telegram(error) => Some(error.kind()),
+            _ => None,
+        }
+    }
+}
+
 impl From<hyper::Error> for Error {
     fn from(e: hyper::Error) -> Self {
         Self::Hyper(e)
@@ -49,69 +198,312 @@ impl From<mime::FromStrError> for Error {
     }
 }
 
+impl From<hyper::http::Error> for Error {
+    fn from(e: hyper::http::Error) -> Self {
+        Self::Http(e)
+    }
+}
+
 impl Api {
     /// Creates a new API requester with bot token.
-    pub fn new(token: impl AsRef<str>) -> Self {
+    pub fn new(token: impl Into<BotToken>) -> Self {
+        Self::builder(token).build()
+    }
+
+    /// Creates a new [`ApiBuilder`] to configure the underlying HTTP client.
+    pub fn builder(token: impl Into<BotToken>) -> ApiBuilder {
+        ApiBuilder {
+            token: token.into(),
+            server: "https://api.telegram.org".to_string(),
+            http2: false,
+            http2_keep_alive_interval: None,
+            pool_idle_timeout: None,
+            pool_max_idle_per_host: None,
+            tcp_keepalive: None,
+        }
+    }
+
+    /// Sets HTTP headers sent with every request, including file downloads.
+    pub fn with_default_headers<K: Into<String>, V: Into<String>>(
+        self,
+        headers: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        Self {
+            default_headers: headers
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Retries requests which failed with flood control or transient transport errors, following the policy.
+    pub fn with_retry(self, policy: RetryPolicy) -> Self {
         Self {
-            base_url: format!("https://api.telegram.org/bot{}/", token.as_ref()),
-            client: Client::builder().build(HttpsConnector::new()),
+            retry: Some(policy),
+            ..self
         }
     }
 
+    fn request(
+        &self,
+        method: hyper::Method,
+        url: &str,
+        options: &RequestOptions,
+    ) -> hyper::http::request::Builder {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(options.url_with_query(url));
+        for (name, value) in self.default_headers.iter().chain(&options.headers) {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request
+    }
+
     /// Sends a JSON-serializable API request.
     pub async fn send_json<Method: JsonMethod>(&self, method: &Method) -> Result<Method::Response> {
+        self.send_json_with(method, &RequestOptions::default())
+            .await
+    }
+
+    /// Sends a JSON-serializable API request with extra headers and query parameters.
+    pub async fn send_json_with<Method: JsonMethod>(
+        &self,
+        method: &Method,
+        options: &RequestOptions,
+    ) -> Result<Method::Response> {
         let body = serde_json::to_vec(method)?;
+        let url = format!("{}{}", self.base_url, Method::name());
 
-        let request = Request::builder()
-            .method(&hyper::Method::POST)
-            .uri(format!("{}{}", self.base_url, Method::name()))
-            .header("Content-Type", "application/json")
-            .body(Body::from(body))
-            .unwrap();
+        self.retrying(|| async {
+            let request = self
+                .request(hyper::Method::POST, &url, options)
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.clone()))?;
 
-        let response = self.client.request(request).await?;
-        Self::parse_response::<Method>(response).await
+            let response = self.client.request(request).await?;
+            Self::parse_response::<Method>(response).await
+        })
+        .await
+    }
+
+    async fn retrying<T, F: Future<Output = Result<T>>>(
+        &self,
+        mut send: impl FnMut() -> F,
+    ) -> Result<T> {
+        let mut retries = 0;
+        loop {
+            let result = send().await;
+            let policy = match &self.retry {
+                Some(policy) => policy,
+                None => return result,
+            };
+            let delay = match &result {
+                Err(Error::Telegram(error)) => policy.delay_for(retries, error),
+                Err(Error::Hyper(error)) if is_transient(error) => policy.delay(retries, None),
+                _ => None,
+            };
+            match delay {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return result,
+            }
+            retries += 1;
+        }
     }
 
     /// Sends a API request with files.
     pub async fn send_file<Method: FileMethod>(&self, method: &Method) -> Result<Method::Response> {
+        self.send_file_with(method, &RequestOptions::default())
+            .await
+    }
+
+    /// Sends a API request with files, extra headers and query parameters.
+    pub async fn send_file_with<Method: FileMethod>(
+        &self,
+        method: &Method,
+        options: &RequestOptions,
+    ) -> Result<Method::Response> {
         let url = format!("{}{}", self.base_url, Method::name());
-        let files = method.files();
-        let serialized = serde_json::to_value(method).unwrap();
-
-        let mut form = Form::default();
-        for (key, value) in serialized.as_object().unwrap() {
-            if let Some(file) = files.as_ref().and_then(|map| map.get(key.as_str())) {
-                // Form::set_body_convert requires reader to be 'static.
-                form.add_reader_file_with_mime(
-                    key,
-                    Cursor::new(file.data.clone()),
-                    &file.name,
-                    file.mime.parse()?,
-                );
-            } else if let Some(value) = value.as_str() {
-                form.add_text(key, value);
-            } else {
-                form.add_text(key, value.to_string());
+        let fields = to_form_fields(method)?;
+
+        self.retrying(|| async {
+            let mut form = Form::default();
+            for (key, value) in &fields {
+                match value {
+                    FormValue::Text(text) => form.add_text(key, text),
+                    // Form::set_body_convert requires reader to be 'static.
+                    FormValue::File(file) => form.add_reader_file_with_mime(
+                        key,
+                        Cursor::new(file.data.clone()),
+                        &file.name,
+                        file.mime.parse()?,
+                    ),
+                }
             }
+
+            let request = self.request(hyper::Method::POST, &url, options);
+            let request = form.set_body_convert::<hyper::Body, multipart::Body>(request)?;
+            let response = self.client.request(request).await?;
+            Self::parse_response::<Method>(response).await
+        })
+        .await
+    }
+
+    /// Performs the method of a webhook reply as a request, e.g. when the update was received by polling.
+    ///
+    /// The result of the method is returned as JSON, since its type is not known.
+    pub async fn send_reply(&self, reply: &WebhookReply) -> Result<serde_json::Value> {
+        let body = serde_json::to_vec(reply.parameters())?;
+        let url = format!("{}{}", self.base_url, reply.method());
+
+        self.retrying(|| async {
+            let request = self
+                .request(hyper::Method::POST, &url, &RequestOptions::default())
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.clone()))?;
+
+            let response = self.client.request(request).await?;
+            let body = hyper::body::to_bytes(response).await?;
+            match from_json_body(body)? {
+                ApiResponse::Ok { result } => Ok(result),
+                ApiResponse::Err(e) => Err(Error::Telegram(e)),
+            }
+        })
+        .await
+    }
+
+    /// Gets the bot itself, sending [`GetMe`] only the first time.
+    ///
+    /// The bot is cached in this requester and its clones,
+    /// e.g. to ignore commands addressed to other bots with its username.
+    pub async fn me(&self) -> Result<User> {
+        if let Some(user) = &*self.me.lock().unwrap_or_else(PoisonError::into_inner) {
+            return Ok(user.clone());
         }
+        let user = self.send_json(&GetMe).await?;
+        *self.me.lock().unwrap_or_else(PoisonError::into_inner) = Some(user.clone());
+        Ok(user)
+    }
+
+    /// Downloads the small (160x160) chat photo.
+    pub async fn download_chat_photo_small(&self, photo: &ChatPhoto) -> Result<Vec<u8>> {
+        self.download(&photo.get_small()).await
+    }
+
+    /// Downloads the big (640x640) chat photo.
+    pub async fn download_chat_photo_big(&self, photo: &ChatPhoto) -> Result<Vec<u8>> {
+        self.download(&photo.get_big()).await
+    }
 
-        let request = Request::builder().method(&hyper::Method::POST).uri(url);
-        let request = form
-            .set_body_convert::<hyper::Body, multipart::Body>(request)
-            .unwrap();
+    /// Downloads the file.
+    ///
+    /// If the path of the file is unknown, e.g. the file is from a message, it is requested with [`GetFile`] first.
+    /// Bots can download files of up to 20 MB in size.
+    pub async fn download_file(&self, file: &File) -> Result<Vec<u8>> {
+        let body = self.download_file_stream(file).await?;
+        Ok(hyper::body::to_bytes(body).await?.to_vec())
+    }
+
+    /// Downloads the file as a stream of chunks, without keeping the whole file in memory.
+    pub async fn download_file_stream(&self, file: &File) -> Result<Body> {
+        let file_path = match &file.file_path {
+            Some(file_path) => file_path.clone(),
+            None => self
+                .send_json(&GetFile::new(&file.file_id))
+                .await?
+                .file_path
+                .ok_or_else(|| {
+                    Error::Telegram(TelegramError {
+                        error_code: 0,
+                        description: "File is not available for download".to_string(),
+                        parameters: None,
+                    })
+                })?,
+        };
+
+        let request = self
+            .request(
+                hyper::Method::GET,
+                &format!("{}{}", self.file_base_url, file_path),
+                &RequestOptions::default(),
+            )
+            .body(Body::empty())?;
         let response = self.client.request(request).await?;
-        Self::parse_response::<Method>(response).await
+        if response.status().is_success() {
+            Ok(response.into_body())
+        } else {
+            let body = hyper::body::to_bytes(response).await?;
+            Err(Error::Telegram(serde_json::from_slice(&body)?))
+        }
+    }
+
+    /// Downloads the file and verifies the content, e.g. to detect truncated downloads.
+    pub async fn download_file_checked(
+        &self,
+        file: &File,
+        check: &DownloadCheck,
+    ) -> Result<Vec<u8>> {
+        let data = self.download_file(file).await?;
+        check.verify(file, &data).map_err(Error::Integrity)?;
+        Ok(data)
+    }
+
+    async fn download(&self, get_file: &GetFile) -> Result<Vec<u8>> {
+        let file = self.send_json(get_file).await?;
+        self.download_file(&file).await
     }
 
     async fn parse_response<Method: TelegramMethod>(
         response: Response<Body>,
     ) -> Result<Method::Response> {
-        let body = hyper::body::aggregate(response).await?;
-        let tg_response: ApiResponse<_> = serde_json::from_reader(body.reader())?;
+        let body = hyper::body::to_bytes(response).await?;
+        let tg_response: ApiResponse<_> = from_json_body(body)?;
         match tg_response {
             ApiResponse::Ok { result } => Ok(result),
             ApiResponse::Err(e) => Err(Error::Telegram(e)),
         }
     }
 }
+
+/// Lets bot logic written against [`TelegramClient`] send requests with this requester.
+impl TelegramClient for Api {
+    type Error = Error;
+
+    async fn execute_json<M: JsonMethod>(&self, method: &M) -> Result<M::Response> {
+        self.send_json(method).await
+    }
+
+    async fn execute_file<M: FileMethod>(&self, method: &M) -> Result<M::Response> {
+        self.send_file(method).await
+    }
+}
+
+/// Checks if the request may succeed when sent again.
+fn is_transient(error: &hyper::Error) -> bool {
+    error.is_connect() || error.is_closed() || error.is_incomplete_message() || error.is_timeout()
+}
+
+/// Deserializes JSON with simd-json, falling back to serde_json on failure.
+#[cfg(feature = "simd-json")]
+fn from_json_body<T: DeserializeOwned>(body: Bytes) -> serde_json::Result<T> {
+    // simd-json parses in place, which takes the buffer of the body
+    // unless it is shared with other bodies.
+    let mut body = BytesMut::from(body);
+    parse_in_place(&mut body)
+}
+
+#[cfg(not(feature = "simd-json"))]
+fn from_json_body<T: DeserializeOwned>(body: Bytes) -> serde_json::Result<T> {
+    serde_json::from_slice(&body)
+}
+
+/// Deserializes JSON with simd-json, which rewrites escaped strings in the buffer,
+/// falling back to serde_json on failure if the buffer is left untouched.
+#[cfg(feature = "simd-json")]
+fn parse_in_place<T: DeserializeOwned>(body: &mut [u8]) -> serde_json::Result<T> {
+    match simd_json::serde::from_slice(body) {
+        Ok(value) => Ok(value),
+        Err(_) if !body.contains(&b'\\') => serde_json::from_slice(body),
+        Err(e) => Err(serde::de::Error::custom(e)),
+    }
+}
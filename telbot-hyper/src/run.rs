@@ -0,0 +1,154 @@
+//! Running a bot by webhook or long polling, chosen by the configuration.
+//!
+//! The same handler as of [`WebhookServer`] is used in both modes,
+//! so that a bot can be polled in development and served by webhook in production.
+
+use std::future::Future;
+use std::net::SocketAddr;
+
+use telbot_types::update::{AllowedUpdate, GetUpdates, Update};
+use telbot_types::webhook::{DeleteWebhook, SetWebhook, WebhookReply};
+
+use crate::webhook::WebhookServer;
+use crate::{Api, Result};
+
+/// How [`run_bot`] receives updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Webhook if [`BotConfig::webhook_url`] is set, long polling otherwise.
+    Auto,
+    /// Webhook, which is set to [`BotConfig::webhook_url`] if it is set, or assumed to be set otherwise.
+    Webhook,
+    /// Long polling, after deleting the webhook if any.
+    Polling,
+}
+
+/// Configuration of [`run_bot`], e.g. read from environment variables or a configuration file.
+#[derive(Debug, Clone)]
+pub struct BotConfig {
+    /// Public HTTPS URL which forwards to the webhook server, like `https://example.com/telegram`.
+    pub webhook_url: Option<String>,
+    /// Secret token which Telegram sends with webhook requests.
+    pub secret_token: Option<String>,
+    /// Address the webhook server listens on, `0.0.0.0:8443` by default.
+    pub listen: SocketAddr,
+    /// Path the webhook server serves at, `/telegram` by default.
+    pub path: String,
+    /// Timeout of long polling in seconds, 30 by default.
+    pub polling_timeout: u32,
+    /// Types of updates to receive, or `None` to keep the list Telegram remembers.
+    pub allowed_updates: Option<Vec<AllowedUpdate>>,
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            secret_token: None,
+            listen: ([0, 0, 0, 0], 8443).into(),
+            path: "/telegram".to_string(),
+            polling_timeout: 30,
+            allowed_updates: None,
+        }
+    }
+}
+
+/// Receives updates by webhook or long polling and hands them to the handler until an error occurs.
+///
+/// With webhook, the reply of the handler is sent in the response as [`WebhookServer`] does.
+/// With long polling, each update is handled in a task spawned with `tokio::spawn`,
+/// and the reply of the handler is sent with [`Api::send_reply`], ignoring its result.
+///
+/// Long polling deletes the webhook first, since Telegram refuses to poll while a webhook is set.
+///
+/// ```no_run
+/// # use std::env;
+/// # use telbot_hyper::run::{run_bot, BotConfig, Mode};
+/// # use telbot_hyper::types::update::Update;
+/// # use telbot_hyper::types::webhook::WebhookReply;
+/// # use telbot_hyper::Api;
+/// # async fn run() -> telbot_hyper::Result<()> {
+/// let api = Api::new(env::var("BOT_TOKEN").unwrap());
+/// let config = BotConfig {
+///     webhook_url: env::var("WEBHOOK_URL").ok(),
+///     secret_token: env::var("WEBHOOK_SECRET").ok(),
+///     ..BotConfig::default()
+/// };
+/// run_bot(
+///     api,
+///     |update: Update| async move {
+///         let message = update.kind.message()?;
+///         WebhookReply::new(&message.reply_text("Hello!")).ok()
+///     },
+///     Mode::Auto,
+///     config,
+/// )
+/// .await
+/// # }
+/// ```
+pub async fn run_bot<F, Fut>(api: Api, handler: F, mode: Mode, config: BotConfig) -> Result<()>
+where
+    F: Fn(Update) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Option<WebhookReply>> + Send + 'static,
+{
+    let webhook = match mode {
+        Mode::Auto => config.webhook_url.is_some(),
+        Mode::Webhook => true,
+        Mode::Polling => false,
+    };
+    if webhook {
+        serve_webhook(api, handler, config).await
+    } else {
+        poll(api, handler, config).await
+    }
+}
+
+async fn serve_webhook<F, Fut>(api: Api, handler: F, config: BotConfig) -> Result<()>
+where
+    F: Fn(Update) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Option<WebhookReply>> + Send + 'static,
+{
+    if let Some(url) = config.webhook_url {
+        let mut set_webhook = SetWebhook::new(url);
+        if let Some(secret_token) = &config.secret_token {
+            set_webhook = set_webhook.with_secret_token(secret_token);
+        }
+        if let Some(allowed_updates) = &config.allowed_updates {
+            set_webhook = set_webhook.with_allowed_updates(allowed_updates.clone());
+        }
+        api.send_file(&set_webhook).await?;
+    }
+    let mut server = WebhookServer::new(config.path);
+    if let Some(secret_token) = config.secret_token {
+        server = server.with_secret_token(secret_token);
+    }
+    server.serve(config.listen, handler).await?;
+    Ok(())
+}
+
+async fn poll<F, Fut>(api: Api, handler: F, config: BotConfig) -> Result<()>
+where
+    F: Fn(Update) -> Fut,
+    Fut: Future<Output = Option<WebhookReply>> + Send + 'static,
+{
+    api.send_json(&DeleteWebhook::new()).await?;
+    let mut offset = 0;
+    loop {
+        let mut get_updates = GetUpdates::new()
+            .with_offset(offset)
+            .with_timeout(config.polling_timeout);
+        if let Some(allowed_updates) = &config.allowed_updates {
+            get_updates = get_updates.with_allowed_updates(allowed_updates.clone());
+        }
+        for update in api.send_json(&get_updates).await? {
+            offset = update.update_id as i32 + 1;
+            let handling = handler(update);
+            let api = api.clone();
+            tokio::spawn(async move {
+                if let Some(reply) = handling.await {
+                    let _ = api.send_reply(&reply).await;
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Self-signed certificates for webhooks.
+//!
+//! Telegram accepts a self-signed certificate if its public part is uploaded with [`SetWebhook`],
+//! so a bot can serve webhooks over HTTPS without a reverse proxy or a certificate authority.
+//! See Telegram's [self-signed guide](https://core.telegram.org/bots/self-signed) for details.
+
+use rcgen::{CertificateParams, DnType, KeyPair};
+use telbot_types::file::InputFile;
+use telbot_types::webhook::SetWebhook;
+
+pub use rcgen::Error;
+
+/// A self-signed certificate and its private key, both in PEM format.
+pub struct SelfSignedCertificate {
+    cert_pem: String,
+    key_pem: String,
+}
+
+impl SelfSignedCertificate {
+    /// Generates a new certificate for the given domain name or IP address of the webhook server.
+    ///
+    /// The host is used as both the common name and the subject alternative name,
+    /// since Telegram checks that the common name matches the webhook URL.
+    pub fn generate(host: impl Into<String>) -> Result<Self, Error> {
+        let host = host.into();
+        let mut params = CertificateParams::new(vec![host.clone()])?;
+        params.distinguished_name.push(DnType::CommonName, host);
+        let key_pair = KeyPair::generate()?;
+        let cert = params.self_signed(&key_pair)?;
+        Ok(Self {
+            cert_pem: cert.pem(),
+            key_pem: key_pair.serialize_pem(),
+        })
+    }
+
+    /// Gets the certificate in PEM format, to be used by the webhook server.
+    pub fn cert_pem(&self) -> &str {
+        &self.cert_pem
+    }
+
+    /// Gets the private key in PKCS#8 PEM format, to be used by the webhook server.
+    ///
+    /// Keep this secret.
+    pub fn key_pem(&self) -> &str {
+        &self.key_pem
+    }
+
+    /// Creates an [`InputFile`] of the certificate to be uploaded to Telegram.
+    pub fn to_input_file(&self) -> InputFile {
+        InputFile {
+            name: "cert.pem".to_string(),
+            data: self.cert_pem.as_bytes().to_vec(),
+            mime: "application/x-pem-file".to_string(),
+        }
+    }
+
+    /// Creates a new [`SetWebhook`] request with the given url, uploading this certificate.
+    ///
+    /// Send it with [`Api::send_file`](crate::Api::send_file).
+    pub fn set_webhook(&self, url: impl Into<String>) -> SetWebhook {
+        SetWebhook::new(url.into()).with_certificate(self.to_input_file())
+    }
+}
@@ -0,0 +1,314 @@
+//! A webhook server receiving updates from Telegram.
+//!
+//! Set the webhook to the public URL of the server with [`SetWebhook`](telbot_types::webhook::SetWebhook),
+//! with the same secret token given to [`WebhookServer::with_secret_token`].
+
+use std::convert::{Infallible, TryFrom};
+use std::future::Future;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hyper::body::HttpBody;
+use hyper::server::Builder;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use telbot_types::update::Update;
+use telbot_types::user::User;
+use telbot_types::webhook::{
+    verify_secret_token, UpdateGuard, UpdateRejection, WebhookReply, SECRET_TOKEN_HEADER,
+};
+
+/// Serves webhook requests of Telegram at a path, handing the updates to a handler.
+///
+/// Each update is handled in a task spawned with `tokio::spawn`.
+/// The handler can return a [`WebhookReply`] to perform a method in the response, saving a round trip,
+/// if it finishes within the reply timeout set with [`WebhookServer::with_reply_timeout`].
+/// Otherwise, the server responds without waiting for it, so that Telegram does not send the update again
+/// while the handler is still running.
+///
+/// Responds with
+/// - `404 Not Found` to requests to other paths,
+/// - `405 Method Not Allowed` to requests other than `POST`,
+/// - `401 Unauthorized` if the secret token is set and the request has no matching header,
+/// - `413 Payload Too Large` if the body is larger than the limit of the [`UpdateGuard`],
+/// - `400 Bad Request` if the body is nested too deep or is not an update,
+/// - `200 OK` with the reply of the handler, if it returns one within the reply timeout,
+/// - `200 OK` without a body otherwise, including if the handler panics.
+///
+/// A health check route can be added with [`WebhookServer::with_health_check`].
+///
+/// ```no_run
+/// # use telbot_hyper::webhook::WebhookServer;
+/// # use telbot_hyper::types::update::Update;
+/// # use telbot_hyper::types::webhook::WebhookReply;
+/// # async fn run() -> Result<(), hyper::Error> {
+/// WebhookServer::new("/telegram")
+///     .with_secret_token("s3cr3t")
+///     .serve_with_shutdown(
+///         ([0, 0, 0, 0], 8443).into(),
+///         |update: Update| async move {
+///             let message = update.kind.message()?;
+///             WebhookReply::new(&message.reply_text("Hello!")).ok()
+///         },
+///         async {
+///             tokio::signal::ctrl_c().await.ok();
+///         },
+///     )
+///     .await
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WebhookServer {
+    path: String,
+    secret_token: Option<String>,
+    guard: UpdateGuard,
+    reply_timeout: Duration,
+    rejection_handler: Option<RejectionHandler>,
+    health_check: Option<HealthCheck>,
+    /// Unix time of the last update received, or 0 if none.
+    last_update_date: Arc<AtomicU64>,
+}
+
+#[derive(Clone)]
+struct HealthCheck {
+    path: String,
+    bot: User,
+}
+
+type RejectionHandler = Arc<dyn Fn(&UpdateRejection) + Send + Sync>;
+
+impl WebhookServer {
+    /// Creates a new webhook server serving at the path, like `/telegram`.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            secret_token: None,
+            guard: UpdateGuard::default(),
+            reply_timeout: Duration::from_secs(5),
+            rejection_handler: None,
+            health_check: None,
+            last_update_date: Arc::default(),
+        }
+    }
+
+    /// Rejects requests without the secret token set with
+    /// [`SetWebhook::with_secret_token`](telbot_types::webhook::SetWebhook::with_secret_token).
+    pub fn with_secret_token(self, secret_token: impl Into<String>) -> Self {
+        Self {
+            secret_token: Some(secret_token.into()),
+            ..self
+        }
+    }
+
+    /// Sets the limits of request bodies, which are 1 MiB and 64 levels of nesting by default.
+    pub fn with_guard(self, guard: UpdateGuard) -> Self {
+        Self { guard, ..self }
+    }
+
+    /// Sets how long to wait for the handler before responding without a reply, which is 5 seconds by default.
+    ///
+    /// The handler keeps running after the response is sent.
+    pub fn with_reply_timeout(self, reply_timeout: Duration) -> Self {
+        Self {
+            reply_timeout,
+            ..self
+        }
+    }
+
+    /// Serves a health check at the path, like `/healthz`, for orchestrators to check the deployment.
+    ///
+    /// Responds to `GET` requests with the bot, as returned by [`GetMe`](telbot_types::bot::GetMe)
+    /// once at startup, and the Unix time of the last update received, or `null` if none:
+    ///
+    /// ```json
+    /// {"ok": true, "bot": {"id": 1234, "is_bot": true, "first_name": "Bot"}, "last_update_date": 1700000000}
+    /// ```
+    ///
+    /// The secret token is not required, since the response contains no secret.
+    pub fn with_health_check(self, path: impl Into<String>, bot: User) -> Self {
+        Self {
+            health_check: Some(HealthCheck {
+                path: path.into(),
+                bot,
+            }),
+            ..self
+        }
+    }
+
+    /// Sets a handler which is called with the reason of each rejected body, e.g. for logging.
+    pub fn on_rejection(self, handler: impl Fn(&UpdateRejection) + Send + Sync + 'static) -> Self {
+        Self {
+            rejection_handler: Some(Arc::new(handler)),
+            ..self
+        }
+    }
+
+    /// Serves on the address until an error occurs.
+    pub async fn serve<F, Fut>(self, addr: SocketAddr, handler: F) -> Result<(), hyper::Error>
+    where
+        F: Fn(Update) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<WebhookReply>> + Send + 'static,
+    {
+        self.serve_with_shutdown(addr, handler, std::future::pending())
+            .await
+    }
+
+    /// Serves on the address until the shutdown future completes,
+    /// then waits for the requests being handled to finish.
+    ///
+    /// Handlers which outlived the reply timeout are not waited for.
+    pub async fn serve_with_shutdown<F, Fut>(
+        self,
+        addr: SocketAddr,
+        handler: F,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<(), hyper::Error>
+    where
+        F: Fn(Update) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<WebhookReply>> + Send + 'static,
+    {
+        self.run(Server::try_bind(&addr)?, handler, shutdown).await
+    }
+
+    /// Serves on the listener, such as one bound to port 0 or passed by a service manager,
+    /// until the shutdown future completes.
+    pub async fn serve_listener<F, Fut>(
+        self,
+        listener: TcpListener,
+        handler: F,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<(), hyper::Error>
+    where
+        F: Fn(Update) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<WebhookReply>> + Send + 'static,
+    {
+        self.run(Server::from_tcp(listener)?, handler, shutdown)
+            .await
+    }
+
+    async fn run<F, Fut>(
+        self,
+        builder: Builder<hyper::server::conn::AddrIncoming>,
+        handler: F,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<(), hyper::Error>
+    where
+        F: Fn(Update) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<WebhookReply>> + Send + 'static,
+    {
+        let server = Arc::new(self);
+        let handler = Arc::new(handler);
+        let make_service = make_service_fn(move |_| {
+            let server = server.clone();
+            let handler = handler.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let server = server.clone();
+                    let handler = handler.clone();
+                    async move { Ok::<_, Infallible>(server.respond(request, &*handler).await) }
+                }))
+            }
+        });
+        builder
+            .serve(make_service)
+            .with_graceful_shutdown(shutdown)
+            .await
+    }
+
+    async fn respond<F, Fut>(&self, request: Request<Body>, handler: &F) -> Response<Body>
+    where
+        F: Fn(Update) -> Fut,
+        Fut: Future<Output = Option<WebhookReply>> + Send + 'static,
+    {
+        if let Some(health_check) = &self.health_check {
+            if request.uri().path() == health_check.path {
+                return match request.method() {
+                    &Method::GET => self.respond_health(&health_check.bot),
+                    _ => status(StatusCode::METHOD_NOT_ALLOWED),
+                };
+            }
+        }
+        if request.uri().path() != self.path {
+            return status(StatusCode::NOT_FOUND);
+        }
+        if request.method() != Method::POST {
+            return status(StatusCode::METHOD_NOT_ALLOWED);
+        }
+        if let Some(secret_token) = &self.secret_token {
+            let header = request
+                .headers()
+                .get(SECRET_TOKEN_HEADER)
+                .and_then(|value| value.to_str().ok());
+            if !verify_secret_token(header, secret_token) {
+                return status(StatusCode::UNAUTHORIZED);
+            }
+        }
+        let update = match self.receive(request.into_body()).await {
+            Ok(update) => update,
+            Err(None) => return status(StatusCode::BAD_REQUEST),
+            Err(Some(rejection)) => {
+                if let Some(handler) = &self.rejection_handler {
+                    handler(&rejection);
+                }
+                return match rejection {
+                    UpdateRejection::TooLarge { .. } => status(StatusCode::PAYLOAD_TOO_LARGE),
+                    _ => status(StatusCode::BAD_REQUEST),
+                };
+            }
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs());
+        self.last_update_date.store(now, Ordering::Relaxed);
+        let handling = tokio::spawn(handler(update));
+        match tokio::time::timeout(self.reply_timeout, handling).await {
+            Ok(Ok(Some(reply))) => match serde_json::to_vec(&reply) {
+                Ok(body) => Response::builder()
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+                Err(_) => status(StatusCode::OK),
+            },
+            _ => status(StatusCode::OK),
+        }
+    }
+
+    fn respond_health(&self, bot: &User) -> Response<Body> {
+        let last_update_date = match self.last_update_date.load(Ordering::Relaxed) {
+            0 => None,
+            date => Some(date),
+        };
+        let health = serde_json::json!({
+            "ok": true,
+            "bot": bot,
+            "last_update_date": last_update_date,
+        });
+        Response::builder()
+            .header("Content-Type", "application/json")
+            .body(Body::from(health.to_string()))
+            .unwrap()
+    }
+
+    /// Receives the body within the size limit and parses it,
+    /// failing with `None` if the connection fails.
+    async fn receive(&self, mut body: Body) -> Result<Update, Option<UpdateRejection>> {
+        let declared = body.size_hint().exact().unwrap_or(0);
+        self.guard
+            .check_size(usize::try_from(declared).unwrap_or(usize::MAX))?;
+        let mut bytes = Vec::with_capacity(declared as usize);
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.map_err(|_| None)?;
+            self.guard.check_size(bytes.len() + chunk.len())?;
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(self.guard.parse(&bytes)?)
+    }
+}
+
+fn status(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
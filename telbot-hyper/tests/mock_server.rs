@@ -0,0 +1,239 @@
+//! Checks the requests sent by the hyper backend against a local mock server.
+
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use telbot_hyper::callback::CallbackAnswer;
+use telbot_hyper::types::bot::GetMe;
+use telbot_hyper::types::file::{File, InputFile};
+use telbot_hyper::types::message::{SendDocument, SendMessage};
+use telbot_hyper::types::query::CallbackQuery;
+use telbot_hyper::{Api, ApiBuilder, Error};
+use tiny_http::{Header, Response, Server};
+
+const TOKEN: &str = "1234:TOKEN";
+
+const MESSAGE: &str = r#"{"ok":true,"result":{"message_id":1,"date":0,"chat":{"id":1,"type":"private"},"text":"hi"}}"#;
+
+const ME: &str =
+    r#"{"ok":true,"result":{"id":1234,"is_bot":true,"first_name":"Kiwi","username":"KiwiBot"}}"#;
+
+struct Captured {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Captured {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Serves a single request with the response, returning the API connected to the server.
+fn serve_once(status: u16, response: &'static str) -> (Api, JoinHandle<Captured>) {
+    serve_once_with(status, response, |builder| builder)
+}
+
+/// Like [`serve_once`], configuring the API with `configure`.
+fn serve_once_with(
+    status: u16,
+    response: &'static str,
+    configure: impl FnOnce(ApiBuilder) -> ApiBuilder,
+) -> (Api, JoinHandle<Captured>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let handle = thread::spawn(move || {
+        let mut request = server.recv().unwrap();
+        let mut body = vec![];
+        request.as_reader().read_to_end(&mut body).unwrap();
+        let captured = Captured {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers: request
+                .headers()
+                .iter()
+                .map(|header| (header.field.to_string(), header.value.to_string()))
+                .collect(),
+            body,
+        };
+        let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+        let response = Response::from_string(response)
+            .with_status_code(status)
+            .with_header(content_type);
+        request.respond(response).unwrap();
+        captured
+    });
+    (
+        configure(Api::builder(TOKEN).server(address)).build(),
+        handle,
+    )
+}
+
+/// Splits a multipart body into pairs of part headers and contents.
+fn multipart_parts(body: &[u8], boundary: &str) -> Vec<(String, Vec<u8>)> {
+    let delimiter = format!("--{}", boundary);
+    let body = String::from_utf8_lossy(body);
+    body.split(delimiter.as_str())
+        .filter_map(|part| {
+            let part = part.strip_prefix("\r\n")?;
+            let (headers, content) = part.split_at(part.find("\r\n\r\n")?);
+            let content = content[4..].strip_suffix("\r\n").unwrap_or(&content[4..]);
+            Some((headers.to_string(), content.as_bytes().to_vec()))
+        })
+        .collect()
+}
+
+fn part<'a>(parts: &'a [(String, Vec<u8>)], name: &str) -> &'a (String, Vec<u8>) {
+    let disposition = format!("name=\"{}\"", name);
+    parts
+        .iter()
+        .find(|(headers, _)| headers.contains(&disposition))
+        .unwrap_or_else(|| panic!("no part named {}", name))
+}
+
+#[tokio::test]
+async fn send_json_posts_json_body() {
+    let (api, server) = serve_once(200, MESSAGE);
+    let message = api.send_json(&SendMessage::new(1, "hi")).await.unwrap();
+    let request = server.join().unwrap();
+
+    assert_eq!(message.kind.text(), Some("hi"));
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.url, format!("/bot{}/sendMessage", TOKEN));
+    assert_eq!(request.header("Content-Type"), Some("application/json"));
+    let body: Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body, json!({"chat_id": 1, "text": "hi"}));
+}
+
+#[tokio::test]
+async fn http2_falls_back_to_http1_without_tls() {
+    let (api, server) = serve_once_with(200, ME, |builder| {
+        builder
+            .http2()
+            .http2_keep_alive_interval(Duration::from_secs(30))
+    });
+    let me = api.send_json(&GetMe).await.unwrap();
+    let request = server.join().unwrap();
+
+    assert_eq!(me.id, 1234);
+    assert_eq!(request.url, format!("/bot{}/getMe", TOKEN));
+}
+
+#[tokio::test]
+async fn callback_answer_answers_when_dropped() {
+    let (api, server) = serve_once(200, r#"{"ok":true,"result":true}"#);
+    let query: CallbackQuery = serde_json::from_value(json!({
+        "id": "42", "chat_instance": "1",
+        "from": {"id": 1, "is_bot": false, "first_name": "Kiwi"}
+    }))
+    .unwrap();
+    drop(CallbackAnswer::new(&api, &query));
+    let request = tokio::task::spawn_blocking(move || server.join().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(request.url, format!("/bot{}/answerCallbackQuery", TOKEN));
+    let body: Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body, json!({"callback_query_id": "42"}));
+}
+
+#[tokio::test]
+async fn send_file_posts_multipart_body() {
+    let (api, server) = serve_once(200, MESSAGE);
+    let document = InputFile {
+        name: "report.txt".to_string(),
+        data: b"hello, world".to_vec(),
+        mime: "text/plain".to_string(),
+    };
+    let send = SendDocument::new(1, document).with_caption("*monthly* report");
+    api.send_file(&send).await.unwrap();
+    let request = server.join().unwrap();
+
+    assert_eq!(request.url, format!("/bot{}/sendDocument", TOKEN));
+    let content_type = request.header("Content-Type").unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap()
+        .trim_matches('"');
+
+    let parts = multipart_parts(&request.body, boundary);
+    assert_eq!(part(&parts, "chat_id").1, b"1");
+    // Strings are sent as is, not as JSON strings with quotes.
+    assert_eq!(part(&parts, "caption").1, b"*monthly* report");
+    let (headers, data) = part(&parts, "document");
+    assert!(headers.contains("filename=\"report.txt\""));
+    assert!(headers.contains("text/plain"));
+    assert_eq!(data, b"hello, world");
+}
+
+#[tokio::test]
+async fn telegram_error_is_mapped() {
+    let (api, server) = serve_once(
+        400,
+        r#"{"ok":false,"error_code":400,"description":"Bad Request: chat not found"}"#,
+    );
+    let result = api.send_json(&SendMessage::new(1, "hi")).await;
+    server.join().unwrap();
+
+    match result {
+        Err(Error::Telegram(error)) => {
+            assert_eq!(error.error_code, 400);
+            assert_eq!(error.description, "Bad Request: chat not found");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn malformed_response_is_mapped() {
+    let (api, server) = serve_once(502, "<html>Bad Gateway</html>");
+    let result = api.send_json(&SendMessage::new(1, "hi")).await;
+    server.join().unwrap();
+
+    assert!(matches!(result, Err(Error::Serde(_))));
+}
+
+#[tokio::test]
+async fn download_file_fetches_file_path() {
+    let (api, server) = serve_once(200, "file contents");
+    let file = File {
+        file_id: "id".to_string(),
+        file_unique_id: "unique".to_string(),
+        file_size: Some(13),
+        file_path: Some("documents/file_0.txt".to_string()),
+    };
+    let data = api.download_file(&file).await.unwrap();
+    let request = server.join().unwrap();
+
+    assert_eq!(data, b"file contents");
+    assert_eq!(request.method, "GET");
+    assert_eq!(
+        request.url,
+        format!("/file/bot{}/documents/file_0.txt", TOKEN)
+    );
+}
+
+#[tokio::test]
+async fn me_is_fetched_once() {
+    let (api, server) = serve_once(200, ME);
+    let me = tokio::spawn({
+        let api = api.clone();
+        async move { api.me().await }
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    let request = server.join().unwrap();
+    // the server is gone, so another request would fail
+    let cached = api.me().await.unwrap();
+
+    assert_eq!(request.url, format!("/bot{}/getMe", TOKEN));
+    assert_eq!(me.username.as_deref(), Some("KiwiBot"));
+    assert_eq!(cached.id, me.id);
+}
@@ -0,0 +1,100 @@
+//! Checks the mode selection of `run_bot` against a local mock server.
+#![cfg(feature = "webhook")]
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::Value;
+use telbot_hyper::run::{run_bot, BotConfig, Mode};
+use telbot_hyper::types::update::Update;
+use telbot_hyper::types::webhook::WebhookReply;
+use telbot_hyper::Api;
+use tiny_http::{Header, Response, Server};
+
+const UPDATES: &str = r#"{"ok":true,"result":[{"update_id":7,"message":{"message_id":1,"date":0,"chat":{"id":1,"type":"private"},"text":"hi"}}]}"#;
+
+const MESSAGE: &str = r#"{"ok":true,"result":{"message_id":2,"date":0,"chat":{"id":1,"type":"private"},"text":"hi"}}"#;
+
+/// Serves the Bot API, sending the method and body of each request.
+///
+/// Only the first `getUpdates` returns an update, and the others return nothing after a while.
+fn serve() -> (Api, mpsc::Receiver<(String, Value)>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let (requests, received) = mpsc::channel();
+    thread::spawn(move || {
+        let mut polled = false;
+        for mut request in server.incoming_requests() {
+            let method = request.url().rsplit('/').next().unwrap().to_string();
+            let mut body = vec![];
+            request.as_reader().read_to_end(&mut body).unwrap();
+            let response = match method.as_str() {
+                "getUpdates" if !polled => {
+                    polled = true;
+                    UPDATES
+                }
+                "getUpdates" => {
+                    thread::sleep(Duration::from_millis(50));
+                    r#"{"ok":true,"result":[]}"#
+                }
+                "sendMessage" => MESSAGE,
+                _ => r#"{"ok":true,"result":true}"#,
+            };
+            let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+            if requests.send((method, body)).is_err() {
+                return;
+            }
+            let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+            request
+                .respond(Response::from_string(response).with_header(content_type))
+                .unwrap();
+        }
+    });
+    (Api::builder("1234:TOKEN").server(address).build(), received)
+}
+
+#[tokio::test]
+async fn auto_mode_polls_without_webhook_url() {
+    let (api, received) = serve();
+    let bot = tokio::spawn(run_bot(
+        api,
+        |update: Update| async move {
+            let message = update.kind.message()?;
+            WebhookReply::new(&message.reply_text("Hello!")).ok()
+        },
+        Mode::Auto,
+        BotConfig {
+            polling_timeout: 0,
+            ..BotConfig::default()
+        },
+    ));
+
+    let requests = tokio::task::spawn_blocking(move || {
+        let mut requests = vec![];
+        while let Ok(request) = received.recv_timeout(Duration::from_secs(5)) {
+            let replied = request.0 == "sendMessage";
+            requests.push(request);
+            if replied {
+                break;
+            }
+        }
+        requests
+    })
+    .await
+    .unwrap();
+    bot.abort();
+
+    assert_eq!(requests[0].0, "deleteWebhook");
+    assert_eq!(requests[1].0, "getUpdates");
+    assert_eq!(requests[1].1["offset"], 0);
+    let (method, body) = requests.last().unwrap();
+    assert_eq!(method, "sendMessage");
+    assert_eq!(body["text"], "Hello!");
+    assert_eq!(body["reply_to_message_id"], 1);
+    assert!(requests
+        .iter()
+        .skip(2)
+        .filter(|(method, _)| method == "getUpdates")
+        .all(|(_, body)| body["offset"] == 8));
+}
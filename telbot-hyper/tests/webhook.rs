@@ -0,0 +1,227 @@
+//! Checks the responses of the webhook server to requests from a local client.
+#![cfg(feature = "webhook")]
+
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use hyper::{Body, Client, Request, StatusCode};
+use telbot_dispatcher::{Dispatcher, State};
+use telbot_hyper::types::message::Message;
+use telbot_hyper::types::update::Update;
+use telbot_hyper::types::webhook::{UpdateGuard, WebhookReply, SECRET_TOKEN_HEADER};
+use telbot_hyper::webhook::WebhookServer;
+use tokio::sync::{mpsc, oneshot};
+
+const UPDATE: &str = r#"{"update_id":7,"message":{"message_id":1,"date":0,"chat":{"id":1,"type":"private"},"text":"hi"}}"#;
+
+async fn post(
+    url: &str,
+    secret_token: Option<&str>,
+    body: impl Into<Body>,
+) -> (StatusCode, Vec<u8>) {
+    let mut request = Request::post(url);
+    if let Some(secret_token) = secret_token {
+        request = request.header(SECRET_TOKEN_HEADER, secret_token);
+    }
+    let request = request.body(body.into()).unwrap();
+    let response = Client::new().request(request).await.unwrap();
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    (status, body.to_vec())
+}
+
+async fn send(url: &str, secret_token: Option<&str>, body: impl Into<Body>) -> StatusCode {
+    post(url, secret_token, body).await.0
+}
+
+#[tokio::test]
+async fn webhook_server_hands_updates_to_handler() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let (updates, mut received) = mpsc::unbounded_channel();
+    let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+    let server = tokio::spawn(
+        WebhookServer::new("/telegram")
+            .with_secret_token("s3cr3t")
+            .with_guard(UpdateGuard::new().with_max_body_size(1024))
+            .serve_listener(
+                listener,
+                move |update: Update| {
+                    let updates = updates.clone();
+                    async move {
+                        updates.send(update.update_id).unwrap();
+                        None
+                    }
+                },
+                async {
+                    shutdown_signal.await.ok();
+                },
+            ),
+    );
+
+    let url = format!("{}/telegram", address);
+    assert_eq!(
+        send(&format!("{}/other", address), Some("s3cr3t"), UPDATE).await,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(send(&url, None, UPDATE).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        send(&url, Some("guess"), UPDATE).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        send(&url, Some("s3cr3t"), "not json").await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        send(&url, Some("s3cr3t"), " ".repeat(2048)).await,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+    assert_eq!(send(&url, Some("s3cr3t"), UPDATE).await, StatusCode::OK);
+    assert_eq!(received.recv().await, Some(7));
+
+    shutdown.send(()).unwrap();
+    server.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_server_runs_dispatcher() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/telegram", listener.local_addr().unwrap());
+    let (messages, mut received) = mpsc::unbounded_channel::<i64>();
+    let dispatcher = Arc::new(Dispatcher::new().on_message(
+        |message: Message, State(messages): State<mpsc::UnboundedSender<i64>>| async move {
+            messages.send(message.message_id).unwrap();
+        },
+    ));
+    let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+    let server = tokio::spawn(WebhookServer::new("/telegram").serve_listener(
+        listener,
+        move |update: Update| {
+            let dispatcher = Arc::clone(&dispatcher);
+            let messages = messages.clone();
+            async move {
+                dispatcher.dispatch(&update, &messages).await;
+                None
+            }
+        },
+        async {
+            shutdown_signal.await.ok();
+        },
+    ));
+
+    assert_eq!(send(&url, None, UPDATE).await, StatusCode::OK);
+    assert_eq!(received.recv().await, Some(1));
+
+    shutdown.send(()).unwrap();
+    server.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_server_responds_with_reply() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/telegram", listener.local_addr().unwrap());
+    let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+    let server = tokio::spawn(WebhookServer::new("/telegram").serve_listener(
+        listener,
+        |update: Update| async move {
+            let message = update.kind.message()?;
+            WebhookReply::new(&message.reply_text("hello")).ok()
+        },
+        async {
+            shutdown_signal.await.ok();
+        },
+    ));
+
+    let (status, body) = post(&url, None, UPDATE).await;
+    assert_eq!(status, StatusCode::OK);
+    let reply: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(reply["method"], "sendMessage");
+    assert_eq!(reply["text"], "hello");
+    assert_eq!(reply["reply_to_message_id"], 1);
+
+    shutdown.send(()).unwrap();
+    server.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_server_responds_before_slow_handler_finishes() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/telegram", listener.local_addr().unwrap());
+    let (finished, mut finished_signal) = mpsc::unbounded_channel();
+    let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+    let server = tokio::spawn(
+        WebhookServer::new("/telegram")
+            .with_reply_timeout(Duration::from_millis(50))
+            .serve_listener(
+                listener,
+                move |update: Update| {
+                    let finished = finished.clone();
+                    async move {
+                        tokio::time::sleep(Duration::from_millis(500)).await;
+                        finished.send(()).unwrap();
+                        let message = update.kind.message()?;
+                        WebhookReply::new(&message.reply_text("too late")).ok()
+                    }
+                },
+                async {
+                    shutdown_signal.await.ok();
+                },
+            ),
+    );
+
+    let start = Instant::now();
+    let (status, body) = post(&url, None, UPDATE).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.is_empty());
+    assert!(start.elapsed() < Duration::from_millis(500));
+    assert_eq!(finished_signal.recv().await, Some(()));
+
+    shutdown.send(()).unwrap();
+    server.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_server_serves_health_check() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let bot = serde_json::from_str(
+        r#"{"id":1234,"is_bot":true,"first_name":"Bot","username":"TestBot"}"#,
+    )
+    .unwrap();
+    let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+    let server = tokio::spawn(
+        WebhookServer::new("/telegram")
+            .with_secret_token("s3cr3t")
+            .with_health_check("/healthz", bot)
+            .serve_listener(listener, |_: Update| async { None }, async {
+                shutdown_signal.await.ok();
+            }),
+    );
+
+    let health = || async {
+        let response = Client::new()
+            .get(format!("{}/healthz", address).parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+    };
+    let before = health().await;
+    assert_eq!(before["ok"], true);
+    assert_eq!(before["bot"]["username"], "TestBot");
+    assert!(before["last_update_date"].is_null());
+
+    let url = format!("{}/telegram", address);
+    assert_eq!(send(&url, Some("s3cr3t"), UPDATE).await, StatusCode::OK);
+    assert!(health().await["last_update_date"].as_u64().unwrap() > 0);
+    assert_eq!(
+        send(&format!("{}/healthz", address), None, "").await,
+        StatusCode::METHOD_NOT_ALLOWED
+    );
+
+    shutdown.send(()).unwrap();
+    server.await.unwrap().unwrap();
+}
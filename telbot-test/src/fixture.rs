@@ -0,0 +1,42 @@
+//! Response bodies and updates for tests.
+
+use serde_json::{json, Value};
+use telbot_types::update::Update;
+
+/// A successful response body with the result.
+pub fn ok(result: Value) -> Value {
+    json!({ "ok": true, "result": result })
+}
+
+/// An error response body, like `err(403, "Forbidden: bot was blocked by the user")`.
+pub fn err(error_code: i64, description: &str) -> Value {
+    json!({ "ok": false, "error_code": error_code, "description": description })
+}
+
+/// A text message in a private chat with the user whose identifier is the same as the chat.
+///
+/// Texts starting with a command, like `/start`, have the command entity.
+pub fn text_message(chat_id: i64, text: &str) -> Value {
+    let mut message = json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": { "id": chat_id, "type": "private", "first_name": "Kiwi" },
+        "from": { "id": chat_id, "is_bot": false, "first_name": "Kiwi" },
+        "text": text,
+    });
+    if text.starts_with('/') {
+        let command = text.split_whitespace().next().unwrap_or("");
+        message["entities"] = json!([{
+            "type": "bot_command",
+            "offset": 0,
+            "length": command.encode_utf16().count(),
+        }]);
+    }
+    message
+}
+
+/// An update with the [`text_message`].
+pub fn text_update(chat_id: i64, text: &str) -> Update {
+    serde_json::from_value(json!({ "update_id": 1, "message": text_message(chat_id, text) }))
+        .expect("text message fixture should be a valid update")
+}
@@ -0,0 +1,205 @@
+//! Testing bots offline, without sending requests to Telegram.
+//!
+//! Write bot logic against [`TelegramClient`], which is implemented by the `Api` of every backend,
+//! and give it a [`MockApi`] in tests.
+//! The mock records the requests sent to it, and returns the responses set for each method.
+//!
+//! ```
+//! use telbot_test::{fixture, MockApi};
+//! use telbot_types::message::Message;
+//! use telbot_types::TelegramClient;
+//!
+//! async fn greet<C: TelegramClient>(api: &C, message: &Message) -> Result<Message, C::Error> {
+//!     api.execute_json(&message.reply_text("Hello!")).await
+//! }
+//!
+//! let api = MockApi::new();
+//! api.respond_ok("sendMessage", fixture::text_message(1234, "Hello!"));
+//! let message = serde_json::from_value(fixture::text_message(1234, "Hi")).unwrap();
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let reply = greet(&api, &message).await.unwrap();
+//! assert_eq!(reply.kind.text(), Some("Hello!"));
+//! # });
+//!
+//! let sent = api.sent();
+//! assert_eq!(sent[0].name, "sendMessage");
+//! assert_eq!(sent[0].body["chat_id"], 1234);
+//! assert_eq!(sent[0].body["text"], "Hello!");
+//! ```
+//!
+//! Handlers of `telbot-dispatcher` can take the mock as the state, since it is cheap to clone.
+//! Clones share the recorded requests and the responses.
+
+pub mod fixture;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use serde::Serialize;
+use serde_json::Value;
+pub use telbot_types as types;
+use telbot_types::file::InputFile;
+use telbot_types::{
+    ApiResponse, FileMethod, JsonMethod, TelegramClient, TelegramError, TelegramMethod,
+};
+
+/// A request sent to a [`MockApi`].
+#[derive(Clone)]
+pub struct SentMethod {
+    /// Name of the method, like `sendMessage`.
+    pub name: &'static str,
+    /// The request serialized in JSON. Files to upload are serialized as empty strings.
+    pub body: Value,
+    /// Files to upload, by the names of their fields.
+    pub files: HashMap<String, InputFile>,
+}
+
+/// Error returned by requests to a [`MockApi`].
+#[derive(Debug)]
+pub enum Error {
+    /// The error response set for the method, like with [`MockApi::respond_err`].
+    Telegram(TelegramError),
+    /// No response is set for the method.
+    NoResponse(&'static str),
+    /// The request can't be serialized,
+    /// or the response set for the method does not match the response type of the method.
+    Serde(serde_json::Error),
+}
+
+/// Result having [`Error`] as error type.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Telegram(error) => write!(f, "{}", error.description),
+            Self::NoResponse(method) => write!(f, "no response is set for `{}`", method),
+            Self::Serde(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serde(error)
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    sent: Vec<SentMethod>,
+    responses: HashMap<String, Value>,
+    queued: HashMap<String, VecDeque<Value>>,
+}
+
+/// A fake Telegram API client, which records requests and returns canned responses.
+///
+/// Responses are set for each method as the whole response body, like `{"ok": true, "result": ...}`.
+/// Requests to methods without a response fail with [`Error::NoResponse`], but are still recorded.
+#[derive(Clone, Default)]
+pub struct MockApi {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl MockApi {
+    /// Creates a new mock without responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn inner(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the response body to every request of the method, like `sendMessage`.
+    pub fn respond(&self, method: impl Into<String>, response: Value) {
+        self.inner().responses.insert(method.into(), response);
+    }
+
+    /// Returns the response body to the next request of the method only.
+    ///
+    /// Responses set with this are returned in the order they are set,
+    /// before the one set with [`MockApi::respond`].
+    pub fn respond_once(&self, method: impl Into<String>, response: Value) {
+        self.inner()
+            .queued
+            .entry(method.into())
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Returns the result to every request of the method.
+    pub fn respond_ok(&self, method: impl Into<String>, result: Value) {
+        self.respond(method, fixture::ok(result))
+    }
+
+    /// Returns the error to every request of the method.
+    pub fn respond_err(&self, method: impl Into<String>, error_code: i64, description: &str) {
+        self.respond(method, fixture::err(error_code, description))
+    }
+
+    /// Gets the requests sent so far, oldest first.
+    pub fn sent(&self) -> Vec<SentMethod> {
+        self.inner().sent.clone()
+    }
+
+    /// Gets the requests of the method sent so far, oldest first.
+    pub fn sent_of(&self, method: &str) -> Vec<SentMethod> {
+        self.inner()
+            .sent
+            .iter()
+            .filter(|sent| sent.name == method)
+            .cloned()
+            .collect()
+    }
+
+    /// Forgets the requests sent so far, keeping the responses.
+    pub fn clear_sent(&self) {
+        self.inner().sent.clear()
+    }
+
+    fn execute<Method: TelegramMethod + Serialize>(
+        &self,
+        method: &Method,
+        files: HashMap<String, InputFile>,
+    ) -> Result<Method::Response> {
+        let name = Method::name();
+        let response = {
+            let mut inner = self.inner();
+            inner.sent.push(SentMethod {
+                name,
+                body: serde_json::to_value(method)?,
+                files,
+            });
+            let queued = inner
+                .queued
+                .get_mut(name)
+                .and_then(|queued| queued.pop_front());
+            queued.or_else(|| inner.responses.get(name).cloned())
+        };
+        match serde_json::from_value(response.ok_or(Error::NoResponse(name))?)? {
+            ApiResponse::Ok { result } => Ok(result),
+            ApiResponse::Err(error) => Err(Error::Telegram(error)),
+        }
+    }
+}
+
+impl TelegramClient for MockApi {
+    type Error = Error;
+
+    async fn execute_json<M: JsonMethod>(&self, method: &M) -> Result<M::Response> {
+        self.execute(method, HashMap::new())
+    }
+
+    async fn execute_file<M: FileMethod>(&self, method: &M) -> Result<M::Response> {
+        let files = method
+            .files()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(field, file)| (field.to_string(), file.clone()))
+            .collect();
+        self.execute(method, files)
+    }
+}
@@ -0,0 +1,110 @@
+//! Checks the mock API with handlers of the dispatcher.
+
+use telbot_dispatcher::{Context, Dispatcher, State};
+use telbot_test::{fixture, Error, MockApi};
+use telbot_types::file::InputFile;
+use telbot_types::message::{Message, SendDocument};
+use telbot_types::TelegramClient;
+
+#[tokio::test]
+async fn records_requests_of_handlers() {
+    let api = MockApi::new();
+    api.respond_ok("sendMessage", fixture::text_message(1234, "Welcome!"));
+    let dispatcher = Dispatcher::new().on_command(
+        "start",
+        |message: Message, State(api): State<MockApi>| async move {
+            api.execute_json(&message.reply_text("Welcome!"))
+                .await
+                .unwrap();
+        },
+    );
+
+    assert!(
+        dispatcher
+            .dispatch(&fixture::text_update(1234, "/start"), &api)
+            .await
+    );
+    assert!(
+        !dispatcher
+            .dispatch(&fixture::text_update(1234, "hi"), &api)
+            .await
+    );
+
+    let sent = api.sent_of("sendMessage");
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].body["text"], "Welcome!");
+    assert_eq!(sent[0].body["reply_to_message_id"], 1);
+}
+
+#[tokio::test]
+async fn replies_with_context() {
+    let api = MockApi::new();
+    api.respond_ok("sendMessage", fixture::text_message(1234, "Hello"));
+    let dispatcher = Dispatcher::new().on_command("hello", |ctx: Context<MockApi>| async move {
+        assert_eq!(ctx.chat().map(|chat| chat.id), Some(1234));
+        assert!(ctx.me().is_none());
+        ctx.reply("Hello").await.unwrap();
+    });
+
+    assert!(
+        dispatcher
+            .dispatch(&fixture::text_update(1234, "/hello"), &api)
+            .await
+    );
+
+    let sent = api.sent_of("sendMessage");
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].body["chat_id"], 1234);
+    assert_eq!(sent[0].body["text"], "Hello");
+    assert_eq!(sent[0].body["reply_to_message_id"], 1);
+}
+
+#[tokio::test]
+async fn returns_queued_responses_first() {
+    let api = MockApi::new();
+    api.respond_ok("sendMessage", fixture::text_message(1, "later"));
+    api.respond_once(
+        "sendMessage",
+        fixture::err(429, "Too Many Requests: retry after 1"),
+    );
+    let message: Message = serde_json::from_value(fixture::text_message(1, "hi")).unwrap();
+
+    let error = api
+        .execute_json(&message.reply_text("hi"))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, Error::Telegram(error) if error.error_code == 429));
+    let reply = api.execute_json(&message.reply_text("hi")).await.unwrap();
+    assert_eq!(reply.kind.text(), Some("later"));
+    assert_eq!(api.sent().len(), 2);
+}
+
+#[tokio::test]
+async fn fails_without_response() {
+    let api = MockApi::new();
+    let message: Message = serde_json::from_value(fixture::text_message(1, "hi")).unwrap();
+
+    let error = api
+        .execute_json(&message.reply_text("hi"))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, Error::NoResponse("sendMessage")));
+    api.clear_sent();
+    assert!(api.sent().is_empty());
+}
+
+#[tokio::test]
+async fn records_files() {
+    let api = MockApi::new();
+    api.respond_ok("sendDocument", fixture::text_message(1, ""));
+    let file = InputFile {
+        name: "report.csv".to_string(),
+        data: b"a,b\n1,2\n".to_vec(),
+        mime: "text/csv".to_string(),
+    };
+
+    let _ = api.execute_file(&SendDocument::new(1, file)).await;
+    let sent = api.sent();
+    assert_eq!(sent[0].name, "sendDocument");
+    assert_eq!(sent[0].files["document"].name, "report.csv");
+}
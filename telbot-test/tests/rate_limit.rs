@@ -0,0 +1,72 @@
+//! Checks throttling of requests sent through the mock API.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use telbot_test::{fixture, MockApi};
+use telbot_types::message::SendMessage;
+use telbot_types::rate_limit::{RateLimitedApi, RateLimiter};
+use telbot_types::TelegramClient;
+
+#[tokio::test]
+async fn waits_between_requests_to_the_same_chat() {
+    let mock = MockApi::new();
+    for _ in 0..3 {
+        mock.respond_ok("sendMessage", fixture::text_message(1234, "Hello"));
+    }
+    let limiter = RateLimiter::new().with_chat_interval(Duration::from_millis(50));
+    let api = RateLimitedApi::new(mock.clone()).with_limiter(limiter);
+
+    let start = Instant::now();
+    for _ in 0..3 {
+        api.execute_json(&SendMessage::new(1234, "Hello"))
+            .await
+            .unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert_eq!(mock.sent().len(), 3);
+}
+
+#[tokio::test]
+async fn waits_with_the_given_timer() {
+    let mock = MockApi::new();
+    for _ in 0..3 {
+        mock.respond_ok("sendMessage", fixture::text_message(1, "Hello"));
+    }
+    let limiter = RateLimiter::new().with_chat_interval(Duration::from_secs(60));
+    // A mock timer, whose clock only moves when sleeping.
+    let start = Instant::now();
+    let clock = Arc::new(Mutex::new(start));
+    let api = RateLimitedApi::new(mock.clone())
+        .with_limiter(limiter)
+        .with_timer(
+            {
+                let clock = Arc::clone(&clock);
+                move || *clock.lock().unwrap()
+            },
+            {
+                let clock = Arc::clone(&clock);
+                move |delay| {
+                    *clock.lock().unwrap() += delay;
+                    std::future::ready(())
+                }
+            },
+        );
+
+    let mut sent_at = vec![];
+    for _ in 0..3 {
+        api.execute_json(&SendMessage::new(1, "Hello"))
+            .await
+            .unwrap();
+        sent_at.push(*clock.lock().unwrap() - start);
+    }
+    assert_eq!(
+        sent_at,
+        [
+            Duration::ZERO,
+            Duration::from_secs(60),
+            Duration::from_secs(120)
+        ]
+    );
+    assert_eq!(mock.sent().len(), 3);
+}
@@ -0,0 +1,122 @@
+use std::io::Read;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use multipart::client::lazy::Multipart;
+use telbot_types::file::{InputFile, InputMedia};
+use telbot_types::form::{to_form_fields, FormValue};
+use telbot_types::markup::{MessageEntity, MessageEntityKind};
+use telbot_types::message::{SendMediaGroup, SendMessage, SendPhoto};
+use telbot_types::update::Update;
+use telbot_types::ApiResponse;
+
+fn send_message(c: &mut Criterion) {
+    let text = "bold italic code ".repeat(64);
+    let entities = (0..192)
+        .map(|index| MessageEntity {
+            kind: match index % 3 {
+                0 => MessageEntityKind::Bold,
+                1 => MessageEntityKind::Italic,
+                _ => MessageEntityKind::Code,
+            },
+            offset: index / 3 * 17 + [0, 5, 12][index % 3],
+            length: [4, 6, 4][index % 3],
+        })
+        .collect();
+    let request = SendMessage::new(123456789, text).with_entities(entities);
+
+    c.bench_function("serialize SendMessage with entities", |b| {
+        b.iter(|| serde_json::to_vec(black_box(&request)).unwrap())
+    });
+}
+
+fn send_photo(c: &mut Criterion) {
+    let photo = InputFile {
+        name: "photo.jpg".to_string(),
+        data: vec![0xAB; 5 * 1024 * 1024],
+        mime: "image/jpeg".to_string(),
+    };
+    let request = SendPhoto::new(123456789, photo).with_caption("caption");
+
+    // Same encoding as the backends do in `send_file`.
+    c.bench_function("multipart SendPhoto with 5MB file", |b| {
+        b.iter(|| {
+            let request = black_box(&request);
+            let fields = to_form_fields(request).unwrap();
+            let mut multipart = Multipart::new();
+            for (key, value) in &fields {
+                match value {
+                    FormValue::Text(text) => {
+                        multipart.add_text(key.as_str(), text.as_str());
+                    }
+                    FormValue::File(file) => {
+                        multipart.add_stream(
+                            key.as_str(),
+                            &file.data[..],
+                            Some(file.name.as_str()),
+                            Some(file.mime.parse().unwrap()),
+                        );
+                    }
+                }
+            }
+            let mut body = vec![];
+            multipart.prepare().unwrap().read_to_end(&mut body).unwrap();
+            body
+        })
+    });
+}
+
+fn send_media_group(c: &mut Criterion) {
+    let request = (0..10).fold(SendMediaGroup::new(123456789), |request, index| {
+        request.with_media(InputMedia::Photo {
+            media: format!("AgACAgIAAxkBAAIBY2G{:020}", index).into(),
+            caption: Some(format!("photo #{}", index)),
+            parse_mode: None,
+            caption_entities: None,
+            show_caption_above_media: None,
+        })
+    });
+
+    c.bench_function("serialize SendMediaGroup with 10 items", |b| {
+        b.iter(|| serde_json::to_vec(black_box(&request)).unwrap())
+    });
+}
+
+fn get_updates(c: &mut Criterion) {
+    let updates = (0..100)
+        .map(|index| {
+            format!(
+                r#"{{"update_id":{},"message":{{"message_id":{},"from":{{"id":1234,"is_bot":false,"first_name":"Kiwi","username":"kiwi"}},"chat":{{"id":1234,"type":"private","first_name":"Kiwi","username":"kiwi"}},"date":1640000000,"text":"/start hello world","entities":[{{"type":"bot_command","offset":0,"length":6}}]}}}}"#,
+                index, index
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let body = format!(r#"{{"ok":true,"result":[{}]}}"#, updates);
+
+    c.bench_function("parse getUpdates with 100 updates", |b| {
+        b.iter(|| {
+            let response: ApiResponse<Vec<Update>> =
+                serde_json::from_str(black_box(&body)).unwrap();
+            response
+        })
+    });
+
+    // Compare with `simd-json` feature of the backends, which parses a copy of the body in place.
+    c.bench_function("parse getUpdates with 100 updates (simd-json)", |b| {
+        b.iter(|| {
+            let mut body = black_box(body.as_bytes()).to_vec();
+            let response: ApiResponse<Vec<Update>> =
+                simd_json::serde::from_slice(&mut body).unwrap();
+            response
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    send_message,
+    send_photo,
+    send_media_group,
+    get_updates
+);
+criterion_main!(benches);
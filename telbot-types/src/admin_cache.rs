@@ -0,0 +1,126 @@
+//! Caching of chat administrators.
+//!
+//! Checking if a user is an administrator is the most frequent permission check of group bots,
+//! but calling [`GetChatAdministrators`](crate::chat::GetChatAdministrators) for each message quickly hits rate limits.
+//! [`AdminCache`] keeps the lists of administrators, and keeps them up to date with `chat_member` updates.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::chat::{ChatMember, ChatMemberUpdated};
+use crate::ttl_map::TtlMap;
+use crate::update::{Update, UpdateKind};
+
+/// Caches the lists of administrators of chats.
+///
+/// A list expires `ttl` after it was fetched, since changes are not delivered as updates
+/// unless the bot is an administrator and receives `chat_member` updates.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use telbot_types::admin_cache::AdminCache;
+/// # use telbot_types::chat::ChatMember;
+/// # let admins: Vec<ChatMember> = serde_json::from_str(r#"[
+/// #     {"status": "creator", "user": {"id": 1234, "is_bot": false, "first_name": "Kiwi"}, "is_anonymous": false}
+/// # ]"#).unwrap();
+/// let mut cache = AdminCache::new(Duration::from_secs(10 * 60));
+/// assert_eq!(cache.is_admin(-100, 1234), None);
+/// // admins = api.send_json(&GetChatAdministrators::new(-100))?;
+/// cache.insert(-100, admins);
+/// assert_eq!(cache.is_admin(-100, 1234), Some(true));
+/// assert_eq!(cache.is_admin(-100, 5678), Some(false));
+/// ```
+pub struct AdminCache {
+    chats: TtlMap<i64, HashMap<i64, ChatMember>>,
+}
+
+impl AdminCache {
+    /// Creates a new cache which forgets lists of administrators `ttl` after they were fetched.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            chats: TtlMap::new(ttl),
+        }
+    }
+
+    /// Stores the administrators of the chat, as returned by [`GetChatAdministrators`](crate::chat::GetChatAdministrators).
+    pub fn insert(&mut self, chat_id: i64, admins: Vec<ChatMember>) {
+        self.insert_at(chat_id, admins, Instant::now())
+    }
+
+    /// Stores the administrators of the chat fetched at `now`.
+    pub fn insert_at(&mut self, chat_id: i64, admins: Vec<ChatMember>, now: Instant) {
+        let admins = admins
+            .into_iter()
+            .filter(ChatMember::is_admin)
+            .map(|member| (member.user().id, member))
+            .collect();
+        self.chats.insert_at(chat_id, admins, now);
+    }
+
+    /// Checks if the user is an administrator of the chat.
+    ///
+    /// Returns `None` if the administrators of the chat are not cached or expired.
+    /// Fetch them with [`GetChatAdministrators`](crate::chat::GetChatAdministrators) and store with [`AdminCache::insert`] in that case.
+    pub fn is_admin(&self, chat_id: i64, user_id: i64) -> Option<bool> {
+        self.is_admin_at(chat_id, user_id, Instant::now())
+    }
+
+    /// Checks if the user is an administrator of the chat at `now`.
+    pub fn is_admin_at(&self, chat_id: i64, user_id: i64, now: Instant) -> Option<bool> {
+        self.chats
+            .get_at(&chat_id, now)
+            .map(|admins| admins.contains_key(&user_id))
+    }
+
+    /// Gets the administrators of the chat.
+    ///
+    /// Returns `None` if the administrators of the chat are not cached or expired.
+    pub fn administrators(&self, chat_id: i64) -> Option<Vec<&ChatMember>> {
+        self.administrators_at(chat_id, Instant::now())
+    }
+
+    /// Gets the administrators of the chat at `now`.
+    pub fn administrators_at(&self, chat_id: i64, now: Instant) -> Option<Vec<&ChatMember>> {
+        self.chats
+            .get_at(&chat_id, now)
+            .map(|admins| admins.values().collect())
+    }
+
+    /// Applies a change of a chat member to the cached administrators of the chat, if any.
+    ///
+    /// Promotions, demotions and changes of privileges take effect immediately, without fetching the list again.
+    pub fn record_member_update(&mut self, updated: &ChatMemberUpdated) {
+        if let Some(admins) = self.chats.get_mut(&updated.chat.id) {
+            let member = &updated.new_chat_member;
+            if member.is_admin() {
+                admins.insert(member.user().id, member.clone());
+            } else {
+                admins.remove(&member.user().id);
+            }
+        }
+    }
+
+    /// Applies the change of a chat member in an update, if any.
+    pub fn record_update(&mut self, update: &Update) {
+        match &update.kind {
+            UpdateKind::MyChatMemberUpdated { my_chat_member: u }
+            | UpdateKind::ChatMemberUpdated { chat_member: u } => self.record_member_update(u),
+            _ => {}
+        }
+    }
+
+    /// Forgets the administrators of the chat, so that they are fetched again.
+    pub fn invalidate(&mut self, chat_id: i64) {
+        self.chats.remove(&chat_id);
+    }
+
+    /// Forgets lists of administrators whose TTL has passed.
+    pub fn purge_expired(&mut self) {
+        self.purge_expired_at(Instant::now())
+    }
+
+    /// Forgets lists of administrators whose TTL has passed at `now`.
+    pub fn purge_expired_at(&mut self, now: Instant) {
+        self.chats.purge_expired_at(now)
+    }
+}
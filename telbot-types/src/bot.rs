@@ -1,8 +1,8 @@
 //! Types, requests, and responses related to bot or bot commands.
 
 use crate::chat::ChatId;
+use crate::telegram_method;
 use crate::user::User;
-use crate::{JsonMethod, TelegramMethod};
 use serde::{Deserialize, Serialize};
 
 /// A bot command.
@@ -94,15 +94,7 @@ pub enum BotCommandScope {
 #[derive(Clone, Serialize)]
 pub struct GetMe;
 
-impl TelegramMethod for GetMe {
-    type Response = User;
-
-    fn name() -> &'static str {
-        "getMe"
-    }
-}
-
-impl JsonMethod for GetMe {}
+telegram_method!(GetMe => "getMe", User, json);
 
 /// Logs out from the cloud Bot API server before launching the bot locally.
 ///
@@ -114,15 +106,7 @@ impl JsonMethod for GetMe {}
 #[derive(Clone, Serialize)]
 pub struct LogOut;
 
-impl TelegramMethod for LogOut {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "logOut"
-    }
-}
-
-impl JsonMethod for LogOut {}
+telegram_method!(LogOut => "logOut", bool, json);
 
 /// Closes the bot instance before moving it from one local server to another.
 ///
@@ -134,15 +118,7 @@ impl JsonMethod for LogOut {}
 #[derive(Clone, Serialize)]
 pub struct Close;
 
-impl TelegramMethod for Close {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "close"
-    }
-}
-
-impl JsonMethod for Close {}
+telegram_method!(Close => "close", bool, json);
 
 /// Changes the list of the bot's commands.
 ///
@@ -192,15 +168,7 @@ impl SetMyCommands {
     }
 }
 
-impl TelegramMethod for SetMyCommands {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "setMyCommands"
-    }
-}
-
-impl JsonMethod for SetMyCommands {}
+telegram_method!(SetMyCommands => "setMyCommands", bool, json);
 
 /// Deletes the list of the bot's commands for the given scope and user language.
 ///
@@ -246,15 +214,7 @@ impl DeleteMyCommands {
     }
 }
 
-impl TelegramMethod for DeleteMyCommands {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "deleteMyCommands"
-    }
-}
-
-impl JsonMethod for DeleteMyCommands {}
+telegram_method!(DeleteMyCommands => "deleteMyCommands", bool, json);
 
 /// Gets the current list of the bot's commands for the given scope and user language.
 ///
@@ -302,12 +262,107 @@ impl GetMyCommands {
     }
 }
 
-impl TelegramMethod for GetMyCommands {
-    type Response = Vec<BotCommand>;
+telegram_method!(GetMyCommands => "getMyCommands", Vec<BotCommand>, json);
+
+/// A bot token in the form of `123456:ABC-DEF1234ghIkl-zyx57W2v1u123ew11`.
+///
+/// Its [`Debug`](std::fmt::Debug) output hides the secret part,
+/// and it does not implement [`Display`](std::fmt::Display),
+/// so that the token is not leaked through logs by accident.
+///
+/// Use [`str::parse`] to validate the token, or `From<String>` to take it as is.
+///
+/// ```
+/// # use telbot_types::bot::BotToken;
+/// let token: BotToken = "123456:ABC-DEF1234ghIkl-zyx57W2v1u123ew11".parse().unwrap();
+/// assert_eq!(format!("{:?}", token), "BotToken(123456:***)");
+/// assert!("123456".parse::<BotToken>().is_err());
+///
+/// let token = std::env::var("BOT_TOKEN").unwrap_or_default();
+/// assert_eq!(BotToken::from(&token).as_str(), token);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BotToken(String);
+
+impl BotToken {
+    /// Gets the token as a string.
+    ///
+    /// Be careful not to print or log the returned value.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Gets the identifier of the bot, which is the part before the colon.
+    pub fn bot_id(&self) -> Option<i64> {
+        self.0.split(':').next()?.parse().ok()
+    }
+}
+
+impl std::fmt::Debug for BotToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0.split_once(':') {
+            Some((bot_id, _)) => write!(f, "BotToken({}:***)", bot_id),
+            None => write!(f, "BotToken(***)"),
+        }
+    }
+}
+
+impl std::str::FromStr for BotToken {
+    type Err = BotTokenError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let (bot_id, hash) = token.split_once(':').ok_or(BotTokenError::MissingColon)?;
+        if bot_id.is_empty() || !bot_id.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(BotTokenError::InvalidBotId);
+        }
+        if hash.is_empty()
+            || !hash
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+        {
+            return Err(BotTokenError::InvalidHash);
+        }
+        Ok(Self(token.to_string()))
+    }
+}
+
+impl From<String> for BotToken {
+    fn from(token: String) -> Self {
+        Self(token)
+    }
+}
+
+impl From<&str> for BotToken {
+    fn from(token: &str) -> Self {
+        Self(token.to_string())
+    }
+}
 
-    fn name() -> &'static str {
-        "getMyCommands"
+impl From<&String> for BotToken {
+    fn from(token: &String) -> Self {
+        Self(token.clone())
+    }
+}
+
+/// Error that can occur while parsing [`BotToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotTokenError {
+    /// The token does not contain a colon.
+    MissingColon,
+    /// The part before the colon is not a number.
+    InvalidBotId,
+    /// The part after the colon is empty or contains invalid characters.
+    InvalidHash,
+}
+
+impl std::fmt::Display for BotTokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingColon => write!(f, "bot token must be in the form of `<bot id>:<hash>`"),
+            Self::InvalidBotId => write!(f, "bot id of the token must be a number"),
+            Self::InvalidHash => write!(f, "hash of the token contains invalid characters"),
+        }
     }
 }
 
-impl JsonMethod for GetMyCommands {}
+impl std::error::Error for BotTokenError {}
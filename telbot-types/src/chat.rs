@@ -1,8 +1,12 @@
 //! Types, requests, and responses related to chats.
 
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use serde::{Deserialize, Serialize};
 
-use crate::file::{InputFile, InputFileVariant, InputMedia};
+use crate::file::{GetFile, InputFile, InputFileVariant, InputMedia};
 use crate::markup::InlineKeyboardMarkup;
 use crate::message::{
     ChatActionKind, DeleteMessage, EditMessageCaption, EditMessageMedia, EditMessageReplyMarkup,
@@ -11,12 +15,34 @@ use crate::message::{
     SendVenue, SendVideo, SendVideoNote, SendVoice, StopPoll,
 };
 use crate::user::User;
-use crate::{JsonMethod, TelegramMethod};
+use crate::{telegram_method, FileMethod, TelegramMethod};
+
+/// `until_date` value which means the restriction or ban is applied forever.
+///
+/// Any date more than 366 days or less than 30 seconds from the current time
+/// is also considered to be forever.
+pub const UNTIL_FOREVER: u64 = 0;
+
+/// Shortest duration applied by `for_duration`, leaving time for the request to arrive
+/// before less than 30 seconds are left.
+const MIN_UNTIL_DURATION: Duration = Duration::from_secs(60);
+
+/// Longest duration applied by `for_duration`.
+const MAX_UNTIL_DURATION: Duration = Duration::from_secs(366 * 24 * 60 * 60);
+
+fn until_after(duration: Duration) -> u64 {
+    let duration = duration.clamp(MIN_UNTIL_DURATION, MAX_UNTIL_DURATION);
+    SystemTime::now()
+        .checked_add(duration)
+        .and_then(|until| until.duration_since(UNIX_EPOCH).ok())
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or(UNTIL_FOREVER)
+}
 
 /// A chat room including supergroup, channel, and private chat.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#chat)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Chat {
     /// Unique identifier for this chat.
     pub id: i64,
@@ -24,54 +50,124 @@ pub struct Chat {
     #[serde(flatten)]
     pub kind: ChatKind,
     /// Title, for supergroups, channels and group chats
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     /// Username, for private chats, supergroups and channels if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
     /// First name of the other party in a private chat.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub first_name: Option<String>,
     /// Last name of the other party in a private chat.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_name: Option<String>,
     /// Chat photo.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub photo: Option<ChatPhoto>,
     /// Bio of the other party in a private chat.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub bio: Option<String>,
     /// Description, for groups, supergroups and channel chats.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Primary invite link, for groups, supergroups and channel chats.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub invite_link: Option<String>,
     /// The most recent pinned message (by sending date).
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pinned_message: Option<Box<Message>>,
     /// Default chat member permissions, for groups and supergroups.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub permissions: Option<ChatPermissions>,
     /// Default chat member permissions, for groups and supergroups.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub slow_mode_delay: Option<i32>,
     /// The time after which all messages sent to the chat will be automatically deleted; in seconds.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub message_auto_delete_time: Option<i32>,
     /// For supergroups, name of group sticker set.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sticker_set_name: Option<String>,
     /// `true` if the bot can change the group sticker set.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_set_sticker_set: Option<bool>,
     /// Unique identifier for the linked chat,
     /// i.e. the discussion group identifier for a channel and vice versa;
     /// for supergroups and channel chats.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub linked_chat_id: Option<i32>,
     /// For supergroups, the location to which the supergroup is connected.
     /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<ChatLocation>,
+    /// Identifier of the [accent color](https://core.telegram.org/bots/api#accent-colors)
+    /// for the chat name and backgrounds of the chat photo, reply header, and link preview.
+    /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent_color_id: Option<u32>,
+    /// Custom emoji identifier of emoji chosen by the chat for the reply header and link preview background.
+    /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_custom_emoji_id: Option<String>,
+    /// Identifier of the [accent color](https://core.telegram.org/bots/api#profile-accent-colors)
+    /// for the chat's profile background.
+    /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_accent_color_id: Option<u32>,
+    /// Custom emoji identifier of the emoji chosen by the chat for its profile background.
+    /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_background_custom_emoji_id: Option<String>,
+    /// Custom emoji identifier of the emoji status of the chat or the other party in a private chat.
+    /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji_status_custom_emoji_id: Option<String>,
+    /// Expiration date of the emoji status of the chat or the other party in a private chat, in Unix time, if any.
+    /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji_status_expiration_date: Option<u64>,
+    /// `true` if new chat members will have access to old messages; available only to chat administrators.
+    /// Returned only in [`GetChat`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_visible_history: Option<bool>,
 }
 
 impl Chat {
+    /// Gets the custom emoji identifier of the emoji status, if it is set and not expired.
+    pub fn emoji_status(&self) -> Option<&str> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        self.emoji_status_at(now)
+    }
+
+    /// Gets the custom emoji identifier of the emoji status at `now` in Unix time, if it is set and not expired.
+    pub fn emoji_status_at(&self, now: u64) -> Option<&str> {
+        match self.emoji_status_expiration_date {
+            Some(expiration_date) if expiration_date <= now => None,
+            _ => self.emoji_status_custom_emoji_id.as_deref(),
+        }
+    }
+
+    /// `true` if new members can see messages sent before they joined.
+    ///
+    /// Returns `false` if unknown, e.g. the chat is not from [`GetChat`] or the bot is not an administrator.
+    pub fn has_visible_history(&self) -> bool {
+        self.has_visible_history.unwrap_or(false)
+    }
+
     /// Creates a [`SendAnimation`] request which will send an animation to this chat.
     pub fn send_animation(&self, animation: impl Into<InputFileVariant>) -> SendAnimation {
         SendAnimation::new(self.id, animation)
@@ -107,15 +203,10 @@ impl Chat {
         SendDocument::new(self.id, document)
     }
 
-    /// Creates a [`SendLocation`] request with given latitude, longitude, and horizontal accuracy
+    /// Creates a [`SendLocation`] request with given latitude and longitude
     /// which will send a location to this chat.
-    pub fn send_location(
-        &self,
-        latitude: f32,
-        longitude: f32,
-        horizontal_accuracy: f32,
-    ) -> SendLocation {
-        SendLocation::new(self.id, latitude, longitude, horizontal_accuracy)
+    pub fn send_location(&self, latitude: f32, longitude: f32) -> SendLocation {
+        SendLocation::new(self.id, latitude, longitude)
     }
 
     /// Creates a [`SendMediaGroup`] request which will send a group of media to this chat.
@@ -373,10 +464,30 @@ impl Chat {
     pub fn delete_message(&self, message_id: i64) -> DeleteMessage {
         DeleteMessage::new(self.id, message_id)
     }
+
+    /// Checks that the given request can target this chat.
+    ///
+    /// ```
+    /// # use telbot_types::chat::{Chat, SetChatStickerSet};
+    /// # let chat: Chat = serde_json::from_str(r#"{"id":1,"type":"private"}"#).unwrap();
+    /// assert!(chat.check_kind::<SetChatStickerSet>().is_err());
+    /// ```
+    pub fn check_kind<Method: ChatKindRestricted + TelegramMethod>(
+        &self,
+    ) -> Result<(), UnsupportedChatKind> {
+        if Method::CHAT_KINDS.contains(&self.kind) {
+            Ok(())
+        } else {
+            Err(UnsupportedChatKind {
+                method: Method::name(),
+                kind: self.kind,
+            })
+        }
+    }
 }
 
 /// Kinds of chat.
-#[derive(Debug, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum ChatKind {
     Private,
@@ -385,10 +496,78 @@ pub enum ChatKind {
     Channel,
 }
 
+/// Requests which can target only certain kinds of chats.
+///
+/// Telegram rejects such requests targeting other kinds of chats with an error.
+/// Use [`Chat::check_kind`] to check it beforehand.
+pub trait ChatKindRestricted {
+    /// Kinds of chats which the request can target.
+    const CHAT_KINDS: &'static [ChatKind];
+}
+
+const NOT_PRIVATE: &[ChatKind] = &[ChatKind::Group, ChatKind::Supergroup, ChatKind::Channel];
+const GROUPS: &[ChatKind] = &[ChatKind::Group, ChatKind::Supergroup];
+const SUPERGROUPS_AND_CHANNELS: &[ChatKind] = &[ChatKind::Supergroup, ChatKind::Channel];
+const SUPERGROUPS: &[ChatKind] = &[ChatKind::Supergroup];
+
+macro_rules! impl_chat_kind_restricted {
+    ($kinds:ident: $($method:ty),+ $(,)?) => {
+        $(
+            impl ChatKindRestricted for $method {
+                const CHAT_KINDS: &'static [ChatKind] = $kinds;
+            }
+        )+
+    };
+}
+
+impl_chat_kind_restricted!(NOT_PRIVATE:
+    BanChatMember,
+    ExportChatInviteLink,
+    CreateChatInviteLink,
+    EditChatInviteLink,
+    RevokeChatInviteLink,
+    SetChatPhoto,
+    DeleteChatPhoto,
+    SetChatTitle,
+    SetChatDescription,
+    LeaveChat,
+    GetChatAdministrators,
+);
+impl_chat_kind_restricted!(GROUPS: SetChatPermissions);
+impl_chat_kind_restricted!(SUPERGROUPS_AND_CHANNELS:
+    UnbanChatMember,
+    PromoteChatMember,
+    ApproveChatJoinRequest,
+    DeclineChatJoinRequest,
+);
+impl_chat_kind_restricted!(SUPERGROUPS:
+    RestrictChatMember,
+    SetChatAdministratorCustomTitle,
+    SetChatStickerSet,
+    DeleteChatStickerSet,
+);
+
+/// Error of a request targeting a kind of chat it does not support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedChatKind {
+    /// Name of the request.
+    pub method: &'static str,
+    /// Kind of the targeted chat.
+    pub kind: ChatKind,
+}
+
+impl std::fmt::Display for UnsupportedChatKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} cannot target {:?} chats", self.method, self.kind)
+    }
+}
+
+impl std::error::Error for UnsupportedChatKind {}
+
 /// A chat photo.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#chatphoto)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatPhoto {
     /// File identifier of small (160x160) chat photo.
     ///
@@ -412,10 +591,22 @@ pub struct ChatPhoto {
     pub big_file_unique_id: String,
 }
 
+impl ChatPhoto {
+    /// Creates a new [`GetFile`] request that gets the small (160x160) chat photo.
+    pub fn get_small(&self) -> GetFile {
+        GetFile::new(&self.small_file_id)
+    }
+
+    /// Creates a new [`GetFile`] request that gets the big (640x640) chat photo.
+    pub fn get_big(&self) -> GetFile {
+        GetFile::new(&self.big_file_id)
+    }
+}
+
 /// Location of a chat, especially supergroup.\
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#chatlocation)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatLocation {
     /// The location to which the supergroup is connected.
     ///
@@ -425,6 +616,37 @@ pub struct ChatLocation {
     pub address: String,
 }
 
+/// A named set of [`ChatPermissions`], to apply the same permissions consistently,
+/// e.g. for lockdown and unlock commands of moderation bots.
+///
+/// Every permission is given explicitly, so applying a preset overrides all the previous permissions.
+///
+/// ```
+/// # use telbot_types::chat::{ChatPermissions, PermissionPreset, SetChatPermissions};
+/// // lockdown
+/// let request = SetChatPermissions::preset(-1001234, PermissionPreset::ReadOnly);
+/// assert_eq!(request.permissions.can_send_messages, Some(false));
+/// // unlock
+/// let request = SetChatPermissions::preset(-1001234, PermissionPreset::FullMember);
+/// assert_eq!(request.permissions.can_send_other_messages, Some(true));
+/// assert_eq!(request.permissions.can_pin_messages, Some(false));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionPreset {
+    /// Members can only read the chat. See [`ChatPermissions::read_only`].
+    ReadOnly,
+    /// Members can send text messages and media. See [`ChatPermissions::media_allowed`].
+    MediaAllowed,
+    /// Members can send any message and invite users. See [`ChatPermissions::full_member`].
+    FullMember,
+}
+
+impl From<PermissionPreset> for ChatPermissions {
+    fn from(preset: PermissionPreset) -> Self {
+        Self::preset(preset)
+    }
+}
+
 /// Describes actions that a non-administrator user is allowed to take in a chat.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#chatpermissions)
@@ -469,6 +691,51 @@ impl ChatPermissions {
         Default::default()
     }
 
+    /// Creates the permissions of the preset.
+    pub fn preset(preset: PermissionPreset) -> Self {
+        match preset {
+            PermissionPreset::ReadOnly => Self::read_only(),
+            PermissionPreset::MediaAllowed => Self::media_allowed(),
+            PermissionPreset::FullMember => Self::full_member(),
+        }
+    }
+
+    /// Creates permissions which disallow every action, so that members can only read the chat.
+    pub fn read_only() -> Self {
+        Self {
+            can_send_messages: Some(false),
+            can_send_media_messages: Some(false),
+            can_send_polls: Some(false),
+            can_send_other_messages: Some(false),
+            can_add_web_page_previews: Some(false),
+            can_change_info: Some(false),
+            can_invite_users: Some(false),
+            can_pin_messages: Some(false),
+        }
+    }
+
+    /// Creates permissions which allow sending text messages, media and web page previews,
+    /// but not polls, stickers, animations, games or inline bots.
+    pub fn media_allowed() -> Self {
+        Self {
+            can_send_messages: Some(true),
+            can_send_media_messages: Some(true),
+            can_add_web_page_previews: Some(true),
+            ..Self::read_only()
+        }
+    }
+
+    /// Creates permissions which allow sending any message and inviting users,
+    /// but not changing chat settings or pinning messages.
+    pub fn full_member() -> Self {
+        Self {
+            can_send_polls: Some(true),
+            can_send_other_messages: Some(true),
+            can_invite_users: Some(true),
+            ..Self::media_allowed()
+        }
+    }
+
     /// Allows sending text messages, contacts, locations and venues.
     pub fn allow_send_messages(self) -> Self {
         Self {
@@ -542,7 +809,7 @@ impl ChatPermissions {
 /// Can be obtained with [`GetChatMember`]
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#chatmember)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "status")]
 pub enum ChatMember {
     /// The owner of the chat with all privileges.
@@ -553,6 +820,7 @@ pub enum ChatMember {
         /// `true` if the user's presence in the chat is hidden.
         is_anonymous: bool,
         /// Custom title for this user.
+        #[serde(skip_serializing_if = "Option::is_none")]
         custom_title: Option<String>,
     },
     /// An administrator of the chat with some additional privileges.
@@ -590,12 +858,16 @@ pub enum ChatMember {
         /// `true` if the user is allowed to invite new users to the chat.
         can_invite_users: bool,
         /// `true` if the administrator can post in the channel; channels only.
+        #[serde(skip_serializing_if = "Option::is_none")]
         can_post_messages: Option<bool>,
         /// `true` if the administrator can edit messages of other users and can pin messages; channels only.
+        #[serde(skip_serializing_if = "Option::is_none")]
         can_edit_messages: Option<bool>,
         /// `true` if the user is allowed to pin messages; groups and supergroups only.
+        #[serde(skip_serializing_if = "Option::is_none")]
         can_pin_messages: Option<bool>,
         /// Custom title for this user.
+        #[serde(skip_serializing_if = "Option::is_none")]
         custom_title: Option<String>,
     },
     /// A chat member without additional privileges or restrictions.
@@ -888,6 +1160,11 @@ impl ChatMember {
         }
     }
 
+    /// Returns `true` if the user is the owner or an administrator of the chat.
+    pub fn is_admin(&self) -> bool {
+        matches!(self, Self::Owner { .. } | Self::Administrator { .. })
+    }
+
     /// Returns `true` if the user is currently a member of the chat.
     pub fn is_member(&self) -> bool {
         match self {
@@ -925,7 +1202,7 @@ impl ChatMember {
 /// An invite link for a chat.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#chatinvitelink)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatInviteLink {
     /// The invite link.
     ///
@@ -939,15 +1216,17 @@ pub struct ChatInviteLink {
     /// `true` if the link is revoked.
     pub is_revoked: bool,
     /// Point in time (Unix timestamp) when the link will expire or has been expired.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub expire_date: Option<u64>,
     /// Maximum number of users that can be members of the chat simultaneously after joining the chat via this invite link; 1-99999.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub member_limit: Option<u32>,
 }
 
 /// Changes in the status of a chat member.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#chatmemberupdated)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMemberUpdated {
     /// Chat the user belongs to.
     pub chat: Chat,
@@ -961,9 +1240,58 @@ pub struct ChatMemberUpdated {
     pub new_chat_member: ChatMember,
     /// Chat invite link, which was used by the user to join the chat;
     /// for joining by invite link events only.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub invite_link: Option<ChatInviteLink>,
 }
 
+/// A join request sent to a chat.
+///
+/// The bot must have the `can_invite_users` administrator right in the chat to receive and answer join requests.
+///
+/// ```
+/// # use telbot_types::chat::ChatJoinRequest;
+/// # let request: ChatJoinRequest = serde_json::from_str(r#"{
+/// #     "chat": {"id": -1001234, "type": "supergroup", "title": "Kiwi"},
+/// #     "from": {"id": 5678, "is_bot": false, "first_name": "Newcomer"},
+/// #     "user_chat_id": 5678, "date": 0, "bio": "Hello"
+/// # }"#).unwrap();
+/// let approve = request.approve();
+/// assert_eq!(approve.user_id, 5678);
+/// ```
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#chatjoinrequest)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatJoinRequest {
+    /// Chat to which the request was sent.
+    pub chat: Chat,
+    /// User that sent the join request.
+    pub from: User,
+    /// Identifier of a private chat with the user who sent the join request.
+    /// The bot can use this identifier for 5 minutes to send messages until the join request is processed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_chat_id: Option<i64>,
+    /// Date the request was sent in Unix time.
+    pub date: u64,
+    /// Bio of the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    /// Chat invite link that was used by the user to send the join request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invite_link: Option<ChatInviteLink>,
+}
+
+impl ChatJoinRequest {
+    /// Creates a new [`ApproveChatJoinRequest`] request which will approve this join request.
+    pub fn approve(&self) -> ApproveChatJoinRequest {
+        ApproveChatJoinRequest::new(self.chat.id, self.from.id)
+    }
+
+    /// Creates a new [`DeclineChatJoinRequest`] request which will decline this join request.
+    pub fn decline(&self) -> DeclineChatJoinRequest {
+        DeclineChatJoinRequest::new(self.chat.id, self.from.id)
+    }
+}
+
 /// Identifier of the chat or username of the supergroup (in the format `@supergroupusername`)
 ///
 /// You can pass values of type `i64`, `&str`, and `String` to parameters of type `impl Into<ChatId>`.
@@ -988,18 +1316,184 @@ impl From<i64> for ChatId {
     }
 }
 
+/// Usernames like `Name`, `@name` or `t.me/name` are normalized into `@name`,
+/// and other strings are passed as is.
 impl From<String> for ChatId {
     fn from(username: String) -> Self {
-        Self::Username(username)
+        match Username::parse(&username) {
+            Ok(username) => username.into(),
+            Err(_) => Self::Username(username),
+        }
     }
 }
 
+/// Usernames like `Name`, `@name` or `t.me/name` are normalized into `@name`,
+/// and other strings are passed as is.
 impl From<&str> for ChatId {
     fn from(username: &str) -> Self {
-        Self::Username(username.to_string())
+        match Username::parse(username) {
+            Ok(username) => username.into(),
+            Err(_) => Self::Username(username.to_string()),
+        }
+    }
+}
+
+impl From<Username> for ChatId {
+    fn from(username: Username) -> Self {
+        Self::Username(format!("@{}", username.0))
     }
 }
 
+/// Types of `chat_id` fields, read by [`telegram_method!`](crate::telegram_method).
+#[doc(hidden)]
+pub trait TargetChat {
+    fn target_chat(&self) -> Option<ChatId>;
+}
+
+impl TargetChat for ChatId {
+    fn target_chat(&self) -> Option<ChatId> {
+        Some(self.clone())
+    }
+}
+
+impl TargetChat for i64 {
+    fn target_chat(&self) -> Option<ChatId> {
+        Some(ChatId::Id(*self))
+    }
+}
+
+impl<T: TargetChat> TargetChat for Option<T> {
+    fn target_chat(&self) -> Option<ChatId> {
+        self.as_ref()?.target_chat()
+    }
+}
+
+/// A normalized username of a user, bot, group or channel.
+///
+/// The leading `@` and `t.me` link prefixes are stripped, and the username is lowercased,
+/// so that usernames pasted in any form compare equal.
+///
+/// ```
+/// # use telbot_types::chat::Username;
+/// let username = Username::parse("@TelBot").unwrap();
+/// assert_eq!(username.as_str(), "telbot");
+/// assert_eq!(Username::parse("https://t.me/telbot").unwrap(), username);
+/// assert_eq!(username.to_string(), "@telbot");
+/// assert!(Username::parse("@1bot").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Username(String);
+
+impl Username {
+    /// Minimum length of usernames, which is 4 for collectible usernames and 5 otherwise.
+    pub const MIN_LENGTH: usize = 4;
+    /// Maximum length of usernames.
+    pub const MAX_LENGTH: usize = 32;
+
+    /// Normalizes and validates the username, given as `name`, `@name` or `t.me/name`.
+    pub fn parse(input: &str) -> Result<Self, UsernameError> {
+        let input = input.trim();
+        let input = input
+            .strip_prefix("https://")
+            .or_else(|| input.strip_prefix("http://"))
+            .unwrap_or(input);
+        let input = ["t.me/", "telegram.me/", "telegram.dog/"]
+            .iter()
+            .find_map(|host| input.strip_prefix(host))
+            .map(|path| path.split(['/', '?'].as_ref()).next().unwrap_or(""))
+            .unwrap_or(input);
+        let username = input.strip_prefix('@').unwrap_or(input);
+
+        let length = username.chars().count();
+        if !(Self::MIN_LENGTH..=Self::MAX_LENGTH).contains(&length) {
+            return Err(UsernameError::InvalidLength(length));
+        }
+        if let Some(c) = username
+            .chars()
+            .find(|c| !c.is_ascii_alphanumeric() && *c != '_')
+        {
+            return Err(UsernameError::InvalidCharacter(c));
+        }
+        if !username.starts_with(|c: char| c.is_ascii_alphabetic()) || username.ends_with('_') {
+            return Err(UsernameError::InvalidFormat);
+        }
+        Ok(Self(username.to_ascii_lowercase()))
+    }
+
+    /// Gets the lowercased username without the leading `@`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Formats the username with the leading `@`.
+impl std::fmt::Display for Username {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@{}", self.0)
+    }
+}
+
+impl std::str::FromStr for Username {
+    type Err = UsernameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Username {
+    type Error = UsernameError;
+
+    fn try_from(username: String) -> Result<Self, Self::Error> {
+        Self::parse(&username)
+    }
+}
+
+impl From<Username> for String {
+    fn from(username: Username) -> Self {
+        username.0
+    }
+}
+
+impl AsRef<str> for Username {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Error from validating a [`Username`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UsernameError {
+    /// The username is shorter than [`Username::MIN_LENGTH`] or longer than [`Username::MAX_LENGTH`].
+    InvalidLength(usize),
+    /// The username contains a character other than ASCII letters, digits and underscores.
+    InvalidCharacter(char),
+    /// The username does not start with a letter, or ends with an underscore.
+    InvalidFormat,
+}
+
+impl std::fmt::Display for UsernameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength(length) => write!(
+                f,
+                "username must be {} to {} characters long, but has {}",
+                Username::MIN_LENGTH,
+                Username::MAX_LENGTH,
+                length
+            ),
+            Self::InvalidCharacter(c) => write!(f, "username cannot contain {:?}", c),
+            Self::InvalidFormat => write!(
+                f,
+                "username must start with a letter and cannot end with an underscore"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UsernameError {}
+
 /// Bans a user in a group, a supergroup or a channel.
 ///
 /// In the case of supergroups and channels, the user will not be able to return to the chat
@@ -1052,6 +1546,22 @@ impl BanChatMember {
         }
     }
 
+    /// Sets the user to be unbanned after the given duration from now.
+    ///
+    /// Telegram bans users forever for durations under 30 seconds or over 366 days,
+    /// so the duration is clamped between a minute and 366 days.
+    /// Use [`BanChatMember::forever`] to ban the user forever.
+    ///
+    /// See also [`BanChatMember::until_date`].
+    pub fn for_duration(self, duration: Duration) -> Self {
+        self.until_date(until_after(duration))
+    }
+
+    /// Sets the user to be banned forever.
+    pub fn forever(self) -> Self {
+        self.until_date(UNTIL_FOREVER)
+    }
+
     /// Deletes all messages from the chat for the user that is being removed.
     ///
     /// See also [`BanChatMember::revoke_messages`].
@@ -1063,15 +1573,7 @@ impl BanChatMember {
     }
 }
 
-impl TelegramMethod for BanChatMember {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "banChatMember"
-    }
-}
-
-impl JsonMethod for BanChatMember {}
+telegram_method!(BanChatMember => "banChatMember", bool, json, chat_id);
 
 /// Unbans a previously banned user in a supergroup or channel.
 ///
@@ -1116,15 +1618,7 @@ impl UnbanChatMember {
     }
 }
 
-impl TelegramMethod for UnbanChatMember {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "unbanChatMember"
-    }
-}
-
-impl JsonMethod for UnbanChatMember {}
+telegram_method!(UnbanChatMember => "unbanChatMember", bool, json, chat_id);
 
 /// Restricts a user in a supergroup.
 ///
@@ -1188,17 +1682,25 @@ impl RestrictChatMember {
             ..self
         }
     }
-}
 
-impl TelegramMethod for RestrictChatMember {
-    type Response = bool;
+    /// Sets the restriction to be lifted after the given duration from now.
+    ///
+    /// Telegram restricts users forever for durations under 30 seconds or over 366 days,
+    /// so the duration is clamped between a minute and 366 days.
+    /// Use [`RestrictChatMember::forever`] to restrict the user forever.
+    ///
+    /// See also [`RestrictChatMember::until_date`].
+    pub fn for_duration(self, duration: Duration) -> Self {
+        self.until_date(until_after(duration))
+    }
 
-    fn name() -> &'static str {
-        "restrictChatMember"
+    /// Sets the restriction to be applied forever.
+    pub fn forever(self) -> Self {
+        self.until_date(UNTIL_FOREVER)
     }
 }
 
-impl JsonMethod for RestrictChatMember {}
+telegram_method!(RestrictChatMember => "restrictChatMember", bool, json, chat_id);
 
 /// Promotes or demotes a user in a supergroup or a channel.
 ///
@@ -1388,15 +1890,7 @@ impl PromoteChatMember {
     }
 }
 
-impl TelegramMethod for PromoteChatMember {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "promoteChatMember"
-    }
-}
-
-impl JsonMethod for PromoteChatMember {}
+telegram_method!(PromoteChatMember => "promoteChatMember", bool, json, chat_id);
 
 /// Sets a custom title for an administrator in a supergroup promoted by the bot.
 ///
@@ -1424,15 +1918,7 @@ impl SetChatAdministratorCustomTitle {
     }
 }
 
-impl TelegramMethod for SetChatAdministratorCustomTitle {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "setChatAdministratorCustomTitle"
-    }
-}
-
-impl JsonMethod for SetChatAdministratorCustomTitle {}
+telegram_method!(SetChatAdministratorCustomTitle => "setChatAdministratorCustomTitle", bool, json, chat_id);
 
 /// Sets default chat permissions for all members.
 ///
@@ -1458,17 +1944,14 @@ impl SetChatPermissions {
             permissions,
         }
     }
-}
 
-impl TelegramMethod for SetChatPermissions {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "setChatPermissions"
+    /// Creates a new [`SetChatPermissions`] request which will set the permissions of the preset in the chat.
+    pub fn preset(chat_id: impl Into<ChatId>, preset: PermissionPreset) -> Self {
+        Self::new(chat_id, preset.into())
     }
 }
 
-impl JsonMethod for SetChatPermissions {}
+telegram_method!(SetChatPermissions => "setChatPermissions", bool, json, chat_id);
 
 /// Generates a new primary invite link for a chat;
 /// any previously generated primary link is revoked.
@@ -1500,15 +1983,7 @@ impl ExportChatInviteLink {
     }
 }
 
-impl TelegramMethod for ExportChatInviteLink {
-    type Response = String;
-
-    fn name() -> &'static str {
-        "exportChatInviteLink"
-    }
-}
-
-impl JsonMethod for ExportChatInviteLink {}
+telegram_method!(ExportChatInviteLink => "exportChatInviteLink", String, json, chat_id);
 
 /// Creates an additional invite link for a chat.
 ///
@@ -1588,15 +2063,7 @@ impl CreateChatInviteLink {
     }
 }
 
-impl TelegramMethod for CreateChatInviteLink {
-    type Response = ChatInviteLink;
-
-    fn name() -> &'static str {
-        "createChatInviteLink"
-    }
-}
-
-impl JsonMethod for CreateChatInviteLink {}
+telegram_method!(CreateChatInviteLink => "createChatInviteLink", ChatInviteLink, json, chat_id);
 
 /// Edits a non-primary invite link created by the bot.
 ///
@@ -1673,6 +2140,8 @@ impl EditChatInviteLink {
     }
 }
 
+telegram_method!(EditChatInviteLink => "editChatInviteLink", ChatInviteLink, json, chat_id);
+
 /// Revokes an invite link created by the bot.
 ///
 /// If the primary link is revoked, a new link is automatically generated.
@@ -1700,15 +2169,7 @@ impl RevokeChatInviteLink {
     }
 }
 
-impl TelegramMethod for RevokeChatInviteLink {
-    type Response = ChatInviteLink;
-
-    fn name() -> &'static str {
-        "revokeChatInviteLink"
-    }
-}
-
-impl JsonMethod for RevokeChatInviteLink {}
+telegram_method!(RevokeChatInviteLink => "revokeChatInviteLink", ChatInviteLink, json, chat_id);
 
 /// Approves a chat join request.
 ///
@@ -1735,15 +2196,7 @@ impl ApproveChatJoinRequest {
     }
 }
 
-impl TelegramMethod for ApproveChatJoinRequest {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "approveChatJoinRequest"
-    }
-}
-
-impl JsonMethod for ApproveChatJoinRequest {}
+telegram_method!(ApproveChatJoinRequest => "approveChatJoinRequest", bool, json, chat_id);
 
 /// Declines a chat join request.
 ///
@@ -1770,15 +2223,7 @@ impl DeclineChatJoinRequest {
     }
 }
 
-impl TelegramMethod for DeclineChatJoinRequest {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "declineChatJoinRequest"
-    }
-}
-
-impl JsonMethod for DeclineChatJoinRequest {}
+telegram_method!(DeclineChatJoinRequest => "declineChatJoinRequest", bool, json, chat_id);
 
 /// Sets a new profile photo for the chat.
 ///
@@ -1807,16 +2252,16 @@ impl SetChatPhoto {
     }
 }
 
-impl TelegramMethod for SetChatPhoto {
-    type Response = bool;
+telegram_method!(SetChatPhoto => "setChatPhoto", bool, chat_id);
 
-    fn name() -> &'static str {
-        "setChatPhoto"
+impl FileMethod for SetChatPhoto {
+    fn files(&self) -> Option<HashMap<&str, &InputFile>> {
+        let mut map = HashMap::new();
+        map.insert("photo", &self.photo);
+        Some(map)
     }
 }
 
-impl JsonMethod for SetChatPhoto {}
-
 /// Deletes a chat photo.
 ///
 /// Photos can't be changed for private chats.
@@ -1841,15 +2286,7 @@ impl DeleteChatPhoto {
     }
 }
 
-impl TelegramMethod for DeleteChatPhoto {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "deleteChatPhoto"
-    }
-}
-
-impl JsonMethod for DeleteChatPhoto {}
+telegram_method!(DeleteChatPhoto => "deleteChatPhoto", bool, json, chat_id);
 
 /// Changes the title of a chat.
 ///
@@ -1864,10 +2301,47 @@ impl JsonMethod for DeleteChatPhoto {}
 pub struct SetChatTitle {
     /// Unique identifier for the target group or username of the target supergroup or channel (in the format `@username`).
     pub chat_id: ChatId,
-    /// New chat title, 1-255 characters.
+    /// New chat title, 1-128 characters.
     pub title: String,
 }
 
+/// Maximum number of characters in a chat title.
+pub const MAX_CHAT_TITLE_LENGTH: usize = 128;
+
+/// Maximum number of characters in a chat description.
+pub const MAX_CHAT_DESCRIPTION_LENGTH: usize = 255;
+
+/// Error from validating chat title or description locally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatTextError {
+    /// The title is empty.
+    EmptyTitle,
+    /// The title is longer than [`MAX_CHAT_TITLE_LENGTH`] characters.
+    TitleTooLong(usize),
+    /// The description is longer than [`MAX_CHAT_DESCRIPTION_LENGTH`] characters.
+    DescriptionTooLong(usize),
+}
+
+impl std::fmt::Display for ChatTextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyTitle => write!(f, "chat title is empty"),
+            Self::TitleTooLong(length) => write!(
+                f,
+                "chat title has {} characters, exceeding {}",
+                length, MAX_CHAT_TITLE_LENGTH
+            ),
+            Self::DescriptionTooLong(length) => write!(
+                f,
+                "chat description has {} characters, exceeding {}",
+                length, MAX_CHAT_DESCRIPTION_LENGTH
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChatTextError {}
+
 impl SetChatTitle {
     /// Creates a new [`SetChatTitle`] request which will change the title of the chat.
     pub fn new(chat_id: impl Into<ChatId>, title: impl Into<String>) -> Self {
@@ -1876,17 +2350,24 @@ impl SetChatTitle {
             title: title.into(),
         }
     }
-}
-
-impl TelegramMethod for SetChatTitle {
-    type Response = bool;
 
-    fn name() -> &'static str {
-        "setChatTitle"
+    /// Creates a new [`SetChatTitle`] request, validating the length of the title.
+    ///
+    /// Titles do not support formatting; use [`ParseMode::strip_formatting`](crate::markup::ParseMode::strip_formatting) to remove it beforehand.
+    pub fn new_checked(
+        chat_id: impl Into<ChatId>,
+        title: impl Into<String>,
+    ) -> Result<Self, ChatTextError> {
+        let title = title.into();
+        match title.chars().count() {
+            0 => Err(ChatTextError::EmptyTitle),
+            length if length > MAX_CHAT_TITLE_LENGTH => Err(ChatTextError::TitleTooLong(length)),
+            _ => Ok(Self::new(chat_id, title)),
+        }
     }
 }
 
-impl JsonMethod for SetChatTitle {}
+telegram_method!(SetChatTitle => "setChatTitle", bool, json, chat_id);
 
 /// Changes the description of a group, a supergroup or a channel.
 ///
@@ -1920,16 +2401,26 @@ impl SetChatDescription {
             description: Some(description.into()),
         }
     }
-}
-
-impl TelegramMethod for SetChatDescription {
-    type Response = bool;
 
-    fn name() -> &'static str {
-        "setChatDescription"
+    /// Creates a new [`SetChatDescription`] request, validating the length of the description.
+    ///
+    /// Descriptions do not support formatting; use [`ParseMode::strip_formatting`](crate::markup::ParseMode::strip_formatting) to remove it beforehand.
+    pub fn new_checked(
+        chat_id: impl Into<ChatId>,
+        description: impl Into<String>,
+    ) -> Result<Self, ChatTextError> {
+        let description = description.into();
+        match description.chars().count() {
+            length if length > MAX_CHAT_DESCRIPTION_LENGTH => {
+                Err(ChatTextError::DescriptionTooLong(length))
+            }
+            _ => Ok(Self::new(chat_id, description)),
+        }
     }
 }
 
+telegram_method!(SetChatDescription => "setChatDescription", bool, json, chat_id);
+
 /// Adds a message to the list of pinned messages in a chat.
 ///
 /// If the chat is not a private chat, the bot must be an administrator in the chat for this to work
@@ -1969,15 +2460,7 @@ impl PinChatMessage {
     }
 }
 
-impl TelegramMethod for PinChatMessage {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "pinChatMessage"
-    }
-}
-
-impl JsonMethod for PinChatMessage {}
+telegram_method!(PinChatMessage => "pinChatMessage", bool, json, chat_id);
 
 /// Removes a message from the list of pinned messages in a chat.
 ///
@@ -2016,15 +2499,7 @@ impl UnpinChatMessage {
     }
 }
 
-impl TelegramMethod for UnpinChatMessage {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "unpinChatMessage"
-    }
-}
-
-impl JsonMethod for UnpinChatMessage {}
+telegram_method!(UnpinChatMessage => "unpinChatMessage", bool, json, chat_id);
 
 /// Clears the list of pinned messages in a chat.
 ///
@@ -2049,15 +2524,7 @@ impl UnpinAllChatMessages {
     }
 }
 
-impl TelegramMethod for UnpinAllChatMessages {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "unpinAllChatMessages"
-    }
-}
-
-impl JsonMethod for UnpinAllChatMessages {}
+telegram_method!(UnpinAllChatMessages => "unpinAllChatMessages", bool, json, chat_id);
 
 /// Leaves a group, supergroup or channel.
 ///
@@ -2079,15 +2546,7 @@ impl LeaveChat {
     }
 }
 
-impl TelegramMethod for LeaveChat {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "leaveChat"
-    }
-}
-
-impl JsonMethod for LeaveChat {}
+telegram_method!(LeaveChat => "leaveChat", bool, json, chat_id);
 
 /// Gets up to date information about the chat
 /// (current name of the user for one-on-one conversations, current username of a user, group or channel, etc.).
@@ -2110,15 +2569,7 @@ impl GetChat {
     }
 }
 
-impl TelegramMethod for GetChat {
-    type Response = Chat;
-
-    fn name() -> &'static str {
-        "getChat"
-    }
-}
-
-impl JsonMethod for GetChat {}
+telegram_method!(GetChat => "getChat", Chat, json, chat_id);
 
 /// Gets a list of administrators in a chat.
 ///
@@ -2142,15 +2593,7 @@ impl GetChatAdministrators {
     }
 }
 
-impl TelegramMethod for GetChatAdministrators {
-    type Response = Vec<ChatMember>;
-
-    fn name() -> &'static str {
-        "getChatAdministrators"
-    }
-}
-
-impl JsonMethod for GetChatAdministrators {}
+telegram_method!(GetChatAdministrators => "getChatAdministrators", Vec<ChatMember>, json, chat_id);
 
 /// Gets the number of members in a chat.
 ///
@@ -2172,15 +2615,7 @@ impl GetChatMemberCount {
     }
 }
 
-impl TelegramMethod for GetChatMemberCount {
-    type Response = u32;
-
-    fn name() -> &'static str {
-        "getChatMemberCount"
-    }
-}
-
-impl JsonMethod for GetChatMemberCount {}
+telegram_method!(GetChatMemberCount => "getChatMemberCount", u32, json, chat_id);
 
 /// Gets information about a member of a chat.
 ///
@@ -2205,13 +2640,7 @@ impl GetChatMember {
     }
 }
 
-impl TelegramMethod for GetChatMember {
-    type Response = ChatMember;
-
-    fn name() -> &'static str {
-        "getChatMember"
-    }
-}
+telegram_method!(GetChatMember => "getChatMember", ChatMember, json, chat_id);
 
 /// Sets a new group sticker set for a supergroup.
 ///
@@ -2239,15 +2668,7 @@ impl SetChatStickerSet {
     }
 }
 
-impl TelegramMethod for SetChatStickerSet {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "setChatStickerSet"
-    }
-}
-
-impl JsonMethod for SetChatStickerSet {}
+telegram_method!(SetChatStickerSet => "setChatStickerSet", bool, json, chat_id);
 
 /// Deletes a group sticker set from a supergroup.
 ///
@@ -2272,12 +2693,29 @@ impl DeleteChatStickerSet {
     }
 }
 
-impl TelegramMethod for DeleteChatStickerSet {
-    type Response = bool;
+telegram_method!(DeleteChatStickerSet => "deleteChatStickerSet", bool, json, chat_id);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn name() -> &'static str {
-        "deleteChatStickerSet"
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
     }
-}
 
-impl JsonMethod for DeleteChatStickerSet {}
+    #[test]
+    fn clamps_durations_telegram_considers_forever() {
+        let start = now();
+        let ban = BanChatMember::new(1, 2).for_duration(Duration::from_secs(10));
+        let until = ban.until_date.unwrap();
+        assert!(until >= start + 60 && until <= now() + 60);
+
+        let restrict =
+            RestrictChatMember::new(1, 2, ChatPermissions::default()).for_duration(Duration::MAX);
+        let until = restrict.until_date.unwrap();
+        assert!(until >= start + 366 * 24 * 60 * 60 && until <= now() + 366 * 24 * 60 * 60);
+    }
+}
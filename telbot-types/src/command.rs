@@ -0,0 +1,246 @@
+//! Parsing of bot commands like `/start@MyBot arg1 arg2`.
+//!
+//! Commands are located with `bot_command` entities, whose offsets are counted in UTF-16 code units,
+//! so text with emoji or other characters outside the BMP before the arguments is handled correctly.
+
+use std::fmt;
+use std::iter::Peekable;
+use std::str::SplitWhitespace;
+
+use crate::markup::{MessageEntity, MessageEntityKind};
+use crate::message::Message;
+
+/// Parses bot commands at the start of messages.
+///
+/// ```
+/// # use telbot_types::command::BotCommandParser;
+/// # use telbot_types::message::Message;
+/// # let message: Message = serde_json::from_str(r#"{
+/// #     "message_id": 1, "date": 0, "chat": {"id": 1, "type": "private"},
+/// #     "text": "/ban@MyBot 1234 7", "entities": [{"type": "bot_command", "offset": 0, "length": 10}]
+/// # }"#).unwrap();
+/// let parser = BotCommandParser::new().with_username("mybot");
+/// let command = parser.parse_message(&message).unwrap();
+/// assert_eq!(command.name, "ban");
+/// assert_eq!(command.mention, Some("MyBot"));
+/// let (user_id, days): (i64, Option<u32>) = command.parse_args().unwrap();
+/// assert_eq!((user_id, days), (1234, Some(7)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BotCommandParser {
+    username: Option<String>,
+}
+
+impl BotCommandParser {
+    /// Creates a new parser accepting commands addressed to any bot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ignores commands addressed to other bots, like `/start@OtherBot`.
+    ///
+    /// Usernames are compared case-insensitively, with or without the leading `@`.
+    pub fn with_username(self, username: impl Into<String>) -> Self {
+        let username = username.into();
+        Self {
+            username: Some(username.trim_start_matches('@').to_string()),
+        }
+    }
+
+    /// Parses the command at the start of the text, located by the `bot_command` entity.
+    pub fn parse<'a>(
+        &self,
+        text: &'a str,
+        entities: &[MessageEntity],
+    ) -> Option<ParsedCommand<'a>> {
+        let entity = entities.iter().find(|entity| {
+            matches!(entity.kind, MessageEntityKind::BotCommand) && entity.offset == 0
+        })?;
+        let command = entity.text_in(text)?;
+        let args = text[command.len()..].trim();
+        let command = command.strip_prefix('/')?;
+        let (name, mention) = match command.find('@') {
+            Some(at) => (&command[..at], Some(&command[at + 1..])),
+            None => (command, None),
+        };
+        let parsed = ParsedCommand {
+            name,
+            mention,
+            args,
+        };
+        match &self.username {
+            Some(username) if !parsed.is_addressed_to(username) => None,
+            _ => Some(parsed),
+        }
+    }
+
+    /// Parses the command at the start of the text message.
+    pub fn parse_message<'a>(&self, message: &'a Message) -> Option<ParsedCommand<'a>> {
+        self.parse(message.kind.text()?, message.kind.entities()?)
+    }
+}
+
+/// A bot command parsed by [`BotCommandParser`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedCommand<'a> {
+    /// Name of the command without the leading slash, like `start`.
+    pub name: &'a str,
+    /// Username of the bot mentioned after `@`, if any.
+    pub mention: Option<&'a str>,
+    /// Text after the command, without surrounding whitespace.
+    pub args: &'a str,
+}
+
+impl<'a> ParsedCommand<'a> {
+    /// `true` if the command mentions no bot, or mentions the bot with the username case-insensitively.
+    pub fn is_addressed_to(&self, username: &str) -> bool {
+        match self.mention {
+            Some(mention) => mention.eq_ignore_ascii_case(username.trim_start_matches('@')),
+            None => true,
+        }
+    }
+
+    /// Gets the arguments separated by whitespace.
+    pub fn args(&self) -> CommandArgs<'a> {
+        CommandArgs::new(self.args)
+    }
+
+    /// Parses all arguments into a value, e.g. a tuple of the argument types.
+    ///
+    /// Fails if any argument is left unparsed.
+    pub fn parse_args<T: FromCommandArgs>(&self) -> Result<T, CommandArgsError> {
+        let mut args = self.args();
+        let value = T::from_args(&mut args)?;
+        match args.next() {
+            Some(arg) => Err(CommandArgsError::TooMany(arg.to_string())),
+            None => Ok(value),
+        }
+    }
+}
+
+/// Arguments of a command separated by whitespace.
+#[derive(Debug, Clone)]
+pub struct CommandArgs<'a> {
+    args: Peekable<SplitWhitespace<'a>>,
+}
+
+impl<'a> CommandArgs<'a> {
+    /// Creates a new [`CommandArgs`] splitting the text by whitespace.
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            args: text.split_whitespace().peekable(),
+        }
+    }
+
+    /// `true` if no argument is left.
+    pub fn is_empty(&mut self) -> bool {
+        self.args.peek().is_none()
+    }
+}
+
+impl<'a> Iterator for CommandArgs<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.args.next()
+    }
+}
+
+/// A type which can be parsed from arguments of a command.
+///
+/// Implementations consume as many arguments as they need,
+/// so that a derive macro for structs can parse each field in order.
+/// Implemented for strings, numbers, `bool` and `char` taking one argument,
+/// `Option<T>` taking an argument only if any is left, `Vec<T>` taking all the remaining arguments,
+/// and tuples of up to 6 elements.
+pub trait FromCommandArgs: Sized {
+    /// Parses the value, consuming the arguments it needs.
+    fn from_args(args: &mut CommandArgs<'_>) -> Result<Self, CommandArgsError>;
+}
+
+macro_rules! impl_from_command_args {
+    ($($ty:ty),*) => {
+        $(
+            impl FromCommandArgs for $ty {
+                fn from_args(args: &mut CommandArgs<'_>) -> Result<Self, CommandArgsError> {
+                    let arg = args.next().ok_or(CommandArgsError::Missing)?;
+                    arg.parse().map_err(|_| CommandArgsError::Invalid {
+                        arg: arg.to_string(),
+                        expected: stringify!($ty),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_from_command_args!(
+    String, bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+impl<T: FromCommandArgs> FromCommandArgs for Option<T> {
+    fn from_args(args: &mut CommandArgs<'_>) -> Result<Self, CommandArgsError> {
+        if args.is_empty() {
+            Ok(None)
+        } else {
+            T::from_args(args).map(Some)
+        }
+    }
+}
+
+impl<T: FromCommandArgs> FromCommandArgs for Vec<T> {
+    fn from_args(args: &mut CommandArgs<'_>) -> Result<Self, CommandArgsError> {
+        let mut values = vec![];
+        while !args.is_empty() {
+            values.push(T::from_args(args)?);
+        }
+        Ok(values)
+    }
+}
+
+macro_rules! impl_from_command_args_for_tuple {
+    ($($ty:ident),*) => {
+        impl<$($ty: FromCommandArgs),*> FromCommandArgs for ($($ty,)*) {
+            fn from_args(args: &mut CommandArgs<'_>) -> Result<Self, CommandArgsError> {
+                Ok(($($ty::from_args(args)?,)*))
+            }
+        }
+    };
+}
+
+impl_from_command_args_for_tuple!(A);
+impl_from_command_args_for_tuple!(A, B);
+impl_from_command_args_for_tuple!(A, B, C);
+impl_from_command_args_for_tuple!(A, B, C, D);
+impl_from_command_args_for_tuple!(A, B, C, D, E);
+impl_from_command_args_for_tuple!(A, B, C, D, E, F);
+
+/// Error from parsing arguments of a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandArgsError {
+    /// An argument is required, but no argument is left.
+    Missing,
+    /// An argument cannot be parsed into the expected type.
+    Invalid {
+        /// The argument.
+        arg: String,
+        /// Name of the expected type.
+        expected: &'static str,
+    },
+    /// Arguments are left after parsing, starting with this argument.
+    TooMany(String),
+}
+
+impl fmt::Display for CommandArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "missing argument"),
+            Self::Invalid { arg, expected } => {
+                write!(f, "invalid argument `{}`, expected {}", arg, expected)
+            }
+            Self::TooMany(arg) => write!(f, "unexpected argument `{}`", arg),
+        }
+    }
+}
+
+impl std::error::Error for CommandArgsError {}
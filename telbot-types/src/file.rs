@@ -1,14 +1,55 @@
 //! Types, requests, and responses related to files.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::markup::{MessageEntity, ParseMode};
-use crate::{JsonMethod, TelegramMethod};
+use crate::telegram_method;
+
+/// Unique identifier of a file, which is supposed to be the same over time and for different bots.
+///
+/// Unlike file identifiers, it can't be used to download or reuse the file,
+/// but it can be used to tell whether two files are the same, e.g. with [`SeenMedia`](crate::seen_media::SeenMedia).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FileUniqueId(pub String);
+
+impl FileUniqueId {
+    /// Gets the identifier as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for FileUniqueId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for FileUniqueId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for FileUniqueId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl AsRef<str> for FileUniqueId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
 
 /// An animation file (GIF or H.264/MPEG-4 AVC video without sound).
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#animation)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Animation {
     /// Identifier for this file, which can be used to download or reuse the file.
     pub file_id: String,
@@ -23,19 +64,23 @@ pub struct Animation {
     /// Duration of the video in seconds as defined by sender.
     pub duration: u32,
     /// Animation thumbnail as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
     /// Original animation filename as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_name: Option<String>,
     /// MIME type of the file as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     /// File size.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<usize>,
 }
 
 /// An audio file to be treated as music by the Telegram clients.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#audio)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Audio {
     /// Identifier for this file, which can be used to download or reuse the file.
     pub file_id: String,
@@ -46,16 +91,22 @@ pub struct Audio {
     /// Duration of the audio in seconds as defined by sender.
     pub duration: u32,
     /// Performer of the audio as defined by sender or by audio tags.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub performer: Option<String>,
     /// Title of the audio as defined by sender or by audio tags.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     /// Original filename as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_name: Option<String>,
     /// MIME type of the file as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     /// File size.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<usize>,
     /// Thumbnail of the album cover to which the music file belongs.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
 }
 
@@ -65,7 +116,7 @@ pub struct Audio {
 /// [audio files](struct.Audio.html)).
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#document)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Document {
     /// Identifier for this file, which can be used to download or reuse the file.
     pub file_id: String,
@@ -74,12 +125,16 @@ pub struct Document {
     /// Can't be used to download or reuse the file.
     pub file_unique_id: String,
     /// Document thumbnail as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
     /// Original filename as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_name: Option<String>,
     /// MIME type of the file as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     /// File size.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<usize>,
 }
 
@@ -88,7 +143,7 @@ pub struct Document {
 /// [sticker](../sticker/struct.Sticker.html) thumbnail.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#photosize)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhotoSize {
     /// Identifier for this file, which can be used to download or reuse the file.
     pub file_id: String,
@@ -107,7 +162,7 @@ pub struct PhotoSize {
 /// A video file.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#video)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Video {
     /// Identifier for this file, which can be used to download or reuse the file.
     pub file_id: String,
@@ -122,12 +177,16 @@ pub struct Video {
     /// Duration of the video in seconds as defined by sender.
     pub duration: u32,
     /// Video thumbnail.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
     /// Original animation filename as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_name: Option<String>,
     /// MIME type of the file as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     /// File size.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<u32>,
 }
 
@@ -135,7 +194,7 @@ pub struct Video {
 /// (available in Telegram apps as of [v.4.0](https://telegram.org/blog/video-messages-and-telescope)).
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#videonote)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoNote {
     /// Identifier for this file, which can be used to download or reuse the file.
     pub file_id: String,
@@ -148,15 +207,17 @@ pub struct VideoNote {
     /// Duration of the video in seconds as defined by sender.
     pub duration: u32,
     /// Video thumbnail.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<PhotoSize>,
     /// File size.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<u32>,
 }
 
 /// A voice note.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#voice)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Voice {
     /// Identifier for this file, which can be used to download or reuse the file.
     pub file_id: String,
@@ -167,8 +228,10 @@ pub struct Voice {
     /// Duration of the audio in seconds as defined by sender.
     pub duration: u32,
     /// MIME type of the file as defined by sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     /// File size.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<u32>,
 }
 
@@ -179,7 +242,7 @@ pub struct Voice {
 /// When the link expires, a new one can be requested by calling [`GetFile`].
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#file)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct File {
     /// Identifier for this file, which can be used to download or reuse the file.
     pub file_id: String,
@@ -188,11 +251,114 @@ pub struct File {
     /// Can't be used to download or reuse the file.
     pub file_unique_id: String,
     /// File size, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size: Option<u32>,
     /// File path. Use `https://api.telegram.org/file/bot<token>/<file_path>` to get the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file_path: Option<String>,
 }
 
+/// Verification of downloaded files, which detects truncated downloads.
+///
+/// The size of the content is compared with [`File::file_size`] if known,
+/// and the SHA-256 hash of the content with the expected one if given.
+///
+/// ```
+/// # use telbot_types::file::{DownloadCheck, File, IntegrityError};
+/// let file = File {
+///     file_id: "id".to_string(),
+///     file_unique_id: "unique".to_string(),
+///     file_size: Some(5),
+///     file_path: Some("documents/file_0.txt".to_string()),
+/// };
+/// let check = DownloadCheck::new();
+/// assert_eq!(check.verify(&file, b"hello"), Ok(()));
+/// assert_eq!(
+///     check.verify(&file, b"hel"),
+///     Err(IntegrityError::SizeMismatch { expected: 5, actual: 3 })
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DownloadCheck {
+    #[cfg(feature = "sha256")]
+    sha256: Option<[u8; 32]>,
+}
+
+impl DownloadCheck {
+    /// Creates a new [`DownloadCheck`] comparing only the size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compares the SHA-256 hash of the content with the expected one, e.g. one stored on the first download.
+    #[cfg(feature = "sha256")]
+    pub fn with_sha256(self, sha256: [u8; 32]) -> Self {
+        Self {
+            sha256: Some(sha256),
+        }
+    }
+
+    /// Verifies the content downloaded from the file.
+    pub fn verify(&self, file: &File, data: &[u8]) -> Result<(), IntegrityError> {
+        if let Some(expected) = file.file_size {
+            if data.len() as u64 != expected as u64 {
+                return Err(IntegrityError::SizeMismatch {
+                    expected: expected as u64,
+                    actual: data.len() as u64,
+                });
+            }
+        }
+        #[cfg(feature = "sha256")]
+        if let Some(expected) = self.sha256 {
+            let actual = sha256(data);
+            if actual != expected {
+                return Err(IntegrityError::HashMismatch { expected, actual });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Computes the SHA-256 hash of downloaded content, e.g. to store it for later verification.
+#[cfg(feature = "sha256")]
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    hmac_sha256::Hash::hash(data)
+}
+
+/// Error from verifying a downloaded file with [`DownloadCheck`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityError {
+    /// The size of the content differs from [`File::file_size`], e.g. the download was truncated.
+    SizeMismatch {
+        /// Size of the file reported by Telegram.
+        expected: u64,
+        /// Size of the downloaded content.
+        actual: u64,
+    },
+    /// The SHA-256 hash of the content differs from the expected one.
+    HashMismatch {
+        /// Expected hash.
+        expected: [u8; 32],
+        /// Hash of the downloaded content.
+        actual: [u8; 32],
+    },
+}
+
+impl std::fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SizeMismatch { expected, actual } => write!(
+                f,
+                "downloaded {} bytes, but the file has {} bytes",
+                actual, expected
+            ),
+            Self::HashMismatch { .. } => write!(f, "SHA-256 hash of the downloaded file differs"),
+        }
+    }
+}
+
+impl std::error::Error for IntegrityError {}
+
 /// The content of a media message to be sent.
 ///
 /// It should be one of
@@ -212,10 +378,10 @@ pub enum InputMedia {
         ///
         /// Pass a `file_id` to send a file that exists on the Telegram servers (recommended),
         /// pass an HTTP URL for Telegram to get a file from the Internet,
-        /// or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data under <file_attach_name> name.
+        /// or pass an [`InputFile`] to upload a new one, which is attached as “attach://<file_attach_name>”.
         ///
         //// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
-        media: String,
+        media: InputFileVariant,
         /// Caption of the photo to be sent, 0-1024 characters after entities parsing.
         #[serde(skip_serializing_if = "Option::is_none")]
         caption: Option<String>,
@@ -228,6 +394,9 @@ pub enum InputMedia {
         /// which can be specified instead of [`InputMedia::Photo::parse_mode`].
         #[serde(skip_serializing_if = "Option::is_none")]
         caption_entities: Option<Vec<MessageEntity>>,
+        /// Pass `true` if the caption must be shown above the message media.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        show_caption_above_media: Option<bool>,
     },
     /// A video to be sent.
     Video {
@@ -235,10 +404,10 @@ pub enum InputMedia {
         ///
         /// Pass a `file_id` to send a file that exists on the Telegram servers (recommended),
         /// pass an HTTP URL for Telegram to get a file from the Internet,
-        /// or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data under <file_attach_name> name.
+        /// or pass an [`InputFile`] to upload a new one, which is attached as “attach://<file_attach_name>”.
         ///
         //// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
-        media: String,
+        media: InputFileVariant,
         /// Thumbnail of the file sent; can be ignored if thumbnail generation for the file is supported server-side.
         ///
         /// The thumbnail should be in JPEG format and less than 200 kB in size.
@@ -247,8 +416,8 @@ pub enum InputMedia {
         ///
         /// Ignored if the file is not uploaded using multipart/form-data.
         ///
-        /// Thumbnails can't be reused and can be only uploaded as a new file, so you can pass “attach://<file_attach_name>”
-        /// if the thumbnail was uploaded using multipart/form-data under <file_attach_name>.
+        /// Thumbnails can't be reused and can be only uploaded as a new file,
+        /// which is attached as “attach://<file_attach_name>”.
         ///
         /// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -263,6 +432,7 @@ pub enum InputMedia {
         #[serde(skip_serializing_if = "Option::is_none")]
         duration: Option<u32>,
         /// Pass `true` if the uploaded video is suitable for streaming.
+        #[serde(skip_serializing_if = "Option::is_none")]
         supports_streaming: Option<bool>,
         /// Caption of the video to be sent, 0-1024 characters after entities parsing.
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -276,6 +446,9 @@ pub enum InputMedia {
         /// which can be specified instead of [`InputMedia::Video::parse_mode`]
         #[serde(skip_serializing_if = "Option::is_none")]
         caption_entities: Option<Vec<MessageEntity>>,
+        /// Pass `true` if the caption must be shown above the message media.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        show_caption_above_media: Option<bool>,
     },
     /// An animation file (GIF or H.264/MPEG-4 AVC video without sound) to be sent.
     Animation {
@@ -283,10 +456,10 @@ pub enum InputMedia {
         ///
         /// Pass a `file_id` to send a file that exists on the Telegram servers (recommended),
         /// pass an HTTP URL for Telegram to get a file from the Internet,
-        /// or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data under <file_attach_name> name.
+        /// or pass an [`InputFile`] to upload a new one, which is attached as “attach://<file_attach_name>”.
         ///
         //// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
-        media: String,
+        media: InputFileVariant,
         /// Thumbnail of the file sent; can be ignored if thumbnail generation for the file is supported server-side.
         ///
         /// The thumbnail should be in JPEG format and less than 200 kB in size.
@@ -295,8 +468,8 @@ pub enum InputMedia {
         ///
         /// Ignored if the file is not uploaded using multipart/form-data.
         ///
-        /// Thumbnails can't be reused and can be only uploaded as a new file, so you can pass “attach://<file_attach_name>”
-        /// if the thumbnail was uploaded using multipart/form-data under <file_attach_name>.
+        /// Thumbnails can't be reused and can be only uploaded as a new file,
+        /// which is attached as “attach://<file_attach_name>”.
         ///
         /// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -322,6 +495,9 @@ pub enum InputMedia {
         /// which can be specified instead of [`InputMedia::Animation::parse_mode`]
         #[serde(skip_serializing_if = "Option::is_none")]
         caption_entities: Option<Vec<MessageEntity>>,
+        /// Pass `true` if the caption must be shown above the message media.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        show_caption_above_media: Option<bool>,
     },
     /// An audio file to be treated as music to be sent.
     Audio {
@@ -329,10 +505,10 @@ pub enum InputMedia {
         ///
         /// Pass a `file_id` to send a file that exists on the Telegram servers (recommended),
         /// pass an HTTP URL for Telegram to get a file from the Internet,
-        /// or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data under <file_attach_name> name.
+        /// or pass an [`InputFile`] to upload a new one, which is attached as “attach://<file_attach_name>”.
         ///
         //// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
-        media: String,
+        media: InputFileVariant,
         /// Thumbnail of the file sent; can be ignored if thumbnail generation for the file is supported server-side.
         ///
         /// The thumbnail should be in JPEG format and less than 200 kB in size.
@@ -341,8 +517,8 @@ pub enum InputMedia {
         ///
         /// Ignored if the file is not uploaded using multipart/form-data.
         ///
-        /// Thumbnails can't be reused and can be only uploaded as a new file, so you can pass “attach://<file_attach_name>”
-        /// if the thumbnail was uploaded using multipart/form-data under <file_attach_name>.
+        /// Thumbnails can't be reused and can be only uploaded as a new file,
+        /// which is attached as “attach://<file_attach_name>”.
         ///
         /// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -375,10 +551,10 @@ pub enum InputMedia {
         ///
         /// Pass a `file_id` to send a file that exists on the Telegram servers (recommended),
         /// pass an HTTP URL for Telegram to get a file from the Internet,
-        /// or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data under <file_attach_name> name.
+        /// or pass an [`InputFile`] to upload a new one, which is attached as “attach://<file_attach_name>”.
         ///
         //// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
-        media: String,
+        media: InputFileVariant,
         /// Thumbnail of the file sent; can be ignored if thumbnail generation for the file is supported server-side.
         ///
         /// The thumbnail should be in JPEG format and less than 200 kB in size.
@@ -387,8 +563,8 @@ pub enum InputMedia {
         ///
         /// Ignored if the file is not uploaded using multipart/form-data.
         ///
-        /// Thumbnails can't be reused and can be only uploaded as a new file, so you can pass “attach://<file_attach_name>”
-        /// if the thumbnail was uploaded using multipart/form-data under <file_attach_name>.
+        /// Thumbnails can't be reused and can be only uploaded as a new file,
+        /// which is attached as “attach://<file_attach_name>”.
         ///
         /// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -408,6 +584,114 @@ pub enum InputMedia {
     },
 }
 
+/// Names of form fields under which new files of [`InputMedia`] are uploaded, one for each item of a media group.
+const MEDIA_ATTACH_NAMES: [&str; 10] = [
+    "media0", "media1", "media2", "media3", "media4", "media5", "media6", "media7", "media8",
+    "media9",
+];
+/// Names of form fields under which new thumbnails of [`InputMedia`] are uploaded.
+const THUMB_ATTACH_NAMES: [&str; 10] = [
+    "thumb0", "thumb1", "thumb2", "thumb3", "thumb4", "thumb5", "thumb6", "thumb7", "thumb8",
+    "thumb9",
+];
+
+impl InputMedia {
+    /// Gets the file to send.
+    pub fn media(&self) -> &InputFileVariant {
+        match self {
+            Self::Photo { media, .. }
+            | Self::Video { media, .. }
+            | Self::Animation { media, .. }
+            | Self::Audio { media, .. }
+            | Self::Document { media, .. } => media,
+        }
+    }
+
+    /// Gets the thumbnail of the file, if any.
+    pub fn thumb(&self) -> Option<&InputFileVariant> {
+        match self {
+            Self::Photo { .. } => None,
+            Self::Video { thumb, .. }
+            | Self::Animation { thumb, .. }
+            | Self::Audio { thumb, .. }
+            | Self::Document { thumb, .. } => thumb.as_ref(),
+        }
+    }
+
+    /// Adds new files of the `index`-th media to the files of a request, under their attach names.
+    pub(crate) fn attach_files<'a>(
+        &'a self,
+        index: usize,
+        files: &mut HashMap<&'a str, &'a InputFile>,
+    ) {
+        if let (InputFileVariant::File(file), Some(name)) =
+            (self.media(), MEDIA_ATTACH_NAMES.get(index))
+        {
+            files.insert(name, file);
+        }
+        if let (Some(InputFileVariant::File(file)), Some(name)) =
+            (self.thumb(), THUMB_ATTACH_NAMES.get(index))
+        {
+            files.insert(name, file);
+        }
+    }
+}
+
+/// Serializes the `index`-th media, replacing new files with “attach://<file_attach_name>”.
+struct AttachedMedia<'a> {
+    media: &'a InputMedia,
+    index: usize,
+}
+
+impl Serialize for AttachedMedia<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::Error;
+
+        let mut value = serde_json::to_value(self.media).map_err(S::Error::custom)?;
+        let attach = |names: &[&str]| match names.get(self.index) {
+            Some(name) => Ok(serde_json::Value::from(format!("attach://{}", name))),
+            None => Err(S::Error::custom("media group can upload up to 10 files")),
+        };
+        if let InputFileVariant::File(_) = self.media.media() {
+            value["media"] = attach(&MEDIA_ATTACH_NAMES)?;
+        }
+        if let Some(InputFileVariant::File(_)) = self.media.thumb() {
+            value["thumb"] = attach(&THUMB_ATTACH_NAMES)?;
+        }
+        value.serialize(serializer)
+    }
+}
+
+/// Serializes a media with new files attached, for `#[serde(serialize_with)]`.
+pub(crate) fn serialize_attached_media<S>(
+    media: &InputMedia,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    AttachedMedia { media, index: 0 }.serialize(serializer)
+}
+
+/// Serializes a media group with new files attached, for `#[serde(serialize_with)]`.
+pub(crate) fn serialize_attached_media_group<S>(
+    media_group: &[InputMedia],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_seq(
+        media_group
+            .iter()
+            .enumerate()
+            .map(|(index, media)| AttachedMedia { media, index }),
+    )
+}
+
 /// A file to be sent.
 #[derive(Clone, Serialize)]
 #[serde(untagged)]
@@ -486,12 +770,4 @@ impl GetFile {
     }
 }
 
-impl TelegramMethod for GetFile {
-    type Response = File;
-
-    fn name() -> &'static str {
-        "getFile"
-    }
-}
-
-impl JsonMethod for GetFile {}
+telegram_method!(GetFile => "getFile", File, json);
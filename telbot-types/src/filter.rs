@@ -0,0 +1,218 @@
+//! Declarative conditions on incoming messages.
+//!
+//! Filters are plain predicates which can be combined, so that rules like
+//! "documents larger than 20 MB which are not PDFs" are declared once and shared between handlers.
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::command::BotCommandParser;
+use crate::member_count::MemberCountCache;
+use crate::message::{Message, MessageKind};
+
+/// A condition on messages.
+///
+/// Closures taking `&Message` are also filters.
+///
+/// ```
+/// # use telbot_types::filter::{document_larger_than, mime_matches, Filter};
+/// # use telbot_types::message::Message;
+/// let too_large = document_larger_than(20 * 1024 * 1024).and(mime_matches("application/pdf").not());
+/// # let message: Message = serde_json::from_str(r#"{
+/// #     "message_id": 1, "date": 0, "chat": {"id": 1, "type": "private"},
+/// #     "document": {"file_id": "a", "file_unique_id": "b", "mime_type": "video/mp4", "file_size": 104857600}
+/// # }"#).unwrap();
+/// // a message with a 100 MB video as a document
+/// assert!(too_large.matches(&message));
+/// ```
+pub trait Filter {
+    /// `true` if the message satisfies the condition.
+    fn matches(&self, message: &Message) -> bool;
+
+    /// Creates a filter which matches if both filters match.
+    fn and<F: Filter>(self, other: F) -> And<Self, F>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Creates a filter which matches if either filter matches.
+    fn or<F: Filter>(self, other: F) -> Or<Self, F>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+
+    /// Creates a filter which matches if this filter does not match.
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+}
+
+impl<F: Fn(&Message) -> bool> Filter for F {
+    fn matches(&self, message: &Message) -> bool {
+        self(message)
+    }
+}
+
+/// Filter created by [`Filter::and`].
+#[derive(Debug, Clone)]
+pub struct And<A, B>(A, B);
+
+impl<A: Filter, B: Filter> Filter for And<A, B> {
+    fn matches(&self, message: &Message) -> bool {
+        self.0.matches(message) && self.1.matches(message)
+    }
+}
+
+/// Filter created by [`Filter::or`].
+#[derive(Debug, Clone)]
+pub struct Or<A, B>(A, B);
+
+impl<A: Filter, B: Filter> Filter for Or<A, B> {
+    fn matches(&self, message: &Message) -> bool {
+        self.0.matches(message) || self.1.matches(message)
+    }
+}
+
+/// Filter created by [`Filter::not`].
+#[derive(Debug, Clone)]
+pub struct Not<F>(F);
+
+impl<F: Filter> Filter for Not<F> {
+    fn matches(&self, message: &Message) -> bool {
+        !self.0.matches(message)
+    }
+}
+
+/// Matches documents, including animations, larger than the size in bytes.
+///
+/// Documents of unknown size do not match.
+pub fn document_larger_than(bytes: usize) -> impl Filter + Clone {
+    move |message: &Message| match message.kind.document() {
+        Some(document) => document.file_size.unwrap_or(0) > bytes,
+        None => false,
+    }
+}
+
+/// Matches messages sent by the bot, or sent via the bot in inline mode.
+///
+/// See [`Message::is_sent_by`].
+pub fn sent_by(bot_id: i64) -> impl Filter + Clone {
+    move |message: &Message| message.is_sent_by(bot_id)
+}
+
+/// Matches media whose MIME type matches the pattern, like `image/png` or `image/*`.
+///
+/// Documents, animations, audios, videos and voices have MIME types.
+/// Media of unknown type do not match.
+pub fn mime_matches(pattern: &str) -> impl Filter + Clone {
+    let pattern = pattern.to_ascii_lowercase();
+    move |message: &Message| match mime_type(&message.kind) {
+        Some(mime_type) => is_mime_match(&pattern, &mime_type.to_ascii_lowercase()),
+        None => false,
+    }
+}
+
+/// Matches messages starting with the command, like `start` for `/start`, addressed to the bot.
+///
+/// Commands addressed to other bots, like `/start@OtherBot` in groups, do not match;
+/// the username of the bot is compared case-insensitively, with or without the leading `@`.
+/// Get it once with [`GetMe`](crate::bot::GetMe), e.g. from the `me()` cache of the backend.
+///
+/// ```
+/// # use telbot_types::filter::{command, Filter};
+/// # use telbot_types::message::Message;
+/// # let message = |text: &str| -> Message {
+/// #     let length = text.split_whitespace().next().unwrap().len();
+/// #     serde_json::from_value(serde_json::json!({
+/// #         "message_id": 1, "date": 0, "chat": {"id": -1, "type": "group"}, "text": text,
+/// #         "entities": [{"type": "bot_command", "offset": 0, "length": length}]
+/// #     })).unwrap()
+/// # };
+/// let start = command("start", "MyBot");
+/// assert!(start.matches(&message("/start")));
+/// assert!(start.matches(&message("/start@mybot now")));
+/// assert!(!start.matches(&message("/start@OtherBot")));
+/// assert!(!start.matches(&message("/stop@MyBot")));
+/// ```
+pub fn command(name: &str, bot_username: &str) -> impl Filter + Clone {
+    let name = name.trim_start_matches('/').to_string();
+    let parser = BotCommandParser::new().with_username(bot_username);
+    move |message: &Message| matches!(parser.parse_message(message), Some(parsed) if parsed.name == name)
+}
+
+/// Matches messages sent to the forum topic of the chat.
+///
+/// See [`Message::topic_id`].
+pub fn in_topic(chat_id: i64, topic_id: i64) -> impl Filter + Clone {
+    move |message: &Message| message.chat.id == chat_id && message.topic_id() == Some(topic_id)
+}
+
+/// Matches photos whose largest size is at least `width` by `height`.
+pub fn photo_min_resolution(width: u32, height: u32) -> impl Filter + Clone {
+    move |message: &Message| match message.kind.photo().and_then(|photo| photo.last()) {
+        Some(size) => size.width >= width && size.height >= height,
+        None => false,
+    }
+}
+
+/// Matches messages in chats with at least `threshold` members, according to the cache.
+///
+/// Chats whose count is not cached or expired do not match.
+/// The cache is shared with the code filling it, which fetches counts of chats with
+/// [`GetChatMemberCount`](crate::chat::GetChatMemberCount) when [`MemberCountCache::get`] returns `None`.
+///
+/// ```
+/// # use std::sync::{Arc, Mutex};
+/// # use std::time::Duration;
+/// # use telbot_types::filter::{large_chat, Filter};
+/// # use telbot_types::member_count::MemberCountCache;
+/// # use telbot_types::message::Message;
+/// # let message: Message = serde_json::from_str(r#"{
+/// #     "message_id": 1, "date": 0, "chat": {"id": -100, "type": "supergroup", "title": "Kiwi"}, "text": "Hi"
+/// # }"#).unwrap();
+/// let counts = Arc::new(Mutex::new(MemberCountCache::new(Duration::from_secs(60 * 60))));
+/// let chatty_allowed = large_chat(&counts, 1000).not();
+/// counts.lock().unwrap().insert(-100, 5000);
+/// assert!(!chatty_allowed.matches(&message));
+/// ```
+pub fn large_chat(cache: &Arc<Mutex<MemberCountCache>>, threshold: u32) -> impl Filter + Clone {
+    let cache = Arc::clone(cache);
+    move |message: &Message| {
+        cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_large(message.chat.id, threshold)
+            .unwrap_or(false)
+    }
+}
+
+fn mime_type(kind: &MessageKind) -> Option<&str> {
+    let mime_type = match kind {
+        MessageKind::Animation { document, .. } | MessageKind::Document { document, .. } => {
+            &document.mime_type
+        }
+        MessageKind::Audio { audio, .. } => &audio.mime_type,
+        MessageKind::Video { video, .. } => &video.mime_type,
+        MessageKind::Voice { voice, .. } => &voice.mime_type,
+        _ => return None,
+    };
+    mime_type.as_deref()
+}
+
+fn is_mime_match(pattern: &str, mime_type: &str) -> bool {
+    if pattern == "*" || pattern == "*/*" {
+        return true;
+    }
+    // Parameters like `; charset=utf-8` are not part of the type.
+    let mime_type = mime_type.split(';').next().unwrap_or("").trim();
+    match pattern.strip_suffix("/*") {
+        Some(kind) => matches!(mime_type.split('/').next(), Some(prefix) if prefix == kind),
+        None => pattern == mime_type,
+    }
+}
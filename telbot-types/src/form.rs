@@ -0,0 +1,96 @@
+//! Encoding of requests with files into fields of `multipart/form-data` bodies.
+//!
+//! Telegram reads each form field as a string, so string fields are sent as is,
+//! numbers and booleans as their literals, and only structured fields like `entities` or
+//! `reply_markup` as JSON.
+
+use serde::ser::Error as _;
+
+use crate::file::InputFile;
+use crate::FileMethod;
+
+/// A value of a form field.
+#[derive(Clone)]
+pub enum FormValue<'a> {
+    /// A text field.
+    Text(String),
+    /// A file to be uploaded.
+    File(&'a InputFile),
+}
+
+impl FormValue<'_> {
+    /// Gets the text of the field, or `None` if the field is a file.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::File(_) => None,
+        }
+    }
+
+    /// Gets the file of the field, or `None` if the field is a text.
+    pub fn as_file(&self) -> Option<&InputFile> {
+        match self {
+            Self::Text(_) => None,
+            Self::File(file) => Some(file),
+        }
+    }
+}
+
+/// Encodes the request into (name, value) pairs of form fields.
+///
+/// Fields of [`FileMethod::files`] become files, and fields serialized into `null` are omitted.
+/// Files which are not fields of the request, like those attached as `attach://<file_attach_name>`,
+/// are added after the fields.
+///
+/// ```
+/// # use telbot_types::form::{to_form_fields, FormValue};
+/// # use telbot_types::file::InputFile;
+/// # use telbot_types::message::SendDocument;
+/// let document = InputFile {
+///     name: "report.txt".to_string(),
+///     data: b"hello".to_vec(),
+///     mime: "text/plain".to_string(),
+/// };
+/// let send = SendDocument::new(1, document).with_caption(r#"the "monthly" report"#);
+/// let fields = to_form_fields(&send).unwrap();
+/// let field = |name| fields.iter().find(|(key, _)| key == name).map(|(_, value)| value);
+/// assert_eq!(field("chat_id").and_then(FormValue::as_text), Some("1"));
+/// assert_eq!(field("caption").and_then(FormValue::as_text), Some(r#"the "monthly" report"#));
+/// assert_eq!(field("document").and_then(FormValue::as_file).unwrap().name, "report.txt");
+/// ```
+pub fn to_form_fields<Method: FileMethod>(
+    method: &Method,
+) -> serde_json::Result<Vec<(String, FormValue<'_>)>> {
+    let fields = match serde_json::to_value(method)? {
+        serde_json::Value::Object(fields) => fields,
+        _ => {
+            return Err(serde_json::Error::custom(
+                "request must be serialized into a map",
+            ))
+        }
+    };
+    let mut files = method.files().unwrap_or_default();
+    let mut fields: Vec<_> = fields
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let value = match files.remove(key.as_str()) {
+                Some(file) => FormValue::File(file),
+                None => FormValue::Text(match value {
+                    serde_json::Value::Null => return None,
+                    serde_json::Value::String(text) => text,
+                    value => value.to_string(),
+                }),
+            };
+            Some((key, value))
+        })
+        .collect();
+    // Files attached by name, like items of media groups, are not fields of the request.
+    let mut attached: Vec<_> = files.into_iter().collect();
+    attached.sort_by_key(|(key, _)| *key);
+    fields.extend(
+        attached
+            .into_iter()
+            .map(|(key, file)| (key.to_string(), FormValue::File(file))),
+    );
+    Ok(fields)
+}
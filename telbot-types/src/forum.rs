@@ -0,0 +1,487 @@
+//! Types, requests, and responses related to forum topics of supergroups.
+
+use serde::{Deserialize, Serialize};
+
+use crate::chat::ChatId;
+use crate::sticker::Sticker;
+use crate::telegram_method;
+
+/// A forum topic.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#forumtopic)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForumTopic {
+    /// Unique identifier of the forum topic.
+    pub message_thread_id: i64,
+    /// Name of the topic.
+    pub name: String,
+    /// Color of the topic icon in RGB format.
+    pub icon_color: u32,
+    /// Unique identifier of the custom emoji shown as the topic icon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_custom_emoji_id: Option<String>,
+}
+
+/// A service message about a new forum topic created in the chat.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#forumtopiccreated)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForumTopicCreated {
+    /// Name of the topic.
+    pub name: String,
+    /// Color of the topic icon in RGB format.
+    pub icon_color: u32,
+    /// Unique identifier of the custom emoji shown as the topic icon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_custom_emoji_id: Option<String>,
+}
+
+/// A service message about an edited forum topic.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#forumtopicedited)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForumTopicEdited {
+    /// New name of the topic, if it was edited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// New identifier of the custom emoji shown as the topic icon, if it was edited;
+    /// an empty string if the icon was removed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_custom_emoji_id: Option<String>,
+}
+
+/// A service message about a forum topic closed in the chat.
+/// Currently holds no information.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#forumtopicclosed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForumTopicClosed {}
+
+/// A service message about a forum topic reopened in the chat.
+/// Currently holds no information.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#forumtopicreopened)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForumTopicReopened {}
+
+/// Finds the custom emoji identifier of the forum topic icon showing the emoji,
+/// among the stickers returned by [`GetForumTopicIconStickers`].
+///
+/// Emoji are compared without variation selectors, so `"❤"` and `"❤️"` are the same icon.
+///
+/// ```
+/// # use telbot_types::forum::find_topic_icon;
+/// # use telbot_types::sticker::Sticker;
+/// # let stickers: Vec<Sticker> = serde_json::from_str(r#"[{
+/// #     "file_id": "file", "file_unique_id": "unique", "type": "custom_emoji",
+/// #     "width": 512, "height": 512, "is_animated": false, "is_video": false,
+/// #     "emoji": "❤️", "custom_emoji_id": "5312016608254762256"
+/// # }]"#).unwrap();
+/// // let stickers = api.send_json(&GetForumTopicIconStickers)?;
+/// assert_eq!(find_topic_icon(&stickers, "❤"), Some("5312016608254762256"));
+/// assert_eq!(find_topic_icon(&stickers, "🔥"), None);
+/// ```
+pub fn find_topic_icon<'a>(stickers: &'a [Sticker], emoji: &str) -> Option<&'a str> {
+    let is_same = |other: &str| {
+        let without_selector = |c: &char| *c != '\u{fe0f}';
+        emoji
+            .chars()
+            .filter(without_selector)
+            .eq(other.chars().filter(without_selector))
+    };
+    stickers
+        .iter()
+        .find(|sticker| matches!(sticker.emoji.as_deref(), Some(other) if is_same(other)))?
+        .custom_emoji_id
+        .as_deref()
+}
+
+/// Gets custom emoji stickers, which can be used as a forum topic icon by any user. Requires no parameters.
+///
+/// Returns an Array of [`Sticker`] objects.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#getforumtopiciconstickers)
+#[derive(Clone, Serialize)]
+pub struct GetForumTopicIconStickers;
+
+telegram_method!(GetForumTopicIconStickers => "getForumTopicIconStickers", Vec<Sticker>, json);
+
+/// Creates a topic in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_manage_topics` administrator rights.
+///
+/// Returns information about the created topic as a [`ForumTopic`] object.
+///
+/// ```
+/// # use telbot_types::forum::CreateForumTopic;
+/// # use telbot_types::message::SendMessage;
+/// let request = CreateForumTopic::new(-1001234, "Announcements").with_icon_color(0x6FB9F0);
+/// // let topic = api.send_json(&request)?;
+/// # let topic_id = 42;
+/// let message = SendMessage::new(-1001234, "Welcome!").with_message_thread_id(topic_id);
+/// # assert_eq!(serde_json::to_value(&request).unwrap()["icon_color"], 0x6FB9F0);
+/// # assert_eq!(serde_json::to_value(&message).unwrap()["message_thread_id"], 42);
+/// ```
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#createforumtopic)
+#[derive(Clone, Serialize)]
+pub struct CreateForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+    /// Topic name, 1-128 characters.
+    pub name: String,
+    /// Color of the topic icon in RGB format.
+    /// Currently, must be one of 7322096 (0x6FB9F0), 16766590 (0xFFD67E), 13338331 (0xCB86DB),
+    /// 9367192 (0x8EEE98), 16749490 (0xFF93B2), or 16478047 (0xFB6F5F).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<u32>,
+    /// Unique identifier of the custom emoji shown as the topic icon.
+    /// Use [`GetForumTopicIconStickers`] to get all allowed custom emoji identifiers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_custom_emoji_id: Option<String>,
+}
+
+impl CreateForumTopic {
+    /// Creates a new [`CreateForumTopic`] request which will create a topic with the name.
+    pub fn new(chat_id: impl Into<ChatId>, name: impl Into<String>) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            name: name.into(),
+            icon_color: None,
+            icon_custom_emoji_id: None,
+        }
+    }
+
+    /// Sets the color of the topic icon.
+    pub fn with_icon_color(self, color: u32) -> Self {
+        Self {
+            icon_color: Some(color),
+            ..self
+        }
+    }
+
+    /// Sets the custom emoji shown as the topic icon.
+    pub fn with_icon_custom_emoji_id(self, custom_emoji_id: impl Into<String>) -> Self {
+        Self {
+            icon_custom_emoji_id: Some(custom_emoji_id.into()),
+            ..self
+        }
+    }
+}
+
+telegram_method!(CreateForumTopic => "createForumTopic", ForumTopic, json, chat_id);
+
+/// Edits the name and icon of a topic in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have `can_manage_topics` administrator rights,
+/// unless it is the creator of the topic.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#editforumtopic)
+#[derive(Clone, Serialize)]
+pub struct EditForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+    /// Unique identifier for the target message thread of the forum topic.
+    pub message_thread_id: i64,
+    /// New topic name, 0-128 characters. If not specified, the current name of the topic will be kept.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// New unique identifier of the custom emoji shown as the topic icon.
+    /// Pass an empty string to remove the icon. If not specified, the current icon will be kept.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_custom_emoji_id: Option<String>,
+}
+
+impl EditForumTopic {
+    /// Creates a new [`EditForumTopic`] request which will keep the topic as is, until the name or the icon is set.
+    pub fn new(chat_id: impl Into<ChatId>, message_thread_id: i64) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            message_thread_id,
+            name: None,
+            icon_custom_emoji_id: None,
+        }
+    }
+
+    /// Sets the new name of the topic.
+    pub fn with_name(self, name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Sets the new custom emoji shown as the topic icon.
+    pub fn with_icon_custom_emoji_id(self, custom_emoji_id: impl Into<String>) -> Self {
+        Self {
+            icon_custom_emoji_id: Some(custom_emoji_id.into()),
+            ..self
+        }
+    }
+
+    /// Removes the icon of the topic.
+    pub fn remove_icon(self) -> Self {
+        self.with_icon_custom_emoji_id("")
+    }
+}
+
+telegram_method!(EditForumTopic => "editForumTopic", bool, json, chat_id);
+
+/// Closes an open topic in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_manage_topics` administrator rights, unless it is the creator of the topic.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#closeforumtopic)
+#[derive(Clone, Serialize)]
+pub struct CloseForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+    /// Unique identifier for the target message thread of the forum topic.
+    pub message_thread_id: i64,
+}
+
+impl CloseForumTopic {
+    /// Creates a new [`CloseForumTopic`] request which will close the topic.
+    pub fn new(chat_id: impl Into<ChatId>, message_thread_id: i64) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            message_thread_id,
+        }
+    }
+}
+
+telegram_method!(CloseForumTopic => "closeForumTopic", bool, json, chat_id);
+
+/// Reopens a closed topic in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_manage_topics` administrator rights, unless it is the creator of the topic.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#reopenforumtopic)
+#[derive(Clone, Serialize)]
+pub struct ReopenForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+    /// Unique identifier for the target message thread of the forum topic.
+    pub message_thread_id: i64,
+}
+
+impl ReopenForumTopic {
+    /// Creates a new [`ReopenForumTopic`] request which will reopen the topic.
+    pub fn new(chat_id: impl Into<ChatId>, message_thread_id: i64) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            message_thread_id,
+        }
+    }
+}
+
+telegram_method!(ReopenForumTopic => "reopenForumTopic", bool, json, chat_id);
+
+/// Deletes a forum topic along with all its messages in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_delete_messages` administrator rights.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#deleteforumtopic)
+#[derive(Clone, Serialize)]
+pub struct DeleteForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+    /// Unique identifier for the target message thread of the forum topic.
+    pub message_thread_id: i64,
+}
+
+impl DeleteForumTopic {
+    /// Creates a new [`DeleteForumTopic`] request which will delete the topic.
+    pub fn new(chat_id: impl Into<ChatId>, message_thread_id: i64) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            message_thread_id,
+        }
+    }
+}
+
+telegram_method!(DeleteForumTopic => "deleteForumTopic", bool, json, chat_id);
+
+/// Clears the list of pinned messages in a forum topic.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_pin_messages` administrator rights in the supergroup.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#unpinallforumtopicmessages)
+#[derive(Clone, Serialize)]
+pub struct UnpinAllForumTopicMessages {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+    /// Unique identifier for the target message thread of the forum topic.
+    pub message_thread_id: i64,
+}
+
+impl UnpinAllForumTopicMessages {
+    /// Creates a new [`UnpinAllForumTopicMessages`] request which will unpin all messages in the topic.
+    pub fn new(chat_id: impl Into<ChatId>, message_thread_id: i64) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            message_thread_id,
+        }
+    }
+}
+
+telegram_method!(UnpinAllForumTopicMessages => "unpinAllForumTopicMessages", bool, json, chat_id);
+
+/// Edits the name of the 'General' topic in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have `can_manage_topics` administrator rights.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#editgeneralforumtopic)
+#[derive(Clone, Serialize)]
+pub struct EditGeneralForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+    /// New topic name, 1-128 characters.
+    pub name: String,
+}
+
+impl EditGeneralForumTopic {
+    /// Creates a new [`EditGeneralForumTopic`] request which will rename the 'General' topic.
+    pub fn new(chat_id: impl Into<ChatId>, name: impl Into<String>) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+telegram_method!(EditGeneralForumTopic => "editGeneralForumTopic", bool, json, chat_id);
+
+/// Closes an open 'General' topic in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_manage_topics` administrator rights.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#closegeneralforumtopic)
+#[derive(Clone, Serialize)]
+pub struct CloseGeneralForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+}
+
+impl CloseGeneralForumTopic {
+    /// Creates a new [`CloseGeneralForumTopic`] request which will close the 'General' topic.
+    pub fn new(chat_id: impl Into<ChatId>) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+        }
+    }
+}
+
+telegram_method!(CloseGeneralForumTopic => "closeGeneralForumTopic", bool, json, chat_id);
+
+/// Reopens a closed 'General' topic in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_manage_topics` administrator rights.
+/// The topic will be automatically unhidden if it was hidden.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#reopengeneralforumtopic)
+#[derive(Clone, Serialize)]
+pub struct ReopenGeneralForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+}
+
+impl ReopenGeneralForumTopic {
+    /// Creates a new [`ReopenGeneralForumTopic`] request which will reopen the 'General' topic.
+    pub fn new(chat_id: impl Into<ChatId>) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+        }
+    }
+}
+
+telegram_method!(ReopenGeneralForumTopic => "reopenGeneralForumTopic", bool, json, chat_id);
+
+/// Hides the 'General' topic in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_manage_topics` administrator rights.
+/// The topic will be automatically closed if it was open.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#hidegeneralforumtopic)
+#[derive(Clone, Serialize)]
+pub struct HideGeneralForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+}
+
+impl HideGeneralForumTopic {
+    /// Creates a new [`HideGeneralForumTopic`] request which will hide the 'General' topic.
+    pub fn new(chat_id: impl Into<ChatId>) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+        }
+    }
+}
+
+telegram_method!(HideGeneralForumTopic => "hideGeneralForumTopic", bool, json, chat_id);
+
+/// Unhides the 'General' topic in a forum supergroup chat.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_manage_topics` administrator rights.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#unhidegeneralforumtopic)
+#[derive(Clone, Serialize)]
+pub struct UnhideGeneralForumTopic {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+}
+
+impl UnhideGeneralForumTopic {
+    /// Creates a new [`UnhideGeneralForumTopic`] request which will unhide the 'General' topic.
+    pub fn new(chat_id: impl Into<ChatId>) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+        }
+    }
+}
+
+telegram_method!(UnhideGeneralForumTopic => "unhideGeneralForumTopic", bool, json, chat_id);
+
+/// Clears the list of pinned messages in a 'General' forum topic.
+///
+/// The bot must be an administrator in the chat for this to work and must have the `can_pin_messages` administrator right in the supergroup.
+///
+/// Returns `true` on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#unpinallgeneralforumtopicmessages)
+#[derive(Clone, Serialize)]
+pub struct UnpinAllGeneralForumTopicMessages {
+    /// Unique identifier for the target chat or username of the target supergroup (in the format `@supergroupusername`).
+    pub chat_id: ChatId,
+}
+
+impl UnpinAllGeneralForumTopicMessages {
+    /// Creates a new [`UnpinAllGeneralForumTopicMessages`] request which will unpin all messages in the 'General' topic.
+    pub fn new(chat_id: impl Into<ChatId>) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+        }
+    }
+}
+
+telegram_method!(UnpinAllGeneralForumTopicMessages => "unpinAllGeneralForumTopicMessages", bool, json, chat_id);
@@ -0,0 +1,45 @@
+//! Types, requests, and responses related to [games](https://core.telegram.org/bots/games).
+//!
+//! Games are created with [BotFather](https://t.me/botfather) and sent with [`SendGame`].
+//! When the user presses the [`CallbackGame`](crate::markup::CallbackGame) button of the game message,
+//! the bot receives a callback query with the `game_short_name`, which is answered with the URL of the game.
+//! Scores are reported with [`SetGameScore`] and listed with [`GetGameHighScores`].
+//!
+//! The requests are enabled by the `games` feature.
+
+use serde::{Deserialize, Serialize};
+
+use crate::file::{Animation, PhotoSize};
+use crate::markup::MessageEntity;
+
+#[cfg(feature = "games")]
+mod requests;
+#[cfg(feature = "games")]
+pub use requests::*;
+
+/// A game.
+///
+/// Use BotFather to create and edit games, their short names will act as unique identifiers.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#game)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Game {
+    /// Title of the game.
+    pub title: String,
+    /// Description of the game.
+    pub description: String,
+    /// Photo that will be displayed in the game message in chats.
+    pub photo: Vec<PhotoSize>,
+    /// Brief description of the game or high scores included in the game message.
+    /// Can be automatically edited to include current high scores for the game
+    /// when the bot calls [`SetGameScore`], or manually edited using [`EditMessageText`](crate::message::EditMessageText).
+    /// 0-4096 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Special entities that appear in *text*, such as usernames, URLs, bot commands, etc.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_entities: Option<Vec<MessageEntity>>,
+    /// Animation that will be displayed in the game message in chats. Upload via [BotFather](https://t.me/botfather).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animation: Option<Animation>,
+}
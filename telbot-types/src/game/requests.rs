@@ -0,0 +1,305 @@
+//! Requests of games, enabled by the `games` feature.
+
+use serde::{Deserialize, Serialize};
+
+use crate::chat::Chat;
+use crate::markup::InlineKeyboardMarkup;
+use crate::message::Message;
+use crate::telegram_method;
+use crate::user::User;
+
+impl Chat {
+    /// Creates a [`SendGame`] request with the short name of the game
+    /// which will send the game to this chat.
+    pub fn send_game(&self, game_short_name: impl Into<String>) -> SendGame {
+        SendGame::new(self.id, game_short_name)
+    }
+}
+
+/// One row of the high scores table for a game.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#gamehighscore)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameHighScore {
+    /// Position in high score table for the game.
+    pub position: u32,
+    /// User.
+    pub user: User,
+    /// Score.
+    pub score: i64,
+}
+
+/// Sends a game.
+///
+/// On success, the sent [`Message`] is returned.
+///
+/// ```
+/// # use telbot_types::game::SendGame;
+/// # use telbot_types::markup::{CallbackGame, InlineKeyboardButtonKind, InlineKeyboardMarkup, InlineKeyboardRow};
+/// let play = InlineKeyboardButtonKind::CallbackGame {
+///     callback_game: CallbackGame {},
+/// };
+/// let request = SendGame::new(1234, "tetris").with_reply_markup(
+///     InlineKeyboardMarkup::new_with_row(InlineKeyboardRow::new_emplace("Play", play)),
+/// );
+/// assert_eq!(
+///     serde_json::to_value(&request).unwrap()["reply_markup"]["inline_keyboard"][0][0],
+///     serde_json::json!({"text": "Play", "callback_game": {}})
+/// );
+/// ```
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#sendgame)
+#[derive(Clone, Serialize)]
+pub struct SendGame {
+    /// Unique identifier for the target chat. Games can't be sent to channels.
+    pub chat_id: i64,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
+    /// Short name of the game, serves as the unique identifier for the game. Set up your games via [BotFather](https://t.me/botfather).
+    pub game_short_name: String,
+    /// Sends the message [silently](https://telegram.org/blog/channels-2-0#silent-messages).
+    /// Users will receive a notification with no sound.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_notification: Option<bool>,
+    /// If the message is a reply, ID of the original message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to_message_id: Option<i64>,
+    /// Pass *True*, if the message should be sent even if the specified replied-to message is not found.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_sending_without_reply: Option<bool>,
+    /// A JSON-serialized object for an [inline keyboard](https://core.telegram.org/bots#inline-keyboards-and-on-the-fly-updating).
+    /// If empty, one 'Play game_title' button will be shown.
+    /// If not empty, the first button must launch the game.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+    /// Protects the contents of the sent message from forwarding and saving.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protect_content: Option<bool>,
+}
+
+impl SendGame {
+    /// Creates a new [`SendGame`] request that sends the game with the given short name to the given chat.
+    pub fn new(chat_id: i64, game_short_name: impl Into<String>) -> Self {
+        Self {
+            chat_id,
+            message_thread_id: None,
+            game_short_name: game_short_name.into(),
+            disable_notification: None,
+            reply_to_message_id: None,
+            allow_sending_without_reply: None,
+            reply_markup: None,
+            protect_content: None,
+        }
+    }
+
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
+    }
+
+    /// Disables notification.
+    pub fn disable_notification(self) -> Self {
+        Self {
+            disable_notification: Some(true),
+            ..self
+        }
+    }
+
+    /// Replys to message.
+    pub fn reply_to(self, message_id: i64) -> Self {
+        Self {
+            reply_to_message_id: Some(message_id),
+            ..self
+        }
+    }
+
+    /// Allows sending message even if the replying message isn't present.
+    pub fn allow_sending_without_reply(self) -> Self {
+        Self {
+            allow_sending_without_reply: Some(true),
+            ..self
+        }
+    }
+
+    /// Sets reply markup, whose first button must launch the game.
+    pub fn with_reply_markup(self, markup: impl Into<InlineKeyboardMarkup>) -> Self {
+        Self {
+            reply_markup: Some(markup.into()),
+            ..self
+        }
+    }
+
+    /// Protects content.
+    pub fn protect_content(self) -> Self {
+        Self {
+            protect_content: Some(true),
+            ..self
+        }
+    }
+}
+
+telegram_method!(SendGame => "sendGame", Message, json, chat_id);
+
+/// Sets the score of the specified user in a game message.
+///
+/// On success, the edited [`Message`] is returned.
+/// Returns an error, if the new score is not greater than the user's current score in the chat and *force* is false.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#setgamescore)
+#[derive(Clone, Serialize)]
+pub struct SetGameScore {
+    /// User identifier.
+    pub user_id: i64,
+    /// New score, must be non-negative.
+    pub score: i64,
+    /// Pass True if the high score is allowed to decrease.
+    /// This can be useful when fixing mistakes or banning cheaters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force: Option<bool>,
+    /// Pass True if the game message should not be automatically edited to include the current scoreboard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_edit_message: Option<bool>,
+    /// Unique identifier for the target chat.
+    pub chat_id: i64,
+    /// Identifier of the sent message.
+    pub message_id: i64,
+}
+
+impl SetGameScore {
+    /// Creates a new [`SetGameScore`] request that sets the score of the user in the given game message.
+    pub fn new(chat_id: i64, message_id: i64, user_id: i64, score: i64) -> Self {
+        Self {
+            user_id,
+            score,
+            force: None,
+            disable_edit_message: None,
+            chat_id,
+            message_id,
+        }
+    }
+
+    /// Allows the high score to decrease.
+    pub fn force(self) -> Self {
+        Self {
+            force: Some(true),
+            ..self
+        }
+    }
+
+    /// Keeps the game message from being edited to include the current scoreboard.
+    pub fn disable_edit_message(self) -> Self {
+        Self {
+            disable_edit_message: Some(true),
+            ..self
+        }
+    }
+}
+
+telegram_method!(SetGameScore => "setGameScore", Message, json, chat_id);
+
+/// Sets the score of the specified user in an inline game message.
+///
+/// On success, `true` is returned.
+/// Returns an error, if the new score is not greater than the user's current score in the chat and *force* is false.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#setgamescore)
+#[derive(Clone, Serialize)]
+pub struct SetInlineGameScore {
+    /// User identifier.
+    pub user_id: i64,
+    /// New score, must be non-negative.
+    pub score: i64,
+    /// Pass True if the high score is allowed to decrease.
+    /// This can be useful when fixing mistakes or banning cheaters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force: Option<bool>,
+    /// Pass True if the game message should not be automatically edited to include the current scoreboard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_edit_message: Option<bool>,
+    /// Identifier of the inline message.
+    pub inline_message_id: String,
+}
+
+impl SetInlineGameScore {
+    /// Creates a new [`SetInlineGameScore`] request that sets the score of the user in the given inline game message.
+    pub fn new(inline_message_id: impl Into<String>, user_id: i64, score: i64) -> Self {
+        Self {
+            user_id,
+            score,
+            force: None,
+            disable_edit_message: None,
+            inline_message_id: inline_message_id.into(),
+        }
+    }
+
+    /// Allows the high score to decrease.
+    pub fn force(self) -> Self {
+        Self {
+            force: Some(true),
+            ..self
+        }
+    }
+
+    /// Keeps the game message from being edited to include the current scoreboard.
+    pub fn disable_edit_message(self) -> Self {
+        Self {
+            disable_edit_message: Some(true),
+            ..self
+        }
+    }
+}
+
+telegram_method!(SetInlineGameScore => "setGameScore", bool, json);
+
+/// Gets data for high score tables.
+///
+/// Will return the score of the specified user and several of their neighbors in a game.
+/// On success, returns an array of [`GameHighScore`] objects.
+///
+/// > This method will currently return scores for the target user, plus two of their closest neighbors on each side.
+/// > Will also return the top three users if the user and their neighbors are not among them.
+/// > Please note that this behavior is subject to change.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#getgamehighscores)
+#[derive(Clone, Serialize)]
+pub struct GetGameHighScores {
+    /// Target user id.
+    pub user_id: i64,
+    /// Unique identifier for the target chat. Required if *inline_message_id* is not specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<i64>,
+    /// Identifier of the sent message. Required if *inline_message_id* is not specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<i64>,
+    /// Identifier of the inline message. Required if *chat_id* and *message_id* are not specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline_message_id: Option<String>,
+}
+
+impl GetGameHighScores {
+    /// Creates a new [`GetGameHighScores`] request that gets high scores around the user in the given game message.
+    pub fn new(chat_id: i64, message_id: i64, user_id: i64) -> Self {
+        Self {
+            user_id,
+            chat_id: Some(chat_id),
+            message_id: Some(message_id),
+            inline_message_id: None,
+        }
+    }
+
+    /// Creates a new [`GetGameHighScores`] request that gets high scores around the user in the given inline game message.
+    pub fn new_inline(inline_message_id: impl Into<String>, user_id: i64) -> Self {
+        Self {
+            user_id,
+            chat_id: None,
+            message_id: None,
+            inline_message_id: Some(inline_message_id.into()),
+        }
+    }
+}
+
+telegram_method!(GetGameHighScores => "getGameHighScores", Vec<GameHighScore>, json, chat_id);
@@ -0,0 +1,93 @@
+//! Attribution of new chat members to the invite links they used.
+//!
+//! Telegram reports the invite link in [`ChatMemberUpdated::invite_link`] when a user joins by the link directly,
+//! but not always when a join request created by the link is approved later.
+//! [`InviteLinkTracker`] remembers the links of pending join requests to fill the gap.
+
+use std::collections::HashMap;
+
+use crate::chat::{ChatInviteLink, ChatMemberUpdated};
+
+/// Counts members who joined chats through each invite link.
+///
+/// ```
+/// # use telbot_types::invite_link::InviteLinkTracker;
+/// let mut tracker = InviteLinkTracker::new();
+/// // for each `chat_member` update:
+/// // if let Some(link) = tracker.record_member_update(&update) { /* a member joined by `link` */ }
+/// for (link, count) in tracker.counts() {
+///     println!("{}: {}", link, count);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InviteLinkTracker {
+    pending: HashMap<(i64, i64), String>,
+    counts: HashMap<String, usize>,
+}
+
+impl InviteLinkTracker {
+    /// Creates a new empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the invite link used by a pending join request of the given user.
+    ///
+    /// The link is attributed when the user actually joins the chat,
+    /// i.e. after the request is approved.
+    pub fn record_join_request(
+        &mut self,
+        chat_id: i64,
+        user_id: i64,
+        invite_link: &ChatInviteLink,
+    ) {
+        self.pending
+            .insert((chat_id, user_id), invite_link.invite_link.clone());
+    }
+
+    /// Forgets a pending join request of the given user, e.g. after declining it.
+    pub fn forget_join_request(&mut self, chat_id: i64, user_id: i64) {
+        self.pending.remove(&(chat_id, user_id));
+    }
+
+    /// Records a change of a chat member.
+    ///
+    /// Returns the invite link the member used if the change is a join attributed to an invite link.
+    pub fn record_member_update(&mut self, update: &ChatMemberUpdated) -> Option<&str> {
+        let key = (update.chat.id, update.new_chat_member.user().id);
+        if update.old_chat_member.is_member() || !update.new_chat_member.is_member() {
+            if !update.new_chat_member.is_member() {
+                self.pending.remove(&key);
+            }
+            return None;
+        }
+        let pending = self.pending.remove(&key);
+        let link = match &update.invite_link {
+            Some(invite_link) => invite_link.invite_link.clone(),
+            None => pending?,
+        };
+        *self.counts.entry(link.clone()).or_insert(0) += 1;
+        self.counts
+            .get_key_value(&link)
+            .map(|(link, _)| link.as_str())
+    }
+
+    /// Gets the number of members who joined by the given invite link.
+    pub fn count(&self, invite_link: &str) -> usize {
+        self.counts.get(invite_link).copied().unwrap_or_default()
+    }
+
+    /// Iterates over invite links and the number of members who joined by each of them.
+    pub fn counts(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.counts
+            .iter()
+            .map(|(link, count)| (link.as_str(), *count))
+    }
+
+    /// Takes all counters, resetting them to zero.
+    ///
+    /// Pending join requests are kept.
+    pub fn take_counts(&mut self) -> HashMap<String, usize> {
+        std::mem::take(&mut self.counts)
+    }
+}
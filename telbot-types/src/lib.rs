@@ -14,24 +14,55 @@
 //! representing those should be serialized into JSON format and multipart format, respectively.
 //! Your backend should take these two types of request and deserialize the response body into [`ApiResponse<T>`].
 //! Then you can take the actual response `T` from `ApiResponse<T>`.
+//! Implement [`TelegramClient`] for the API requester of your backend, so that bot logic can be shared between backends.
 
 use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
 
 use file::InputFile;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+pub mod admin_cache;
 pub mod bot;
 pub mod chat;
+pub mod command;
 pub mod file;
+pub mod filter;
+pub mod form;
+pub mod forum;
+pub mod game;
+pub mod invite_link;
+pub mod link;
+#[cfg(feature = "login")]
+pub mod login;
 pub mod markup;
+pub mod media_group;
+pub mod member_count;
 pub mod message;
+pub mod message_log;
+pub mod passport;
+#[cfg(feature = "passport-decrypt")]
+pub mod passport_decrypt;
 pub mod payment;
+pub mod pin_tracker;
 pub mod query;
+#[cfg(feature = "rate-limit")]
+pub mod rate_limit;
+pub mod reaction;
+pub mod reaction_poll;
+pub mod resolver;
+pub mod seen_media;
 pub mod sticker;
+pub mod ttl_map;
 pub mod update;
 pub mod user;
+#[cfg(feature = "vcard")]
+pub mod vcard;
+pub mod webapp;
 pub mod webhook;
+pub mod widget;
 
 /// Base trait for telegram method.
 pub trait TelegramMethod {
@@ -42,6 +73,13 @@ pub trait TelegramMethod {
     ///
     /// Used in request URL, like `https://api.telegram.org/bot<BOT TOKEN>/<METHOD NAME>`.
     fn name() -> &'static str;
+
+    /// Gets the chat the request is sent to, if the method has a `chat_id` parameter.
+    ///
+    /// Used to apply the rate limits of each chat.
+    fn chat_id(&self) -> Option<chat::ChatId> {
+        None
+    }
 }
 
 /// Methods that should be sent in JSON format.
@@ -53,6 +91,267 @@ pub trait FileMethod: TelegramMethod + Serialize {
     fn files(&self) -> Option<HashMap<&str, &InputFile>>;
 }
 
+/// A client sending requests to the Telegram API, implemented by the `Api` of every backend.
+///
+/// Bot logic written against this trait instead of a concrete backend
+/// can be tested offline with `MockApi` of `telbot-test`.
+///
+/// Futures are not required to be [`Send`], since those of backends like `telbot-cf-worker` are not.
+/// Synchronous backends like `telbot-ureq` block until the response arrives, and return a ready future.
+pub trait TelegramClient {
+    /// Error returned by requests.
+    type Error;
+
+    /// Sends the request in JSON format.
+    fn execute_json<Method: JsonMethod>(
+        &self,
+        method: &Method,
+    ) -> impl Future<Output = Result<Method::Response, Self::Error>>;
+
+    /// Sends the request in multipart format if it has files to upload, or in JSON format otherwise.
+    fn execute_file<Method: FileMethod>(
+        &self,
+        method: &Method,
+    ) -> impl Future<Output = Result<Method::Response, Self::Error>>;
+}
+
+/// Implements [`TelegramMethod`] for a request type with the method name and the response type,
+/// and [`JsonMethod`] if `json` is given.
+///
+/// If `chat_id` is given, [`TelegramMethod::chat_id`] returns the `chat_id` field of the request,
+/// which can be a [`ChatId`](chat::ChatId), an `i64`, or an [`Option`] of them.
+///
+/// Requests with files should implement [`FileMethod`] by hand.
+///
+/// ```
+/// # use serde::Serialize;
+/// # use telbot_types::chat::ChatId;
+/// # use telbot_types::message::Message;
+/// # use telbot_types::telegram_method;
+/// # use telbot_types::user::User;
+/// #[derive(Serialize)]
+/// struct GetMe;
+///
+/// telegram_method!(GetMe => "getMe", User, json);
+///
+/// #[derive(Serialize)]
+/// struct SendDice {
+///     chat_id: ChatId,
+/// }
+///
+/// telegram_method!(SendDice => "sendDice", Message, json, chat_id);
+/// ```
+#[macro_export]
+macro_rules! telegram_method {
+    ($method:ty => $name:literal, $response:ty) => {
+        impl $crate::TelegramMethod for $method {
+            type Response = $response;
+
+            fn name() -> &'static str {
+                $name
+            }
+        }
+    };
+    ($method:ty => $name:literal, $response:ty, chat_id) => {
+        impl $crate::TelegramMethod for $method {
+            type Response = $response;
+
+            fn name() -> &'static str {
+                $name
+            }
+
+            fn chat_id(&self) -> Option<$crate::chat::ChatId> {
+                $crate::chat::TargetChat::target_chat(&self.chat_id)
+            }
+        }
+    };
+    ($method:ty => $name:literal, $response:ty, json) => {
+        $crate::telegram_method!($method => $name, $response);
+
+        impl $crate::JsonMethod for $method {}
+    };
+    ($method:ty => $name:literal, $response:ty, json, chat_id) => {
+        $crate::telegram_method!($method => $name, $response, chat_id);
+
+        impl $crate::JsonMethod for $method {}
+    };
+}
+
+/// Extra options of a single request, such as headers required by a proxy in front of a local Bot API server.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Extra HTTP headers.
+    pub headers: Vec<(String, String)>,
+    /// Extra query parameters.
+    pub query: Vec<(String, String)>,
+}
+
+impl RequestOptions {
+    /// Create a new empty [`RequestOptions`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an HTTP header.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Adds a query parameter.
+    pub fn with_query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Appends the query parameters to the URL, percent-encoding them.
+    ///
+    /// ```
+    /// # use telbot_types::RequestOptions;
+    /// let options = RequestOptions::new().with_query("key", "a b&c");
+    /// assert_eq!(options.url_with_query("https://example.com/"), "https://example.com/?key=a%20b%26c");
+    /// ```
+    pub fn url_with_query(&self, url: &str) -> String {
+        let mut url = url.to_string();
+        for (index, (name, value)) in self.query.iter().enumerate() {
+            url.push(if index == 0 && !url.contains('?') {
+                '?'
+            } else {
+                '&'
+            });
+            percent_encode(&mut url, name);
+            url.push('=');
+            percent_encode(&mut url, value);
+        }
+        url
+    }
+}
+
+/// Policy of retrying requests which failed with flood control or transient transport errors.
+///
+/// Requests limited by flood control are retried after the time requested by Telegram,
+/// and other requests are retried with exponential backoff.
+/// Note that a request failing with a transport error may have reached Telegram,
+/// so retrying it can, for example, send a message twice.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use telbot_types::RetryPolicy;
+/// let policy = RetryPolicy::new(3).with_base_delay(Duration::from_secs(1));
+/// assert_eq!(policy.delay(0, None), Some(Duration::from_secs(1)));
+/// assert_eq!(policy.delay(2, None), Some(Duration::from_secs(4)));
+/// assert_eq!(policy.delay(1, Some(Duration::from_secs(30))), Some(Duration::from_secs(30)));
+/// assert_eq!(policy.delay(3, None), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a new [`RetryPolicy`] which retries a request at most `max_retries` times,
+    /// waiting 500 milliseconds at first and at most 30 seconds between transient failures.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+
+    /// Sets the delay before the first retry after a transient failure, which doubles with each retry.
+    pub fn with_base_delay(self, base_delay: Duration) -> Self {
+        Self { base_delay, ..self }
+    }
+
+    /// Sets the maximum delay between retries after transient failures.
+    ///
+    /// Waiting times requested by Telegram are respected regardless of this limit.
+    pub fn with_max_delay(self, max_delay: Duration) -> Self {
+        Self { max_delay, ..self }
+    }
+
+    /// Gets the maximum number of retries of a request.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Gets the delay before retrying a request which has been retried `retries` times,
+    /// or `None` if the request should not be retried anymore.
+    ///
+    /// `retry_after` is the waiting time requested by Telegram, if any.
+    pub fn delay(&self, retries: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if retries >= self.max_retries {
+            return None;
+        }
+        let backoff = 2u32
+            .checked_pow(retries)
+            .and_then(|factor| self.base_delay.checked_mul(factor))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+        Some(retry_after.unwrap_or(backoff))
+    }
+
+    /// Gets the delay before retrying a request which failed with the error, if it should be retried.
+    pub fn delay_for(&self, retries: u32, error: &TelegramError) -> Option<Duration> {
+        match error.kind() {
+            ApiErrorKind::TooManyRequests => self.delay(retries, error.retry_after()),
+            _ => None,
+        }
+    }
+}
+
+fn percent_encode(output: &mut String, text: &str) {
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                output.push(byte as char)
+            }
+            _ => output.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+}
+
+#[cfg(any(feature = "login", feature = "webapp"))]
+fn decode_hex(hex: &str) -> Option<[u8; 32]> {
+    let hex = hex.as_bytes();
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut bytes = [0; 32];
+    for (byte, pair) in bytes.iter_mut().zip(hex.chunks(2)) {
+        let pair = std::str::from_utf8(pair).ok()?;
+        *byte = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(any(feature = "login", feature = "webapp"))]
+fn percent_decode(text: &str) -> String {
+    let text = text.as_bytes();
+    let mut decoded = Vec::with_capacity(text.len());
+    let mut i = 0;
+    while i < text.len() {
+        match text[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < text.len() => {
+                let hex = std::str::from_utf8(&text[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
 /// Telegram API response.
 ///
 /// Response body should be deserialized into [`ApiResponse<T>`] to handle error correctly.
@@ -74,6 +373,184 @@ pub enum ApiResponse<T: DeserializeOwned> {
 /// Error from Telegram API server.
 #[derive(Debug, Deserialize)]
 pub struct TelegramError {
+    /// Error code, or 0 if the error did not come from the server.
+    #[serde(default)]
+    pub error_code: i64,
     /// Cause of the error.
     pub description: String,
+    /// Information which can help to automatically handle the error.
+    #[serde(default)]
+    pub parameters: Option<ResponseParameters>,
+}
+
+/// Information about why a request was unsuccessful.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#responseparameters)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ResponseParameters {
+    /// The group has been migrated to a supergroup with the specified identifier.
+    pub migrate_to_chat_id: Option<i64>,
+    /// In case of exceeding flood control, the number of seconds left to wait before the request can be repeated.
+    pub retry_after: Option<u64>,
+}
+
+impl TelegramError {
+    /// Gets the time to wait before repeating the request after exceeding flood control, if any.
+    ///
+    /// Falls back to the description like `Too Many Requests: retry after 5` if the parameters are missing.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use telbot_types::TelegramError;
+    /// let error: TelegramError = serde_json::from_str(r#"{
+    ///     "ok": false, "error_code": 429, "description": "Too Many Requests: retry after 5",
+    ///     "parameters": {"retry_after": 5}
+    /// }"#).unwrap();
+    /// assert_eq!(error.retry_after(), Some(Duration::from_secs(5)));
+    /// ```
+    pub fn retry_after(&self) -> Option<Duration> {
+        let parameters = self.parameters.as_ref().and_then(|p| p.retry_after);
+        let seconds = match parameters {
+            Some(seconds) => seconds,
+            None => self
+                .description
+                .strip_prefix("Too Many Requests: retry after ")?
+                .parse()
+                .ok()?,
+        };
+        Some(Duration::from_secs(seconds))
+    }
+
+    /// Gets the identifier of the supergroup the group has been migrated to, if the request failed because of it.
+    pub fn migrate_to_chat_id(&self) -> Option<i64> {
+        self.parameters.as_ref()?.migrate_to_chat_id
+    }
+
+    /// Gets the kind of the error from the error code, the parameters and the description,
+    /// so that errors can be matched without comparing descriptions.
+    ///
+    /// ```
+    /// # use telbot_types::{ApiErrorKind, TelegramError};
+    /// let error: TelegramError = serde_json::from_str(r#"{
+    ///     "ok": false, "error_code": 400, "description": "Bad Request: chat not found"
+    /// }"#).unwrap();
+    /// assert_eq!(error.kind(), ApiErrorKind::ChatNotFound);
+    ///
+    /// let error: TelegramError = serde_json::from_str(r#"{
+    ///     "ok": false, "error_code": 400, "description": "Bad Request: group chat was upgraded to a supergroup chat",
+    ///     "parameters": {"migrate_to_chat_id": -1001234}
+    /// }"#).unwrap();
+    /// assert_eq!(error.kind(), ApiErrorKind::GroupMigrated);
+    /// assert_eq!(error.migrate_to_chat_id(), Some(-1001234));
+    /// ```
+    pub fn kind(&self) -> ApiErrorKind {
+        if self.migrate_to_chat_id().is_some() {
+            return ApiErrorKind::GroupMigrated;
+        }
+        let description = self.description.as_str();
+        let reason = description
+            .split_once(": ")
+            .map_or(description, |(_, reason)| reason);
+        match self.error_code {
+            401 => ApiErrorKind::Unauthorized,
+            403 => match reason {
+                "bot was blocked by the user" => ApiErrorKind::BotBlockedByUser,
+                "user is deactivated" => ApiErrorKind::UserDeactivated,
+                "bot can't initiate conversation with a user" => {
+                    ApiErrorKind::CannotInitiateConversation
+                }
+                _ if reason.starts_with("bot was kicked from") => ApiErrorKind::BotKicked,
+                _ if reason.starts_with("bot is not a member of") => ApiErrorKind::BotNotMember,
+                _ => ApiErrorKind::Other,
+            },
+            400 => match reason {
+                "chat not found" => ApiErrorKind::ChatNotFound,
+                "user not found" => ApiErrorKind::UserNotFound,
+                "message to edit not found" => ApiErrorKind::MessageToEditNotFound,
+                "message to delete not found" => ApiErrorKind::MessageToDeleteNotFound,
+                "message to unpin not found" => ApiErrorKind::MessageToUnpinNotFound,
+                "message can't be edited" => ApiErrorKind::MessageCannotBeEdited,
+                "message can't be deleted" => ApiErrorKind::MessageCannotBeDeleted,
+                "message text is empty" => ApiErrorKind::MessageTextEmpty,
+                "message is too long" => ApiErrorKind::MessageTooLong,
+                _ if reason.starts_with("message is not modified") => {
+                    ApiErrorKind::MessageNotModified
+                }
+                _ if reason.starts_with("query is too old") => ApiErrorKind::QueryTooOld,
+                _ if reason.starts_with("not enough rights") => ApiErrorKind::NotEnoughRights,
+                _ if reason.starts_with("wrong file identifier")
+                    || reason.starts_with("wrong remote file identifier") =>
+                {
+                    ApiErrorKind::WrongFileId
+                }
+                _ if reason.starts_with("group chat was upgraded") => ApiErrorKind::GroupMigrated,
+                _ => ApiErrorKind::Other,
+            },
+            409 => ApiErrorKind::Conflict,
+            429 => ApiErrorKind::TooManyRequests,
+            _ => ApiErrorKind::Other,
+        }
+    }
+}
+
+/// Kind of [`TelegramError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// The user has blocked the bot.
+    BotBlockedByUser,
+    /// The user has deleted their account.
+    UserDeactivated,
+    /// The bot was removed from the group or the channel.
+    BotKicked,
+    /// The bot is not a member of the group or the channel.
+    BotNotMember,
+    /// The user has never started a conversation with the bot.
+    CannotInitiateConversation,
+    /// The chat does not exist, or the bot has never seen it.
+    ChatNotFound,
+    /// The user does not exist, or the bot has never seen them.
+    UserNotFound,
+    /// The group has been migrated to a supergroup, whose identifier is in [`TelegramError::migrate_to_chat_id`].
+    GroupMigrated,
+    /// The bot lacks the rights for the request in the chat.
+    NotEnoughRights,
+    /// The new content of the message is the same as the current one.
+    MessageNotModified,
+    /// The message to edit does not exist, e.g. it was deleted.
+    MessageToEditNotFound,
+    /// The message to delete does not exist, e.g. it was already deleted.
+    MessageToDeleteNotFound,
+    /// The message to unpin is not pinned, e.g. it was already unpinned or deleted.
+    MessageToUnpinNotFound,
+    /// The message cannot be edited, e.g. it was sent by another user or is too old.
+    MessageCannotBeEdited,
+    /// The message cannot be deleted, e.g. it is too old or the bot lacks the rights.
+    MessageCannotBeDeleted,
+    /// The text of the message is empty.
+    MessageTextEmpty,
+    /// The text of the message is too long.
+    MessageTooLong,
+    /// The callback query or the inline query has expired, or its identifier is invalid.
+    QueryTooOld,
+    /// The file identifier is invalid, or belongs to another bot.
+    WrongFileId,
+    /// The bot token is invalid or revoked.
+    Unauthorized,
+    /// Another instance is receiving updates with `getUpdates`, or a webhook is set.
+    Conflict,
+    /// Too many requests are sent; the request should be retried later.
+    TooManyRequests,
+    /// Any other error.
+    Other,
+}
+
+impl ApiErrorKind {
+    /// Checks if the chat can no longer receive messages from the bot,
+    /// e.g. to remove the chat from a mailing list.
+    pub fn is_unreachable_chat(&self) -> bool {
+        matches!(
+            self,
+            Self::BotBlockedByUser | Self::UserDeactivated | Self::BotKicked | Self::BotNotMember
+        )
+    }
 }
@@ -0,0 +1,335 @@
+//! Parsing of Telegram links like `t.me/username/123` or `tg://user?id=123`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::chat::{ChatId, Username, UsernameError};
+
+/// A link to a chat, message or user.
+///
+/// ```
+/// # use telbot_types::link::TelegramLink;
+/// # use telbot_types::chat::Username;
+/// let link: TelegramLink = "https://t.me/c/1234567890/42".parse().unwrap();
+/// assert_eq!(
+///     link,
+///     TelegramLink::PrivateMessage {
+///         chat_id: -1001234567890,
+///         thread_id: None,
+///         message_id: 42,
+///     }
+/// );
+///
+/// let link = TelegramLink::parse("t.me/+AbCdEf123").unwrap();
+/// assert_eq!(link, TelegramLink::Invite("AbCdEf123".to_string()));
+///
+/// let link = TelegramLink::parse("tg://user?id=777000").unwrap();
+/// assert_eq!(link, TelegramLink::User(777000));
+///
+/// let link = TelegramLink::parse("@TelBot").unwrap();
+/// assert_eq!(link, TelegramLink::Chat(Username::parse("telbot").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelegramLink {
+    /// A public chat or user, like `t.me/username`, `@username` or `tg://resolve?domain=username`.
+    Chat(Username),
+    /// A message in a public chat, like `t.me/username/123` or `t.me/username/5/123` in a forum topic.
+    Message {
+        /// Username of the chat.
+        chat: Username,
+        /// Identifier of the forum topic, if any.
+        thread_id: Option<i64>,
+        /// Identifier of the message.
+        message_id: i64,
+    },
+    /// A message in a private supergroup or channel, like `t.me/c/1234567890/123`.
+    PrivateMessage {
+        /// Identifier of the chat, with the `-100` prefix restored.
+        chat_id: i64,
+        /// Identifier of the forum topic, if any.
+        thread_id: Option<i64>,
+        /// Identifier of the message.
+        message_id: i64,
+    },
+    /// An invite link, like `t.me/+hash`, `t.me/joinchat/hash` or `tg://join?invite=hash`.
+    Invite(String),
+    /// A user by identifier, like `tg://user?id=123`.
+    User(i64),
+    /// A user by phone number in international format without `+`,
+    /// like `t.me/+15551234567` or `tg://resolve?phone=15551234567`.
+    Phone(String),
+}
+
+impl TelegramLink {
+    /// Parses the link, with or without the `https://` scheme.
+    pub fn parse(link: &str) -> Result<Self, LinkError> {
+        let link = link.trim();
+        if let Some(link) = link.strip_prefix("tg://") {
+            return Self::parse_tg(link);
+        }
+        if link.starts_with('@') {
+            return Ok(Self::Chat(Username::parse(link)?));
+        }
+        let link = link
+            .strip_prefix("https://")
+            .or_else(|| link.strip_prefix("http://"))
+            .unwrap_or(link);
+        let path = ["t.me/", "telegram.me/", "telegram.dog/"]
+            .iter()
+            .find_map(|host| link.strip_prefix(host))
+            .ok_or(LinkError::NotTelegramLink)?;
+        let path = path.split(['?', '#'].as_ref()).next().unwrap_or("");
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        match segments.as_slice() {
+            [hash] if hash.starts_with('+') => match &hash[1..] {
+                "" => Err(LinkError::Unsupported),
+                phone if phone.bytes().all(|b| b.is_ascii_digit()) => {
+                    Ok(Self::Phone(phone.to_string()))
+                }
+                hash => Ok(Self::Invite(hash.to_string())),
+            },
+            ["joinchat", hash] => Ok(Self::Invite(hash.to_string())),
+            ["c", chat_id, rest @ ..] => {
+                let (thread_id, message_id) = parse_message_path(rest)?;
+                Ok(Self::PrivateMessage {
+                    chat_id: format!("-100{}", parse_id(chat_id)?)
+                        .parse()
+                        .map_err(|_| LinkError::InvalidId)?,
+                    thread_id,
+                    message_id,
+                })
+            }
+            // Public previews of channels, like `t.me/s/username`.
+            ["s", username] | [username] => Ok(Self::Chat(Username::parse(username)?)),
+            [username, rest @ ..] => {
+                let (thread_id, message_id) = parse_message_path(rest)?;
+                Ok(Self::Message {
+                    chat: Username::parse(username)?,
+                    thread_id,
+                    message_id,
+                })
+            }
+            [] => Err(LinkError::Unsupported),
+        }
+    }
+
+    fn parse_tg(link: &str) -> Result<Self, LinkError> {
+        let (action, query) = link.split_once('?').unwrap_or((link, ""));
+        let param = |name: &str| {
+            query
+                .split('&')
+                .find_map(|pair| match pair.split_once('=') {
+                    Some((key, value)) if key == name => Some(value),
+                    _ => None,
+                })
+        };
+        match action.trim_end_matches('/') {
+            "user" => Ok(Self::User(parse_id(
+                param("id").ok_or(LinkError::InvalidId)?,
+            )?)),
+            "join" => match param("invite") {
+                Some(hash) if !hash.is_empty() => Ok(Self::Invite(hash.to_string())),
+                _ => Err(LinkError::Unsupported),
+            },
+            "resolve" => {
+                if let Some(phone) = param("phone") {
+                    return match phone {
+                        "" => Err(LinkError::Unsupported),
+                        phone if phone.bytes().all(|b| b.is_ascii_digit()) => {
+                            Ok(Self::Phone(phone.to_string()))
+                        }
+                        _ => Err(LinkError::Unsupported),
+                    };
+                }
+                let chat = Username::parse(param("domain").ok_or(LinkError::Unsupported)?)?;
+                match param("post") {
+                    Some(post) => Ok(Self::Message {
+                        chat,
+                        thread_id: param("thread").map(parse_id).transpose()?,
+                        message_id: parse_id(post)?,
+                    }),
+                    None => Ok(Self::Chat(chat)),
+                }
+            }
+            "privatepost" => Ok(Self::PrivateMessage {
+                chat_id: format!(
+                    "-100{}",
+                    parse_id(param("channel").ok_or(LinkError::InvalidId)?)?
+                )
+                .parse()
+                .map_err(|_| LinkError::InvalidId)?,
+                thread_id: param("thread").map(parse_id).transpose()?,
+                message_id: parse_id(param("post").ok_or(LinkError::InvalidId)?)?,
+            }),
+            _ => Err(LinkError::Unsupported),
+        }
+    }
+
+    /// Gets the chat of the link to be used in requests, or `None` for invite and phone links.
+    ///
+    /// Users linked by identifier can only be used if the bot has seen them before.
+    pub fn chat_id(&self) -> Option<ChatId> {
+        match self {
+            Self::Chat(chat) | Self::Message { chat, .. } => Some(chat.clone().into()),
+            Self::PrivateMessage { chat_id, .. } => Some(ChatId::Id(*chat_id)),
+            Self::User(user_id) => Some(ChatId::Id(*user_id)),
+            Self::Invite(_) | Self::Phone(_) => None,
+        }
+    }
+
+    /// Gets the identifier of the linked message, if any.
+    pub fn message_id(&self) -> Option<i64> {
+        match self {
+            Self::Message { message_id, .. } | Self::PrivateMessage { message_id, .. } => {
+                Some(*message_id)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for TelegramLink {
+    type Err = LinkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Formats the link as a `https://t.me` link, or a `tg://user` link for users.
+impl fmt::Display for TelegramLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let thread = |thread_id: &Option<i64>| match thread_id {
+            Some(thread_id) => format!("/{}", thread_id),
+            None => String::new(),
+        };
+        match self {
+            Self::Chat(chat) => write!(f, "https://t.me/{}", chat.as_str()),
+            Self::Message {
+                chat,
+                thread_id,
+                message_id,
+            } => write!(
+                f,
+                "https://t.me/{}{}/{}",
+                chat.as_str(),
+                thread(thread_id),
+                message_id
+            ),
+            Self::PrivateMessage {
+                chat_id,
+                thread_id,
+                message_id,
+            } => {
+                let chat_id = chat_id.to_string();
+                let chat_id = chat_id.strip_prefix("-100").unwrap_or(&chat_id);
+                write!(
+                    f,
+                    "https://t.me/c/{}{}/{}",
+                    chat_id,
+                    thread(thread_id),
+                    message_id
+                )
+            }
+            Self::Invite(hash) => write!(f, "https://t.me/+{}", hash),
+            Self::Phone(phone) => write!(f, "https://t.me/+{}", phone),
+            Self::User(user_id) => write!(f, "tg://user?id={}", user_id),
+        }
+    }
+}
+
+/// Parses `123` or `5/123` after the chat in message links.
+fn parse_message_path(path: &[&str]) -> Result<(Option<i64>, i64), LinkError> {
+    match path {
+        [message_id] => Ok((None, parse_id(message_id)?)),
+        [thread_id, message_id] => Ok((Some(parse_id(thread_id)?), parse_id(message_id)?)),
+        _ => Err(LinkError::Unsupported),
+    }
+}
+
+fn parse_id(id: &str) -> Result<i64, LinkError> {
+    match id.parse() {
+        Ok(id) if id > 0 => Ok(id),
+        _ => Err(LinkError::InvalidId),
+    }
+}
+
+/// Error from parsing a [`TelegramLink`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkError {
+    /// The link is not on `t.me`, `telegram.me`, `telegram.dog` or of the `tg://` scheme.
+    NotTelegramLink,
+    /// The link is a Telegram link of another kind, like a sticker set or a proxy.
+    Unsupported,
+    /// The username in the link is invalid.
+    InvalidUsername(UsernameError),
+    /// An identifier of a chat, user or message in the link is not a positive number.
+    InvalidId,
+}
+
+impl From<UsernameError> for LinkError {
+    fn from(error: UsernameError) -> Self {
+        Self::InvalidUsername(error)
+    }
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotTelegramLink => write!(f, "not a telegram link"),
+            Self::Unsupported => write!(f, "unsupported telegram link"),
+            Self::InvalidUsername(error) => write!(f, "invalid username in link: {}", error),
+            Self::InvalidId => write!(f, "invalid identifier in link"),
+        }
+    }
+}
+
+impl std::error::Error for LinkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidUsername(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_invite_is_rejected() {
+        assert_eq!(TelegramLink::parse("t.me/+"), Err(LinkError::Unsupported));
+        assert_eq!(
+            TelegramLink::parse("https://t.me/+?start=1"),
+            Err(LinkError::Unsupported)
+        );
+        assert_eq!(
+            TelegramLink::parse("tg://join?invite="),
+            Err(LinkError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn plus_with_digits_is_phone() {
+        let link = TelegramLink::parse("https://t.me/+15551234567").unwrap();
+        assert_eq!(link, TelegramLink::Phone("15551234567".to_string()));
+        assert!(link.chat_id().is_none());
+        assert_eq!(link.to_string(), "https://t.me/+15551234567");
+        assert_eq!(
+            TelegramLink::parse("tg://resolve?phone=15551234567").unwrap(),
+            link
+        );
+        assert_eq!(
+            TelegramLink::parse("tg://resolve?phone=+1"),
+            Err(LinkError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn plus_with_hash_is_invite() {
+        let link = TelegramLink::parse("t.me/+AbC-12_3").unwrap();
+        assert_eq!(link, TelegramLink::Invite("AbC-12_3".to_string()));
+        assert_eq!(link.to_string(), "https://t.me/+AbC-12_3");
+    }
+}
@@ -0,0 +1,153 @@
+//! Verification of data sent by the [Telegram Login Widget](https://core.telegram.org/widgets/login).
+//!
+//! The widget redirects the user to the website, or calls a JavaScript callback, with the fields of [`LoginData`]
+//! and a `hash` signed with the bot token.
+//! The data must be verified on the server before trusting it, since anyone can forge a request.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac_sha256::{Hash, HMAC};
+
+use crate::{decode_hex, percent_decode};
+
+/// Information about a user authorized with the Telegram Login Widget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginData {
+    /// Unique identifier for the user.
+    pub id: i64,
+    /// User's first name.
+    pub first_name: String,
+    /// User's last name.
+    pub last_name: Option<String>,
+    /// User's username.
+    pub username: Option<String>,
+    /// URL of the user's profile photo.
+    pub photo_url: Option<String>,
+    /// Unix time when the user authorized.
+    pub auth_date: u64,
+}
+
+/// Error that can occur while verifying login data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginError {
+    /// A required field is missing.
+    MissingField(&'static str),
+    /// A field has a malformed value.
+    InvalidField(&'static str),
+    /// The hash does not match the data, i.e. the data is not sent by Telegram.
+    HashMismatch,
+    /// The data is older than the allowed age.
+    Expired,
+}
+
+impl std::fmt::Display for LoginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing field `{}`", field),
+            Self::InvalidField(field) => write!(f, "invalid field `{}`", field),
+            Self::HashMismatch => write!(f, "hash mismatch"),
+            Self::Expired => write!(f, "login data expired"),
+        }
+    }
+}
+
+impl std::error::Error for LoginError {}
+
+impl LoginData {
+    /// Verifies the fields sent by the widget, including `hash`, with the bot token.
+    ///
+    /// Data authorized more than `max_age` ago is rejected to prevent replaying leaked data.
+    ///
+    /// ```
+    /// # use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    /// # use telbot_types::login::{LoginData, LoginError};
+    /// # let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs().to_string();
+    /// # let token = "1234:TOKEN";
+    /// # let data_check_string = format!("auth_date={}\nfirst_name=Kiwi\nid=1234", now);
+    /// # let secret = hmac_sha256::Hash::hash(token.as_bytes());
+    /// # let hash: String = hmac_sha256::HMAC::mac(data_check_string, secret).iter().map(|b| format!("{:02x}", b)).collect();
+    /// let fields = vec![("id", "1234"), ("first_name", "Kiwi"), ("auth_date", &now), ("hash", &hash)];
+    /// let user = LoginData::verify(fields, token, Duration::from_secs(24 * 60 * 60))?;
+    /// assert_eq!(user.id, 1234);
+    /// # Ok::<(), LoginError>(())
+    /// ```
+    pub fn verify<K: AsRef<str>, V: AsRef<str>>(
+        fields: impl IntoIterator<Item = (K, V)>,
+        token: &str,
+        max_age: Duration,
+    ) -> Result<Self, LoginError> {
+        Self::verify_at(fields, token, max_age, SystemTime::now())
+    }
+
+    /// Verifies the fields sent by the widget, regarding the current time as `now`.
+    pub fn verify_at<K: AsRef<str>, V: AsRef<str>>(
+        fields: impl IntoIterator<Item = (K, V)>,
+        token: &str,
+        max_age: Duration,
+        now: SystemTime,
+    ) -> Result<Self, LoginError> {
+        let mut fields: BTreeMap<String, String> = fields
+            .into_iter()
+            .map(|(key, value)| (key.as_ref().to_string(), value.as_ref().to_string()))
+            .collect();
+        let hash = fields
+            .remove("hash")
+            .ok_or(LoginError::MissingField("hash"))?;
+        let hash = decode_hex(&hash).ok_or(LoginError::InvalidField("hash"))?;
+
+        // Fields are sorted by key, since they are in a BTreeMap.
+        let data_check_string = fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let secret = Hash::hash(token.as_bytes());
+        if !HMAC::verify(data_check_string, secret, &hash) {
+            return Err(LoginError::HashMismatch);
+        }
+
+        let auth_date: u64 = fields
+            .get("auth_date")
+            .ok_or(LoginError::MissingField("auth_date"))?
+            .parse()
+            .map_err(|_| LoginError::InvalidField("auth_date"))?;
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        if now.saturating_sub(auth_date) > max_age.as_secs() {
+            return Err(LoginError::Expired);
+        }
+
+        Ok(Self {
+            id: fields
+                .get("id")
+                .ok_or(LoginError::MissingField("id"))?
+                .parse()
+                .map_err(|_| LoginError::InvalidField("id"))?,
+            first_name: fields
+                .remove("first_name")
+                .ok_or(LoginError::MissingField("first_name"))?,
+            last_name: fields.remove("last_name"),
+            username: fields.remove("username"),
+            photo_url: fields.remove("photo_url"),
+            auth_date,
+        })
+    }
+
+    /// Verifies the query string of the redirect URL, like `id=1234&first_name=Kiwi&auth_date=...&hash=...`.
+    pub fn verify_query(query: &str, token: &str, max_age: Duration) -> Result<Self, LoginError> {
+        let fields = query
+            .trim_start_matches('?')
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut pair = pair.splitn(2, '=');
+                let key = percent_decode(pair.next().unwrap_or(""));
+                let value = percent_decode(pair.next().unwrap_or(""));
+                (key, value)
+            });
+        Self::verify(fields, token, max_age)
+    }
+}
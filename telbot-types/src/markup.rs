@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::user::User;
+use crate::webapp::WebAppInfo;
 
 /// A [custom keyboard](https://core.telegram.org/bots#keyboards) with reply options
 /// (see [Introduction to bots](https://core.telegram.org/bots#keyboards) for details and examples).
@@ -10,11 +11,15 @@ use crate::user::User;
 pub struct ReplyKeyboardMarkup {
     /// Array of button rows, each represented by an Array of [`KeyboardButton`] objects
     pub keyboard: Vec<Vec<KeyboardButton>>,
+    /// Requests clients to always show the keyboard when the regular keyboard is hidden.
+    /// Defaults to false, in which case the custom keyboard can be hidden and opened with a keyboard icon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_persistent: Option<bool>,
     /// Requests clients to resize the keyboard vertically for optimal fit
-    // (e.g., make the keyboard smaller if there are just two rows of buttons).
+    /// (e.g., make the keyboard smaller if there are just two rows of buttons).
     /// Defaults to false, in which case the custom keyboard is always of the same height as the app's standard keyboard.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub reisze_keyboard: Option<bool>,
+    pub resize_keyboard: Option<bool>,
     /// Requests clients to hide the keyboard as soon as it's been used.
     /// The keyboard will still be available, but clients will automatically display the usual letter-keyboard in the chat
     /// – the user can press a special button in the input field to see the custom keyboard again.
@@ -36,6 +41,66 @@ pub struct ReplyKeyboardMarkup {
     pub selective: Option<bool>,
 }
 
+impl ReplyKeyboardMarkup {
+    /// Creates a new ReplyKeyboardMarkup with a row.
+    pub fn new_with_row(row: Vec<KeyboardButton>) -> Self {
+        Self {
+            keyboard: vec![row],
+            is_persistent: None,
+            resize_keyboard: None,
+            one_time_keyboard: None,
+            input_field_placeholder: None,
+            selective: None,
+        }
+    }
+
+    /// Adds a row.
+    pub fn with_row(mut self, row: Vec<KeyboardButton>) -> Self {
+        self.keyboard.push(row);
+        self
+    }
+
+    /// Always shows the keyboard when the regular keyboard is hidden.
+    pub fn persistent(self) -> Self {
+        Self {
+            is_persistent: Some(true),
+            ..self
+        }
+    }
+
+    /// Resizes the keyboard vertically for optimal fit.
+    pub fn resize(self) -> Self {
+        Self {
+            resize_keyboard: Some(true),
+            ..self
+        }
+    }
+
+    /// Hides the keyboard as soon as it's been used.
+    pub fn one_time(self) -> Self {
+        Self {
+            one_time_keyboard: Some(true),
+            ..self
+        }
+    }
+
+    /// Sets the placeholder shown in the input field.
+    pub fn with_input_field_placeholder(self, placeholder: impl Into<String>) -> Self {
+        Self {
+            input_field_placeholder: Some(placeholder.into()),
+            ..self
+        }
+    }
+
+    /// Shows the keyboard to specific users only.
+    pub fn selective(self) -> Self {
+        Self {
+            selective: Some(true),
+            ..self
+        }
+    }
+}
+
 /// This object represents one button of the reply keyboard.
 /// For simple text buttons *String* can be used instead of this object to specify text of the button.
 /// Optional fields *request_contact*, *request_location*, and *request_poll* are mutually exclusive.
@@ -64,6 +129,48 @@ pub struct KeyboardButton {
     /// Available in private chats only
     #[serde(skip_serializing_if = "Option::is_none")]
     request_poll: Option<KeyboardButtonPollType>,
+    /// If specified, the described [Web App](https://core.telegram.org/bots/webapps) will be launched when the button is pressed.
+    /// The Web App will be able to send a `web_app_data` service message.
+    /// Available in private chats only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    web_app: Option<WebAppInfo>,
+}
+
+impl KeyboardButton {
+    /// Creates a new text button.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            request_contact: None,
+            request_location: None,
+            request_poll: None,
+            web_app: None,
+        }
+    }
+
+    /// Sends the user's phone number as a contact when the button is pressed.
+    pub fn request_contact(self) -> Self {
+        Self {
+            request_contact: Some(true),
+            ..self
+        }
+    }
+
+    /// Sends the user's current location when the button is pressed.
+    pub fn request_location(self) -> Self {
+        Self {
+            request_location: Some(true),
+            ..self
+        }
+    }
+
+    /// Launches the Web App when the button is pressed.
+    pub fn with_web_app(self, web_app: WebAppInfo) -> Self {
+        Self {
+            web_app: Some(web_app),
+            ..self
+        }
+    }
 }
 
 /// Type of a poll, which is allowed to be created and sent when the corresponding button is pressed.
@@ -127,6 +234,171 @@ impl InlineKeyboardMarkup {
         self.inline_keyboard.push(row.buttons);
         self
     }
+
+    /// Adds a row, checking that the keyboard is still valid.
+    pub fn with_row_checked(self, row: InlineKeyboardRow) -> Result<Self, MarkupError> {
+        let markup = self.with_row(row);
+        markup.validate()?;
+        Ok(markup)
+    }
+
+    /// Gets the number of buttons in the keyboard.
+    pub fn button_count(&self) -> usize {
+        self.inline_keyboard.iter().map(Vec::len).sum()
+    }
+
+    /// Gets the length of the keyboard serialized in JSON, in bytes.
+    ///
+    /// ```
+    /// # use telbot_types::markup::{InlineKeyboardButtonKind, InlineKeyboardMarkup, InlineKeyboardRow};
+    /// let markup = InlineKeyboardMarkup::new_with_row(InlineKeyboardRow::new_emplace(
+    ///     "Yes \"really\"",
+    ///     InlineKeyboardButtonKind::Callback {
+    ///         callback_data: "yes\n".to_string(),
+    ///     },
+    /// ));
+    /// assert_eq!(markup.serialized_len(), serde_json::to_string(&markup).unwrap().len());
+    /// ```
+    pub fn serialized_len(&self) -> usize {
+        let rows = self.inline_keyboard.iter().map(|row| {
+            let buttons = row.iter().map(InlineKeyboardButton::serialized_len);
+            json_array_len(buttons)
+        });
+        json_object_len(&[("inline_keyboard", json_array_len(rows))])
+    }
+
+    /// Checks the keyboard against the limits of Telegram.
+    ///
+    /// Telegram rejects invalid keyboards with an error which doesn't tell what is wrong.
+    pub fn validate(&self) -> Result<(), MarkupError> {
+        for (row_index, row) in self.inline_keyboard.iter().enumerate() {
+            if row.is_empty() {
+                return Err(MarkupError::EmptyRow(row_index));
+            }
+            if row.len() > MAX_INLINE_KEYBOARD_ROW_BUTTONS {
+                return Err(MarkupError::TooManyButtonsInRow(row_index, row.len()));
+            }
+            for (column, button) in row.iter().enumerate() {
+                if button.text.is_empty() {
+                    return Err(MarkupError::EmptyText(row_index, column));
+                }
+                if let InlineKeyboardButtonKind::Callback { callback_data } = &button.kind {
+                    if callback_data.is_empty() || callback_data.len() > MAX_CALLBACK_DATA_LENGTH {
+                        return Err(MarkupError::InvalidCallbackData(row_index, column));
+                    }
+                }
+            }
+        }
+        let button_count = self.button_count();
+        if button_count > MAX_INLINE_KEYBOARD_BUTTONS {
+            return Err(MarkupError::TooManyButtons(button_count));
+        }
+        let size = self.serialized_len();
+        if size > MAX_REPLY_MARKUP_SIZE {
+            return Err(MarkupError::TooLarge(size));
+        }
+        Ok(())
+    }
+}
+
+/// Maximum number of buttons in a row of an inline keyboard.
+pub const MAX_INLINE_KEYBOARD_ROW_BUTTONS: usize = 8;
+
+/// Maximum number of buttons in an inline keyboard.
+pub const MAX_INLINE_KEYBOARD_BUTTONS: usize = 100;
+
+/// Maximum length of callback data in bytes.
+pub const MAX_CALLBACK_DATA_LENGTH: usize = 64;
+
+/// Maximum length of a reply markup serialized in JSON, in bytes.
+///
+/// This is not documented, but larger markups are rejected in practice.
+pub const MAX_REPLY_MARKUP_SIZE: usize = 10 * 1024;
+
+/// Error found while validating a reply markup.
+///
+/// Rows and columns are zero-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupError {
+    /// The row has no button.
+    EmptyRow(usize),
+    /// The row has more than [`MAX_INLINE_KEYBOARD_ROW_BUTTONS`] buttons.
+    TooManyButtonsInRow(usize, usize),
+    /// The keyboard has more than [`MAX_INLINE_KEYBOARD_BUTTONS`] buttons.
+    TooManyButtons(usize),
+    /// The button at the row and the column has no text.
+    EmptyText(usize, usize),
+    /// The callback data of the button at the row and the column is not 1-64 bytes long.
+    InvalidCallbackData(usize, usize),
+    /// The markup is larger than [`MAX_REPLY_MARKUP_SIZE`] bytes.
+    TooLarge(usize),
+}
+
+impl std::fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyRow(row) => write!(f, "row {} is empty", row),
+            Self::TooManyButtonsInRow(row, count) => write!(
+                f,
+                "row {} has {} buttons, more than {}",
+                row, count, MAX_INLINE_KEYBOARD_ROW_BUTTONS
+            ),
+            Self::TooManyButtons(count) => write!(
+                f,
+                "keyboard has {} buttons, more than {}",
+                count, MAX_INLINE_KEYBOARD_BUTTONS
+            ),
+            Self::EmptyText(row, column) => {
+                write!(f, "button at row {} column {} has no text", row, column)
+            }
+            Self::InvalidCallbackData(row, column) => write!(
+                f,
+                "callback data of button at row {} column {} must be 1-{} bytes",
+                row, column, MAX_CALLBACK_DATA_LENGTH
+            ),
+            Self::TooLarge(size) => write!(
+                f,
+                "markup is {} bytes, larger than {}",
+                size, MAX_REPLY_MARKUP_SIZE
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+/// Gets the length of the string serialized in JSON, including quotes.
+fn json_string_len(text: &str) -> usize {
+    let escaped: usize = text
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' | '\n' | '\r' | '\t' | '\u{8}' | '\u{c}' => 2,
+            c if (c as u32) < 0x20 => 6,
+            c => c.len_utf8(),
+        })
+        .sum();
+    escaped + 2
+}
+
+fn json_array_len(values: impl Iterator<Item = usize>) -> usize {
+    let (count, len) = values.fold((0, 0), |(count, len), value| (count + 1, len + value));
+    2 + len + count.max(1) - 1
+}
+
+fn json_object_len(fields: &[(&str, usize)]) -> usize {
+    let len: usize = fields
+        .iter()
+        .map(|(key, value)| json_string_len(key) + 1 + value)
+        .sum();
+    2 + len + fields.len().max(1) - 1
+}
+
+fn json_bool_len(value: bool) -> usize {
+    if value {
+        4
+    } else {
+        5
+    }
 }
 
 /// A row of inline keyboard buttons.
@@ -199,6 +471,11 @@ pub enum InlineKeyboardButtonKind {
         /// Data to be sent in a [callback query](https://core.telegram.org/bots/api#callbackquery) to the bot when button is pressed, 1-64 bytes
         callback_data: String,
     },
+    WebApp {
+        /// Description of the [Web App](https://core.telegram.org/bots/webapps) that will be launched when the user presses the button.
+        /// Available only in private chats between a user and the bot.
+        web_app: WebAppInfo,
+    },
     SwitchInlineQuery {
         /// If set, pressing the button will prompt the user to select one of their chats, open that chat and insert the bot's username and the specified inline query in the input field. Can be empty, in which case just the bot's username will be inserted.
         ///
@@ -230,6 +507,48 @@ pub enum InlineKeyboardButtonKind {
     },
 }
 
+impl InlineKeyboardButton {
+    fn serialized_len(&self) -> usize {
+        let text = ("text", json_string_len(&self.text));
+        let kind = match &self.kind {
+            InlineKeyboardButtonKind::Url { url } => ("url", json_string_len(url)),
+            InlineKeyboardButtonKind::Login { login_url } => {
+                let mut fields = vec![("url", json_string_len(&login_url.url))];
+                if let Some(forward_text) = &login_url.forward_text {
+                    fields.push(("forward_text", json_string_len(forward_text)));
+                }
+                if let Some(bot_username) = &login_url.bot_username {
+                    fields.push(("bot_username", json_string_len(bot_username)));
+                }
+                if let Some(request_write_access) = login_url.request_write_access {
+                    fields.push(("request_write_access", json_bool_len(request_write_access)));
+                }
+                ("login_url", json_object_len(&fields))
+            }
+            InlineKeyboardButtonKind::Callback { callback_data } => {
+                ("callback_data", json_string_len(callback_data))
+            }
+            InlineKeyboardButtonKind::WebApp { web_app } => (
+                "web_app",
+                json_object_len(&[("url", json_string_len(&web_app.url))]),
+            ),
+            InlineKeyboardButtonKind::SwitchInlineQuery {
+                switch_inline_query,
+            } => ("switch_inline_query", json_string_len(switch_inline_query)),
+            InlineKeyboardButtonKind::SwitchInlineQueryCurrentChat {
+                switch_inline_query_current_chat,
+            } => (
+                "switch_inline_query_current_chat",
+                json_string_len(switch_inline_query_current_chat),
+            ),
+            // `CallbackGame` has no field, serialized as `{}`.
+            InlineKeyboardButtonKind::CallbackGame { .. } => ("callback_game", 2),
+            InlineKeyboardButtonKind::Pay { pay } => ("pay", json_bool_len(*pay)),
+        };
+        json_object_len(&[text, kind])
+    }
+}
+
 impl InlineKeyboardButtonKind {
     /// Gets the url associated with this button, if any.
     pub fn url(&self) -> Option<&str> {
@@ -255,6 +574,14 @@ impl InlineKeyboardButtonKind {
         }
     }
 
+    /// Gets the Web App launched when the user clicks this button, if any.
+    pub fn web_app(&self) -> Option<&WebAppInfo> {
+        match self {
+            Self::WebApp { web_app } => Some(web_app),
+            _ => None,
+        }
+    }
+
     /// Gets the inline query prompt entered to user when the user clicks this button, if any.
     pub fn inline_query_prompt(&self) -> Option<&str> {
         match self {
@@ -290,6 +617,11 @@ impl InlineKeyboardButtonKind {
         matches!(self, Self::Callback { .. })
     }
 
+    /// `true` if this button launches a Web App.
+    pub fn is_web_app(&self) -> bool {
+        matches!(self, Self::WebApp { .. })
+    }
+
     /// `true` if the user is provided with inline query prompt when clicking this button.
     pub fn is_switch_inline_query(&self) -> bool {
         matches!(self, Self::SwitchInlineQuery { .. })
@@ -317,8 +649,8 @@ impl InlineKeyboardButtonKind {
 /// A placeholder, currently holds no information. Use [BotFather](https://t.me/botfather) to set up your game.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#callbackgame)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CallbackGame;
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CallbackGame {}
 
 /// A parameter of the inline keyboard button used to automatically authorize a user.
 ///
@@ -414,6 +746,92 @@ impl ParseMode {
         }
     }
 
+    /// Removes formatting of given parse mode, leaving plain text.
+    ///
+    /// Useful for the text which does not support formatting, such as chat titles.
+    pub fn strip_formatting(&self, text: impl AsRef<str>) -> String {
+        match self {
+            Self::MarkdownV2 | Self::Markdown => Self::strip_markdown(text.as_ref()),
+            Self::HTML => Self::strip_html(text.as_ref()),
+        }
+    }
+
+    fn strip_markdown(text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+        let mut line_start = true;
+        while let Some(char) = chars.next() {
+            let at_line_start = line_start;
+            line_start = char == '\n';
+            match char {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        output.push(escaped);
+                    }
+                }
+                '*' | '_' | '~' | '`' | '[' => {}
+                '|' if chars.peek() == Some(&'|') => {
+                    chars.next();
+                }
+                '>' if at_line_start => {}
+                ']' if chars.peek() == Some(&'(') => {
+                    while let Some(char) = chars.next() {
+                        match char {
+                            '\\' => {
+                                chars.next();
+                            }
+                            ')' => break,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => output.push(char),
+            }
+        }
+        output
+    }
+
+    fn strip_html(text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(index) = rest.find(&['<', '&'][..]) {
+            output.push_str(&rest[..index]);
+            rest = &rest[index..];
+            if rest.starts_with('<') {
+                rest = match rest.find('>') {
+                    Some(end) => &rest[end + 1..],
+                    None => "",
+                };
+                continue;
+            }
+            let entity_end = rest.find(';').filter(|&end| end <= 10);
+            let decoded = entity_end.and_then(|end| match &rest[1..end] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                entity => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(std::char::from_u32),
+            });
+            match (decoded, entity_end) {
+                (Some(decoded), Some(end)) => {
+                    output.push(decoded);
+                    rest = &rest[end + 1..];
+                }
+                _ => {
+                    output.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        output.push_str(rest);
+        output
+    }
+
     fn escape_markdown_v2(text: &str) -> String {
         const ESCAPE_CHARS: [char; 18] = [
             '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.',
@@ -474,6 +892,745 @@ impl ParseMode {
         }
         output
     }
+
+    /// Validates MarkdownV2 syntax of the text locally.
+    ///
+    /// Checks that reserved characters are escaped, and entities are balanced and properly nested.
+    /// Custom emoji (`![👍](tg://emoji?id=...)`) and expandable block quotations
+    /// (`**>` ... `||`) are accepted.
+    pub fn validate_markdown_v2(text: &str) -> Result<(), MarkdownV2Error> {
+        const RESERVED_CHARS: [char; 18] = [
+            '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.',
+            '!',
+        ];
+        let error = |offset, kind| Err(MarkdownV2Error { offset, kind });
+        let mut open: Vec<(&'static str, usize)> = vec![];
+        let mut chars = text.char_indices().peekable();
+        let mut line_start = true;
+        // Whether the current line belongs to an expandable block quotation.
+        let mut expandable = false;
+        while let Some((offset, char)) = chars.next() {
+            let at_line_start = line_start;
+            line_start = char == '\n';
+            if at_line_start && char != '>' && !text[offset..].starts_with("**>") {
+                expandable = false;
+            }
+            let marker = match char {
+                '\\' => {
+                    if chars.next().is_none() {
+                        return error(offset, MarkdownV2ErrorKind::TrailingBackslash);
+                    }
+                    continue;
+                }
+                '`' => {
+                    let fence = if text[offset..].starts_with("```") {
+                        chars.next();
+                        chars.next();
+                        "```"
+                    } else {
+                        "`"
+                    };
+                    let kind = if fence == "`" { "code" } else { "pre" };
+                    loop {
+                        match chars.next() {
+                            Some((_, '\\')) => {
+                                chars.next();
+                            }
+                            Some((end, '`')) if text[end..].starts_with(fence) => {
+                                for _ in 1..fence.len() {
+                                    chars.next();
+                                }
+                                break;
+                            }
+                            Some(_) => {}
+                            None => {
+                                return error(offset, MarkdownV2ErrorKind::UnclosedEntity(kind))
+                            }
+                        }
+                    }
+                    continue;
+                }
+                '*' if at_line_start && text[offset..].starts_with("**>") => {
+                    chars.next();
+                    chars.next();
+                    expandable = true;
+                    continue;
+                }
+                '*' => "bold",
+                '_' if matches!(chars.peek(), Some((_, '_'))) => {
+                    chars.next();
+                    "underline"
+                }
+                '_' => "italic",
+                '~' => "strikethrough",
+                '|' if matches!(chars.peek(), Some((_, '|'))) => {
+                    chars.next();
+                    let line_end = matches!(chars.peek(), None | Some((_, '\n')));
+                    let closes_spoiler = matches!(open.last(), Some(("spoiler", _)));
+                    if expandable && line_end && !closes_spoiler {
+                        expandable = false;
+                        continue;
+                    }
+                    "spoiler"
+                }
+                '!' if matches!(chars.peek(), Some((_, '['))) => {
+                    chars.next();
+                    open.push(("custom_emoji", offset));
+                    continue;
+                }
+                '[' => {
+                    open.push(("text_link", offset));
+                    continue;
+                }
+                ']' => {
+                    match open.pop() {
+                        Some(("text_link", _)) | Some(("custom_emoji", _)) => {}
+                        Some((kind, _)) => {
+                            return error(offset, MarkdownV2ErrorKind::UnclosedEntity(kind))
+                        }
+                        None => {
+                            return error(offset, MarkdownV2ErrorKind::UnescapedCharacter(char))
+                        }
+                    }
+                    if !matches!(chars.next(), Some((_, '('))) {
+                        return error(offset, MarkdownV2ErrorKind::MissingUrl);
+                    }
+                    loop {
+                        match chars.next() {
+                            Some((_, '\\')) => {
+                                chars.next();
+                            }
+                            Some((_, ')')) => break,
+                            Some(_) => {}
+                            None => return error(offset, MarkdownV2ErrorKind::MissingUrl),
+                        }
+                    }
+                    continue;
+                }
+                '>' if at_line_start => continue,
+                char if RESERVED_CHARS.contains(&char) => {
+                    return error(offset, MarkdownV2ErrorKind::UnescapedCharacter(char))
+                }
+                _ => continue,
+            };
+            match open.iter().rposition(|(kind, _)| *kind == marker) {
+                Some(index) if index + 1 == open.len() => {
+                    open.pop();
+                }
+                Some(_) => {
+                    let (kind, offset) = open[open.len() - 1];
+                    return error(offset, MarkdownV2ErrorKind::UnclosedEntity(kind));
+                }
+                None => open.push((marker, offset)),
+            }
+        }
+        match open.pop() {
+            Some((kind, offset)) => error(offset, MarkdownV2ErrorKind::UnclosedEntity(kind)),
+            None => Ok(()),
+        }
+    }
+
+    /// Sanitizes text to be sent with [`ParseMode::HTML`], e.g. when echoing user-generated content.
+    ///
+    /// Tags not supported by Telegram are removed with their contents kept,
+    /// unbalanced tags are closed or removed, and stray `<`, `>` and `&` are escaped.
+    ///
+    /// ```
+    /// # use telbot_types::markup::ParseMode;
+    /// let html = ParseMode::sanitize_html(r#"<div><b>Hi</b> & <a href="https://example.com" target="_blank">bye</div>"#);
+    /// assert_eq!(html, r#"<b>Hi</b> &amp; <a href="https://example.com">bye</a>"#);
+    /// ```
+    pub fn sanitize_html(text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        // Canonical names of open tags, with whether each of them is kept.
+        let mut open: Vec<(&'static str, bool)> = vec![];
+        let mut rest = text;
+        while let Some(index) = rest.find(&['<', '>', '&'][..]) {
+            output.push_str(&rest[..index]);
+            rest = &rest[index..];
+            if rest.starts_with('<') {
+                if let Some((tag, length)) = HtmlTag::parse(rest) {
+                    tag.push_sanitized(&mut output, &mut open);
+                    rest = &rest[length..];
+                    continue;
+                }
+            }
+            let length = html_escape_char(&mut output, rest);
+            rest = &rest[length..];
+        }
+        output.push_str(rest);
+        for (name, kept) in open.into_iter().rev() {
+            if kept {
+                output.push_str("</");
+                output.push_str(name);
+                output.push('>');
+            }
+        }
+        output
+    }
+}
+
+/// Counts the length of the text after entities parsing, in UTF-16 code units as Telegram does.
+///
+/// Limits like the 4096 characters of a text message apply to this length, not to the length of the markup.
+///
+/// ```
+/// # use telbot_types::markup::{text_len, ParseMode};
+/// assert_eq!(text_len("<b>Hi</b> &amp; bye", Some(ParseMode::HTML)), 8);
+/// assert_eq!(text_len("*Hi* \\& bye", Some(ParseMode::MarkdownV2)), 8);
+/// assert_eq!(text_len("*Hi* & bye", None), 10);
+/// ```
+pub fn text_len(text: &str, parse_mode: Option<ParseMode>) -> usize {
+    tokenize(text, parse_mode)
+        .iter()
+        .map(|token| match token {
+            Token::Char(_, char) => char.len_utf16(),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Splits the text into chunks whose lengths after entities parsing are at most `max_len`.
+///
+/// Chunks end at line breaks if possible, and then at whitespaces.
+/// Entities spanning several chunks are closed at the end of a chunk and reopened at the start of the next one,
+/// except text links in Markdown, which are never split unless they are longer than `max_len` themselves.
+///
+/// ```
+/// # use telbot_types::markup::{split_text, ParseMode};
+/// let chunks = split_text("<b>first line\nsecond line</b>", Some(ParseMode::HTML), 15);
+/// assert_eq!(chunks, ["<b>first line\n</b>", "<b>second line</b>"]);
+/// ```
+pub fn split_text(text: &str, parse_mode: Option<ParseMode>, max_len: usize) -> Vec<String> {
+    let tokens = tokenize(text, parse_mode);
+    let mut chunks = vec![];
+    let mut start = 0;
+    // Entities open at the start of the chunk, with their opening markup and closing markup.
+    let mut open: Vec<(&str, Option<&str>)> = vec![];
+    while start < tokens.len() {
+        let mut stack = open.clone();
+        let mut len = 0;
+        let mut end = start;
+        // Ends of the chunk after a line break, a whitespace and any character, with the entities open there.
+        let mut at_line_break = None;
+        let mut at_whitespace = None;
+        let mut at_char = None;
+        while end < tokens.len() {
+            match &tokens[end] {
+                Token::Char(_, char) => {
+                    if len + char.len_utf16() > max_len {
+                        break;
+                    }
+                    len += char.len_utf16();
+                }
+                Token::Open(source, closing) => stack.push((*source, closing.as_deref())),
+                Token::Close(_) => {
+                    stack.pop();
+                }
+                Token::Markup(_) => {}
+            }
+            end += 1;
+            // Closing markup right after the end belongs to the chunk.
+            while let Some(Token::Close(_)) = tokens.get(end) {
+                stack.pop();
+                end += 1;
+            }
+            let splittable = stack.iter().all(|(_, closing)| closing.is_some());
+            if let (Token::Char(_, char), true) = (&tokens[end - 1], splittable) {
+                let candidate = Some((end, stack.clone()));
+                if *char == '\n' {
+                    at_line_break = candidate.clone();
+                } else if char.is_whitespace() {
+                    at_whitespace = candidate.clone();
+                }
+                at_char = candidate;
+            }
+        }
+        if end < tokens.len() {
+            if let Some((split, split_stack)) = at_line_break.or(at_whitespace).or(at_char) {
+                end = split;
+                stack = split_stack;
+            } else if len == 0 {
+                // A character longer than the limit cannot be split.
+                if let Token::Char(..) = tokens[end] {
+                    end += 1;
+                }
+                while let Some(Token::Close(_)) = tokens.get(end) {
+                    stack.pop();
+                    end += 1;
+                }
+            }
+        }
+
+        let mut chunk = String::new();
+        for (source, _) in &open {
+            chunk.push_str(source);
+        }
+        for token in &tokens[start..end] {
+            chunk.push_str(token.source());
+        }
+        for (_, closing) in stack.iter().rev() {
+            chunk.push_str(closing.unwrap_or(""));
+        }
+        chunks.push(chunk);
+        start = end;
+        open = stack;
+    }
+    chunks
+}
+
+/// Error found while validating MarkdownV2 text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownV2Error {
+    /// Byte offset in the text where the error is found.
+    pub offset: usize,
+    /// Kind of the error.
+    pub kind: MarkdownV2ErrorKind,
+}
+
+/// Kind of [`MarkdownV2Error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownV2ErrorKind {
+    /// A reserved character is not escaped with `\`.
+    UnescapedCharacter(char),
+    /// An entity is not closed, or is closed after another entity opened inside it.
+    UnclosedEntity(&'static str),
+    /// A text link or custom emoji has no URL in parentheses.
+    MissingUrl,
+    /// The text ends with a single `\`.
+    TrailingBackslash,
+}
+
+impl std::fmt::Display for MarkdownV2Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            MarkdownV2ErrorKind::UnescapedCharacter(char) => write!(
+                f,
+                "character '{}' is reserved and must be escaped at byte offset {}",
+                char, self.offset
+            ),
+            MarkdownV2ErrorKind::UnclosedEntity(kind) => write!(
+                f,
+                "can't find end of {} entity at byte offset {}",
+                kind, self.offset
+            ),
+            MarkdownV2ErrorKind::MissingUrl => {
+                write!(
+                    f,
+                    "can't find url of text link at byte offset {}",
+                    self.offset
+                )
+            }
+            MarkdownV2ErrorKind::TrailingBackslash => {
+                write!(f, "trailing backslash at byte offset {}", self.offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MarkdownV2Error {}
+
+/// A piece of formatted text, used to measure and split it.
+enum Token<'a> {
+    /// Markup of a visible character, with the character.
+    Char(&'a str, char),
+    /// Markup opening an entity, with the markup closing it if the entity can be split.
+    Open(&'a str, Option<String>),
+    /// Markup closing the innermost entity.
+    Close(&'a str),
+    /// Markup which does not open or close entities, like an unsupported tag.
+    Markup(&'a str),
+}
+
+impl<'a> Token<'a> {
+    fn source(&self) -> &'a str {
+        match self {
+            Self::Char(source, _)
+            | Self::Open(source, _)
+            | Self::Close(source)
+            | Self::Markup(source) => source,
+        }
+    }
+}
+
+fn tokenize(text: &str, parse_mode: Option<ParseMode>) -> Vec<Token<'_>> {
+    match parse_mode {
+        Some(ParseMode::MarkdownV2) => tokenize_markdown(text, true),
+        Some(ParseMode::Markdown) => tokenize_markdown(text, false),
+        Some(ParseMode::HTML) => tokenize_html(text),
+        None => text
+            .char_indices()
+            .map(|(index, char)| Token::Char(&text[index..index + char.len_utf8()], char))
+            .collect(),
+    }
+}
+
+fn tokenize_markdown(text: &str, v2: bool) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    // Markers of open entities, where `[` is a text link.
+    let mut open: Vec<&str> = vec![];
+    let mut chars = text.char_indices().peekable();
+    let mut line_start = true;
+    while let Some((offset, char)) = chars.next() {
+        let at_line_start = line_start;
+        line_start = char == '\n';
+        let in_code = matches!(open.last(), Some(&"`") | Some(&"```"));
+        let marker = match char {
+            '\\' => {
+                match chars.next() {
+                    Some((index, escaped)) => tokens.push(Token::Char(
+                        &text[offset..index + escaped.len_utf8()],
+                        escaped,
+                    )),
+                    None => tokens.push(Token::Markup(&text[offset..])),
+                }
+                continue;
+            }
+            '`' if text[offset..].starts_with("```") => {
+                chars.next();
+                chars.next();
+                if open.last() == Some(&"```") {
+                    open.pop();
+                    tokens.push(Token::Close(&text[offset..offset + 3]));
+                } else {
+                    // The language of the block is on the same line.
+                    let end = match text[offset..].find('\n') {
+                        Some(line_end) => offset + line_end + 1,
+                        None => offset + 3,
+                    };
+                    while matches!(chars.peek(), Some((index, _)) if *index < end) {
+                        chars.next();
+                    }
+                    line_start = end > offset + 3;
+                    open.push("```");
+                    let source = &text[offset..end];
+                    tokens.push(Token::Open(source, Some("```".to_string())));
+                }
+                continue;
+            }
+            '`' => "`",
+            _ if in_code => {
+                tokens.push(Token::Char(&text[offset..offset + char.len_utf8()], char));
+                continue;
+            }
+            '*' => "*",
+            '_' if v2 && matches!(chars.peek(), Some((_, '_'))) => {
+                chars.next();
+                "__"
+            }
+            '_' => "_",
+            '~' if v2 => "~",
+            '|' if v2 && matches!(chars.peek(), Some((_, '|'))) => {
+                chars.next();
+                "||"
+            }
+            '[' => {
+                open.push("[");
+                tokens.push(Token::Open(&text[offset..offset + 1], None));
+                continue;
+            }
+            ']' if open.last() == Some(&"[") && matches!(chars.peek(), Some((_, '('))) => {
+                let mut end = text.len();
+                while let Some((index, char)) = chars.next() {
+                    match char {
+                        '\\' => {
+                            chars.next();
+                        }
+                        ')' => {
+                            end = index + 1;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                open.pop();
+                tokens.push(Token::Close(&text[offset..end]));
+                continue;
+            }
+            '>' if v2 && at_line_start => {
+                tokens.push(Token::Markup(&text[offset..offset + 1]));
+                continue;
+            }
+            _ => {
+                tokens.push(Token::Char(&text[offset..offset + char.len_utf8()], char));
+                continue;
+            }
+        };
+        let source = &text[offset..offset + marker.len()];
+        if open.last() == Some(&marker) {
+            open.pop();
+            tokens.push(Token::Close(source));
+        } else {
+            open.push(marker);
+            tokens.push(Token::Open(source, Some(marker.to_string())));
+        }
+    }
+    tokens
+}
+
+fn tokenize_html(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    // Names of open tags.
+    let mut open: Vec<String> = vec![];
+    let mut rest = text;
+    while let Some(index) = rest.find(&['<', '&'][..]) {
+        tokens.extend(
+            rest[..index]
+                .char_indices()
+                .map(|(offset, char)| Token::Char(&rest[offset..offset + char.len_utf8()], char)),
+        );
+        rest = &rest[index..];
+        if rest.starts_with('<') {
+            let (tag, length) = match HtmlTag::parse(rest) {
+                Some(parsed) => parsed,
+                None => {
+                    let length = rest.find('>').map_or(rest.len(), |end| end + 1);
+                    tokens.push(Token::Markup(&rest[..length]));
+                    rest = &rest[length..];
+                    continue;
+                }
+            };
+            let source = &rest[..length];
+            if !tag.closing {
+                tokens.push(Token::Open(source, Some(format!("</{}>", tag.name))));
+                open.push(tag.name);
+            } else if let Some(index) = open.iter().rposition(|name| *name == tag.name) {
+                // Tags opened inside are closed together.
+                for _ in index + 1..open.len() {
+                    tokens.push(Token::Close(""));
+                }
+                tokens.push(Token::Close(source));
+                open.truncate(index);
+            } else {
+                tokens.push(Token::Markup(source));
+            }
+            rest = &rest[length..];
+            continue;
+        }
+        let entity_end = rest.find(';').filter(|&end| end <= 10);
+        let decoded = entity_end.and_then(|end| match &rest[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            entity => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(std::char::from_u32),
+        });
+        match (decoded, entity_end) {
+            (Some(decoded), Some(end)) => {
+                tokens.push(Token::Char(&rest[..=end], decoded));
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                tokens.push(Token::Char(&rest[..1], '&'));
+                rest = &rest[1..];
+            }
+        }
+    }
+    tokens.extend(
+        rest.char_indices()
+            .map(|(offset, char)| Token::Char(&rest[offset..offset + char.len_utf8()], char)),
+    );
+    tokens
+}
+
+/// A start or end tag in HTML text.
+struct HtmlTag<'a> {
+    closing: bool,
+    name: String,
+    attributes: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> HtmlTag<'a> {
+    /// Parses a tag at the start of the text, returning the tag and its length.
+    fn parse(text: &'a str) -> Option<(Self, usize)> {
+        let mut rest = text.strip_prefix('<')?;
+        let closing = rest.starts_with('/');
+        if closing {
+            rest = &rest[1..];
+        }
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let name_end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = &rest[name_end..];
+
+        let mut attributes = vec![];
+        loop {
+            rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+            if let Some(after) = rest.strip_prefix('>') {
+                let tag = Self {
+                    closing,
+                    name,
+                    attributes,
+                };
+                return Some((tag, text.len() - after.len()));
+            }
+            let name_end =
+                rest.find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')?;
+            if name_end == 0 {
+                return None;
+            }
+            let name = &rest[..name_end];
+            rest = rest[name_end..].trim_start();
+            let value = match rest.strip_prefix('=') {
+                Some(after) => {
+                    let after = after.trim_start();
+                    let (value, after) = match after.chars().next()? {
+                        quote @ '"' | quote @ '\'' => {
+                            let end = after[1..].find(quote)? + 1;
+                            (&after[1..end], &after[end + 1..])
+                        }
+                        _ => {
+                            let end = after.find(|c: char| c.is_whitespace() || c == '>')?;
+                            after.split_at(end)
+                        }
+                    };
+                    rest = after;
+                    value
+                }
+                None => "",
+            };
+            attributes.push((name, value));
+        }
+    }
+
+    fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+
+    /// Writes the tag if Telegram supports it, keeping track of open tags.
+    fn push_sanitized(&self, output: &mut String, open: &mut Vec<(&'static str, bool)>) {
+        let name = match self.name.as_str() {
+            "b" | "strong" => "b",
+            "i" | "em" => "i",
+            "u" | "ins" => "u",
+            "s" | "strike" | "del" => "s",
+            "a" => "a",
+            "code" => "code",
+            "pre" => "pre",
+            "span" => "span",
+            "tg-spoiler" => "tg-spoiler",
+            _ => return,
+        };
+
+        if self.closing {
+            // Closes the innermost tag with the same name and all tags opened inside it.
+            if let Some(index) = open.iter().rposition(|(open_name, _)| *open_name == name) {
+                for (name, kept) in open.drain(index..).rev() {
+                    if kept {
+                        output.push_str("</");
+                        output.push_str(name);
+                        output.push('>');
+                    }
+                }
+            }
+            return;
+        }
+
+        // Only a code block is allowed inside a preformatted block, and nothing inside code.
+        let innermost = open
+            .iter()
+            .rev()
+            .find(|(_, kept)| *kept)
+            .map(|(name, _)| *name);
+        let in_code = open
+            .iter()
+            .any(|(name, kept)| *kept && (*name == "code" || *name == "pre"));
+        let allowed = !in_code || (innermost == Some("pre") && name == "code");
+        let attributes = match name {
+            "a" => self.attribute("href").map(|href| vec![("href", href)]),
+            "span" => self
+                .attribute("class")
+                .filter(|class| *class == "tg-spoiler")
+                .map(|class| vec![("class", class)]),
+            "code" => Some(
+                self.attribute("class")
+                    .filter(|class| innermost == Some("pre") && class.starts_with("language-"))
+                    .map(|class| vec![("class", class)])
+                    .unwrap_or_default(),
+            ),
+            _ => Some(vec![]),
+        };
+
+        match attributes.filter(|_| allowed) {
+            Some(attributes) => {
+                output.push('<');
+                output.push_str(name);
+                for (attribute, value) in attributes {
+                    output.push(' ');
+                    output.push_str(attribute);
+                    output.push_str("=\"");
+                    let mut rest = value;
+                    while let Some(index) = rest.find(&['<', '>', '&', '"'][..]) {
+                        output.push_str(&rest[..index]);
+                        rest = &rest[index..];
+                        let length = html_escape_char(output, rest);
+                        rest = &rest[length..];
+                    }
+                    output.push_str(rest);
+                    output.push('"');
+                }
+                output.push('>');
+                open.push((name, true));
+            }
+            // Unsupported spans are tracked to match their end tags.
+            None if name == "span" => open.push((name, false)),
+            None => {}
+        }
+    }
+}
+
+/// Escapes the special character at the start of the text, keeping entities supported by Telegram.
+///
+/// Returns the length of the text consumed.
+fn html_escape_char(output: &mut String, text: &str) -> usize {
+    match text.as_bytes()[0] {
+        b'<' => output.push_str("&lt;"),
+        b'>' => output.push_str("&gt;"),
+        b'"' => output.push_str("&quot;"),
+        _ => {
+            let entity_end = text
+                .find(';')
+                .filter(|&end| end <= 10 && is_html_entity(&text[1..end]));
+            match entity_end {
+                Some(end) => {
+                    output.push_str(&text[..=end]);
+                    return end + 1;
+                }
+                None => output.push_str("&amp;"),
+            }
+        }
+    }
+    1
+}
+
+/// Checks if the name of an entity, without `&` and `;`, is supported by Telegram.
+fn is_html_entity(entity: &str) -> bool {
+    let (digits, radix) = match entity {
+        "lt" | "gt" | "amp" | "quot" => return true,
+        _ => match entity
+            .strip_prefix("#x")
+            .or_else(|| entity.strip_prefix("#X"))
+        {
+            Some(hex) => (hex, 16),
+            None => match entity.strip_prefix('#') {
+                Some(dec) => (dec, 10),
+                None => return false,
+            },
+        },
+    };
+    !digits.is_empty() && digits.chars().all(|c| c.is_digit(radix))
 }
 
 /// One special entity in a text message.
@@ -492,6 +1649,34 @@ pub struct MessageEntity {
     pub length: usize,
 }
 
+impl MessageEntity {
+    /// Gets the part of the given text covered by this entity.
+    ///
+    /// Returns `None` if the entity does not fit into the text.
+    pub fn text_in<'a>(&self, text: &'a str) -> Option<&'a str> {
+        let end = self.offset + self.length;
+        let mut start_byte = None;
+        let mut utf16_offset = 0;
+        for (byte_offset, c) in text.char_indices() {
+            if utf16_offset == self.offset {
+                start_byte = Some(byte_offset);
+            }
+            if utf16_offset == end {
+                return start_byte.map(|start| &text[start..byte_offset]);
+            }
+            utf16_offset += c.len_utf16();
+        }
+        if utf16_offset == self.offset {
+            start_byte = Some(text.len());
+        }
+        if utf16_offset == end {
+            start_byte.map(|start| &text[start..])
+        } else {
+            None
+        }
+    }
+}
+
 /// Type of the message entity.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
@@ -522,7 +1707,8 @@ pub enum MessageEntityKind {
     Code,
     /// ```monowidth block```.
     Pre {
-        /// The programming language of the entity text.
+        /// The programming language of the entity text, empty if not specified.
+        #[serde(default, skip_serializing_if = "String::is_empty")]
         language: String,
     },
     /// clickable text URLs.
@@ -673,3 +1859,126 @@ impl From<ForceReply> for ReplyMarkup {
         Self::ForceReply(markup)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_text_reopens_entities_in_next_chunk() {
+        let chunks = split_text("<b>aaaa <i>bbbb</i></b>", Some(ParseMode::HTML), 5);
+        assert_eq!(chunks, ["<b>aaaa </b>", "<b><i>bbbb</i></b>"]);
+        let chunks = split_text("*aaaa bbbb*", Some(ParseMode::MarkdownV2), 5);
+        assert_eq!(chunks, ["*aaaa *", "*bbbb*"]);
+    }
+
+    #[test]
+    fn split_text_keeps_escapes_whole() {
+        let chunks = split_text("a&amp;b&lt;c", Some(ParseMode::HTML), 2);
+        assert_eq!(chunks, ["a&amp;", "b&lt;", "c"]);
+        let chunks = split_text("*a\\.b\\.c*", Some(ParseMode::MarkdownV2), 2);
+        assert_eq!(chunks, ["*a\\.*", "*b\\.*", "*c*"]);
+    }
+
+    #[test]
+    fn split_text_keeps_surrogate_pairs_whole() {
+        assert_eq!(split_text("ab😀c", None, 3), ["ab", "😀c"]);
+        assert_eq!(split_text("😀😀", None, 3), ["😀", "😀"]);
+        assert_eq!(text_len("😀", None), 2);
+    }
+
+    #[test]
+    fn split_text_with_limit_smaller_than_a_character() {
+        assert_eq!(split_text("😀a", None, 1), ["😀", "a"]);
+        assert_eq!(split_text("ab", None, 0), ["a", "b"]);
+        let chunks = split_text("<b>😀</b>", Some(ParseMode::HTML), 1);
+        assert_eq!(chunks, ["<b>😀</b>"]);
+    }
+
+    #[test]
+    fn sanitize_html_drops_unsupported_tags() {
+        let html = ParseMode::sanitize_html("<div><p>Hi</p><script>x</script><img src=a></div>");
+        assert_eq!(html, "Hix");
+        let html = ParseMode::sanitize_html(r#"<span style="color: red">red</span>"#);
+        assert_eq!(html, "red");
+        let html = ParseMode::sanitize_html(r#"<a onclick="x()">link</a>"#);
+        assert_eq!(html, "link");
+    }
+
+    #[test]
+    fn sanitize_html_keeps_supported_attributes() {
+        let html = ParseMode::sanitize_html(
+            r#"<span class="tg-spoiler">s</span><pre><code class="language-rust">fn</code></pre>"#,
+        );
+        assert_eq!(
+            html,
+            r#"<span class="tg-spoiler">s</span><pre><code class="language-rust">fn</code></pre>"#
+        );
+        let html = ParseMode::sanitize_html(r#"<STRONG>b</STRONG><em>i</em><del>s</del>"#);
+        assert_eq!(html, "<b>b</b><i>i</i><s>s</s>");
+    }
+
+    #[test]
+    fn sanitize_html_nests_tags() {
+        let html = ParseMode::sanitize_html("<b>bold <i>both</b> italic</i>");
+        assert_eq!(html, "<b>bold <i>both</i></b> italic");
+        let html = ParseMode::sanitize_html("<code>a <b>b</b></code>");
+        assert_eq!(html, "<code>a b</code>");
+        let html = ParseMode::sanitize_html("<pre><b>a</b><code>b</code></pre>");
+        assert_eq!(html, "<pre>a<code>b</code></pre>");
+    }
+
+    #[test]
+    fn sanitize_html_closes_unclosed_tags() {
+        assert_eq!(ParseMode::sanitize_html("<b><i>text"), "<b><i>text</i></b>");
+        assert_eq!(ParseMode::sanitize_html("text</b>"), "text");
+        assert_eq!(ParseMode::sanitize_html("<b>a</i>b</b>"), "<b>ab</b>");
+    }
+
+    #[test]
+    fn sanitize_html_escapes_entities() {
+        assert_eq!(
+            ParseMode::sanitize_html("1 < 2 & 3 > 2 &amp; &#39; &#x27; &nbsp; &bogus;"),
+            "1 &lt; 2 &amp; 3 &gt; 2 &amp; &#39; &#x27; &amp;nbsp; &amp;bogus;"
+        );
+        assert_eq!(ParseMode::sanitize_html("<3 <b"), "&lt;3 &lt;b");
+        let html =
+            ParseMode::sanitize_html(r#"<a href="https://example.com/?a=1&b=&quot;2&quot;">x</a>"#);
+        assert_eq!(
+            html,
+            r#"<a href="https://example.com/?a=1&amp;b=&quot;2&quot;">x</a>"#
+        );
+    }
+
+    #[test]
+    fn markdown_v2_accepts_custom_emoji() {
+        let text = "Nice ![👍](tg://emoji?id=5368324170671202286) *job*";
+        assert_eq!(ParseMode::validate_markdown_v2(text), Ok(()));
+    }
+
+    #[test]
+    fn markdown_v2_rejects_custom_emoji_without_url() {
+        let error = ParseMode::validate_markdown_v2("![👍] done").unwrap_err();
+        assert_eq!(error.kind, MarkdownV2ErrorKind::MissingUrl);
+    }
+
+    #[test]
+    fn markdown_v2_accepts_expandable_block_quotation() {
+        let text = "**>The expandable block quotation\n>continues here\n>and ends here||\nafter";
+        assert_eq!(ParseMode::validate_markdown_v2(text), Ok(()));
+    }
+
+    #[test]
+    fn markdown_v2_keeps_spoiler_at_end_of_expandable_line() {
+        let text = "**>quote with ||spoiler||\n>last line||";
+        assert_eq!(ParseMode::validate_markdown_v2(text), Ok(()));
+        let error = ParseMode::validate_markdown_v2("**>quote with ||spoiler").unwrap_err();
+        assert_eq!(error.kind, MarkdownV2ErrorKind::UnclosedEntity("spoiler"));
+    }
+
+    #[test]
+    fn markdown_v2_rejects_expandability_mark_outside_quotation() {
+        let error = ParseMode::validate_markdown_v2("plain||").unwrap_err();
+        assert_eq!(error.kind, MarkdownV2ErrorKind::UnclosedEntity("spoiler"));
+    }
+}
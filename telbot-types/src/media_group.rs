@@ -0,0 +1,118 @@
+//! Aggregation of incoming media groups (albums).
+//!
+//! Telegram delivers each item of an album as a separate message sharing the same `media_group_id`.
+//! [`MediaGroupBuffer`] collects them so that an album can be handled as a whole.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::message::Message;
+
+/// Buffers messages of media groups until no more items arrive for a while.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use telbot_types::media_group::MediaGroupBuffer;
+/// let mut buffer = MediaGroupBuffer::new(Duration::from_secs(1));
+/// // for each received message:
+/// // if let Some(message) = buffer.push(message) { /* not in a media group */ }
+/// for album in buffer.take_expired() {
+///     // handle album as Vec<Message>
+/// }
+/// ```
+pub struct MediaGroupBuffer {
+    window: Duration,
+    groups: HashMap<String, PendingGroup>,
+}
+
+struct PendingGroup {
+    messages: Vec<Message>,
+    deadline: Instant,
+}
+
+impl MediaGroupBuffer {
+    /// Creates a new buffer which waits for `window` after the last item of each media group.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            groups: HashMap::new(),
+        }
+    }
+
+    /// Pushes a message into the buffer.
+    ///
+    /// Returns the message back if it does not belong to any media group.
+    pub fn push(&mut self, message: Message) -> Option<Message> {
+        self.push_at(message, Instant::now())
+    }
+
+    /// Pushes a message into the buffer as if it were received at `now`.
+    pub fn push_at(&mut self, message: Message, now: Instant) -> Option<Message> {
+        let media_group_id = match &message.media_group_id {
+            Some(id) => id.clone(),
+            None => return Some(message),
+        };
+        let deadline = now + self.window;
+        let group = self
+            .groups
+            .entry(media_group_id)
+            .or_insert_with(|| PendingGroup {
+                messages: vec![],
+                deadline,
+            });
+        group.messages.push(message);
+        group.deadline = deadline;
+        None
+    }
+
+    /// Takes media groups whose window has passed, in order of their first message.
+    pub fn take_expired(&mut self) -> Vec<Vec<Message>> {
+        self.take_expired_at(Instant::now())
+    }
+
+    /// Takes media groups whose window has passed at `now`, in order of their first message.
+    pub fn take_expired_at(&mut self, now: Instant) -> Vec<Vec<Message>> {
+        let expired = self
+            .groups
+            .iter()
+            .filter(|(_, group)| group.deadline <= now)
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        let groups = expired
+            .into_iter()
+            .filter_map(|id| self.groups.remove(&id))
+            .map(|group| group.messages)
+            .collect();
+        Self::sorted(groups)
+    }
+
+    /// Takes all buffered media groups regardless of their window.
+    pub fn take_all(&mut self) -> Vec<Vec<Message>> {
+        let groups = self
+            .groups
+            .drain()
+            .map(|(_, group)| group.messages)
+            .collect();
+        Self::sorted(groups)
+    }
+
+    /// Gets the earliest time when a buffered media group expires, if any.
+    ///
+    /// Useful for choosing a polling timeout.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.groups.values().map(|group| group.deadline).min()
+    }
+
+    /// `true` if no media group is buffered.
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    fn sorted(mut groups: Vec<Vec<Message>>) -> Vec<Vec<Message>> {
+        for messages in &mut groups {
+            messages.sort_by_key(|message| message.message_id);
+        }
+        groups.sort_by_key(|messages| messages[0].message_id);
+        groups
+    }
+}
@@ -0,0 +1,117 @@
+//! Caching of chat member counts.
+//!
+//! Bots often adapt to the size of the chat, like disabling chatty features in huge groups,
+//! but the number of members is only given by [`GetChatMemberCount`](crate::chat::GetChatMemberCount).
+//! [`MemberCountCache`] keeps the counts, and keeps them up to date with `chat_member` updates.
+//! Use it with [`large_chat`](crate::filter::large_chat) to handle messages of large chats differently.
+
+use std::time::{Duration, Instant};
+
+use crate::chat::ChatMemberUpdated;
+use crate::ttl_map::TtlMap;
+use crate::update::{Update, UpdateKind};
+
+/// Caches the number of members of chats.
+///
+/// A count expires `ttl` after it was fetched, since joins and leaves are not delivered as updates
+/// unless the bot is an administrator and receives `chat_member` updates.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use telbot_types::member_count::MemberCountCache;
+/// let mut cache = MemberCountCache::new(Duration::from_secs(60 * 60));
+/// assert_eq!(cache.get(-100), None);
+/// // count = api.send_json(&chat.get_member_count())?;
+/// cache.insert(-100, 5000);
+/// assert_eq!(cache.get(-100), Some(5000));
+/// assert_eq!(cache.is_large(-100, 1000), Some(true));
+/// ```
+pub struct MemberCountCache {
+    chats: TtlMap<i64, u32>,
+}
+
+impl MemberCountCache {
+    /// Creates a new cache which forgets member counts `ttl` after they were fetched.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            chats: TtlMap::new(ttl),
+        }
+    }
+
+    /// Stores the number of members of the chat, as returned by [`GetChatMemberCount`](crate::chat::GetChatMemberCount).
+    pub fn insert(&mut self, chat_id: i64, count: u32) {
+        self.insert_at(chat_id, count, Instant::now())
+    }
+
+    /// Stores the number of members of the chat fetched at `now`.
+    pub fn insert_at(&mut self, chat_id: i64, count: u32, now: Instant) {
+        self.chats.insert_at(chat_id, count, now);
+    }
+
+    /// Gets the number of members of the chat.
+    ///
+    /// Returns `None` if the count of the chat is not cached or expired.
+    /// Fetch it with [`GetChatMemberCount`](crate::chat::GetChatMemberCount) and store with [`MemberCountCache::insert`] in that case.
+    pub fn get(&self, chat_id: i64) -> Option<u32> {
+        self.get_at(chat_id, Instant::now())
+    }
+
+    /// Gets the number of members of the chat at `now`.
+    pub fn get_at(&self, chat_id: i64, now: Instant) -> Option<u32> {
+        self.chats.get_at(&chat_id, now).copied()
+    }
+
+    /// Checks if the chat has at least `threshold` members.
+    ///
+    /// Returns `None` if the count of the chat is not cached or expired.
+    pub fn is_large(&self, chat_id: i64, threshold: u32) -> Option<bool> {
+        self.is_large_at(chat_id, threshold, Instant::now())
+    }
+
+    /// Checks if the chat has at least `threshold` members at `now`.
+    pub fn is_large_at(&self, chat_id: i64, threshold: u32, now: Instant) -> Option<bool> {
+        self.get_at(chat_id, now).map(|count| count >= threshold)
+    }
+
+    /// Applies a change of a chat member to the cached count of the chat, if any.
+    ///
+    /// Joins and leaves take effect immediately, without fetching the count again.
+    pub fn record_member_update(&mut self, updated: &ChatMemberUpdated) {
+        if let Some(count) = self.chats.get_mut(&updated.chat.id) {
+            match (
+                updated.old_chat_member.is_member(),
+                updated.new_chat_member.is_member(),
+            ) {
+                (false, true) => *count = count.saturating_add(1),
+                (true, false) => *count = count.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+
+    /// Applies the change of a chat member in an update, if any.
+    pub fn record_update(&mut self, update: &Update) {
+        match &update.kind {
+            UpdateKind::MyChatMemberUpdated { my_chat_member: u }
+            | UpdateKind::ChatMemberUpdated { chat_member: u } => self.record_member_update(u),
+            _ => {}
+        }
+    }
+
+    /// Forgets the count of the chat, so that it is fetched again.
+    pub fn invalidate(&mut self, chat_id: i64) {
+        self.chats.remove(&chat_id);
+    }
+
+    /// Forgets counts whose TTL has passed.
+    ///
+    /// Call this periodically, so that counts of chats the bot no longer hears from don't pile up.
+    pub fn purge_expired(&mut self) {
+        self.purge_expired_at(Instant::now())
+    }
+
+    /// Forgets counts whose TTL has passed at `now`.
+    pub fn purge_expired_at(&mut self, now: Instant) {
+        self.chats.purge_expired_at(now)
+    }
+}
@@ -2,71 +2,204 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::chat::{Chat, ChatId, PinChatMessage, UnpinChatMessage};
+use crate::chat::{Chat, ChatId, ChatKind, PinChatMessage, UnpinChatMessage};
 use crate::file::{
-    Animation, Audio, Document, InputFile, InputFileVariant, InputMedia, PhotoSize, Video,
-    VideoNote, Voice,
+    serialize_attached_media, serialize_attached_media_group, Animation, Audio, Document,
+    FileUniqueId, InputFile, InputFileVariant, InputMedia, PhotoSize, Video, VideoNote, Voice,
 };
-use crate::markup::{InlineKeyboardMarkup, MessageEntity, ParseMode, ReplyMarkup};
+use crate::forum::{ForumTopicClosed, ForumTopicCreated, ForumTopicEdited, ForumTopicReopened};
+use crate::markup::{
+    split_text, text_len, InlineKeyboardMarkup, MarkdownV2Error, MessageEntity, MessageEntityKind,
+    ParseMode, ReplyMarkup,
+};
+pub use crate::game::Game;
+pub use crate::passport::PassportData;
 use crate::payment::{Invoice, SuccessfulPayment};
+use crate::reaction::{ReactionType, SetMessageReaction};
 use crate::sticker::Sticker;
-use crate::user::User;
-use crate::{FileMethod, JsonMethod, TelegramMethod};
+use crate::user::{User, GROUP_ANONYMOUS_BOT_ID, TELEGRAM_USER_ID};
+use crate::webapp::WebAppData;
+use crate::{telegram_method, FileMethod};
 
 /// A message.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#message)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     /// Unique message identifier inside this chat.
     pub message_id: i64,
+    /// Unique identifier of the message thread (topic) the message belongs to; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
+    /// `true` if the message is sent to a forum topic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_topic_message: Option<bool>,
     /// Sender, empty for messages sent to channels.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub from: Option<User>,
     /// Sender of the message, sent on behalf of a chat.
     /// The channel itself for channel messages.
     /// The supergroup itself for messages from anonymous group administrators.
     /// The linked channel for messages automatically forwarded to the discussion group
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sender_chat: Option<Chat>,
     /// Date the message was sent in Unix time.
     pub date: u64,
     /// Conversation the message belongs to.
     pub chat: Chat,
     /// For forwarded messages, sender of the original message.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_from: Option<User>,
     /// For messages forwarded from channels or from anonymous administrators, information about the original sender chat.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_from_chat: Option<Chat>,
     /// For messages forwarded from channels, identifier of the original message in the channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_from_message_id: Option<i64>,
     /// For messages forwarded from channels, signature of the post author if present.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_signature: Option<String>,
     /// Sender's name for messages forwarded from users who disallow adding a link to their account in forwarded messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_sender_name: Option<String>,
     /// For forwarded messages, date the original message was sent in Unix time.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub forward_date: Option<u64>,
     /// For replies, the original message.
     /// Note that the Message object in this field will not contain further reply_to_message fields even if it itself is a reply.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reply_to_message: Option<Box<Message>>,
     /// Bot through which the message was sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub via_bot: Option<User>,
     /// Date the message was last edited in Unix time.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub edit_date: Option<u64>,
     /// The unique identifier of a media message group this message belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub media_group_id: Option<String>,
     /// Signature of the post author for messages in channels,
     /// or the custom title of an anonymous group administrator
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_signature: Option<String>,
+    /// Unique identifier of the message effect added to the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effect_id: Option<String>,
     /// Additional information about the message.
     #[serde(flatten)]
     pub kind: MessageKind,
     /// Inline keyboard attached to the message.
     /// `login_url` buttons are represented as ordinary `url` buttons.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reply_markup: Option<InlineKeyboardMarkup>,
 }
 
 impl Message {
-    /// Creates a new [`SendMessage`] request that replies to this message.
+    /// Resolves the actual sender of this message.
+    ///
+    /// Messages sent on behalf of chats carry placeholder users in [`Message::from`],
+    /// such as [`GROUP_ANONYMOUS_BOT_ID`] and [`TELEGRAM_USER_ID`],
+    /// which must not be treated as real users, e.g. when banning senders.
+    pub fn sender(&self) -> Sender<'_> {
+        match (&self.sender_chat, &self.from) {
+            (Some(sender_chat), _) if sender_chat.id == self.chat.id => {
+                if matches!(self.chat.kind, ChatKind::Channel) {
+                    Sender::Channel(sender_chat)
+                } else {
+                    Sender::AnonymousAdmin(sender_chat)
+                }
+            }
+            (Some(sender_chat), Some(from)) if from.id == TELEGRAM_USER_ID => {
+                Sender::LinkedChannelAutoForward(sender_chat)
+            }
+            (Some(sender_chat), _) => Sender::Channel(sender_chat),
+            (None, Some(from)) if from.id == GROUP_ANONYMOUS_BOT_ID => {
+                Sender::AnonymousAdmin(&self.chat)
+            }
+            (None, Some(from)) if from.id == TELEGRAM_USER_ID => match &self.forward_from_chat {
+                Some(channel) => Sender::LinkedChannelAutoForward(channel),
+                None => Sender::User(from),
+            },
+            (None, Some(from)) => Sender::User(from),
+            (None, None) => Sender::Channel(&self.chat),
+        }
+    }
+
+    /// `true` if the message is sent by the bot, or sent via the bot in inline mode.
+    ///
+    /// Echo-style bots should ignore such messages to avoid replying to themselves in a loop.
+    pub fn is_sent_by(&self, bot_id: i64) -> bool {
+        let is_bot = |user: &Option<User>| matches!(user, Some(user) if user.id == bot_id);
+        is_bot(&self.from) || is_bot(&self.via_bot)
+    }
+
+    /// Gets the identifier of the message effect added to this message, if any.
+    pub fn effect_id(&self) -> Option<&str> {
+        self.effect_id.as_deref()
+    }
+
+    /// Gets the identifier of the forum topic the message is sent to, if any.
+    pub fn topic_id(&self) -> Option<i64> {
+        match self.is_topic_message {
+            Some(true) => self.message_thread_id,
+            _ => None,
+        }
+    }
+
+    /// Sends the request to the forum topic of this message, if any.
+    fn in_topic<R>(&self, request: R, with_topic: fn(R, i64) -> R) -> R {
+        match self.topic_id() {
+            Some(topic_id) => with_topic(request, topic_id),
+            None => request,
+        }
+    }
+
+    /// Creates a new [`SendMessage`] request that replies to this message, in the same forum topic.
     pub fn reply_text(&self, text: impl Into<String>) -> SendMessage {
-        SendMessage::new(self.chat.id, text).reply_to(self.message_id)
+        let request = SendMessage::new(self.chat.id, text).reply_to(self.message_id);
+        self.in_topic(request, SendMessage::with_message_thread_id)
+    }
+
+    /// Creates a new [`SendPhoto`] request that replies to this message, in the same forum topic.
+    pub fn reply_photo(&self, photo: impl Into<InputFileVariant>) -> SendPhoto {
+        let request = SendPhoto::new(self.chat.id, photo).reply_to(self.message_id);
+        self.in_topic(request, SendPhoto::with_message_thread_id)
+    }
+
+    /// Creates a new [`SendAudio`] request that replies to this message, in the same forum topic.
+    pub fn reply_audio(&self, audio: impl Into<InputFileVariant>) -> SendAudio {
+        let request = SendAudio::new(self.chat.id, audio).reply_to(self.message_id);
+        self.in_topic(request, SendAudio::with_message_thread_id)
+    }
+
+    /// Creates a new [`SendDocument`] request that replies to this message, in the same forum topic.
+    pub fn reply_document(&self, document: impl Into<InputFileVariant>) -> SendDocument {
+        let request = SendDocument::new(self.chat.id, document).reply_to(self.message_id);
+        self.in_topic(request, SendDocument::with_message_thread_id)
+    }
+
+    /// Creates a new [`SendVideo`] request that replies to this message, in the same forum topic.
+    pub fn reply_video(&self, video: impl Into<InputFileVariant>) -> SendVideo {
+        let request = SendVideo::new(self.chat.id, video).reply_to(self.message_id);
+        self.in_topic(request, SendVideo::with_message_thread_id)
+    }
+
+    /// Creates a new [`SendAnimation`] request that replies to this message, in the same forum topic.
+    pub fn reply_animation(&self, animation: impl Into<InputFileVariant>) -> SendAnimation {
+        let request = SendAnimation::new(self.chat.id, animation).reply_to(self.message_id);
+        self.in_topic(request, SendAnimation::with_message_thread_id)
+    }
+
+    /// Creates a new [`SendVoice`] request that replies to this message, in the same forum topic.
+    pub fn reply_voice(&self, voice: impl Into<InputFileVariant>) -> SendVoice {
+        let request = SendVoice::new(self.chat.id, voice).reply_to(self.message_id);
+        self.in_topic(request, SendVoice::with_message_thread_id)
+    }
+
+    /// Creates a new [`SendVideoNote`] request that replies to this message, in the same forum topic.
+    pub fn reply_video_note(&self, video_note: impl Into<InputFileVariant>) -> SendVideoNote {
+        let request = SendVideoNote::new(self.chat.id, video_note).reply_to(self.message_id);
+        self.in_topic(request, SendVideoNote::with_message_thread_id)
     }
 
     /// Creates a new [`ForwardMessage`] request that forwards this message to the given chat.
@@ -79,6 +212,23 @@ impl Message {
         CopyMessage::new(chat_id, self.chat.id, self.message_id)
     }
 
+    /// Creates a new [`CopyMessage`] request that copies this message to the given chat,
+    /// passing the original caption and its entities explicitly.
+    ///
+    /// Builders like [`CopyMessage::with_caption`] replace the caption without formatting,
+    /// so the request can be adjusted while keeping the formatting, e.g. by [`CopyMessage::with_entity`].
+    /// Messages without a caption are copied as [`Message::copy_to`] does.
+    pub fn copy_with_original_formatting(&self, chat_id: impl Into<ChatId>) -> CopyMessage {
+        let copy = self.copy_to(chat_id);
+        match (self.kind.caption(), self.kind.caption_entities()) {
+            (Some(caption), Some(entities)) => {
+                copy.with_caption(caption).with_entities(entities.to_vec())
+            }
+            (Some(caption), None) => copy.with_caption(caption),
+            _ => copy,
+        }
+    }
+
     /// Creates a new [`PinChatMessage`] request that pins this message.
     pub fn pin(&self) -> PinChatMessage {
         PinChatMessage::new(self.chat.id, self.message_id)
@@ -131,10 +281,69 @@ impl Message {
     pub fn delete(&self) -> DeleteMessage {
         DeleteMessage::new(self.chat.id, self.message_id)
     }
+
+    /// Creates a new [`SetMessageReaction`] request that reacts to this message with the emoji.
+    pub fn react(&self, emoji: impl Into<String>) -> SetMessageReaction {
+        SetMessageReaction::new(self.chat.id, self.message_id)
+            .with_reaction(ReactionType::emoji(emoji))
+    }
+}
+
+/// The actual sender of a message, resolved by [`Message::sender`].
+#[derive(Debug, Clone, Copy)]
+pub enum Sender<'a> {
+    /// A user or a bot.
+    User(&'a User),
+    /// An anonymous administrator of the group, which is the given chat.
+    AnonymousAdmin(&'a Chat),
+    /// A channel, either posting in itself or sending a message on behalf of it in a group.
+    Channel(&'a Chat),
+    /// The linked channel of the discussion group, whose post is automatically forwarded to the group.
+    LinkedChannelAutoForward(&'a Chat),
+}
+
+impl<'a> Sender<'a> {
+    /// Gets the user if the sender is a user.
+    pub fn user(&self) -> Option<&'a User> {
+        match self {
+            Self::User(user) => Some(user),
+            _ => None,
+        }
+    }
+
+    /// Gets the chat if the message is sent on behalf of a chat.
+    pub fn chat(&self) -> Option<&'a Chat> {
+        match self {
+            Self::User(_) => None,
+            Self::AnonymousAdmin(chat)
+            | Self::Channel(chat)
+            | Self::LinkedChannelAutoForward(chat) => Some(chat),
+        }
+    }
+
+    /// `true` if the sender is a user.
+    pub fn is_user(&self) -> bool {
+        matches!(self, Self::User(_))
+    }
+
+    /// `true` if the sender is an anonymous group administrator.
+    pub fn is_anonymous_admin(&self) -> bool {
+        matches!(self, Self::AnonymousAdmin(_))
+    }
+
+    /// `true` if the sender is a channel.
+    pub fn is_channel(&self) -> bool {
+        matches!(self, Self::Channel(_))
+    }
+
+    /// `true` if the message is automatically forwarded from the linked channel.
+    pub fn is_linked_channel_auto_forward(&self) -> bool {
+        matches!(self, Self::LinkedChannelAutoForward(_))
+    }
 }
 
 /// Variants of a message.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum MessageKind {
     /// Text message.
@@ -142,6 +351,7 @@ pub enum MessageKind {
         /// The actual UTF-8 text of the message, 0-4096 characters.
         text: String,
         /// Special entities like usernames, URLs, bot commands, etc. that appear in the text.
+        #[serde(skip_serializing_if = "Option::is_none")]
         entities: Option<Vec<MessageEntity>>,
     },
     /// Animation message.
@@ -152,8 +362,10 @@ pub enum MessageKind {
         /// Information about the file.
         document: Document,
         /// Caption for the animation, 0-1024 characters.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption: Option<String>,
         /// For messages with a caption, special entities like usernames, URLs, bot commands, etc. that appear in the caption.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption_entities: Option<Vec<MessageEntity>>,
     },
     /// Audio message.
@@ -161,8 +373,10 @@ pub enum MessageKind {
         /// Information about the file.
         audio: Audio,
         /// Caption for the audio, 0-1024 characters.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption: Option<String>,
         /// For messages with a caption, special entities like usernames, URLs, bot commands, etc. that appear in the caption.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption_entities: Option<Vec<MessageEntity>>,
     },
     /// General file message.
@@ -170,8 +384,10 @@ pub enum MessageKind {
         /// Information about the file.
         document: Document,
         /// Caption for the document, 0-1024 characters.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption: Option<String>,
         /// For messages with a caption, special entities like usernames, URLs, bot commands, etc. that appear in the caption.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption_entities: Option<Vec<MessageEntity>>,
     },
     /// Photo message.
@@ -179,8 +395,10 @@ pub enum MessageKind {
         /// Available sizes of the photo.
         photo: Vec<PhotoSize>,
         /// Caption for the photo, 0-1024 characters.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption: Option<String>,
         /// For messages with a caption, special entities like usernames, URLs, bot commands, etc. that appear in the caption.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption_entities: Option<Vec<MessageEntity>>,
     },
     /// Sticker message.
@@ -193,8 +411,10 @@ pub enum MessageKind {
         /// Information about the video.
         video: Video,
         /// Caption for the video, 0-1024 characters.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption: Option<String>,
         /// For messages with a caption, special entities like usernames, URLs, bot commands, etc. that appear in the caption.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption_entities: Option<Vec<MessageEntity>>,
     },
     /// [Video note](https://telegram.org/blog/video-messages-and-telescope).
@@ -207,8 +427,10 @@ pub enum MessageKind {
         /// Information about the file.
         voice: Voice,
         /// Caption for the voice, 0-1024 characters.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption: Option<String>,
         /// For messages with a caption, special entities like usernames, URLs, bot commands, etc. that appear in the caption.
+        #[serde(skip_serializing_if = "Option::is_none")]
         caption_entities: Option<Vec<MessageEntity>>,
     },
     /// Shared contact.
@@ -323,6 +545,9 @@ pub enum MessageKind {
         /// The domain name of the website on which the user has logged in.
         /// [More about Telegram Login »](https://core.telegram.org/widgets/login)
         connected_website: String,
+    },
+    /// Telegram Passport data shared with the bot.
+    PassportData {
         /// Telegram Passport data
         passport_data: PassportData,
     },
@@ -330,6 +555,26 @@ pub enum MessageKind {
     ProximityAlertTriggered {
         proximity_alert_triggered: ProximityAlertTriggered,
     },
+    /// Service message: data sent by a Web App.
+    WebAppData {
+        web_app_data: WebAppData,
+    },
+    /// Service message: forum topic created.
+    ForumTopicCreated {
+        forum_topic_created: ForumTopicCreated,
+    },
+    /// Service message: forum topic edited.
+    ForumTopicEdited {
+        forum_topic_edited: ForumTopicEdited,
+    },
+    /// Service message: forum topic closed.
+    ForumTopicClosed {
+        forum_topic_closed: ForumTopicClosed,
+    },
+    /// Service message: forum topic reopened.
+    ForumTopicReopened {
+        forum_topic_reopened: ForumTopicReopened,
+    },
     /// Service message: voice chat scheduled.
     VoiceChatScheduled {
         voice_chat_scheduled: VoiceChatScheduled,
@@ -365,6 +610,24 @@ impl MessageKind {
         }
     }
 
+    /// Gets the unique identifier of the media associated with this message, if any.
+    ///
+    /// For photos, the identifier of the largest size is returned.
+    pub fn file_unique_id(&self) -> Option<FileUniqueId> {
+        let id = match self {
+            Self::Animation { animation, .. } => &animation.file_unique_id,
+            Self::Audio { audio, .. } => &audio.file_unique_id,
+            Self::Document { document, .. } => &document.file_unique_id,
+            Self::Photo { photo, .. } => &photo.last()?.file_unique_id,
+            Self::Sticker { sticker } => &sticker.file_unique_id,
+            Self::Video { video, .. } => &video.file_unique_id,
+            Self::VideoNote { video_note } => &video_note.file_unique_id,
+            Self::Voice { voice, .. } => &voice.file_unique_id,
+            _ => return None,
+        };
+        Some(FileUniqueId::from(id.as_str()))
+    }
+
     /// Gets the animation associated with this message, if any.
     pub fn animation(&self) -> Option<&Animation> {
         match self {
@@ -419,6 +682,65 @@ impl MessageKind {
         }
     }
 
+    /// Gets a list of URLs in the text or the caption associated with this message.
+    ///
+    /// Both `url` and `text_link` entities are collected, in order of appearance.
+    /// URLs without a scheme get `http://` prepended, like Telegram clients do.
+    /// If `strip_tracking` is `true`, well-known tracking query parameters
+    /// such as `utm_*` and `fbclid` are removed.
+    pub fn urls(&self, strip_tracking: bool) -> Vec<String> {
+        let (text, entities) = match (self.text(), self.entities()) {
+            (Some(text), Some(entities)) => (text, entities),
+            _ => match (self.caption(), self.caption_entities()) {
+                (Some(caption), Some(entities)) => (caption, entities),
+                _ => return vec![],
+            },
+        };
+        entities
+            .iter()
+            .filter_map(|entity| match &entity.kind {
+                MessageEntityKind::Url => entity.text_in(text),
+                MessageEntityKind::TextLink { url } => Some(url.as_str()),
+                _ => None,
+            })
+            .map(|url| normalize_url(url, strip_tracking))
+            .collect()
+    }
+
+    /// Gets the code blocks in the text or the caption associated with this message.
+    ///
+    /// Each block is a pair of the language, if specified, and the content of a `pre` entity,
+    /// in order of appearance.
+    ///
+    /// ```
+    /// # use telbot_types::message::MessageKind;
+    /// # let kind: MessageKind = serde_json::from_str(r#"{
+    /// #     "text": "Run 🦀\nfn main() {}",
+    /// #     "entities": [{"type": "pre", "offset": 7, "length": 12, "language": "rust"}]
+    /// # }"#).unwrap();
+    /// // a message with the text "Run 🦀\nfn main() {}" formatted as a Rust code block
+    /// assert_eq!(kind.code_blocks(), vec![(Some("rust"), "fn main() {}")]);
+    /// ```
+    pub fn code_blocks(&self) -> Vec<(Option<&str>, &str)> {
+        let (text, entities) = match (self.text(), self.entities()) {
+            (Some(text), Some(entities)) => (text, entities),
+            _ => match (self.caption(), self.caption_entities()) {
+                (Some(caption), Some(entities)) => (caption, entities),
+                _ => return vec![],
+            },
+        };
+        entities
+            .iter()
+            .filter_map(|entity| match &entity.kind {
+                MessageEntityKind::Pre { language } => {
+                    let language = Some(language.as_str()).filter(|language| !language.is_empty());
+                    Some((language, entity.text_in(text)?))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Gets the audio associated with this message, if any.
     pub fn audio(&self) -> Option<&Audio> {
         match self {
@@ -597,9 +919,7 @@ impl MessageKind {
     /// Gets the connected website associated with this message, if any.
     pub fn connected_website(&self) -> Option<&str> {
         match self {
-            Self::Login {
-                connected_website, ..
-            } => Some(connected_website),
+            Self::Login { connected_website } => Some(connected_website),
             _ => None,
         }
     }
@@ -607,7 +927,7 @@ impl MessageKind {
     /// Gets the passport data associated with this message, if any.
     pub fn passport_data(&self) -> Option<&PassportData> {
         match self {
-            Self::Login { passport_data, .. } => Some(passport_data),
+            Self::PassportData { passport_data } => Some(passport_data),
             _ => None,
         }
     }
@@ -788,6 +1108,11 @@ impl MessageKind {
         matches!(self, Self::Login { .. })
     }
 
+    /// `true` if it is Telegram Passport data.
+    pub fn is_passport_data(&self) -> bool {
+        matches!(self, Self::PassportData { .. })
+    }
+
     /// `true` if it refers proximity alert trigger.
     pub fn is_proximity_alert_triggered(&self) -> bool {
         matches!(self, Self::ProximityAlertTriggered { .. })
@@ -814,10 +1139,51 @@ impl MessageKind {
     }
 }
 
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "msclkid", "yclid", "igshid", "mc_cid", "mc_eid", "_ga",
+];
+
+fn normalize_url(url: &str, strip_tracking: bool) -> String {
+    let url = url.trim();
+    let (scheme, rest) = match url.find("://") {
+        Some(index) => (url[..index].to_ascii_lowercase(), &url[index + 3..]),
+        None => ("http".to_string(), url),
+    };
+    let host_end = rest.find(&['/', '?', '#'][..]).unwrap_or(rest.len());
+    let (host, rest) = rest.split_at(host_end);
+    let mut normalized = format!("{}://{}", scheme, host.to_ascii_lowercase());
+
+    let (rest, fragment) = match rest.find('#') {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+    match rest.find('?') {
+        Some(index) if strip_tracking => {
+            normalized.push_str(&rest[..index]);
+            let query = rest[index + 1..]
+                .split('&')
+                .filter(|param| {
+                    let name = param.split('=').next().unwrap_or_default();
+                    !param.is_empty()
+                        && !name.starts_with("utm_")
+                        && !TRACKING_PARAMS.contains(&name)
+                })
+                .collect::<Vec<_>>();
+            if !query.is_empty() {
+                normalized.push('?');
+                normalized.push_str(&query.join("&"));
+            }
+        }
+        _ => normalized.push_str(rest),
+    }
+    normalized.push_str(fragment);
+    normalized
+}
+
 /// A unique message identifier.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#messageid)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageId {
     /// Unique message identifier.
     pub message_id: i64,
@@ -826,46 +1192,78 @@ pub struct MessageId {
 /// A point on the map.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#location)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Location {
     /// Longitude as defined by sender.
     pub longitude: f32,
     /// Latitude as defined by sender.
     pub latitude: f32,
     /// The radius of uncertainty for the location, measured in meters; 0-1500.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub horizontal_accuracy: Option<f32>,
     /// Time relative to the message sending date, during which the location can be updated, in seconds.
     /// For active live locations only.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub live_period: Option<i32>,
     /// The direction in which user is moving, in degrees; 1-360.
     /// For active live locations only.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub heading: Option<i32>,
     /// Maximum distance for proximity alerts about approaching another chat member, in meters.
     /// For sent live locations only.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proximity_alert_radius: Option<i32>,
 }
 
+impl Location {
+    /// Gets the great-circle distance to the point, in meters.
+    pub fn distance_to(&self, latitude: f32, longitude: f32) -> f64 {
+        const EARTH_RADIUS: f64 = 6_371_000.0;
+
+        let (lat1, lat2) = (
+            (self.latitude as f64).to_radians(),
+            (latitude as f64).to_radians(),
+        );
+        let delta_lat = lat2 - lat1;
+        let delta_lon = (longitude as f64 - self.longitude as f64).to_radians();
+        let a = (delta_lat / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
+}
+
 /// A phone contact.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#contact)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Contact {
     /// Contact's phone number.
     pub phone_number: String,
     /// Contact's first name.
     pub first_name: String,
     /// Contact's last name.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_name: Option<String>,
     /// Contact's user identifier in Telegram.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user_id: Option<i64>,
     /// Additional data about the contact in the form of a [vCard](https://en.wikipedia.org/wiki/VCard).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub vcard: Option<String>,
 }
 
+#[cfg(feature = "vcard")]
+impl Contact {
+    /// Parses the attached vCard, if any.
+    pub fn parse_vcard(&self) -> Option<Result<crate::vcard::VCard, crate::vcard::VCardError>> {
+        self.vcard.as_deref().map(crate::vcard::VCard::parse)
+    }
+}
+
 /// This object represents an animated emoji that displays a random value.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#dice)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dice {
     /// Emoji on which the dice throw animation is based.
     pub emoji: String,
@@ -873,16 +1271,8 @@ pub struct Dice {
     pub value: i32,
 }
 
-/// A game.
-///
-/// Use BotFather to create and edit games, their short names will act as unique identifiers.
-///
-/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#game)
-#[derive(Debug, Deserialize)]
-pub struct Game {}
-
 /// Information about one answer option in a poll.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PollOption {
     /// Option text, 1-100 characters.
     pub text: String,
@@ -891,7 +1281,7 @@ pub struct PollOption {
 }
 
 /// An answer of a user in a non-anonymous poll.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PollAnswer {
     /// Unique poll identifier.
     pub poll_id: String,
@@ -903,7 +1293,7 @@ pub struct PollAnswer {
 }
 
 /// Information about a poll.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Poll {
     /// Unique poll identifier.
     pub id: String,
@@ -923,13 +1313,15 @@ pub struct Poll {
     /// True, if the poll allows multiple answers.
     pub allows_multiple_answers: bool,
     /// Amount of time in seconds the poll will be active after creation.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub open_period: Option<u32>,
     /// Point in time (Unix timestamp) when the poll will be automatically closed.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub close_date: Option<u64>,
 }
 
 /// Poll type.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum PollKind {
     Regular,
@@ -937,11 +1329,14 @@ pub enum PollKind {
         /// 0-based identifier of the correct answer option.
         /// Available only for polls in the quiz mode, which are closed,
         /// or was sent (not forwarded) by the bot or to the private chat with the bot.
+        #[serde(skip_serializing_if = "Option::is_none")]
         correct_option_id: Option<usize>,
         /// Text that is shown when a user chooses an incorrect answer or taps on the lamp icon in a quiz-style poll,
         /// 0-200 characters.
+        #[serde(skip_serializing_if = "Option::is_none")]
         explanation: Option<String>,
         /// Special entities like usernames, URLs, bot commands, etc. that appear in the explanation.
+        #[serde(skip_serializing_if = "Option::is_none")]
         explanation_entities: Option<Vec<MessageEntity>>,
     },
 }
@@ -990,7 +1385,7 @@ impl PollKind {
 /// A venue.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#venue)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Venue {
     /// Venue location. Can't be a live location.
     pub location: Location,
@@ -999,12 +1394,15 @@ pub struct Venue {
     /// Address of the venue.
     pub address: String,
     /// Foursquare identifier of the venue.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foursquare_id: Option<String>,
     /// Foursquare type of the venue.
     ///
     /// For example, “arts_entertainment/default”, “arts_entertainment/aquarium” or “food/icecream”.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foursquare_type: Option<String>,
     /// Google Places identifier of the venue
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub google_place_id: Option<String>,
     /// Google Places type of the venue. (See [supported types.](https://developers.google.com/places/web-service/supported_types))
     pub google_place_type: String,
@@ -1013,23 +1411,17 @@ pub struct Venue {
 /// A service message about a change in auto-delete timer settings.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#messageautodeletetimerchanged)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageAutoDeleteTimerChanged {
     /// New auto-delete time for messages in the chat.
     pub message_auto_delete_time: u32,
 }
 
-/// Telegram Passport Data shared with the bot by the user.
-///
-/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#passportdata)
-#[derive(Debug, Deserialize)]
-pub struct PassportData {}
-
 /// The content of a service message,
 /// sent whenever a user in the chat triggers a proximity alert set by another user.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#proximityalerttriggered)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProximityAlertTriggered {
     /// User that triggered the alert.
     pub traveler: User,
@@ -1042,7 +1434,7 @@ pub struct ProximityAlertTriggered {
 /// A service message about a voice chat scheduled in the chat.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#voicechatscheduled)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoiceChatScheduled {
     /// Point in time (Unix timestamp) when the voice chat is supposed to be started by a chat administrator.
     pub start_date: u64,
@@ -1050,23 +1442,29 @@ pub struct VoiceChatScheduled {
 
 /// A service message about a voice chat started in the chat.
 /// Currently holds no information.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoiceChatStarted;
 
 /// A service message about a voice chat ended in the chat.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoiceChatEnded {
     /// Voice chat duration; in seconds.
     pub duration: u32,
 }
 
 /// A service message about new members invited to a voice chat.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoiceChatParticipantsInvited {
     /// New members that were invited to the voice chat.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub users: Option<Vec<User>>,
 }
 
+/// Maximum length of the text of a message after entities parsing.
+///
+/// See [`text_len`] for how the length is counted.
+pub const MAX_MESSAGE_TEXT_LENGTH: usize = 4096;
+
 /// Use this method to send text messages.
 /// 
 /// On success, the sent [`Message`] is returned.
@@ -1076,6 +1474,9 @@ pub struct VoiceChatParticipantsInvited {
 pub struct SendMessage {
     /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Text of the message to be sent, 1-4096 characters after entities parsing.
     pub text: String,
     /// Mode for parsing entities in the message text.
@@ -1108,6 +1509,9 @@ pub struct SendMessage {
     /// Protects the contents of the sent message from forwarding and saving.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub protect_content: Option<bool>,
+    /// Unique identifier of the message effect to be added to the message; for private chats only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_effect_id: Option<String>,
 }
 
 impl SendMessage {
@@ -1115,6 +1519,7 @@ impl SendMessage {
     pub fn new(chat_id: impl Into<ChatId>, text: impl Into<String>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             text: text.into(),
             parse_mode: None,
             entities: None,
@@ -1124,6 +1529,7 @@ impl SendMessage {
             allow_sending_without_reply: None,
             reply_markup: None,
             protect_content: None,
+            message_effect_id: None,
         }
     }
     /// Sets parse mode.
@@ -1133,6 +1539,21 @@ impl SendMessage {
             ..self
         }
     }
+    /// Sets text in MarkdownV2 format, validating its syntax.
+    ///
+    /// See also [`ParseMode::validate_markdown_v2`].
+    pub fn with_markdown_v2_checked(
+        self,
+        text: impl Into<String>,
+    ) -> Result<Self, MarkdownV2Error> {
+        let text = text.into();
+        ParseMode::validate_markdown_v2(&text)?;
+        Ok(Self {
+            text,
+            parse_mode: Some(ParseMode::MarkdownV2),
+            ..self
+        })
+    }
     /// Set entities
     pub fn with_entities(self, entities: Vec<MessageEntity>) -> Self {
         Self {
@@ -1188,17 +1609,211 @@ impl SendMessage {
             ..self
         }
     }
+    /// Sets the message effect to be added to the message.
+    pub fn with_message_effect_id(self, effect_id: impl Into<String>) -> Self {
+        Self {
+            message_effect_id: Some(effect_id.into()),
+            ..self
+        }
+    }
+    /// Gets the length of the text after entities parsing.
+    pub fn text_len(&self) -> usize {
+        text_len(&self.text, self.parse_mode)
+    }
+    /// Splits the message into messages whose texts fit in [`MAX_MESSAGE_TEXT_LENGTH`] after entities parsing.
+    ///
+    /// Only the first message replies to the message replied to, and only the last one has the reply markup.
+    /// Messages with *entities* are not split, since entities can't be moved between messages.
+    ///
+    /// See [`split_text`] for how the text is split.
+    pub fn split(self) -> Vec<Self> {
+        if self.entities.is_some() || self.text_len() <= MAX_MESSAGE_TEXT_LENGTH {
+            return vec![self];
+        }
+        let texts = split_text(&self.text, self.parse_mode, MAX_MESSAGE_TEXT_LENGTH);
+        let last = texts.len() - 1;
+        texts
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| Self {
+                text,
+                reply_to_message_id: self.reply_to_message_id.filter(|_| index == 0),
+                reply_markup: self.reply_markup.clone().filter(|_| index == last),
+                ..self.clone()
+            })
+            .collect()
+    }
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
+    }
 }
 
-impl TelegramMethod for SendMessage {
-    type Response = Message;
+telegram_method!(SendMessage => "sendMessage", Message, json, chat_id);
 
-    fn name() -> &'static str {
-        "sendMessage"
+/// Borrowed version of [`SendMessage`], which sends the same text and markup without cloning them.
+///
+/// Useful in hot paths such as broadcasting, where the owned request would be allocated for each chat.
+///
+/// ```
+/// # use telbot_types::message::SendMessageRef;
+/// let text = String::from("Hello, subscribers!");
+/// let requests: Vec<_> = [1, 2, 3]
+///     .iter()
+///     .map(|&chat_id| SendMessageRef::new(chat_id, &text).disable_notification())
+///     .collect();
+/// ```
+#[derive(Clone, Serialize)]
+pub struct SendMessageRef<'a> {
+    /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
+    pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
+    /// Text of the message to be sent, 1-4096 characters after entities parsing.
+    pub text: &'a str,
+    /// Mode for parsing entities in the message text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_mode: Option<ParseMode>,
+    /// List of special entities that appear in message text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entities: Option<&'a [MessageEntity]>,
+    /// Disables link previews for links in this message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_web_page_preview: Option<bool>,
+    /// Sends the message silently.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_notification: Option<bool>,
+    /// If the message is a reply, ID of the original message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to_message_id: Option<i64>,
+    /// Pass `true`, if the message should be sent even if the specified replied-to message is not found.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_sending_without_reply: Option<bool>,
+    /// Additional interface options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_markup: Option<&'a ReplyMarkup>,
+    /// Protects the contents of the sent message from forwarding and saving.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protect_content: Option<bool>,
+    /// Unique identifier of the message effect to be added to the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_effect_id: Option<&'a str>,
+}
+
+impl<'a> SendMessageRef<'a> {
+    /// Create a new [`SendMessageRef`] request that sends the given text to the given chat.
+    pub fn new(chat_id: impl Into<ChatId>, text: &'a str) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            message_thread_id: None,
+            text,
+            parse_mode: None,
+            entities: None,
+            disable_web_page_preview: None,
+            disable_notification: None,
+            reply_to_message_id: None,
+            allow_sending_without_reply: None,
+            reply_markup: None,
+            protect_content: None,
+            message_effect_id: None,
+        }
+    }
+    /// Sets parse mode.
+    pub fn with_parse_mode(self, parse_mode: ParseMode) -> Self {
+        Self {
+            parse_mode: Some(parse_mode),
+            ..self
+        }
+    }
+    /// Set entities
+    pub fn with_entities(self, entities: &'a [MessageEntity]) -> Self {
+        Self {
+            entities: Some(entities),
+            ..self
+        }
+    }
+    /// Disables web preview.
+    pub fn disable_web_page_preview(self) -> Self {
+        Self {
+            disable_web_page_preview: Some(true),
+            ..self
+        }
+    }
+    /// Disables notification.
+    pub fn disable_notification(self) -> Self {
+        Self {
+            disable_notification: Some(true),
+            ..self
+        }
+    }
+    /// Replies to message.
+    pub fn reply_to(self, message_id: i64) -> Self {
+        Self {
+            reply_to_message_id: Some(message_id),
+            ..self
+        }
+    }
+    /// Allows sending message even if the replying message isn't present.
+    pub fn allow_sending_without_reply(self) -> Self {
+        Self {
+            allow_sending_without_reply: Some(true),
+            ..self
+        }
+    }
+    /// Sets reply markup.
+    pub fn with_reply_markup(self, markup: &'a ReplyMarkup) -> Self {
+        Self {
+            reply_markup: Some(markup),
+            ..self
+        }
+    }
+    /// Protects content from forwarding and saving.
+    pub fn protect_content(self) -> Self {
+        Self {
+            protect_content: Some(true),
+            ..self
+        }
+    }
+    /// Sets the message effect to be added to the message.
+    pub fn with_message_effect_id(self, effect_id: &'a str) -> Self {
+        Self {
+            message_effect_id: Some(effect_id),
+            ..self
+        }
+    }
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
-impl JsonMethod for SendMessage {}
+impl<'a> From<&'a SendMessage> for SendMessageRef<'a> {
+    fn from(message: &'a SendMessage) -> Self {
+        Self {
+            chat_id: message.chat_id.clone(),
+            message_thread_id: message.message_thread_id,
+            text: &message.text,
+            parse_mode: message.parse_mode,
+            entities: message.entities.as_deref(),
+            disable_web_page_preview: message.disable_web_page_preview,
+            disable_notification: message.disable_notification,
+            reply_to_message_id: message.reply_to_message_id,
+            allow_sending_without_reply: message.allow_sending_without_reply,
+            reply_markup: message.reply_markup.as_ref(),
+            protect_content: message.protect_content,
+            message_effect_id: message.message_effect_id.as_deref(),
+        }
+    }
+}
+
+telegram_method!(SendMessageRef<'_> => "sendMessage", Message, json, chat_id);
 
 /// Forwards messages of any kind. Service messages can't be forwarded.
 /// 
@@ -1209,6 +1824,9 @@ impl JsonMethod for SendMessage {}
 pub struct ForwardMessage {
     /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Unique identifier for the chat where the original message was sent. (in the format `@channelusername`)
     pub from_chat_id: ChatId,
     /// Sends the message [silently](https://telegram.org/blog/channels-2-0#silent-messages).
@@ -1227,6 +1845,7 @@ impl ForwardMessage {
     pub fn new(to: impl Into<ChatId>, from: impl Into<ChatId>, message: i64) -> Self {
         Self {
             chat_id: to.into(),
+            message_thread_id: None,
             from_chat_id: from.into(),
             disable_notification: None,
             message_id: message,
@@ -1247,17 +1866,16 @@ impl ForwardMessage {
             ..self
         }
     }
-}
-
-impl TelegramMethod for ForwardMessage {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "forwardMessage"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
-impl JsonMethod for ForwardMessage {}
+telegram_method!(ForwardMessage => "forwardMessage", Message, json, chat_id);
 
 /// Copies messages of any kind.
 ///
@@ -1271,18 +1889,27 @@ impl JsonMethod for ForwardMessage {}
 pub struct CopyMessage {
     /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Unique identifier for the chat where the original message was sent. (in the format `@channelusername`)
     pub from_chat_id: ChatId,
     /// Message identifier in the chat specified in *from_chat_id*.
     pub message_id: i64,
     /// New caption for media, 0-1024 characters after entities parsing.
-    /// If not specified, the original caption is kept
+    /// If not specified, the original caption is kept, and if empty, the caption is removed.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub caption: Option<String>,
     /// Mode for parsing entities in the new caption.
     /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the new caption, which can be specified instead of *parse_mode*.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<MessageEntity>>,
+    /// Pass `true` if the caption must be shown above the message media.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_caption_above_media: Option<bool>,
     /// Sends the message [silently](https://telegram.org/blog/channels-2-0#silent-messages).
     /// Users will receive a notification with no sound.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1309,11 +1936,13 @@ impl CopyMessage {
     pub fn new(to: impl Into<ChatId>, from: impl Into<ChatId>, message: i64) -> Self {
         Self {
             chat_id: to.into(),
+            message_thread_id: None,
             from_chat_id: from.into(),
             message_id: message,
             caption: None,
             parse_mode: None,
             caption_entities: None,
+            show_caption_above_media: None,
             disable_notification: None,
             reply_to_message_id: None,
             allow_sending_without_reply: None,
@@ -1328,6 +1957,15 @@ impl CopyMessage {
             ..self
         }
     }
+    /// Removes the caption of the original message, instead of keeping it.
+    pub fn remove_caption(self) -> Self {
+        Self {
+            caption: Some(String::new()),
+            parse_mode: None,
+            caption_entities: None,
+            ..self
+        }
+    }
     /// Sets parse mode.
     pub fn with_parse_mode(self, parse_mode: ParseMode) -> Self {
         Self {
@@ -1348,6 +1986,13 @@ impl CopyMessage {
         entities.push(entity);
         self
     }
+    /// Shows the caption above the media.
+    pub fn show_caption_above_media(self) -> Self {
+        Self {
+            show_caption_above_media: Some(true),
+            ..self
+        }
+    }
     /// Disables notification.
     pub fn disable_notification(self) -> Self {
         Self {
@@ -1383,17 +2028,16 @@ impl CopyMessage {
             ..self
         }
     }
-}
-
-impl TelegramMethod for CopyMessage {
-    type Response = MessageId;
-
-    fn name() -> &'static str {
-        "copyMessage"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
-impl JsonMethod for CopyMessage {}
+telegram_method!(CopyMessage => "copyMessage", MessageId, json, chat_id);
 
 /// Use this method to send photos.
 /// On success, the sent [Message](https://core.telegram.org/bots/api#message) is returned.
@@ -1401,6 +2045,9 @@ impl JsonMethod for CopyMessage {}
 pub struct SendPhoto {
     /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Photo to send.
     /// Pass a file_id as String to send a photo that exists on the Telegram servers (recommended),
     /// pass an HTTP URL as a String for Telegram to get a photo from the Internet,
@@ -1420,6 +2067,9 @@ pub struct SendPhoto {
     /// List of special entities that appear in the caption, which can be specified instead of *parse_mode*
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<MessageEntity>>,
+    /// Pass `true` if the caption must be shown above the message media.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_caption_above_media: Option<bool>,
     /// Sends the message [silently](https://telegram.org/blog/channels-2-0#silent-messages).
     /// Users will receive a notification with no sound.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1446,10 +2096,12 @@ impl SendPhoto {
     pub fn new(chat_id: impl Into<ChatId>, photo: impl Into<InputFileVariant>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             photo: photo.into(),
             caption: None,
             parse_mode: None,
             caption_entities: None,
+            show_caption_above_media: None,
             disable_notification: None,
             reply_to_message_id: None,
             allow_sending_without_reply: None,
@@ -1484,6 +2136,13 @@ impl SendPhoto {
         entities.push(entity);
         self
     }
+    /// Shows the caption above the media.
+    pub fn show_caption_above_media(self) -> Self {
+        Self {
+            show_caption_above_media: Some(true),
+            ..self
+        }
+    }
     /// Disables notification.
     pub fn disable_notification(self) -> Self {
         Self {
@@ -1519,16 +2178,17 @@ impl SendPhoto {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendPhoto {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendPhoto"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
+telegram_method!(SendPhoto => "sendPhoto", Message, chat_id);
+
 impl FileMethod for SendPhoto {
     fn files(&self) -> Option<HashMap<&str, &InputFile>> {
         if let InputFileVariant::File(file) = &self.photo {
@@ -1551,6 +2211,9 @@ impl FileMethod for SendPhoto {
 pub struct SendAudio {
     /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Audio file to send.
     /// Pass a file_id as String to send an audio file that exists on the Telegram servers (recommended),
     /// pass an HTTP URL as a String for Telegram to get an audio file from the Internet,
@@ -1609,6 +2272,7 @@ impl SendAudio {
     pub fn new(chat_id: impl Into<ChatId>, audio: impl Into<InputFileVariant>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             audio: audio.into(),
             duration: None,
             performer: None,
@@ -1714,16 +2378,17 @@ impl SendAudio {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendAudio {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendAudio"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
+telegram_method!(SendAudio => "sendAudio", Message, chat_id);
+
 impl FileMethod for SendAudio {
     fn files(&self) -> Option<HashMap<&str, &InputFile>> {
         let mut map = HashMap::new();
@@ -1747,6 +2412,9 @@ impl FileMethod for SendAudio {
 pub struct SendDocument {
     /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// File to send. Pass a file_id as String to send a file that exists on the Telegram servers (recommended),
     /// pass an HTTP URL as a String for Telegram to get a file from the Internet,
     /// or upload a new one using multipart/form-data.
@@ -1798,6 +2466,7 @@ impl SendDocument {
     pub fn new(chat_id: impl Into<ChatId>, document: impl Into<InputFileVariant>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             document: document.into(),
             disable_content_type_detection: None,
             thumb: None,
@@ -1887,16 +2556,17 @@ impl SendDocument {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendDocument {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendDocument"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
+telegram_method!(SendDocument => "sendDocument", Message, chat_id);
+
 impl FileMethod for SendDocument {
     fn files(&self) -> Option<HashMap<&str, &InputFile>> {
         let mut map = HashMap::new();
@@ -1921,6 +2591,9 @@ impl FileMethod for SendDocument {
 pub struct SendVideo {
     /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Video to send. Pass a file_id as String to send a video that exists on the Telegram servers (recommended),
     /// pass an HTTP URL as a String for Telegram to get a video from the Internet,
     /// or upload a new video using multipart/form-data.
@@ -1955,6 +2628,9 @@ pub struct SendVideo {
     /// List of special entities that appear in the caption, which can be specified instead of *parse_mode*
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<MessageEntity>>,
+    /// Pass `true` if the caption must be shown above the message media.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_caption_above_media: Option<bool>,
     /// Sends the message [silently](https://telegram.org/blog/channels-2-0#silent-messages).
     /// Users will receive a notification with no sound.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1981,6 +2657,7 @@ impl SendVideo {
     pub fn new(chat_id: impl Into<ChatId>, video: impl Into<InputFileVariant>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             video: video.into(),
             duration: None,
             width: None,
@@ -1990,6 +2667,7 @@ impl SendVideo {
             caption: None,
             parse_mode: None,
             caption_entities: None,
+            show_caption_above_media: None,
             disable_notification: None,
             reply_to_message_id: None,
             allow_sending_without_reply: None,
@@ -2059,6 +2737,13 @@ impl SendVideo {
         entities.push(entity);
         self
     }
+    /// Shows the caption above the media.
+    pub fn show_caption_above_media(self) -> Self {
+        Self {
+            show_caption_above_media: Some(true),
+            ..self
+        }
+    }
     /// Disables notification.
     pub fn disable_notification(self) -> Self {
         Self {
@@ -2094,16 +2779,17 @@ impl SendVideo {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendVideo {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendVideo"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
+telegram_method!(SendVideo => "sendVideo", Message, chat_id);
+
 impl FileMethod for SendVideo {
     fn files(&self) -> Option<HashMap<&str, &InputFile>> {
         let mut map = HashMap::new();
@@ -2128,6 +2814,9 @@ impl FileMethod for SendVideo {
 pub struct SendAnimation {
     /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Animation to send. Pass a file_id as String to send an animation that exists on the Telegram servers (recommended),
     /// pass an HTTP URL as a String for Telegram to get a video from the Internet,
     /// or upload a new video using multipart/form-data.
@@ -2159,6 +2848,9 @@ pub struct SendAnimation {
     /// List of special entities that appear in the caption, which can be specified instead of *parse_mode*
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<MessageEntity>>,
+    /// Pass `true` if the caption must be shown above the message media.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_caption_above_media: Option<bool>,
     /// Sends the message [silently](https://telegram.org/blog/channels-2-0#silent-messages).
     /// Users will receive a notification with no sound.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2185,6 +2877,7 @@ impl SendAnimation {
     pub fn new(chat_id: impl Into<ChatId>, animation: impl Into<InputFileVariant>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             animation: animation.into(),
             duration: None,
             width: None,
@@ -2193,6 +2886,7 @@ impl SendAnimation {
             caption: None,
             parse_mode: None,
             caption_entities: None,
+            show_caption_above_media: None,
             disable_notification: None,
             reply_to_message_id: None,
             allow_sending_without_reply: None,
@@ -2255,6 +2949,13 @@ impl SendAnimation {
         entities.push(entity);
         self
     }
+    /// Shows the caption above the media.
+    pub fn show_caption_above_media(self) -> Self {
+        Self {
+            show_caption_above_media: Some(true),
+            ..self
+        }
+    }
     /// Disables notification.
     pub fn disable_notification(self) -> Self {
         Self {
@@ -2290,16 +2991,17 @@ impl SendAnimation {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendAnimation {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendAnimation"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
+telegram_method!(SendAnimation => "sendAnimation", Message, chat_id);
+
 impl FileMethod for SendAnimation {
     fn files(&self) -> Option<HashMap<&str, &InputFile>> {
         let mut map = HashMap::new();
@@ -2326,6 +3028,9 @@ impl FileMethod for SendAnimation {
 pub struct SendVoice {
     /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Audio file to send. Pass a file_id as String to send a file that exists on the Telegram servers (recommended),
     /// pass an HTTP URL as a String for Telegram to get a video from the Internet,
     /// or upload a new video using multipart/form-data.
@@ -2370,6 +3075,7 @@ impl SendVoice {
     pub fn new(chat_id: impl Into<ChatId>, voice: impl Into<InputFileVariant>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             voice: voice.into(),
             duration: None,
             caption: None,
@@ -2451,16 +3157,17 @@ impl SendVoice {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendVoice {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendVoice"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
+telegram_method!(SendVoice => "sendVoice", Message, chat_id);
+
 impl FileMethod for SendVoice {
     fn files(&self) -> Option<HashMap<&str, &InputFile>> {
         if let InputFileVariant::File(file) = &self.voice {
@@ -2480,6 +3187,9 @@ impl FileMethod for SendVoice {
 pub struct SendVideoNote {
     /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Video note to send. Pass a file_id as String to send a video note that exists on the Telegram servers (recommended)
     /// or upload a new video using multipart/form-data.
     /// [More info on Sending Files »](https://core.telegram.org/bots/api#sending-files)
@@ -2524,6 +3234,7 @@ impl SendVideoNote {
     pub fn new(chat_id: impl Into<ChatId>, video_note: impl Into<InputFileVariant>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             video_note: video_note.into(),
             duration: None,
             length: None,
@@ -2591,16 +3302,17 @@ impl SendVideoNote {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendVideoNote {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendVideoNote"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
+telegram_method!(SendVideoNote => "sendVideoNote", Message, chat_id);
+
 impl FileMethod for SendVideoNote {
     fn files(&self) -> Option<HashMap<&str, &InputFile>> {
         let mut map = HashMap::new();
@@ -2624,7 +3336,13 @@ impl FileMethod for SendVideoNote {
 pub struct SendMediaGroup {
     /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// A JSON-serialized array describing messages to be sent, must include 2-10 items
+    ///
+    /// New files are uploaded with the request, attached as “attach://<file_attach_name>”.
+    #[serde(serialize_with = "serialize_attached_media_group")]
     pub media: Vec<InputMedia>,
     /// Sends the message [silently](https://telegram.org/blog/channels-2-0#silent-messages).
     /// Users will receive a notification with no sound.
@@ -2646,6 +3364,7 @@ impl SendMediaGroup {
     pub fn new(chat_id: impl Into<ChatId>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             media: vec![],
             disable_notification: None,
             reply_to_message_id: None,
@@ -2693,13 +3412,28 @@ impl SendMediaGroup {
             ..self
         }
     }
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
+    }
 }
 
-impl TelegramMethod for SendMediaGroup {
-    type Response = Vec<Message>;
+telegram_method!(SendMediaGroup => "sendMediaGroup", Vec<Message>, chat_id);
 
-    fn name() -> &'static str {
-        "sendMediaGroup"
+impl FileMethod for SendMediaGroup {
+    fn files(&self) -> Option<HashMap<&str, &InputFile>> {
+        let mut map = HashMap::new();
+        for (index, media) in self.media.iter().enumerate() {
+            media.attach_files(index, &mut map);
+        }
+        if map.is_empty() {
+            None
+        } else {
+            Some(map)
+        }
     }
 }
 
@@ -2709,12 +3443,16 @@ impl TelegramMethod for SendMediaGroup {
 pub struct SendLocation {
     /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Latitude of the location
     pub latitude: f32,
     /// Longitude of the location
     pub longitude: f32,
     /// The radius of uncertainty for the location, measured in meters; 0-1500
-    pub horizontal_accuracy: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_accuracy: Option<f32>,
     /// Period in seconds for which the location can be updated
     /// (see [Live Locations](https://telegram.org/blog/live-locations)), should be between 60 and 86400.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2750,17 +3488,13 @@ pub struct SendLocation {
 
 impl SendLocation {
     /// Create a new sendLocation request
-    pub fn new(
-        chat_id: impl Into<ChatId>,
-        latitude: f32,
-        longitude: f32,
-        horizontal_accuracy: f32,
-    ) -> Self {
+    pub fn new(chat_id: impl Into<ChatId>, latitude: f32, longitude: f32) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             latitude,
             longitude,
-            horizontal_accuracy,
+            horizontal_accuracy: None,
             live_period: None,
             heading: None,
             proximity_alert_radius: None,
@@ -2771,6 +3505,13 @@ impl SendLocation {
             protect_content: None,
         }
     }
+    /// Set the radius of uncertainty in meters
+    pub fn with_horizontal_accuracy(self, accuracy: f32) -> Self {
+        Self {
+            horizontal_accuracy: Some(accuracy),
+            ..self
+        }
+    }
     /// Set live period
     pub fn with_live_period(self, live_period: u32) -> Self {
         Self {
@@ -2827,17 +3568,16 @@ impl SendLocation {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendLocation {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendLocation"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
-impl JsonMethod for SendLocation {}
+telegram_method!(SendLocation => "sendLocation", Message, json, chat_id);
 
 /// Edit live location messages.
 ///
@@ -2858,6 +3598,7 @@ pub struct EditMessageLiveLocation {
     /// Longitude of new location.
     pub longitude: f32,
     /// The radius of uncertainty for the location, measured in meters; 0-1500.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub horizontal_accuracy: Option<f32>,
     /// For live locations, a direction in which the user is moving, in degrees.
     /// Must be between 1 and 360 if specified.
@@ -2916,15 +3657,7 @@ impl EditMessageLiveLocation {
     }
 }
 
-impl TelegramMethod for EditMessageLiveLocation {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "editMessageLiveLocation"
-    }
-}
-
-impl JsonMethod for EditMessageLiveLocation {}
+telegram_method!(EditMessageLiveLocation => "editMessageLiveLocation", Message, json, chat_id);
 
 /// Edit live location messages.
 ///
@@ -2943,6 +3676,7 @@ pub struct EditInlineMessageLiveLocation {
     /// Longitude of new location.
     pub longitude: f32,
     /// The radius of uncertainty for the location, measured in meters; 0-1500.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub horizontal_accuracy: Option<f32>,
     /// For live locations, a direction in which the user is moving, in degrees.
     /// Must be between 1 and 360 if specified.
@@ -3000,15 +3734,7 @@ impl EditInlineMessageLiveLocation {
     }
 }
 
-impl TelegramMethod for EditInlineMessageLiveLocation {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "editMessageLiveLocation"
-    }
-}
-
-impl JsonMethod for EditInlineMessageLiveLocation {}
+telegram_method!(EditInlineMessageLiveLocation => "editMessageLiveLocation", bool, json);
 
 /// Stops updating a live location message before `live_period` expires.
 ///
@@ -3044,15 +3770,7 @@ impl StopMessageLiveLocation {
     }
 }
 
-impl TelegramMethod for StopMessageLiveLocation {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "stopMessageLiveLocation"
-    }
-}
-
-impl JsonMethod for StopMessageLiveLocation {}
+telegram_method!(StopMessageLiveLocation => "stopMessageLiveLocation", Message, json, chat_id);
 
 /// Stops updating a live location message before `live_period`` expires.
 ///
@@ -3085,15 +3803,7 @@ impl StopInlineMessageLiveLocation {
     }
 }
 
-impl TelegramMethod for StopInlineMessageLiveLocation {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "stopMessageLiveLocation"
-    }
-}
-
-impl JsonMethod for StopInlineMessageLiveLocation {}
+telegram_method!(StopInlineMessageLiveLocation => "stopMessageLiveLocation", bool, json);
 
 /// Sends information about a venue.
 ///
@@ -3104,6 +3814,9 @@ impl JsonMethod for StopInlineMessageLiveLocation {}
 pub struct SendVenue {
     /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Latitude of the venue.
     pub latitude: f32,
     /// Longitude of the venue.
@@ -3157,6 +3870,7 @@ impl SendVenue {
     ) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             latitude,
             longitude,
             title: title.into(),
@@ -3223,17 +3937,16 @@ impl SendVenue {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendVenue {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendVenue"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
-impl JsonMethod for SendVenue {}
+telegram_method!(SendVenue => "sendVenue", Message, json, chat_id);
 
 /// Send text messages.
 ///
@@ -3244,6 +3957,9 @@ impl JsonMethod for SendVenue {}
 pub struct SendContact {
     /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Contact's phone number.
     pub phone_number: String,
     /// Contact's first name.
@@ -3284,6 +4000,7 @@ impl SendContact {
     ) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             phone_number: phone_number.into(),
             first_name: first_name.into(),
             last_name: None,
@@ -3303,6 +4020,8 @@ impl SendContact {
         }
     }
     /// Sets vcard.
+    ///
+    /// With `vcard` feature enabled, a structured `VCard` can be passed as well.
     pub fn with_vcard(self, vcard: impl Into<String>) -> Self {
         Self {
             vcard: Some(vcard.into()),
@@ -3344,17 +4063,16 @@ impl SendContact {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendContact {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendContact"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
-impl JsonMethod for SendContact {}
+telegram_method!(SendContact => "sendContact", Message, json, chat_id);
 
 /// Sends a native poll.
 ///
@@ -3365,6 +4083,9 @@ impl JsonMethod for SendContact {}
 pub struct SendPoll {
     /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Poll question, 1-300 characters.
     pub question: String,
     /// A JSON-serialized list of answer options, 2-10 strings 1-100 characters each.
@@ -3435,6 +4156,7 @@ impl SendPoll {
     ) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             question: question.into(),
             options,
             is_anonymous: None,
@@ -3463,6 +4185,7 @@ impl SendPoll {
     ) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             question: question.into(),
             options,
             is_anonymous: None,
@@ -3583,17 +4306,16 @@ impl SendPoll {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendPoll {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendPoll"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
-impl JsonMethod for SendPoll {}
+telegram_method!(SendPoll => "sendPoll", Message, json, chat_id);
 
 /// Sends an animated emoji that will display a random value.
 ///
@@ -3604,6 +4326,9 @@ impl JsonMethod for SendPoll {}
 pub struct SendDice {
     /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Emoji on which the dice throw animation is based.
     /// Currently, must be one of “🎲”, “🎯”, “🏀”, “⚽”, “🎳”, or “🎰”.
     /// Dice can have values 1-6 for “🎲”, “🎯” and “🎳”, values 1-5 for “🏀” and “⚽”, and values 1-64 for “🎰”. Defaults to “🎲”.
@@ -3635,6 +4360,7 @@ impl SendDice {
     pub fn new(chat_id: impl Into<ChatId>) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             emoji: None,
             disable_notification: None,
             reply_to_message_id: None,
@@ -3685,17 +4411,16 @@ impl SendDice {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SendDice {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendDice"
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
-impl JsonMethod for SendDice {}
+telegram_method!(SendDice => "sendDice", Message, json, chat_id);
 
 /// Type of chat action.
 #[derive(Clone, Serialize)]
@@ -3729,6 +4454,9 @@ pub enum ChatActionKind {
 pub struct SendChatAction {
     /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
     pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
     /// Type of action to broadcast.
     pub action: ChatActionKind,
 }
@@ -3738,20 +4466,20 @@ impl SendChatAction {
     pub fn new(chat_id: impl Into<ChatId>, action: ChatActionKind) -> Self {
         Self {
             chat_id: chat_id.into(),
+            message_thread_id: None,
             action,
         }
     }
-}
-
-impl TelegramMethod for SendChatAction {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "sendChatAction"
+    /// Shows the action in the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
     }
 }
 
-impl JsonMethod for SendChatAction {}
+telegram_method!(SendChatAction => "sendChatAction", bool, json, chat_id);
 
 /// Edits text and [game](https://core.telegram.org/bots/api#games) messages.
 ///
@@ -3795,6 +4523,10 @@ impl EditMessageText {
             reply_markup: None,
         }
     }
+    /// Creates a new [`EditMessageText`] request that edits the given message with the given text.
+    pub fn from_message(message: &Message, text: impl Into<String>) -> Self {
+        Self::new(message.chat.id, message.message_id, text)
+    }
     /// Sets parse mode.
     pub fn with_parse_mode(self, parse_mode: ParseMode) -> Self {
         Self {
@@ -3831,15 +4563,93 @@ impl EditMessageText {
     }
 }
 
-impl TelegramMethod for EditMessageText {
-    type Response = Message;
+telegram_method!(EditMessageText => "editMessageText", Message, json, chat_id);
+
+/// Borrowed version of [`EditMessageText`], which edits messages with the same text and markup without cloning them.
+#[derive(Clone, Serialize)]
+pub struct EditMessageTextRef<'a> {
+    /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
+    pub chat_id: ChatId,
+    /// Identifier of the message to edit.
+    pub message_id: i64,
+    /// New text of the message, 1-4096 characters after entities parsing.
+    pub text: &'a str,
+    /// Mode for parsing entities in the message text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_mode: Option<ParseMode>,
+    /// List of special entities that appear in message text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entities: Option<&'a [MessageEntity]>,
+    /// Disables link previews for links in the sent message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_web_page_preview: Option<bool>,
+    /// A new inline keyboard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_markup: Option<&'a InlineKeyboardMarkup>,
+}
+
+impl<'a> EditMessageTextRef<'a> {
+    /// Creates a new [`EditMessageTextRef`] request that edits the given message in the given chat with the given text.
+    pub fn new(chat_id: impl Into<ChatId>, message_id: i64, text: &'a str) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            message_id,
+            text,
+            parse_mode: None,
+            entities: None,
+            disable_web_page_preview: None,
+            reply_markup: None,
+        }
+    }
+    /// Creates a new [`EditMessageTextRef`] request that edits the given message with the given text.
+    pub fn from_message(message: &Message, text: &'a str) -> Self {
+        Self::new(message.chat.id, message.message_id, text)
+    }
+    /// Sets parse mode.
+    pub fn with_parse_mode(self, parse_mode: ParseMode) -> Self {
+        Self {
+            parse_mode: Some(parse_mode),
+            ..self
+        }
+    }
+    /// Sets entities.
+    pub fn with_entities(self, entities: &'a [MessageEntity]) -> Self {
+        Self {
+            entities: Some(entities),
+            ..self
+        }
+    }
+    /// Disables web preview.
+    pub fn disable_web_page_preview(self) -> Self {
+        Self {
+            disable_web_page_preview: Some(true),
+            ..self
+        }
+    }
+    /// Sets reply markup.
+    pub fn with_reply_markup(self, markup: &'a InlineKeyboardMarkup) -> Self {
+        Self {
+            reply_markup: Some(markup),
+            ..self
+        }
+    }
+}
 
-    fn name() -> &'static str {
-        "editMessageText"
+impl<'a> From<&'a EditMessageText> for EditMessageTextRef<'a> {
+    fn from(edit: &'a EditMessageText) -> Self {
+        Self {
+            chat_id: edit.chat_id.clone(),
+            message_id: edit.message_id,
+            text: &edit.text,
+            parse_mode: edit.parse_mode,
+            entities: edit.entities.as_deref(),
+            disable_web_page_preview: edit.disable_web_page_preview,
+            reply_markup: edit.reply_markup.as_ref(),
+        }
     }
 }
 
-impl JsonMethod for EditMessageText {}
+telegram_method!(EditMessageTextRef<'_> => "editMessageText", Message, json, chat_id);
 
 /// Edit text and [game](https://core.telegram.org/bots/api#games) messages.
 ///
@@ -3916,15 +4726,7 @@ impl EditInlineMessageText {
     }
 }
 
-impl TelegramMethod for EditInlineMessageText {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "editMessageText"
-    }
-}
-
-impl JsonMethod for EditInlineMessageText {}
+telegram_method!(EditInlineMessageText => "editMessageText", bool, json);
 
 /// Edits captions of messages.
 ///
@@ -3938,10 +4740,14 @@ pub struct EditMessageCaption {
     /// Identifier of the message to edit.
     pub message_id: i64,
     /// New caption of the message, 0-1024 characters after entities parsing.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub caption: Option<String>,
     /// For messages with a caption, special entities like usernames, URLs, bot commands, etc. that appear in the caption.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<MessageEntity>>,
+    /// Pass `true` if the caption must be shown above the message media.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_caption_above_media: Option<bool>,
     /// Mode for parsing entities in the message text.
     /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -3963,6 +4769,7 @@ impl EditMessageCaption {
             caption: None,
             parse_mode: None,
             caption_entities: None,
+            show_caption_above_media: None,
             disable_web_page_preview: None,
             reply_markup: None,
         }
@@ -3975,10 +4782,15 @@ impl EditMessageCaption {
             caption: Some(caption.into()),
             parse_mode: None,
             caption_entities: None,
+            show_caption_above_media: None,
             disable_web_page_preview: None,
             reply_markup: None,
         }
     }
+    /// Creates a new [`EditMessageCaption`] request that edits the given message with the given caption.
+    pub fn from_message(message: &Message, caption: impl Into<String>) -> Self {
+        Self::new(message.chat.id, message.message_id, caption)
+    }
     /// Sets parse mode.
     pub fn with_parse_mode(self, parse_mode: ParseMode) -> Self {
         Self {
@@ -3999,6 +4811,13 @@ impl EditMessageCaption {
         entities.push(entity);
         self
     }
+    /// Shows the caption above the media.
+    pub fn show_caption_above_media(self) -> Self {
+        Self {
+            show_caption_above_media: Some(true),
+            ..self
+        }
+    }
     /// Disables web preview.
     pub fn disable_web_page_preview(self) -> Self {
         Self {
@@ -4015,15 +4834,7 @@ impl EditMessageCaption {
     }
 }
 
-impl TelegramMethod for EditMessageCaption {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "editMessageCaption"
-    }
-}
-
-impl JsonMethod for EditMessageCaption {}
+telegram_method!(EditMessageCaption => "editMessageCaption", Message, json, chat_id);
 
 /// Edits captions of messages.
 ///
@@ -4040,6 +4851,9 @@ pub struct EditInlineMessageCaption {
     /// For messages with a caption, special entities like usernames, URLs, bot commands, etc. that appear in the caption.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<MessageEntity>>,
+    /// Pass `true` if the caption must be shown above the message media.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_caption_above_media: Option<bool>,
     /// Mode for parsing entities in the message text.
     /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -4060,6 +4874,7 @@ impl EditInlineMessageCaption {
             caption: None,
             parse_mode: None,
             caption_entities: None,
+            show_caption_above_media: None,
             disable_web_page_preview: None,
             reply_markup: None,
         }
@@ -4071,6 +4886,7 @@ impl EditInlineMessageCaption {
             caption: Some(caption.into()),
             parse_mode: None,
             caption_entities: None,
+            show_caption_above_media: None,
             disable_web_page_preview: None,
             reply_markup: None,
         }
@@ -4095,6 +4911,13 @@ impl EditInlineMessageCaption {
         entities.push(entity);
         self
     }
+    /// Shows the caption above the media.
+    pub fn show_caption_above_media(self) -> Self {
+        Self {
+            show_caption_above_media: Some(true),
+            ..self
+        }
+    }
     /// Disables web preview.
     pub fn disable_web_page_preview(self) -> Self {
         Self {
@@ -4111,15 +4934,7 @@ impl EditInlineMessageCaption {
     }
 }
 
-impl TelegramMethod for EditInlineMessageCaption {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "editMessageCaption"
-    }
-}
-
-impl JsonMethod for EditInlineMessageCaption {}
+telegram_method!(EditInlineMessageCaption => "editMessageCaption", bool, json);
 
 /// Edits animation, audio, document, photo, or video messages.
 ///
@@ -4128,6 +4943,8 @@ impl JsonMethod for EditInlineMessageCaption {}
 /// When an inline message is edited, a new file can't be uploaded;
 /// use a previously uploaded file via its file_id or specify a URL.
 ///
+/// The request is sent with `send_file`, which uploads new files of the media, if any.
+///
 /// On success, the edited [`Message`] is returned.
 #[derive(Clone, Serialize)]
 pub struct EditMessageMedia {
@@ -4136,6 +4953,9 @@ pub struct EditMessageMedia {
     /// Identifier of the message to edit.
     pub message_id: i64,
     /// A JSON-serialized object for a new media content of the message.
+    ///
+    /// New files are uploaded with the request by [`FileMethod`], attached as “attach://<file_attach_name>”.
+    #[serde(serialize_with = "serialize_attached_media")]
     pub media: InputMedia,
     /// A JSON-serialized object for a new [inline keyboard](https://core.telegram.org/bots#inline-keyboards-and-on-the-fly-updating).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -4152,6 +4972,10 @@ impl EditMessageMedia {
             reply_markup: None,
         }
     }
+    /// Creates a new [`EditMessageMedia`] request that edits the given message with the given media.
+    pub fn from_message(message: &Message, media: impl Into<InputMedia>) -> Self {
+        Self::new(message.chat.id, message.message_id, media)
+    }
     /// Sets reply markup.
     pub fn with_reply_markup(self, markup: impl Into<InlineKeyboardMarkup>) -> Self {
         Self {
@@ -4161,16 +4985,20 @@ impl EditMessageMedia {
     }
 }
 
-impl TelegramMethod for EditMessageMedia {
-    type Response = Message;
+telegram_method!(EditMessageMedia => "editMessageMedia", Message, chat_id);
 
-    fn name() -> &'static str {
-        "editMessageMedia"
+impl FileMethod for EditMessageMedia {
+    fn files(&self) -> Option<HashMap<&str, &InputFile>> {
+        let mut map = HashMap::new();
+        self.media.attach_files(0, &mut map);
+        if map.is_empty() {
+            None
+        } else {
+            Some(map)
+        }
     }
 }
 
-impl JsonMethod for EditMessageMedia {}
-
 /// Edits animation, audio, document, photo, or video messages.
 ///
 /// If a message is part of a message album, then it can be edited only to an audio for audio albums,
@@ -4178,6 +5006,8 @@ impl JsonMethod for EditMessageMedia {}
 /// When an inline message is edited, a new file can't be uploaded;
 /// use a previously uploaded file via its file_id or specify a URL.
 ///
+/// The request is sent with `send_file`, like [`EditMessageMedia`].
+///
 /// On success, `true` is returned.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#editmessagemedia)
@@ -4186,6 +5016,9 @@ pub struct EditInlineMessageMedia {
     /// Identifier of the inline message
     pub inline_message_id: String,
     /// A JSON-serialized object for a new media content of the message
+    ///
+    /// New files are uploaded with the request by [`FileMethod`], attached as “attach://<file_attach_name>”.
+    #[serde(serialize_with = "serialize_attached_media")]
     pub media: InputMedia,
     /// A JSON-serialized object for a new [inline keyboard](https://core.telegram.org/bots#inline-keyboards-and-on-the-fly-updating).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -4210,16 +5043,20 @@ impl EditInlineMessageMedia {
     }
 }
 
-impl TelegramMethod for EditInlineMessageMedia {
-    type Response = bool;
+telegram_method!(EditInlineMessageMedia => "editMessageMedia", bool);
 
-    fn name() -> &'static str {
-        "editMessageMedia"
+impl FileMethod for EditInlineMessageMedia {
+    fn files(&self) -> Option<HashMap<&str, &InputFile>> {
+        let mut map = HashMap::new();
+        self.media.attach_files(0, &mut map);
+        if map.is_empty() {
+            None
+        } else {
+            Some(map)
+        }
     }
 }
 
-impl JsonMethod for EditInlineMessageMedia {}
-
 /// Edits only the reply markup of messages.
 ///
 /// On success, the edited [`Message`] is returned.
@@ -4257,17 +5094,13 @@ impl EditMessageReplyMarkup {
             reply_markup: Some(reply_markup.into()),
         }
     }
-}
-
-impl TelegramMethod for EditMessageReplyMarkup {
-    type Response = Message;
-
-    fn name() -> &'static str {
-        "editMessageReplyMarkup"
+    /// Creates a new [`EditMessageReplyMarkup`] request that edits the given message with reply markup.
+    pub fn from_message(message: &Message, reply_markup: impl Into<InlineKeyboardMarkup>) -> Self {
+        Self::new(message.chat.id, message.message_id, reply_markup)
     }
 }
 
-impl JsonMethod for EditMessageReplyMarkup {}
+telegram_method!(EditMessageReplyMarkup => "editMessageReplyMarkup", Message, json, chat_id);
 
 /// Edits only the reply markup of messages.
 ///
@@ -4303,15 +5136,7 @@ impl EditInlineMessageReplyMarkup {
     }
 }
 
-impl TelegramMethod for EditInlineMessageReplyMarkup {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "editMessageReplyMarkup"
-    }
-}
-
-impl JsonMethod for EditInlineMessageReplyMarkup {}
+telegram_method!(EditInlineMessageReplyMarkup => "editMessageReplyMarkup", bool, json);
 
 /// Stops a poll which was sent by the bot.
 ///
@@ -4347,15 +5172,7 @@ impl StopPoll {
     }
 }
 
-impl TelegramMethod for StopPoll {
-    type Response = Poll;
-
-    fn name() -> &'static str {
-        "stopPoll"
-    }
-}
-
-impl JsonMethod for StopPoll {}
+telegram_method!(StopPoll => "stopPoll", Poll, json, chat_id);
 
 /// Deletes a message, including service messages, with limitations.
 ///
@@ -4388,12 +5205,4 @@ impl DeleteMessage {
     }
 }
 
-impl TelegramMethod for DeleteMessage {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "deleteMessage"
-    }
-}
-
-impl JsonMethod for DeleteMessage {}
+telegram_method!(DeleteMessage => "deleteMessage", bool, json, chat_id);
@@ -0,0 +1,188 @@
+//! Bookkeeping of messages sent by the bot, to delete them later.
+//!
+//! Notification bots often keep chats tidy by removing their own outdated messages,
+//! but Telegram provides no way to list the messages a bot has sent.
+//! [`MessageLog`] remembers the most recent messages sent to each chat,
+//! and creates [`DeleteMessage`] requests for the ones to clean up.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::message::{DeleteMessage, Message};
+
+/// A message sent by the bot, recorded in a [`MessageLog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SentMessage {
+    /// Identifier of the chat the message was sent to.
+    pub chat_id: i64,
+    /// Unique message identifier inside the chat.
+    pub message_id: i64,
+    /// Date the message was sent in Unix time.
+    pub date: u64,
+}
+
+impl SentMessage {
+    /// Creates a new [`SentMessage`] from the message returned by a send request.
+    pub fn from_message(message: &Message) -> Self {
+        Self {
+            chat_id: message.chat.id,
+            message_id: message.message_id,
+            date: message.date,
+        }
+    }
+
+    /// Creates a new [`DeleteMessage`] request which will delete this message.
+    pub fn delete(&self) -> DeleteMessage {
+        DeleteMessage::new(self.chat_id, self.message_id)
+    }
+}
+
+/// Storage of the messages recorded by a [`MessageLog`].
+///
+/// Implement this to keep the log in a database, so that messages can be cleaned up after the bot restarts.
+/// Messages of each chat are expected to be kept in the order they are pushed.
+pub trait MessageStore {
+    /// Appends the message to its chat, forgetting the oldest messages of the chat beyond `capacity`.
+    fn push(&mut self, message: SentMessage, capacity: usize);
+
+    /// Removes and returns the last `n` messages of the chat, newest first.
+    fn pop_last(&mut self, chat_id: i64, n: usize) -> Vec<SentMessage>;
+
+    /// Removes and returns the messages of every chat sent before `date` in Unix time.
+    fn drain_before(&mut self, date: u64) -> Vec<SentMessage>;
+}
+
+/// A [`MessageStore`] keeping a ring buffer for each chat in memory.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    chats: HashMap<i64, VecDeque<SentMessage>>,
+}
+
+impl MemoryStore {
+    /// Creates a new empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl MessageStore for MemoryStore {
+    fn push(&mut self, message: SentMessage, capacity: usize) {
+        let messages = self.chats.entry(message.chat_id).or_default();
+        messages.push_back(message);
+        while messages.len() > capacity {
+            messages.pop_front();
+        }
+    }
+
+    fn pop_last(&mut self, chat_id: i64, n: usize) -> Vec<SentMessage> {
+        let messages = match self.chats.get_mut(&chat_id) {
+            Some(messages) => messages,
+            None => return Vec::new(),
+        };
+        let popped = messages
+            .drain(messages.len().saturating_sub(n)..)
+            .rev()
+            .collect();
+        if messages.is_empty() {
+            self.chats.remove(&chat_id);
+        }
+        popped
+    }
+
+    fn drain_before(&mut self, date: u64) -> Vec<SentMessage> {
+        let mut drained = Vec::new();
+        self.chats.retain(|_, messages| {
+            drained.extend(messages.iter().filter(|message| message.date < date));
+            messages.retain(|message| message.date >= date);
+            !messages.is_empty()
+        });
+        drained
+    }
+}
+
+/// Records messages sent by the bot, to delete them later.
+///
+/// Only the last `capacity` messages of each chat are remembered.
+/// Note that bots can only delete messages sent less than 48 hours ago,
+/// so older messages should be cleaned up before then, or simply forgotten.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use telbot_types::message::Message;
+/// # use telbot_types::message_log::MessageLog;
+/// # let sent = |message_id: i64, date: u64| -> Message {
+/// #     serde_json::from_value(serde_json::json!({
+/// #         "message_id": message_id, "date": date,
+/// #         "chat": {"id": 1234, "type": "private", "first_name": "Kiwi"}, "text": "Alert"
+/// #     })).unwrap()
+/// # };
+/// let mut log = MessageLog::new(10);
+/// // for each message returned by a send request:
+/// log.record(&sent(1, 1000));
+/// log.record(&sent(2, 2000));
+/// log.record(&sent(3, 3000));
+///
+/// let requests = log.cleanup_last(1234, 1);
+/// assert_eq!(requests[0].message_id, 3);
+/// let requests = log.cleanup_older_than_at(Duration::from_secs(500), 2400);
+/// assert_eq!(requests.len(), 1);
+/// assert_eq!(requests[0].message_id, 1);
+/// assert_eq!(log.cleanup_last(1234, 10).len(), 1);
+/// ```
+pub struct MessageLog<S = MemoryStore> {
+    store: S,
+    capacity: usize,
+}
+
+impl MessageLog {
+    /// Creates a new log in memory which remembers the last `capacity` messages of each chat.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_store(MemoryStore::new(), capacity)
+    }
+}
+
+impl<S: MessageStore> MessageLog<S> {
+    /// Creates a new log in the store which remembers the last `capacity` messages of each chat.
+    pub fn with_store(store: S, capacity: usize) -> Self {
+        Self { store, capacity }
+    }
+
+    /// Records the message returned by a send request.
+    pub fn record(&mut self, message: &Message) {
+        self.record_sent(SentMessage::from_message(message))
+    }
+
+    /// Records a sent message.
+    pub fn record_sent(&mut self, message: SentMessage) {
+        self.store.push(message, self.capacity)
+    }
+
+    /// Forgets the last `n` messages sent to the chat,
+    /// returning [`DeleteMessage`] requests for them, newest first.
+    pub fn cleanup_last(&mut self, chat_id: i64, n: usize) -> Vec<DeleteMessage> {
+        let messages = self.store.pop_last(chat_id, n);
+        messages.iter().map(SentMessage::delete).collect()
+    }
+
+    /// Forgets the messages sent to any chat more than `age` ago,
+    /// returning [`DeleteMessage`] requests for them.
+    pub fn cleanup_older_than(&mut self, age: Duration) -> Vec<DeleteMessage> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        self.cleanup_older_than_at(age, now)
+    }
+
+    /// Forgets the messages sent to any chat more than `age` before `now` in Unix time,
+    /// returning [`DeleteMessage`] requests for them.
+    pub fn cleanup_older_than_at(&mut self, age: Duration, now: u64) -> Vec<DeleteMessage> {
+        let messages = self.store.drain_before(now.saturating_sub(age.as_secs()));
+        messages.iter().map(SentMessage::delete).collect()
+    }
+
+    /// Gets the store of the log.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+}
@@ -0,0 +1,190 @@
+//! Types and requests of [Telegram Passport](https://core.telegram.org/passport).
+//!
+//! Users share their personal documents with the bot in [`PassportData`], encrypted with the public key of the bot.
+//! The data can be decrypted with the `passport_decrypt` module, enabled by the `passport-decrypt` feature.
+//! Errors found in the documents are reported back to the user with [`SetPassportDataErrors`],
+//! enabled by the `passport` feature.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "passport")]
+mod requests;
+#[cfg(feature = "passport")]
+pub use requests::*;
+
+/// Telegram Passport data shared with the bot by the user.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#passportdata)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassportData {
+    /// Information about documents and other Telegram Passport elements that was shared with the bot.
+    pub data: Vec<EncryptedPassportElement>,
+    /// Encrypted credentials required to decrypt the data.
+    pub credentials: EncryptedCredentials,
+}
+
+/// A file uploaded to Telegram Passport.
+///
+/// Currently all Telegram Passport files are in JPEG format when decrypted and don't exceed 10MB.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#passportfile)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassportFile {
+    /// Identifier for this file, which can be used to download or reuse the file.
+    pub file_id: String,
+    /// Unique identifier for this file, which is supposed to be the same over time and for different bots.
+    /// Can't be used to download or reuse the file.
+    pub file_unique_id: String,
+    /// File size in bytes.
+    pub file_size: u64,
+    /// Unix time when the file was uploaded.
+    pub file_date: u64,
+}
+
+/// Type of a Telegram Passport element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PassportElementType {
+    /// Personal details.
+    PersonalDetails,
+    /// Passport.
+    Passport,
+    /// Driver license.
+    DriverLicense,
+    /// Identity card.
+    IdentityCard,
+    /// Internal passport.
+    InternalPassport,
+    /// Address.
+    Address,
+    /// Utility bill.
+    UtilityBill,
+    /// Bank statement.
+    BankStatement,
+    /// Rental agreement.
+    RentalAgreement,
+    /// Passport registration.
+    PassportRegistration,
+    /// Temporary registration.
+    TemporaryRegistration,
+    /// Phone number.
+    PhoneNumber,
+    /// Email address.
+    Email,
+}
+
+/// Information about documents or other Telegram Passport elements shared with the bot by the user.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#encryptedpassportelement)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedPassportElement {
+    /// Element type.
+    #[serde(rename = "type")]
+    pub kind: PassportElementType,
+    /// Base64-encoded encrypted Telegram Passport element data provided by the user,
+    /// available for `personal_details`, `passport`, `driver_license`, `identity_card`, `internal_passport` and `address` types.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// User's verified phone number, available only for `phone_number` type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    /// User's verified email address, available only for `email` type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Array of encrypted files with documents provided by the user,
+    /// available for `utility_bill`, `bank_statement`, `rental_agreement`, `passport_registration` and `temporary_registration` types.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<PassportFile>>,
+    /// Encrypted file with the front side of the document, provided by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub front_side: Option<PassportFile>,
+    /// Encrypted file with the reverse side of the document, provided by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse_side: Option<PassportFile>,
+    /// Encrypted file with the selfie of the user holding a document, provided by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selfie: Option<PassportFile>,
+    /// Array of encrypted files with translated versions of documents provided by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<Vec<PassportFile>>,
+    /// Base64-encoded element hash for using in [`PassportElementError::Unspecified`].
+    pub hash: String,
+}
+
+/// Data required for decrypting and authenticating [`EncryptedPassportElement`].
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#encryptedcredentials)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedCredentials {
+    /// Base64-encoded encrypted JSON-serialized data with unique user's payload,
+    /// data hashes and secrets required for [`EncryptedPassportElement`] decryption and authentication.
+    pub data: String,
+    /// Base64-encoded data hash for data authentication.
+    pub hash: String,
+    /// Base64-encoded secret, encrypted with the bot's public RSA key, required for data decryption.
+    pub secret: String,
+}
+
+/// Personal details of the user, decrypted from the `personal_details` element.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/passport#personaldetails)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonalDetails {
+    /// First Name.
+    pub first_name: String,
+    /// Last Name.
+    pub last_name: String,
+    /// Middle Name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub middle_name: Option<String>,
+    /// Date of birth in DD.MM.YYYY format.
+    pub birth_date: String,
+    /// Gender, `male` or `female`.
+    pub gender: String,
+    /// Citizenship (ISO 3166-1 alpha-2 country code).
+    pub country_code: String,
+    /// Country of residence (ISO 3166-1 alpha-2 country code).
+    pub residence_country_code: String,
+    /// First Name in the language of the user's country of residence.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name_native: Option<String>,
+    /// Last Name in the language of the user's country of residence.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name_native: Option<String>,
+    /// Middle Name in the language of the user's country of residence.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub middle_name_native: Option<String>,
+}
+
+/// Residential address of the user, decrypted from the `address` element.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/passport#residentialaddress)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResidentialAddress {
+    /// First line for the address.
+    pub street_line1: String,
+    /// Second line for the address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub street_line2: Option<String>,
+    /// City.
+    pub city: String,
+    /// State.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// ISO 3166-1 alpha-2 country code.
+    pub country_code: String,
+    /// Address post code.
+    pub post_code: String,
+}
+
+/// Data of an identity document, decrypted from the `passport`, `driver_license`, `identity_card` or `internal_passport` element.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/passport#iddocumentdata)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdDocumentData {
+    /// Document number.
+    pub document_no: String,
+    /// Date of expiry, in DD.MM.YYYY format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_date: Option<String>,
+}
@@ -0,0 +1,163 @@
+//! Requests of Telegram Passport, enabled by the `passport` feature.
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::telegram_method;
+
+/// An error in the Telegram Passport element which was submitted that should be resolved by the user.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#passportelementerror)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum PassportElementError {
+    /// An issue in one of the data fields that was provided by the user.
+    /// The error is considered resolved when the field's value changes.
+    Data {
+        /// The section of the user's Telegram Passport which has the error.
+        #[serde(rename = "type")]
+        kind: PassportElementType,
+        /// Name of the data field which has the error.
+        field_name: String,
+        /// Base64-encoded data hash.
+        data_hash: String,
+        /// Error message.
+        message: String,
+    },
+    /// An issue with the front side of a document.
+    /// The error is considered resolved when the file with the front side of the document changes.
+    FrontSide {
+        /// The section of the user's Telegram Passport which has the issue.
+        #[serde(rename = "type")]
+        kind: PassportElementType,
+        /// Base64-encoded hash of the file with the front side of the document.
+        file_hash: String,
+        /// Error message.
+        message: String,
+    },
+    /// An issue with the reverse side of a document.
+    /// The error is considered resolved when the file with reverse side of the document changes.
+    ReverseSide {
+        /// The section of the user's Telegram Passport which has the issue.
+        #[serde(rename = "type")]
+        kind: PassportElementType,
+        /// Base64-encoded hash of the file with the reverse side of the document.
+        file_hash: String,
+        /// Error message.
+        message: String,
+    },
+    /// An issue with the selfie with a document.
+    /// The error is considered resolved when the file with the selfie changes.
+    Selfie {
+        /// The section of the user's Telegram Passport which has the issue.
+        #[serde(rename = "type")]
+        kind: PassportElementType,
+        /// Base64-encoded hash of the file with the selfie.
+        file_hash: String,
+        /// Error message.
+        message: String,
+    },
+    /// An issue with a document scan.
+    /// The error is considered resolved when the file with the document scan changes.
+    File {
+        /// The section of the user's Telegram Passport which has the issue.
+        #[serde(rename = "type")]
+        kind: PassportElementType,
+        /// Base64-encoded file hash.
+        file_hash: String,
+        /// Error message.
+        message: String,
+    },
+    /// An issue with a list of scans.
+    /// The error is considered resolved when the list of files containing the scans changes.
+    Files {
+        /// The section of the user's Telegram Passport which has the issue.
+        #[serde(rename = "type")]
+        kind: PassportElementType,
+        /// List of base64-encoded file hashes.
+        file_hashes: Vec<String>,
+        /// Error message.
+        message: String,
+    },
+    /// An issue with one of the files that constitute the translation of a document.
+    /// The error is considered resolved when the file changes.
+    TranslationFile {
+        /// Type of element of the user's Telegram Passport which has the issue.
+        #[serde(rename = "type")]
+        kind: PassportElementType,
+        /// Base64-encoded file hash.
+        file_hash: String,
+        /// Error message.
+        message: String,
+    },
+    /// An issue with the translated version of a document.
+    /// The error is considered resolved when a file with the document translation changes.
+    TranslationFiles {
+        /// Type of element of the user's Telegram Passport which has the issue.
+        #[serde(rename = "type")]
+        kind: PassportElementType,
+        /// List of base64-encoded file hashes.
+        file_hashes: Vec<String>,
+        /// Error message.
+        message: String,
+    },
+    /// An issue in an unspecified place.
+    /// The error is considered resolved when new data is added.
+    Unspecified {
+        /// Type of element of the user's Telegram Passport which has the issue.
+        #[serde(rename = "type")]
+        kind: PassportElementType,
+        /// Base64-encoded element hash.
+        element_hash: String,
+        /// Error message.
+        message: String,
+    },
+}
+
+/// Informs a user that some of the Telegram Passport elements they provided contains errors.
+///
+/// The user will not be able to re-submit their Passport to you until the errors are fixed
+/// (the contents of the field for which you returned the error must change).
+/// Use this if the data submitted by the user doesn't satisfy the standards your service requires for any reason.
+///
+/// Returns `true` on success.
+///
+/// ```
+/// # use telbot_types::passport::{PassportElementError, PassportElementType, SetPassportDataErrors};
+/// let request = SetPassportDataErrors::new(1234).with_error(PassportElementError::Unspecified {
+///     kind: PassportElementType::Passport,
+///     element_hash: "aGFzaA==".to_string(),
+///     message: "The scan is blurry".to_string(),
+/// });
+/// assert_eq!(
+///     serde_json::to_value(&request).unwrap()["errors"][0]["source"],
+///     "unspecified"
+/// );
+/// ```
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#setpassportdataerrors)
+#[derive(Clone, Serialize)]
+pub struct SetPassportDataErrors {
+    /// User identifier.
+    pub user_id: i64,
+    /// A JSON-serialized array describing the errors.
+    pub errors: Vec<PassportElementError>,
+}
+
+impl SetPassportDataErrors {
+    /// Creates a new [`SetPassportDataErrors`] request which will report no error, until an error is added.
+    pub fn new(user_id: i64) -> Self {
+        Self {
+            user_id,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Adds an error to report.
+    pub fn with_error(mut self, error: PassportElementError) -> Self {
+        self.errors.push(error);
+        self
+    }
+}
+
+telegram_method!(SetPassportDataErrors => "setPassportDataErrors", bool, json);
@@ -0,0 +1,257 @@
+//! Decryption of [Telegram Passport](https://core.telegram.org/passport) data with the private key of the bot.
+//!
+//! The credentials are decrypted first with [`PassportKey::decrypt_credentials`],
+//! which give the secrets to decrypt each [`EncryptedPassportElement`] and the files of the element.
+//! See [Decrypting data](https://core.telegram.org/passport#decrypting-data) for details.
+//!
+//! ```no_run
+//! # use telbot_types::passport::{PassportData, PassportElementType, PersonalDetails};
+//! # use telbot_types::passport_decrypt::{PassportError, PassportKey};
+//! # fn run(passport_data: PassportData) -> Result<(), PassportError> {
+//! let key = PassportKey::from_pem(&std::fs::read("private.key").unwrap())?;
+//! let credentials = key.decrypt_credentials(&passport_data.credentials)?;
+//! // compare `credentials.nonce` with the nonce of the authorization request
+//! for element in &passport_data.data {
+//!     if element.kind == PassportElementType::PersonalDetails {
+//!         let secure_value = &credentials.secure_data[&element.kind];
+//!         let details: PersonalDetails = secure_value.data.as_ref().unwrap().decrypt(element)?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use openssl::error::ErrorStack;
+use openssl::pkey::Private;
+use openssl::rsa::{Padding, Rsa};
+use openssl::sha::{sha256, sha512};
+use openssl::symm::{Cipher, Crypter, Mode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::passport::{EncryptedCredentials, EncryptedPassportElement, PassportElementType};
+
+/// Error that can occur while decrypting Telegram Passport data.
+#[derive(Debug)]
+pub enum PassportError {
+    /// A field is not valid base64.
+    Base64(base64::DecodeError),
+    /// The key is malformed, or the data can't be decrypted with the key.
+    Openssl(ErrorStack),
+    /// The hash does not match the decrypted data, i.e. the data or the secret is corrupted.
+    HashMismatch,
+    /// The element has no data to decrypt.
+    MissingData,
+    /// The decrypted data is not the expected JSON.
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for PassportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Base64(error) => write!(f, "invalid base64: {}", error),
+            Self::Openssl(error) => write!(f, "decryption failed: {}", error),
+            Self::HashMismatch => write!(f, "hash mismatch"),
+            Self::MissingData => write!(f, "missing data"),
+            Self::Json(error) => write!(f, "invalid decrypted data: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for PassportError {}
+
+impl From<base64::DecodeError> for PassportError {
+    fn from(error: base64::DecodeError) -> Self {
+        Self::Base64(error)
+    }
+}
+
+impl From<ErrorStack> for PassportError {
+    fn from(error: ErrorStack) -> Self {
+        Self::Openssl(error)
+    }
+}
+
+impl From<serde_json::Error> for PassportError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+/// The private RSA key of the bot, whose public key is given to [BotFather](https://t.me/botfather).
+pub struct PassportKey {
+    rsa: Rsa<Private>,
+}
+
+impl PassportKey {
+    /// Loads the private key in PEM format.
+    pub fn from_pem(pem: &[u8]) -> Result<Self, PassportError> {
+        Ok(Self {
+            rsa: Rsa::private_key_from_pem(pem)?,
+        })
+    }
+
+    /// Decrypts the credentials, which contain the secrets to decrypt the elements.
+    ///
+    /// ```
+    /// # use base64::Engine;
+    /// # use openssl::rsa::{Padding, Rsa};
+    /// # use openssl::symm::{Cipher, Crypter, Mode};
+    /// # use telbot_types::passport::{EncryptedCredentials, PassportElementType};
+    /// # use telbot_types::passport_decrypt::PassportKey;
+    /// # let rsa = Rsa::generate(2048).unwrap();
+    /// # let pem = rsa.private_key_to_pem().unwrap();
+    /// # let base64 = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+    /// # let secret = [7u8; 32];
+    /// # let mut encrypted_secret = vec![0; rsa.size() as usize];
+    /// # let len = rsa.public_encrypt(&secret, &mut encrypted_secret, Padding::PKCS1_OAEP).unwrap();
+    /// # let json = r#"{"secure_data":{"personal_details":{"data":{"data_hash":"aGFzaA==","secret":"c2VjcmV0"}}},"nonce":"abc"}"#;
+    /// # let padding = 32 + (16 - (json.len() + 32) % 16) % 16;
+    /// # let mut padded = vec![padding as u8; padding];
+    /// # padded.extend_from_slice(json.as_bytes());
+    /// # let hash = openssl::sha::sha256(&padded);
+    /// # let key_iv = openssl::sha::sha512(&[&secret[..], &hash[..]].concat());
+    /// # let mut crypter = Crypter::new(Cipher::aes_256_cbc(), Mode::Encrypt, &key_iv[..32], Some(&key_iv[32..48])).unwrap();
+    /// # crypter.pad(false);
+    /// # let mut data = vec![0; padded.len() + 16];
+    /// # let count = crypter.update(&padded, &mut data).unwrap();
+    /// # data.truncate(count);
+    /// # let encrypted = EncryptedCredentials {
+    /// #     data: base64(&data),
+    /// #     hash: base64(&hash),
+    /// #     secret: base64(&encrypted_secret[..len]),
+    /// # };
+    /// let key = PassportKey::from_pem(&pem)?;
+    /// let credentials = key.decrypt_credentials(&encrypted)?;
+    /// assert_eq!(credentials.nonce, "abc");
+    /// assert!(credentials.secure_data[&PassportElementType::PersonalDetails].data.is_some());
+    /// # Ok::<(), telbot_types::passport_decrypt::PassportError>(())
+    /// ```
+    pub fn decrypt_credentials(
+        &self,
+        credentials: &EncryptedCredentials,
+    ) -> Result<Credentials, PassportError> {
+        let encrypted_secret = STANDARD.decode(&credentials.secret)?;
+        let mut secret = vec![0; self.rsa.size() as usize];
+        let len = self
+            .rsa
+            .private_decrypt(&encrypted_secret, &mut secret, Padding::PKCS1_OAEP)?;
+        secret.truncate(len);
+        let data = decrypt(
+            &STANDARD.decode(&credentials.data)?,
+            &secret,
+            &STANDARD.decode(&credentials.hash)?,
+        )?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+}
+
+/// Decrypted credentials, containing the secrets of the elements shared with the bot.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/passport#credentials)
+#[derive(Debug, Clone, Deserialize)]
+pub struct Credentials {
+    /// Credentials for the encrypted data of each element type.
+    pub secure_data: HashMap<PassportElementType, SecureValue>,
+    /// Bot-specified nonce, which must be checked to be the same as the one in the authorization request.
+    pub nonce: String,
+}
+
+/// Credentials for the encrypted data and files of an element.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/passport#securevalue)
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecureValue {
+    /// Credentials for encrypted Telegram Passport data.
+    pub data: Option<DataCredentials>,
+    /// Credentials for an encrypted document's front side.
+    pub front_side: Option<FileCredentials>,
+    /// Credentials for an encrypted document's reverse side.
+    pub reverse_side: Option<FileCredentials>,
+    /// Credentials for an encrypted selfie of the user with a document.
+    pub selfie: Option<FileCredentials>,
+    /// Credentials for an encrypted translation of the document, in the same order as the files of the element.
+    pub translation: Option<Vec<FileCredentials>>,
+    /// Credentials for encrypted files, in the same order as the files of the element.
+    pub files: Option<Vec<FileCredentials>>,
+}
+
+/// Secret to decrypt the `data` of an [`EncryptedPassportElement`].
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/passport#datacredentials)
+#[derive(Debug, Clone, Deserialize)]
+pub struct DataCredentials {
+    /// Checksum of encrypted data.
+    pub data_hash: String,
+    /// Secret of encrypted data.
+    pub secret: String,
+}
+
+impl DataCredentials {
+    /// Decrypts the data of the element,
+    /// such as [`PersonalDetails`](crate::passport::PersonalDetails) or [`IdDocumentData`](crate::passport::IdDocumentData).
+    pub fn decrypt<T: DeserializeOwned>(
+        &self,
+        element: &EncryptedPassportElement,
+    ) -> Result<T, PassportError> {
+        let data = element.data.as_ref().ok_or(PassportError::MissingData)?;
+        let data = decrypt(
+            &STANDARD.decode(data)?,
+            &STANDARD.decode(&self.secret)?,
+            &STANDARD.decode(&self.data_hash)?,
+        )?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+}
+
+/// Secret to decrypt a [`PassportFile`](crate::passport::PassportFile).
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/passport#filecredentials)
+#[derive(Debug, Clone, Deserialize)]
+pub struct FileCredentials {
+    /// Checksum of encrypted file.
+    pub file_hash: String,
+    /// Secret of encrypted file.
+    pub secret: String,
+}
+
+impl FileCredentials {
+    /// Decrypts the content of the file downloaded from Telegram, which is a JPEG image.
+    pub fn decrypt(&self, encrypted: &[u8]) -> Result<Vec<u8>, PassportError> {
+        decrypt(
+            encrypted,
+            &STANDARD.decode(&self.secret)?,
+            &STANDARD.decode(&self.file_hash)?,
+        )
+    }
+}
+
+/// Decrypts data encrypted with AES-256-CBC, whose key and IV are derived from the secret and the hash.
+fn decrypt(data: &[u8], secret: &[u8], hash: &[u8]) -> Result<Vec<u8>, PassportError> {
+    let key_iv = sha512(&[secret, hash].concat());
+    let mut crypter = Crypter::new(
+        Cipher::aes_256_cbc(),
+        Mode::Decrypt,
+        &key_iv[..32],
+        Some(&key_iv[32..48]),
+    )?;
+    crypter.pad(false);
+    let mut decrypted = vec![0; data.len() + Cipher::aes_256_cbc().block_size()];
+    let mut count = crypter.update(data, &mut decrypted)?;
+    count += crypter.finalize(&mut decrypted[count..])?;
+    decrypted.truncate(count);
+
+    if sha256(&decrypted)[..] != *hash {
+        return Err(PassportError::HashMismatch);
+    }
+    // The data is prepended with 32-255 random padding bytes, the first of which is the length of the padding.
+    let padding = *decrypted.first().ok_or(PassportError::HashMismatch)? as usize;
+    if padding < 32 || padding > decrypted.len() {
+        return Err(PassportError::HashMismatch);
+    }
+    Ok(decrypted.split_off(padding))
+}
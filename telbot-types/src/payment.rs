@@ -2,10 +2,15 @@ use serde::{Deserialize, Serialize};
 
 use crate::user::User;
 
+#[cfg(feature = "payments")]
+mod requests;
+#[cfg(feature = "payments")]
+pub use requests::*;
+
 /// Information about an incoming shipping query.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#shippingquery)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShippingQuery {
     /// Unique query identifier.
     pub id: String,
@@ -20,7 +25,7 @@ pub struct ShippingQuery {
 /// Information about an incoming pre-checkout query.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#precheckoutquery)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreCheckoutQuery {
     /// Unique query identifier.
     pub id: String,
@@ -36,15 +41,17 @@ pub struct PreCheckoutQuery {
     /// Bot specified invoice payload.
     pub invoice_payload: String,
     /// Identifier of the shipping option chosen by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shipping_option_id: Option<String>,
     /// Order info provided by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub order_info: Option<OrderInfo>,
 }
 
 /// Basic information about an invoice.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#invoice)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Invoice {
     /// Product name.
     pub title: String,
@@ -64,7 +71,7 @@ pub struct Invoice {
 /// A shipping address.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#shippingaddress)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShippingAddress {
     /// ISO 3166-1 alpha-2 country code.
     pub country_code: String,
@@ -80,10 +87,87 @@ pub struct ShippingAddress {
     pub post_code: String,
 }
 
+impl ShippingAddress {
+    /// Checks that the address is well-formed.
+    ///
+    /// The country code must be an assigned ISO 3166-1 alpha-2 code,
+    /// and the post code, if any, must consist of 2-10 letters, digits, spaces or hyphens.
+    /// Whether the post code actually exists is not checked.
+    pub fn validate(&self) -> Result<(), ShippingAddressError> {
+        if !COUNTRY_CODES.contains(&self.country_code.as_str()) {
+            return Err(ShippingAddressError::InvalidCountryCode);
+        }
+        if self.city.trim().is_empty() {
+            return Err(ShippingAddressError::MissingCity);
+        }
+        if self.street_line1.trim().is_empty() {
+            return Err(ShippingAddressError::MissingStreet);
+        }
+        let post_code = self.post_code.trim();
+        let post_code_chars = post_code.chars().count();
+        if !post_code.is_empty()
+            && (!(2..=10).contains(&post_code_chars)
+                || !post_code
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-')
+                || !post_code.chars().any(|c| c.is_ascii_alphanumeric()))
+        {
+            return Err(ShippingAddressError::InvalidPostCode);
+        }
+        Ok(())
+    }
+}
+
+/// Error that can occur while validating [`ShippingAddress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShippingAddressError {
+    /// The country code is not an assigned ISO 3166-1 alpha-2 code.
+    InvalidCountryCode,
+    /// The city is empty.
+    MissingCity,
+    /// The first line of the address is empty.
+    MissingStreet,
+    /// The post code is malformed.
+    InvalidPostCode,
+}
+
+impl std::fmt::Display for ShippingAddressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCountryCode => write!(f, "invalid country code"),
+            Self::MissingCity => write!(f, "city is missing"),
+            Self::MissingStreet => write!(f, "street address is missing"),
+            Self::InvalidPostCode => write!(f, "invalid post code"),
+        }
+    }
+}
+
+impl std::error::Error for ShippingAddressError {}
+
+/// Officially assigned ISO 3166-1 alpha-2 country codes.
+const COUNTRY_CODES: [&str; 249] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
 /// Basic information about a successful payment.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#successfulpayment)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SuccessfulPayment {
     /// Three-letter ISO 4217 [currency](https://core.telegram.org/bots/payments#supported-currencies) code.
     pub currency: String,
@@ -95,8 +179,10 @@ pub struct SuccessfulPayment {
     /// Bot specified invoice payload.
     pub invoice_payload: String,
     /// Identifier of the shipping option chosen by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shipping_option_id: Option<String>,
     /// Order info provided by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub order_info: Option<OrderInfo>,
     /// Telegram payment identifier.
     pub telegram_payment_charge_id: String,
@@ -107,31 +193,22 @@ pub struct SuccessfulPayment {
 /// Information about an order.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#orderinfo)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderInfo {
     /// User name.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// User's phone number.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub phone_number: Option<String>,
     /// User email.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
     /// User shipping address.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shipping_address: Option<ShippingAddress>,
 }
 
-/// One shipping option.
-/// 
-/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#shippingoption)
-#[derive(Debug, Deserialize)]
-pub struct ShippingOption {
-    /// Shipping option identifier.
-    pub id: String,
-    /// Option title.
-    pub title: String,
-    /// List of price portions.
-    pub prices: Vec<LabeledPrice>,
-}
-
 /// A portion of the price for goods or services.
 /// 
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#labeledprice)
@@ -145,3 +222,23 @@ pub struct LabeledPrice {
     /// it shows the number of digits past the decimal point for each currency (2 for the majority of currencies).
     amount: i32,
 }
+
+impl LabeledPrice {
+    /// Creates a new price portion.
+    pub fn new(label: impl Into<String>, amount: i32) -> Self {
+        Self {
+            label: label.into(),
+            amount,
+        }
+    }
+
+    /// Gets the portion label.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets the price in the smallest units of the currency.
+    pub fn amount(&self) -> i32 {
+        self.amount
+    }
+}
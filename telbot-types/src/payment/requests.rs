@@ -0,0 +1,568 @@
+//! Requests of payments and Telegram Stars, enabled by the `payments` feature.
+
+use super::*;
+use crate::{chat::ChatId, markup::InlineKeyboardMarkup, message::Message, telegram_method};
+
+impl ShippingQuery {
+    /// Creates a new [`AnswerShippingQuery`] request which will answer this query with the given shipping options.
+    pub fn answer_with_options(
+        &self,
+        shipping_options: Vec<ShippingOption>,
+    ) -> AnswerShippingQuery {
+        AnswerShippingQuery::new_ok(&self.id, shipping_options)
+    }
+
+    /// Creates a new [`AnswerShippingQuery`] request which will tell the user that delivery to the specified address is not possible.
+    pub fn answer_error(&self, error_message: impl Into<String>) -> AnswerShippingQuery {
+        AnswerShippingQuery::new_error(&self.id, error_message)
+    }
+}
+
+impl PreCheckoutQuery {
+    /// Creates a new [`AnswerPreCheckoutQuery`] request which will confirm that the bot is ready to proceed with the order.
+    pub fn answer_ok(&self) -> AnswerPreCheckoutQuery {
+        AnswerPreCheckoutQuery::new_ok(&self.id)
+    }
+
+    /// Creates a new [`AnswerPreCheckoutQuery`] request which will tell the user why the checkout cannot proceed.
+    pub fn answer_error(&self, error_message: impl Into<String>) -> AnswerPreCheckoutQuery {
+        AnswerPreCheckoutQuery::new_error(&self.id, error_message)
+    }
+}
+
+/// One shipping option.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#shippingoption)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShippingOption {
+    /// Shipping option identifier.
+    pub id: String,
+    /// Option title.
+    pub title: String,
+    /// List of price portions.
+    pub prices: Vec<LabeledPrice>,
+}
+
+impl ShippingOption {
+    /// Creates a new shipping option without prices.
+    pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            prices: vec![],
+        }
+    }
+
+    /// Adds a price portion.
+    pub fn with_price(mut self, label: impl Into<String>, amount: i32) -> Self {
+        self.prices.push(LabeledPrice::new(label, amount));
+        self
+    }
+}
+
+/// Replies to a shipping query
+/// sent when an invoice with flexible price is requested and the user has entered a shipping address.
+///
+/// On success, `true` is returned.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#answershippingquery)
+#[derive(Clone, Serialize)]
+pub struct AnswerShippingQuery {
+    /// Unique identifier for the query to be answered.
+    pub shipping_query_id: String,
+    /// Specify `true` if delivery to the specified address is possible and `false` if there are any problems.
+    pub ok: bool,
+    /// Required if *ok* is `true`. A JSON-serialized array of available shipping options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping_options: Option<Vec<ShippingOption>>,
+    /// Required if *ok* is `false`.
+    /// Error message in human readable form that explains why it is impossible to complete the order
+    /// (e.g. "Sorry, delivery to your desired address is unavailable").
+    /// Telegram will display this message to the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+impl AnswerShippingQuery {
+    /// Creates a new [`AnswerShippingQuery`] request which will offer the given shipping options.
+    pub fn new_ok(
+        shipping_query_id: impl Into<String>,
+        shipping_options: Vec<ShippingOption>,
+    ) -> Self {
+        Self {
+            shipping_query_id: shipping_query_id.into(),
+            ok: true,
+            shipping_options: Some(shipping_options),
+            error_message: None,
+        }
+    }
+
+    /// Creates a new [`AnswerShippingQuery`] request which will tell that delivery is not possible.
+    pub fn new_error(
+        shipping_query_id: impl Into<String>,
+        error_message: impl Into<String>,
+    ) -> Self {
+        Self {
+            shipping_query_id: shipping_query_id.into(),
+            ok: false,
+            shipping_options: None,
+            error_message: Some(error_message.into()),
+        }
+    }
+}
+
+telegram_method!(AnswerShippingQuery => "answerShippingQuery", bool, json);
+
+/// Responds to a pre-checkout query, the final confirmation before the user is charged.
+///
+/// The bot must reply within 10 seconds after the query was sent.
+///
+/// On success, `true` is returned.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#answerprecheckoutquery)
+#[derive(Clone, Serialize)]
+pub struct AnswerPreCheckoutQuery {
+    /// Unique identifier for the query to be answered.
+    pub pre_checkout_query_id: String,
+    /// Specify `true` if everything is alright (goods are available, etc.) and the bot is ready to proceed with the order.
+    pub ok: bool,
+    /// Required if *ok* is `false`.
+    /// Error message in human readable form that explains the reason for failure to proceed with the checkout
+    /// (e.g. "Sorry, somebody just bought the last of our amazing black T-shirts while you were busy filling out your payment details.
+    /// Please choose a different color or garment!").
+    /// Telegram will display this message to the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+impl AnswerPreCheckoutQuery {
+    /// Creates a new [`AnswerPreCheckoutQuery`] request which will proceed with the order.
+    pub fn new_ok(pre_checkout_query_id: impl Into<String>) -> Self {
+        Self {
+            pre_checkout_query_id: pre_checkout_query_id.into(),
+            ok: true,
+            error_message: None,
+        }
+    }
+
+    /// Creates a new [`AnswerPreCheckoutQuery`] request which will cancel the checkout.
+    pub fn new_error(
+        pre_checkout_query_id: impl Into<String>,
+        error_message: impl Into<String>,
+    ) -> Self {
+        Self {
+            pre_checkout_query_id: pre_checkout_query_id.into(),
+            ok: false,
+            error_message: Some(error_message.into()),
+        }
+    }
+}
+
+telegram_method!(AnswerPreCheckoutQuery => "answerPreCheckoutQuery", bool, json);
+
+/// Sends an invoice.
+///
+/// On success, the sent [`Message`] is returned.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#sendinvoice)
+#[derive(Clone, Serialize)]
+pub struct SendInvoice {
+    /// Unique identifier for the target chat or username of the target channel. (in the format `@channelusername`)
+    pub chat_id: ChatId,
+    /// Unique identifier for the target message thread (topic) of the forum; for forum supergroups only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
+    /// Product name, 1-32 characters.
+    pub title: String,
+    /// Product description, 1-255 characters.
+    pub description: String,
+    /// Bot-defined invoice payload, 1-128 bytes.
+    /// This will not be displayed to the user, use for your internal processes.
+    pub payload: String,
+    /// Payment provider token, obtained via [@BotFather](https://t.me/botfather).
+    /// Pass an empty string for payments in [Telegram Stars](https://t.me/BotNews/90).
+    pub provider_token: String,
+    /// Three-letter ISO 4217 currency code, see [more on currencies](https://core.telegram.org/bots/payments#supported-currencies).
+    pub currency: String,
+    /// Price breakdown. (e.g. product price, tax, discount, delivery cost, delivery tax, bonus, etc.)
+    pub prices: Vec<LabeledPrice>,
+    /// The maximum accepted amount for tips in the smallest units of the currency.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tip_amount: Option<i32>,
+    /// Suggested amounts of tips in the smallest units of the currency, at most 4.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_tip_amounts: Option<Vec<i32>>,
+    /// Unique deep-linking parameter.
+    /// If left empty, forwarded copies of the sent message will have a *Pay* button.
+    /// If non-empty, forwarded copies will have a *URL* button with a deep link to the bot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_parameter: Option<String>,
+    /// URL of the product photo for the invoice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_url: Option<String>,
+    /// Pass `true`, if you require the user's full name to complete the order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub need_name: Option<bool>,
+    /// Pass `true`, if you require the user's email address to complete the order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub need_email: Option<bool>,
+    /// Pass `true`, if you require the user's shipping address to complete the order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub need_shipping_address: Option<bool>,
+    /// Pass `true`, if the final price depends on the shipping method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_flexible: Option<bool>,
+    /// Sends the message [silently](https://telegram.org/blog/channels-2-0#silent-messages).
+    /// Users will receive a notification with no sound.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_notification: Option<bool>,
+    /// Protects the contents of the sent message from forwarding and saving.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protect_content: Option<bool>,
+    /// If the message is a reply, ID of the original message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to_message_id: Option<i64>,
+    /// An [inline keyboard](https://core.telegram.org/bots#inline-keyboards-and-on-the-fly-updating).
+    /// If empty, one 'Pay `total price`' button will be shown.
+    /// If not empty, the first button must be a Pay button.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+}
+
+impl SendInvoice {
+    /// Creates a new [`SendInvoice`] request that sends an invoice on the given chat.
+    pub fn new(
+        chat_id: impl Into<ChatId>,
+        title: impl Into<String>,
+        description: impl Into<String>,
+        payload: impl Into<String>,
+        provider_token: impl Into<String>,
+        currency: impl Into<String>,
+        prices: Vec<LabeledPrice>,
+    ) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            message_thread_id: None,
+            title: title.into(),
+            description: description.into(),
+            payload: payload.into(),
+            provider_token: provider_token.into(),
+            currency: currency.into(),
+            prices,
+            max_tip_amount: None,
+            suggested_tip_amounts: None,
+            start_parameter: None,
+            photo_url: None,
+            need_name: None,
+            need_email: None,
+            need_shipping_address: None,
+            is_flexible: None,
+            disable_notification: None,
+            protect_content: None,
+            reply_to_message_id: None,
+            reply_markup: None,
+        }
+    }
+    /// Sets the maximum accepted amount for tips and the suggested amounts.
+    pub fn with_tips(self, max_tip_amount: i32, suggested_tip_amounts: Vec<i32>) -> Self {
+        Self {
+            max_tip_amount: Some(max_tip_amount),
+            suggested_tip_amounts: Some(suggested_tip_amounts),
+            ..self
+        }
+    }
+    /// Sets the deep-linking parameter.
+    pub fn with_start_parameter(self, start_parameter: impl Into<String>) -> Self {
+        Self {
+            start_parameter: Some(start_parameter.into()),
+            ..self
+        }
+    }
+    /// Sets the product photo.
+    pub fn with_photo_url(self, photo_url: impl Into<String>) -> Self {
+        Self {
+            photo_url: Some(photo_url.into()),
+            ..self
+        }
+    }
+    /// Requires the user's full name.
+    pub fn need_name(self) -> Self {
+        Self {
+            need_name: Some(true),
+            ..self
+        }
+    }
+    /// Requires the user's email address.
+    pub fn need_email(self) -> Self {
+        Self {
+            need_email: Some(true),
+            ..self
+        }
+    }
+    /// Requires the user's shipping address.
+    pub fn need_shipping_address(self) -> Self {
+        Self {
+            need_shipping_address: Some(true),
+            ..self
+        }
+    }
+    /// Makes the final price depend on the shipping method.
+    pub fn flexible(self) -> Self {
+        Self {
+            is_flexible: Some(true),
+            ..self
+        }
+    }
+    /// Disables notification.
+    pub fn disable_notification(self) -> Self {
+        Self {
+            disable_notification: Some(true),
+            ..self
+        }
+    }
+    /// Protects content from forwarding and saving.
+    pub fn protect_content(self) -> Self {
+        Self {
+            protect_content: Some(true),
+            ..self
+        }
+    }
+    /// Replys to message.
+    pub fn reply_to(self, message_id: i64) -> Self {
+        Self {
+            reply_to_message_id: Some(message_id),
+            ..self
+        }
+    }
+    /// Sets reply markup.
+    pub fn with_reply_markup(self, markup: impl Into<InlineKeyboardMarkup>) -> Self {
+        Self {
+            reply_markup: Some(markup.into()),
+            ..self
+        }
+    }
+    /// Sends to the forum topic.
+    pub fn with_message_thread_id(self, message_thread_id: i64) -> Self {
+        Self {
+            message_thread_id: Some(message_thread_id),
+            ..self
+        }
+    }
+}
+
+telegram_method!(SendInvoice => "sendInvoice", Message, json, chat_id);
+
+/// An amount of Telegram Stars.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#staramount)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct StarAmount {
+    /// Integer amount of Telegram Stars, rounded to 0; can be negative.
+    pub amount: i64,
+    /// The number of 1/1000000000 shares of Telegram Stars; from -999999999 to 999999999;
+    /// can be negative if and only if *amount* is non-positive.
+    #[serde(default)]
+    pub nanostar_amount: i32,
+}
+
+/// A Telegram Star transaction.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#startransaction)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarTransaction {
+    /// Unique identifier of the transaction.
+    /// Coincides with the identifier of the original transaction for refund transactions.
+    /// Coincides with *telegram_payment_charge_id* of [`SuccessfulPayment`] for successful incoming payments from users.
+    pub id: String,
+    /// Integer amount of Telegram Stars transferred by the transaction.
+    pub amount: i64,
+    /// The number of 1/1000000000 shares of Telegram Stars transferred by the transaction; from 0 to 999999999.
+    #[serde(default)]
+    pub nanostar_amount: i32,
+    /// Date the transaction was created in Unix time.
+    pub date: u64,
+    /// Source of an incoming transaction (e.g., a user purchasing goods or services, Fragment refunding a failed withdrawal).
+    /// Only for incoming transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<TransactionPartner>,
+    /// Receiver of an outgoing transaction (e.g., a user for a purchase refund, Fragment for a withdrawal).
+    /// Only for outgoing transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receiver: Option<TransactionPartner>,
+}
+
+impl StarTransaction {
+    /// `true` if the bot received Telegram Stars by the transaction.
+    pub fn is_incoming(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// `true` if the bot paid Telegram Stars by the transaction, e.g. for a refund or a withdrawal.
+    pub fn is_outgoing(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Gets the partner of the transaction, either the source or the receiver.
+    pub fn partner(&self) -> Option<&TransactionPartner> {
+        self.source.as_ref().or(self.receiver.as_ref())
+    }
+}
+
+/// A list of Telegram Star transactions.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#startransactions)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarTransactions {
+    /// The list of transactions.
+    pub transactions: Vec<StarTransaction>,
+}
+
+/// The source of an incoming transaction or the receiver of an outgoing transaction.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#transactionpartner)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransactionPartner {
+    /// A transaction with a user.
+    User {
+        /// Information about the user.
+        user: User,
+        /// Bot-specified invoice payload.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        invoice_payload: Option<String>,
+    },
+    /// A withdrawal transaction with Fragment.
+    Fragment {
+        /// State of the transaction if the transaction is outgoing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        withdrawal_state: Option<RevenueWithdrawalState>,
+    },
+    /// A withdrawal transaction to the Telegram Ads platform.
+    TelegramAds,
+    /// A transaction with payment for [paid broadcasting](https://core.telegram.org/bots/api#paid-broadcasts).
+    TelegramApi {
+        /// The number of successful requests that exceeded regular limits and were therefore billed.
+        request_count: u32,
+    },
+    /// A transaction with an unknown source or recipient.
+    #[serde(other)]
+    Other,
+}
+
+impl TransactionPartner {
+    /// Gets the user of the transaction, if any.
+    pub fn user(&self) -> Option<&User> {
+        match self {
+            Self::User { user, .. } => Some(user),
+            _ => None,
+        }
+    }
+
+    /// Gets the invoice payload of the transaction with a user, if any.
+    pub fn invoice_payload(&self) -> Option<&str> {
+        match self {
+            Self::User {
+                invoice_payload, ..
+            } => invoice_payload.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The state of a revenue withdrawal operation.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#revenuewithdrawalstate)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RevenueWithdrawalState {
+    /// The withdrawal is in progress.
+    Pending,
+    /// The withdrawal succeeded.
+    Succeeded {
+        /// Date the withdrawal was completed in Unix time.
+        date: u64,
+        /// An HTTPS URL that can be used to see transaction details.
+        url: String,
+    },
+    /// The withdrawal failed and the transaction was refunded.
+    Failed,
+}
+
+/// Gets the current Telegram Stars balance of the bot.
+///
+/// Returns a [`StarAmount`] object on success.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#getmystarbalance)
+#[derive(Clone, Serialize)]
+pub struct GetMyStarBalance;
+
+telegram_method!(GetMyStarBalance => "getMyStarBalance", StarAmount, json);
+
+/// Returns the bot's Telegram Star transactions in chronological order.
+///
+/// On success, returns a [`StarTransactions`] object.
+///
+/// ```
+/// # use telbot_types::payment::{GetStarTransactions, StarTransactions};
+/// # fn send(_: &GetStarTransactions) -> StarTransactions {
+/// #     StarTransactions { transactions: vec![] }
+/// # }
+/// let mut request = Some(GetStarTransactions::new());
+/// while let Some(current) = request {
+///     let page = send(&current); // api.send_json(&current)
+///     // reconcile page.transactions
+///     request = current.next_page(&page);
+/// }
+/// ```
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#getstartransactions)
+#[derive(Clone, Default, Serialize)]
+pub struct GetStarTransactions {
+    /// Number of transactions to skip in the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    /// The maximum number of transactions to be retrieved.
+    /// Values between 1-100 are accepted. Defaults to 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl GetStarTransactions {
+    /// Creates a new [`GetStarTransactions`] request which gets the first 100 transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of transactions to skip.
+    pub fn with_offset(self, offset: u32) -> Self {
+        Self {
+            offset: Some(offset),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of transactions to be retrieved.
+    pub fn with_limit(self, limit: u32) -> Self {
+        Self {
+            limit: Some(limit),
+            ..self
+        }
+    }
+
+    /// Creates a request for the page following `page`, the response of this request.
+    ///
+    /// Returns `None` if `page` is the last page.
+    pub fn next_page(&self, page: &StarTransactions) -> Option<Self> {
+        let limit = self.limit.unwrap_or(100);
+        let received = page.transactions.len() as u32;
+        if received < limit || received == 0 {
+            return None;
+        }
+        Some(Self {
+            offset: Some(self.offset.unwrap_or(0) + received),
+            limit: self.limit,
+        })
+    }
+}
+
+telegram_method!(GetStarTransactions => "getStarTransactions", StarTransactions, json);
@@ -0,0 +1,168 @@
+//! Tracking of pinned messages.
+//!
+//! Telegram only exposes the most recent pinned message of a chat through [`GetChat`](crate::chat::GetChat),
+//! and [`UnpinAllChatMessages`](crate::chat::UnpinAllChatMessages) removes every pin at once.
+//! [`PinTracker`] remembers pinned messages from `pinned_message` service messages and chat information,
+//! so that moderation bots can unpin only the messages they want, e.g. the ones posted by a specific user.
+
+use std::collections::HashMap;
+
+use crate::chat::{Chat, UnpinChatMessage};
+use crate::message::Message;
+use crate::update::Update;
+
+/// A message known to be pinned in a chat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedMessage {
+    /// Unique message identifier inside the chat.
+    pub message_id: i64,
+    /// Date the message was sent in Unix time.
+    pub date: u64,
+    /// Identifier of the user who sent the message, if any.
+    pub from_id: Option<i64>,
+    /// Identifier of the chat on behalf of which the message was sent, if any.
+    pub sender_chat_id: Option<i64>,
+    /// Identifier of the user who pinned the message, if known.
+    pub pinned_by: Option<i64>,
+}
+
+impl PinnedMessage {
+    /// Creates a new [`PinnedMessage`] from the pinned message, without knowing who pinned it.
+    pub fn from_message(message: &Message) -> Self {
+        Self {
+            message_id: message.message_id,
+            date: message.date,
+            from_id: message.from.as_ref().map(|user| user.id),
+            sender_chat_id: message.sender_chat.as_ref().map(|chat| chat.id),
+            pinned_by: None,
+        }
+    }
+
+    /// Gets the identifier of the author of the message, which is the sender chat for anonymous messages.
+    pub fn author_id(&self) -> Option<i64> {
+        self.sender_chat_id.or(self.from_id)
+    }
+}
+
+/// Tracks pinned messages of chats.
+///
+/// Only the messages pinned while the bot is receiving updates, and the most recent pinned message
+/// reported by [`GetChat`](crate::chat::GetChat), are known to the tracker.
+///
+/// ```
+/// # use telbot_types::message::Message;
+/// # use telbot_types::pin_tracker::PinTracker;
+/// # let service: Message = serde_json::from_str(r#"{
+/// #     "message_id": 11, "date": 1, "chat": {"id": -100, "type": "supergroup", "title": "Kiwi"},
+/// #     "from": {"id": 1, "is_bot": false, "first_name": "Admin"},
+/// #     "pinned_message": {
+/// #         "message_id": 10, "date": 0, "chat": {"id": -100, "type": "supergroup", "title": "Kiwi"},
+/// #         "from": {"id": 5678, "is_bot": false, "first_name": "Spammer"}, "text": "buy now"
+/// #     }
+/// # }"#).unwrap();
+/// let mut tracker = PinTracker::new();
+/// tracker.record_message(&service);
+/// let requests = tracker.unpin_by_author(-100, 5678);
+/// assert_eq!(requests.len(), 1);
+/// assert_eq!(requests[0].message_id, Some(10));
+/// ```
+#[derive(Debug, Default)]
+pub struct PinTracker {
+    chats: HashMap<i64, Vec<PinnedMessage>>,
+}
+
+impl PinTracker {
+    /// Creates a new empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remembers the message as pinned in the chat.
+    ///
+    /// If the message is already tracked, the identifier of the user who pinned it is kept unless a new one is given.
+    pub fn record_pinned(&mut self, chat_id: i64, pinned: PinnedMessage) {
+        let messages = self.chats.entry(chat_id).or_default();
+        match messages
+            .iter_mut()
+            .find(|message| message.message_id == pinned.message_id)
+        {
+            Some(message) => {
+                let pinned_by = pinned.pinned_by.or(message.pinned_by);
+                *message = PinnedMessage {
+                    pinned_by,
+                    ..pinned
+                };
+            }
+            None => {
+                messages.push(pinned);
+                messages.sort_by_key(|message| (message.date, message.message_id));
+            }
+        }
+    }
+
+    /// Remembers the message pinned by a `pinned_message` service message, if it is one.
+    pub fn record_message(&mut self, message: &Message) {
+        if let Some(pinned) = message.kind.pinned_message() {
+            let pinned_by = message.from.as_ref().map(|user| user.id);
+            self.record_pinned(
+                message.chat.id,
+                PinnedMessage {
+                    pinned_by,
+                    ..PinnedMessage::from_message(pinned)
+                },
+            );
+        }
+    }
+
+    /// Remembers the message pinned by a `pinned_message` service message in an update, if any.
+    pub fn record_update(&mut self, update: &Update) {
+        if let Some(message) = update.kind.any_message() {
+            self.record_message(message);
+        }
+    }
+
+    /// Remembers the most recent pinned message of the chat, as returned by [`GetChat`](crate::chat::GetChat).
+    pub fn record_chat(&mut self, chat: &Chat) {
+        if let Some(pinned) = &chat.pinned_message {
+            self.record_pinned(chat.id, PinnedMessage::from_message(pinned));
+        }
+    }
+
+    /// Forgets the message, e.g. after it was unpinned.
+    pub fn record_unpinned(&mut self, chat_id: i64, message_id: i64) {
+        if let Some(messages) = self.chats.get_mut(&chat_id) {
+            messages.retain(|message| message.message_id != message_id);
+        }
+    }
+
+    /// Forgets all pinned messages of the chat, e.g. after [`UnpinAllChatMessages`](crate::chat::UnpinAllChatMessages).
+    pub fn record_unpinned_all(&mut self, chat_id: i64) {
+        self.chats.remove(&chat_id);
+    }
+
+    /// Gets the known pinned messages of the chat, from the oldest to the most recent.
+    pub fn pinned(&self, chat_id: i64) -> &[PinnedMessage] {
+        self.chats.get(&chat_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Creates [`UnpinChatMessage`] requests for the known pinned messages of the chat matching the predicate.
+    ///
+    /// Messages are not forgotten until [`PinTracker::record_unpinned`] is called,
+    /// so that a failed request can be retried.
+    pub fn unpin_where(
+        &self,
+        chat_id: i64,
+        mut predicate: impl FnMut(&PinnedMessage) -> bool,
+    ) -> Vec<UnpinChatMessage> {
+        self.pinned(chat_id)
+            .iter()
+            .filter(|message| predicate(message))
+            .map(|message| UnpinChatMessage::new(chat_id, message.message_id))
+            .collect()
+    }
+
+    /// Creates [`UnpinChatMessage`] requests for the known pinned messages of the chat sent by the user or chat.
+    pub fn unpin_by_author(&self, chat_id: i64, author_id: i64) -> Vec<UnpinChatMessage> {
+        self.unpin_where(chat_id, |message| message.author_id() == Some(author_id))
+    }
+}
@@ -0,0 +1,1749 @@
+//! Requests of inline mode, enabled by the `inline` feature.
+
+use super::*;
+use crate::markup::{InlineKeyboardMarkup, MessageEntity, ParseMode};
+use crate::payment::LabeledPrice;
+
+impl InlineQuery {
+    /// Creates a new [`AnswerInlineQuery`] request that answers this query with the given results.
+    ///
+    /// ```
+    /// # use telbot_types::query::{InlineQuery, InlineQueryResultKind, InputMessageContent};
+    /// # let query: InlineQuery = serde_json::from_str(r#"{
+    /// #     "id": "1", "from": {"id": 1, "is_bot": false, "first_name": "Kiwi"}, "query": "kiwi", "offset": ""
+    /// # }"#).unwrap();
+    /// let answer = query.answer(vec![
+    ///     InlineQueryResultKind::article("Kiwi", InputMessageContent::text("🥝"))
+    ///         .with_description("A fuzzy fruit")
+    ///         .with_id("fruit"),
+    ///     InlineQueryResultKind::cached_sticker("CAACAgIAAxkBAAEB").with_id("sticker"),
+    /// ]);
+    /// let json = serde_json::to_value(&answer).unwrap();
+    /// assert_eq!(json["inline_query_id"], "1");
+    /// assert_eq!(json["results"][0]["type"], "article");
+    /// assert_eq!(json["results"][0]["input_message_content"]["message_text"], "🥝");
+    /// assert_eq!(json["results"][1]["type"], "sticker");
+    /// ```
+    pub fn answer(&self, results: Vec<InlineQueryResult>) -> AnswerInlineQuery {
+        AnswerInlineQuery::new(&self.id, results)
+    }
+
+    /// Creates a new [`AnswerInlineQuery`] request with the page of results requested by this query.
+    ///
+    /// The offset of this query is interpreted as the number of results already shown,
+    /// and an empty offset as the first page.
+    /// `provider` is called with the index of the first result and the maximum number of results to return,
+    /// which is `page_size` (up to [`MAX_INLINE_QUERY_RESULTS`]) plus one;
+    /// the extra result is only used to tell whether there is a next page, and is not sent.
+    ///
+    /// Fails if the offset is not a number, e.g. one sent by another bot or crafted by a client.
+    ///
+    /// ```
+    /// # use telbot_types::query::{InlineQuery, InlineQueryResultKind, InputMessageContent};
+    /// # let query = |offset: &str| -> InlineQuery {
+    /// #     serde_json::from_value(serde_json::json!({
+    /// #         "id": "1", "from": {"id": 1, "is_bot": false, "first_name": "Kiwi"}, "query": "", "offset": offset
+    /// #     })).unwrap()
+    /// # };
+    /// let numbers = |start: usize, count: usize| {
+    ///     (start..100)
+    ///         .take(count)
+    ///         .map(|n| {
+    ///             let content = InputMessageContent::text(n.to_string());
+    ///             InlineQueryResultKind::article(n.to_string(), content).with_id(n.to_string())
+    ///         })
+    ///         .collect()
+    /// };
+    /// let answer = query("90").answer_page(5, numbers).unwrap();
+    /// assert_eq!(answer.results.len(), 5);
+    /// assert_eq!(answer.next_offset.as_deref(), Some("95"));
+    /// let answer = query("95").answer_page(5, numbers).unwrap();
+    /// assert_eq!(answer.next_offset.as_deref(), Some(""));
+    /// assert!(query("next").answer_page(5, numbers).is_err());
+    /// ```
+    pub fn answer_page(
+        &self,
+        page_size: usize,
+        provider: impl FnOnce(usize, usize) -> Vec<InlineQueryResult>,
+    ) -> Result<AnswerInlineQuery, std::num::ParseIntError> {
+        let page_size = page_size.clamp(1, MAX_INLINE_QUERY_RESULTS);
+        let start = match self.offset.as_str() {
+            "" => 0,
+            offset => offset.parse()?,
+        };
+        let mut results = provider(start, page_size + 1);
+        let has_next = results.len() > page_size;
+        results.truncate(page_size);
+        let next_offset = match start.checked_add(page_size) {
+            Some(next) if has_next => next.to_string(),
+            _ => String::new(),
+        };
+        Ok(AnswerInlineQuery::new(&self.id, results).with_next_offset(next_offset))
+    }
+}
+
+/// Maximum number of results allowed in one [`AnswerInlineQuery`].
+pub const MAX_INLINE_QUERY_RESULTS: usize = 50;
+
+/// Maximum length of [`InlineQueryResult::id`] in bytes.
+pub const MAX_INLINE_QUERY_RESULT_ID_LENGTH: usize = 64;
+
+/// One result of an inline query.
+///
+/// Telegram clients currently support results of the following 20 types:
+///
+/// - [InlineQueryResultCachedAudio](https://core.telegram.org/bots/api#inlinequeryresultcachedaudio)
+/// - [InlineQueryResultCachedDocument](https://core.telegram.org/bots/api#inlinequeryresultcacheddocument)
+/// - [InlineQueryResultCachedGif](https://core.telegram.org/bots/api#inlinequeryresultcachedgif)
+/// - [InlineQueryResultCachedMpeg4Gif](https://core.telegram.org/bots/api#inlinequeryresultcachedmpeg4gif)
+/// - [InlineQueryResultCachedPhoto](https://core.telegram.org/bots/api#inlinequeryresultcachedphoto)
+/// - [InlineQueryResultCachedSticker](https://core.telegram.org/bots/api#inlinequeryresultcachedsticker)
+/// - [InlineQueryResultCachedVideo](https://core.telegram.org/bots/api#inlinequeryresultcachedvideo)
+/// - [InlineQueryResultCachedVoice](https://core.telegram.org/bots/api#inlinequeryresultcachedvoice)
+/// - [InlineQueryResultArticle](https://core.telegram.org/bots/api#inlinequeryresultarticle)
+/// - [InlineQueryResultAudio](https://core.telegram.org/bots/api#inlinequeryresultaudio)
+/// - [InlineQueryResultContact](https://core.telegram.org/bots/api#inlinequeryresultcontact)
+/// - [InlineQueryResultGame](https://core.telegram.org/bots/api#inlinequeryresultgame)
+/// - [InlineQueryResultDocument](https://core.telegram.org/bots/api#inlinequeryresultdocument)
+/// - [InlineQueryResultGif](https://core.telegram.org/bots/api#inlinequeryresultgif)
+/// - [InlineQueryResultLocation](https://core.telegram.org/bots/api#inlinequeryresultlocation)
+/// - [InlineQueryResultMpeg4Gif](https://core.telegram.org/bots/api#inlinequeryresultmpeg4gif)
+/// - [InlineQueryResultPhoto](https://core.telegram.org/bots/api#inlinequeryresultphoto)
+/// - [InlineQueryResultVenue](https://core.telegram.org/bots/api#inlinequeryresultvenue)
+/// - [InlineQueryResultVideo](https://core.telegram.org/bots/api#inlinequeryresultvideo)
+/// - [InlineQueryResultVoice](https://core.telegram.org/bots/api#inlinequeryresultvoice)
+///
+/// **Note:** All URLs passed in inline query results will be available to end users
+/// and therefore must be assumed to be **public**.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#inlinequeryresult)
+#[derive(Clone, Serialize)]
+pub struct InlineQueryResult {
+    /// Unique identifier for this result, 1-64 bytes.
+    pub id: String,
+    /// Result type, should be handled manually.
+    r#type: &'static str,
+    /// Result type.
+    #[serde(flatten)]
+    pub kind: InlineQueryResultKind,
+    /// [Inline keyboard](https://core.telegram.org/bots#inline-keyboards-and-on-the-fly-updating) attached to the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+}
+
+impl InlineQueryResult {
+    /// Generates a result identifier from the hash of the given content.
+    ///
+    /// The identifier is stable across runs and versions of this crate,
+    /// so it keeps referring to the same content even if the answer is served from the server cache
+    /// for up to [`AnswerInlineQuery::cache_time`] seconds,
+    /// e.g. when finding the content of the result chosen by the user.
+    pub fn stable_id(content: impl AsRef<[u8]>) -> String {
+        // 64-bit FNV-1a
+        let hash = content
+            .as_ref()
+            .iter()
+            .fold(0xcbf29ce484222325u64, |hash, &byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            });
+        format!("{:016x}", hash)
+    }
+
+    /// `true` if the given identifier is 1-64 bytes long.
+    pub fn is_valid_id(id: &str) -> bool {
+        (1..=MAX_INLINE_QUERY_RESULT_ID_LENGTH).contains(&id.len())
+    }
+
+    /// Sets reply markup.
+    pub fn with_reply_markup(self, markup: impl Into<InlineKeyboardMarkup>) -> Self {
+        Self {
+            reply_markup: Some(markup.into()),
+            ..self
+        }
+    }
+}
+
+/// Type of inline query result.
+#[derive(Clone, Serialize)]
+#[serde(untagged)]
+pub enum InlineQueryResultKind {
+    /// A link to an article or web page.
+    Article {
+        /// Title of the result.
+        title: String,
+        /// Content of the message to be sent.
+        input_message_content: InputMessageContent,
+        /// URL of the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        /// Pass `true`, if you don't want the URL to be shown in the message.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        hide_url: Option<bool>,
+        /// Short description of the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// Url of the thumbnail for the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_url: Option<String>,
+        /// Thumbnail width.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_width: Option<u32>,
+        /// Thumbnail height.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_height: Option<u32>,
+    },
+    /// A link to a photo.
+    ///
+    /// By default, this photo will be sent by the user with optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the photo.
+    Photo {
+        /// A valid URL of the photo. Photo must be in **jpeg** format.
+        ///
+        /// Photo size must not exceed 5MB.
+        photo_url: String,
+        /// URL of the thumbnail for the photo.
+        thumb_url: String,
+        /// Width of the photo.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        photo_width: Option<u32>,
+        /// Height of the photo.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        photo_height: Option<u32>,
+        /// Title for the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Short description of the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// Caption of the photo to be sent, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the photo caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the photo.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to an animated GIF file.
+    ///
+    /// By default, this animated GIF file will be sent by the user with optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the animation.
+    Gif {
+        /// A valid URL for the GIF file. File size must not exceed 1MB.
+        gif_url: String,
+        /// Width of the GIF.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        gif_width: Option<u32>,
+        /// Height of the GIF.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        gif_height: Option<u32>,
+        /// Duration of the GIF.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        gif_duration: Option<u32>,
+        /// URL of the static (JPEG or GIF) or animated (MPEG4) thumbnail for the result.
+        thumb_url: String,
+        /// MIME type of the thumbnail,
+        /// must be one of “image/jpeg”, “image/gif”, or “video/mp4”. Defaults to “image/jpeg”.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_mime_type: Option<String>,
+        /// Title for the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Caption of the GIF file to be sent, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the GIF animation.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to a video animation (H.264/MPEG-4 AVC video without sound).
+    ///
+    /// By default, this animated MPEG-4 file will be sent by the user with optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the animation.
+    Mpeg4Gif {
+        /// A valid URL for the MP4 file. File size must not exceed 1MB.
+        mpeg4_url: String,
+        /// Video width.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mpeg4_width: Option<u32>,
+        /// Video height.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mpeg4_height: Option<u32>,
+        /// Video duration.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mpeg4_duration: Option<u32>,
+        /// URL of the static (JPEG or GIF) or animated (MPEG4) thumbnail for the result.
+        thumb_url: String,
+        /// MIME type of the thumbnail,
+        /// must be one of “image/jpeg”, “image/gif”, or “video/mp4”. Defaults to “image/jpeg”.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_mime_type: Option<String>,
+        /// Title for the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Caption of the MPEG-4 file to be sent, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the video animation.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to a page containing an embedded video player or a video file.
+    ///
+    /// By default, this video file will be sent by the user with an optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the video.
+    /// > If an InlineQueryResultVideo message contains an embedded video (e.g., YouTube),
+    /// > you **must** replace its content using *input_message_content*.
+    Video {
+        /// A valid URL for the embedded video player or video file.
+        video_url: String,
+        /// Mime type of the content of video url, “text/html” or “video/mp4”.
+        mime_type: String,
+        /// URL of the thumbnail (jpeg only) for the video.
+        thumb_url: String,
+        /// Title for the result.
+        title: String,
+        /// Video width.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        video_width: Option<u32>,
+        /// Video height.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        video_height: Option<u32>,
+        /// Video duration in seconds.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        video_duration: Option<u32>,
+        /// Short description of the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// Caption of the video to be sent, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the video.
+        /// This field is **required** if InlineQueryResultVideo is used to send an HTML-page as a result (e.g., a YouTube video).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to an MP3 audio file.
+    ///
+    /// By default, this audio file will be sent by the user.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the audio.
+    Audio {
+        /// A valid URL for the audio file.
+        audio_url: String,
+        /// Title.
+        title: String,
+        /// Performer.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        performer: Option<String>,
+        /// Audio duration in seconds.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        audio_duration: Option<u32>,
+        /// Caption, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the audio.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to a voice recording in an .OGG container encoded with OPUS.
+    ///
+    /// By default, this voice recording will be sent by the user.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the the voice message.
+    Voice {
+        /// A valid URL for the voice recording.
+        voice_url: String,
+        /// Recording title.
+        title: String,
+        /// Recording duration in seconds.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        voice_duration: Option<u32>,
+        /// Caption, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the voice recording.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to a file.
+    ///
+    /// By default, this file will be sent by the user with an optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the file.
+    /// Currently, only **.PDF** and **.ZIP** files can be sent using this method.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    Document {
+        /// A valid URL for the file.
+        document_url: String,
+        /// Mime type of the content of the file, either “application/pdf” or “application/zip”.
+        mime_type: String,
+        /// Title for the result.
+        title: String,
+        /// Short description of the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// URL of the thumbnail (jpeg only) for the file.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_url: Option<String>,
+        /// Thumbnail width.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_width: Option<u32>,
+        /// Thumbnail height.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_height: Option<u32>,
+        /// Caption of the document to be sent, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the file.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A location on a map.
+    ///
+    /// By default, the location will be sent by the user.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the location.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    Location {
+        /// Location latitude in degrees.
+        latitude: f32,
+        /// Location longitude in degrees.
+        longitude: f32,
+        /// Location title.
+        title: String,
+        /// The radius of uncertainty for the location, measured in meters; 0-1500.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        horizontal_accuracy: Option<f32>,
+        /// Period in seconds for which the location can be updated, should be between 60 and 86400.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        live_period: Option<u32>,
+        /// For live locations, a direction in which the user is moving, in degrees.
+        /// Must be between 1 and 360 if specified.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        heading: Option<u32>,
+        /// For live locations, a maximum distance for proximity alerts about approaching another chat member, in meters.
+        /// Must be between 1 and 100000 if specified.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        proximity_alert_radius: Option<u32>,
+        /// Url of the thumbnail for the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_url: Option<String>,
+        /// Thumbnail width.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_width: Option<u32>,
+        /// Thumbnail height.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_height: Option<u32>,
+        /// Content of the message to be sent instead of the location.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A venue.
+    ///
+    /// By default, the venue will be sent by the user.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the venue.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    Venue {
+        /// Location latitude in degrees.
+        latitude: f32,
+        /// Location longitude in degrees.
+        longitude: f32,
+        /// Location title.
+        title: String,
+        /// Address of the venue.
+        address: String,
+        /// Foursquare identifier of the venue if known.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        foursquare_id: Option<String>,
+        /// Foursquare type of the venue, if known.
+        /// (For example, “arts_entertainment/default”, “arts_entertainment/aquarium” or “food/icecream”.)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        foursquare_type: Option<String>,
+        /// Google Places identifier of the venue.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        google_place_id: Option<String>,
+        /// Google Places type of the venue. (See [supported types.](https://developers.google.com/places/web-service/supported_types))
+        #[serde(skip_serializing_if = "Option::is_none")]
+        google_place_type: Option<String>,
+        /// Url of the thumbnail for the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_url: Option<String>,
+        /// Thumbnail width.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_width: Option<u32>,
+        /// Thumbnail height.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_height: Option<u32>,
+        /// Content of the message to be sent instead of the venue.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A contact with a phone number.
+    ///
+    /// By default, this contact will be sent by the user.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the contact.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    Contact {
+        /// Contact's phone number.
+        phone_number: String,
+        /// Contact's first name.
+        first_name: String,
+        /// Contact's last name.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_name: Option<String>,
+        /// Additional data about the contact in the form of a [vCard](https://en.wikipedia.org/wiki/VCard), 0-2048 bytes
+        #[serde(skip_serializing_if = "Option::is_none")]
+        vcard: Option<String>,
+        /// Url of the thumbnail for the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_url: Option<String>,
+        /// Thumbnail width.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_width: Option<u32>,
+        /// Thumbnail height.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumb_height: Option<u32>,
+        /// Content of the message to be sent instead of the contact.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A [Game](https://core.telegram.org/bots/api#games).
+    Game {
+        /// Short name of the game.
+        game_short_name: String,
+    },
+    /// A link to a photo stored on the Telegram servers.
+    ///
+    /// By default, this photo will be sent by the user with an optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the photo.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    CachedPhoto {
+        /// A valid file identifier of the photo
+        photo_file_id: String,
+        /// Title for the result
+        title: String,
+        /// Short description of the result
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// Caption of the photo to be sent, 0-1024 characters after entities parsing
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the photo caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the photo
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to an animated GIF file stored on the Telegram servers.
+    ///
+    /// By default, this animated GIF file will be sent by the user with optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the animation.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    CachedGif {
+        /// A valid file identifier for the GIF file.
+        gif_file_id: String,
+        /// Title for the result.
+        title: String,
+        /// Caption of the GIF file to be sent, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the GIF animation.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to a video animation (H.264/MPEG-4 AVC video without sound) stored on the Telegram servers.
+    ///
+    /// By default, this animated MPEG-4 file will be sent by the user with optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the animation.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    CachedMpeg4Gif {
+        /// A valid file identifier for the MP4 file.
+        mpeg4_file_id: String,
+        /// Title for the result.
+        title: String,
+        /// Caption of the MPEG-4 file to be sent, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the video animation.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to a sticker stored on the Telegram servers.
+    ///
+    /// By default, this sticker will be sent by the user.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the sticker.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016 for static stickers
+    /// and after 06 July, 2019 for animated stickers. Older clients will ignore them.
+    CachedSticker {
+        /// A valid file identifier of the sticker.
+        sticker_file_id: String,
+        /// Content of the message to be sent instead of the sticker.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to a video file stored on the Telegram servers.
+    ///
+    /// By default, this video file will be sent by the user with an optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the video.
+    /// > If an InlineQueryResultVideo message contains an embedded video (e.g., YouTube),
+    /// > you **must** replace its content using *input_message_content*.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    CachedVideo {
+        /// A valid file identifier for the video file.
+        video_file_id: String,
+        /// Title for the result.
+        title: String,
+        /// Short description of the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// Caption of the video to be sent, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the video.
+        /// This field is **required** if InlineQueryResultVideo is used to send an HTML-page as a result (e.g., a YouTube video).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to an MP3 audio file stored on the Telegram servers.
+    ///
+    /// By default, this audio file will be sent by the user.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the audio.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    CachedAudio {
+        /// A valid file identifier for the audio file.
+        audio_file_id: String,
+        /// Caption, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the audio.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to a voice message stored on the Telegram servers.
+    ///
+    /// By default, this voice recording will be sent by the user.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the the voice message.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    CachedVoice {
+        /// A valid file identifier for the voice message.
+        voice_file_id: String,
+        /// Recording title.
+        title: String,
+        /// Caption, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the voice recording.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+    /// A link to a file stored on the Telegram servers.
+    ///
+    /// By default, this file will be sent by the user with an optional caption.
+    /// Alternatively, you can use *input_message_content* to send a message with the specified content instead of the file.
+    /// Currently, only **.PDF** and **.ZIP** files can be sent using this method.
+    ///
+    /// **Note:** This will only work in Telegram versions released after 9 April, 2016. Older clients will ignore them.
+    CachedDocument {
+        /// A valid file identifier for the file.
+        document_file_id: String,
+        /// Title for the result.
+        title: String,
+        /// Short description of the result.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// Caption of the document to be sent, 0-1024 characters after entities parsing.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        /// Mode for parsing entities in the caption.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in the caption,
+        /// which can be specified instead of parse_mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption_entities: Option<Vec<MessageEntity>>,
+        /// Content of the message to be sent instead of the file.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_message_content: Option<InputMessageContent>,
+    },
+}
+
+impl InlineQueryResultKind {
+    /// Creates a inline query result with the given result kind and the given query id.
+    pub fn with_id(self, id: impl Into<String>) -> InlineQueryResult {
+        use InlineQueryResultKind::*;
+        let r#type = match self {
+            Article { .. } => "article",
+            Photo { .. } | CachedPhoto { .. } => "photo",
+            Gif { .. } | CachedGif { .. } => "gif",
+            Mpeg4Gif { .. } | CachedMpeg4Gif { .. } => "mpeg4_gif",
+            Video { .. } | CachedVideo { .. } => "video",
+            Audio { .. } | CachedAudio { .. } => "audio",
+            Voice { .. } | CachedVoice { .. } => "voice",
+            Document { .. } | CachedDocument { .. } => "document",
+            Location { .. } => "location",
+            Venue { .. } => "venue",
+            Contact { .. } => "contact",
+            Game { .. } => "game",
+            CachedSticker { .. } => "sticker",
+        };
+        InlineQueryResult {
+            id: id.into(),
+            r#type,
+            kind: self,
+            reply_markup: None,
+        }
+    }
+
+    /// Creates a inline query result with the given result kind and an identifier generated from the given content.
+    ///
+    /// See [`InlineQueryResult::stable_id`].
+    pub fn with_stable_id(self, content: impl AsRef<[u8]>) -> InlineQueryResult {
+        self.with_id(InlineQueryResult::stable_id(content))
+    }
+
+    /// Creates a link to an article or web page.
+    pub fn article(title: impl Into<String>, input_message_content: InputMessageContent) -> Self {
+        Self::Article {
+            title: title.into(),
+            input_message_content,
+            url: None,
+            hide_url: None,
+            description: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+        }
+    }
+
+    /// Creates a link to a photo.
+    pub fn photo(photo_url: impl Into<String>, thumb_url: impl Into<String>) -> Self {
+        Self::Photo {
+            photo_url: photo_url.into(),
+            thumb_url: thumb_url.into(),
+            photo_width: None,
+            photo_height: None,
+            title: None,
+            description: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to an animated GIF file.
+    pub fn gif(gif_url: impl Into<String>, thumb_url: impl Into<String>) -> Self {
+        Self::Gif {
+            gif_url: gif_url.into(),
+            gif_width: None,
+            gif_height: None,
+            gif_duration: None,
+            thumb_url: thumb_url.into(),
+            thumb_mime_type: None,
+            title: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to a video animation (H.264/MPEG-4 AVC video without sound).
+    pub fn mpeg4_gif(mpeg4_url: impl Into<String>, thumb_url: impl Into<String>) -> Self {
+        Self::Mpeg4Gif {
+            mpeg4_url: mpeg4_url.into(),
+            mpeg4_width: None,
+            mpeg4_height: None,
+            mpeg4_duration: None,
+            thumb_url: thumb_url.into(),
+            thumb_mime_type: None,
+            title: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to a page containing an embedded video player or a video file.
+    pub fn video(
+        video_url: impl Into<String>,
+        mime_type: impl Into<String>,
+        thumb_url: impl Into<String>,
+        title: impl Into<String>,
+    ) -> Self {
+        Self::Video {
+            video_url: video_url.into(),
+            mime_type: mime_type.into(),
+            thumb_url: thumb_url.into(),
+            title: title.into(),
+            video_width: None,
+            video_height: None,
+            video_duration: None,
+            description: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to an MP3 audio file.
+    pub fn audio(audio_url: impl Into<String>, title: impl Into<String>) -> Self {
+        Self::Audio {
+            audio_url: audio_url.into(),
+            title: title.into(),
+            performer: None,
+            audio_duration: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to a voice recording in an .OGG container encoded with OPUS.
+    pub fn voice(voice_url: impl Into<String>, title: impl Into<String>) -> Self {
+        Self::Voice {
+            voice_url: voice_url.into(),
+            title: title.into(),
+            voice_duration: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to a file.
+    pub fn document(
+        document_url: impl Into<String>,
+        mime_type: impl Into<String>,
+        title: impl Into<String>,
+    ) -> Self {
+        Self::Document {
+            document_url: document_url.into(),
+            mime_type: mime_type.into(),
+            title: title.into(),
+            description: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a location on a map.
+    pub fn location(latitude: f32, longitude: f32, title: impl Into<String>) -> Self {
+        Self::Location {
+            latitude,
+            longitude,
+            title: title.into(),
+            horizontal_accuracy: None,
+            live_period: None,
+            heading: None,
+            proximity_alert_radius: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a venue.
+    pub fn venue(
+        latitude: f32,
+        longitude: f32,
+        title: impl Into<String>,
+        address: impl Into<String>,
+    ) -> Self {
+        Self::Venue {
+            latitude,
+            longitude,
+            title: title.into(),
+            address: address.into(),
+            foursquare_id: None,
+            foursquare_type: None,
+            google_place_id: None,
+            google_place_type: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a contact with a phone number.
+    pub fn contact(phone_number: impl Into<String>, first_name: impl Into<String>) -> Self {
+        Self::Contact {
+            phone_number: phone_number.into(),
+            first_name: first_name.into(),
+            last_name: None,
+            vcard: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a game.
+    pub fn game(game_short_name: impl Into<String>) -> Self {
+        Self::Game {
+            game_short_name: game_short_name.into(),
+        }
+    }
+
+    /// Creates a link to a photo stored on the Telegram servers.
+    pub fn cached_photo(photo_file_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self::CachedPhoto {
+            photo_file_id: photo_file_id.into(),
+            title: title.into(),
+            description: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to an animated GIF file stored on the Telegram servers.
+    pub fn cached_gif(gif_file_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self::CachedGif {
+            gif_file_id: gif_file_id.into(),
+            title: title.into(),
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to a video animation stored on the Telegram servers.
+    pub fn cached_mpeg4_gif(mpeg4_file_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self::CachedMpeg4Gif {
+            mpeg4_file_id: mpeg4_file_id.into(),
+            title: title.into(),
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to a sticker stored on the Telegram servers.
+    pub fn cached_sticker(sticker_file_id: impl Into<String>) -> Self {
+        Self::CachedSticker {
+            sticker_file_id: sticker_file_id.into(),
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to a video file stored on the Telegram servers.
+    pub fn cached_video(video_file_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self::CachedVideo {
+            video_file_id: video_file_id.into(),
+            title: title.into(),
+            description: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to an MP3 audio file stored on the Telegram servers.
+    pub fn cached_audio(audio_file_id: impl Into<String>) -> Self {
+        Self::CachedAudio {
+            audio_file_id: audio_file_id.into(),
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to a voice message stored on the Telegram servers.
+    pub fn cached_voice(voice_file_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self::CachedVoice {
+            voice_file_id: voice_file_id.into(),
+            title: title.into(),
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Creates a link to a file stored on the Telegram servers.
+    pub fn cached_document(document_file_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self::CachedDocument {
+            document_file_id: document_file_id.into(),
+            title: title.into(),
+            description: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+        }
+    }
+
+    /// Sets the caption of the media to be sent.
+    ///
+    /// Has no effect on results without a caption, like articles or locations.
+    pub fn with_caption(mut self, text: impl Into<String>) -> Self {
+        if let Some((caption, _, _)) = self.caption_mut() {
+            *caption = Some(text.into());
+        }
+        self
+    }
+
+    /// Sets the mode for parsing entities in the caption.
+    ///
+    /// Has no effect on results without a caption, like articles or locations.
+    pub fn with_parse_mode(mut self, mode: ParseMode) -> Self {
+        if let Some((_, parse_mode, _)) = self.caption_mut() {
+            *parse_mode = Some(mode);
+        }
+        self
+    }
+
+    /// Sets the special entities that appear in the caption.
+    ///
+    /// Has no effect on results without a caption, like articles or locations.
+    pub fn with_caption_entities(mut self, entities: Vec<MessageEntity>) -> Self {
+        if let Some((_, _, caption_entities)) = self.caption_mut() {
+            *caption_entities = Some(entities);
+        }
+        self
+    }
+
+    /// Sets the short description of the result.
+    ///
+    /// Has no effect on results without a description, like audio files or locations.
+    pub fn with_description(mut self, text: impl Into<String>) -> Self {
+        use InlineQueryResultKind::*;
+        match &mut self {
+            Article { description, .. }
+            | Photo { description, .. }
+            | Video { description, .. }
+            | Document { description, .. }
+            | CachedPhoto { description, .. }
+            | CachedVideo { description, .. }
+            | CachedDocument { description, .. } => *description = Some(text.into()),
+            _ => {}
+        }
+        self
+    }
+
+    /// Sets the content of the message to be sent instead of the result.
+    ///
+    /// Has no effect on games.
+    pub fn with_input_message_content(mut self, content: InputMessageContent) -> Self {
+        use InlineQueryResultKind::*;
+        match &mut self {
+            Article {
+                input_message_content,
+                ..
+            } => *input_message_content = content,
+            Photo {
+                input_message_content,
+                ..
+            }
+            | Gif {
+                input_message_content,
+                ..
+            }
+            | Mpeg4Gif {
+                input_message_content,
+                ..
+            }
+            | Video {
+                input_message_content,
+                ..
+            }
+            | Audio {
+                input_message_content,
+                ..
+            }
+            | Voice {
+                input_message_content,
+                ..
+            }
+            | Document {
+                input_message_content,
+                ..
+            }
+            | Location {
+                input_message_content,
+                ..
+            }
+            | Venue {
+                input_message_content,
+                ..
+            }
+            | Contact {
+                input_message_content,
+                ..
+            }
+            | CachedPhoto {
+                input_message_content,
+                ..
+            }
+            | CachedGif {
+                input_message_content,
+                ..
+            }
+            | CachedMpeg4Gif {
+                input_message_content,
+                ..
+            }
+            | CachedSticker {
+                input_message_content,
+                ..
+            }
+            | CachedVideo {
+                input_message_content,
+                ..
+            }
+            | CachedAudio {
+                input_message_content,
+                ..
+            }
+            | CachedVoice {
+                input_message_content,
+                ..
+            }
+            | CachedDocument {
+                input_message_content,
+                ..
+            } => *input_message_content = Some(content),
+            Game { .. } => {}
+        }
+        self
+    }
+
+    fn caption_mut(&mut self) -> Option<CaptionFields<'_>> {
+        use InlineQueryResultKind::*;
+        match self {
+            Photo {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | Gif {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | Mpeg4Gif {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | Video {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | Audio {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | Voice {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | Document {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | CachedPhoto {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | CachedGif {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | CachedMpeg4Gif {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | CachedVideo {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | CachedAudio {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | CachedVoice {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            }
+            | CachedDocument {
+                caption,
+                parse_mode,
+                caption_entities,
+                ..
+            } => Some((caption, parse_mode, caption_entities)),
+            _ => None,
+        }
+    }
+}
+
+type CaptionFields<'a> = (
+    &'a mut Option<String>,
+    &'a mut Option<ParseMode>,
+    &'a mut Option<Vec<MessageEntity>>,
+);
+
+/// The content of a message to be sent as a result of an inline query.
+///
+/// Telegram clients currently support the following 5 types:
+/// - [InputTextMessageContent](https://core.telegram.org/bots/api#inputtextmessagecontent)
+/// - [InputLocationMessageContent](https://core.telegram.org/bots/api#inputlocationmessagecontent)
+/// - [InputVenueMessageContent](https://core.telegram.org/bots/api#inputvenuemessagecontent)
+/// - [InputContactMessageContent](https://core.telegram.org/bots/api#inputcontactmessagecontent)
+/// - [InputInvoiceMessageContent](https://core.telegram.org/bots/api#inputinvoicemessagecontent)
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#inputmessagecontent)
+#[derive(Clone, Serialize)]
+#[serde(untagged)]
+pub enum InputMessageContent {
+    /// The [content](https://core.telegram.org/bots/api#inputmessagecontent)
+    /// of a text message to be sent as the result of an inline query.
+    Text {
+        /// Text of the message to be sent, 1-4096 characters.
+        message_text: String,
+        /// Mode for parsing entities in the message text.
+        /// See [formatting options](https://core.telegram.org/bots/api#formatting-options) for more details.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parse_mode: Option<ParseMode>,
+        /// List of special entities that appear in message text, which can be specified instead of *parse_mode*.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        entities: Option<Vec<MessageEntity>>,
+        /// Disables link previews for links in the sent message.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        disable_web_page_preview: Option<bool>,
+    },
+    /// The [content](https://core.telegram.org/bots/api#inputmessagecontent)
+    /// of a location message to be sent as the result of an inline query.
+    Location {
+        /// Latitude of the location in degrees.
+        latitude: f32,
+        /// Longitude of the location in degrees.
+        longitude: f32,
+        /// The radius of uncertainty for the location, measured in meters; 0-1500.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        horizontal_accuracy: Option<f32>,
+        /// Period in seconds for which the location can be updated, should be between 60 and 86400.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        live_period: Option<u32>,
+        /// For live locations, a direction in which the user is moving, in degrees.
+        /// Must be between 1 and 360 if specified.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        heading: Option<u32>,
+        /// For live locations, a maximum distance for proximity alerts about approaching another chat member, in meters.
+        /// Must be between 1 and 100000 if specified.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        proximity_alert_radius: Option<u32>,
+    },
+    /// The [content](https://core.telegram.org/bots/api#inputmessagecontent)
+    /// of a venue message to be sent as the result of an inline query.
+    Venue {
+        /// Latitude of the venue in degrees.
+        latitude: f32,
+        /// Longitude of the venue in degrees.
+        longitude: f32,
+        /// Name of the venue.
+        title: String,
+        /// Address of the venue.
+        address: String,
+        /// Foursquare identifier of the venue, if known.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        foursquare_id: Option<String>,
+        /// Foursquare type of the venue, if known.
+        /// (For example, “arts_entertainment/default”, “arts_entertainment/aquarium” or “food/icecream”.)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        foursquare_type: Option<String>,
+        /// Google Places identifier of the venue.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        google_place_id: Option<String>,
+        /// Google Places type of the venue. (See [supported types.](https://developers.google.com/places/web-service/supported_types))
+        #[serde(skip_serializing_if = "Option::is_none")]
+        google_place_type: Option<String>,
+    },
+    /// The [content](https://core.telegram.org/bots/api#inputmessagecontent)
+    /// of a contact message to be sent as the result of an inline query.
+    Contact {
+        /// Contact's phone number.
+        phone_number: String,
+        /// Contact's first name.
+        first_name: String,
+        /// Contact's last name.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_name: Option<String>,
+        /// Additional data about the contact in the form of a [vCard](https://en.wikipedia.org/wiki/VCard), 0-2048 bytes.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        vcard: Option<String>,
+    },
+    /// The [content](https://core.telegram.org/bots/api#inputmessagecontent)
+    /// of an invoice message to be sent as the result of an inline query.
+    Invoice {
+        /// Product name, 1-32 characters.
+        title: String,
+        /// Product description, 1-255 characters.
+        description: String,
+        /// Bot-defined invoice payload, 1-128 bytes.
+        /// This will not be displayed to the user, use for your internal processes.
+        payload: String,
+        /// Payment provider token, obtained via [Botfather](https://t.me/botfather).
+        provider_token: String,
+        /// Three-letter ISO 4217 currency code, see [more on currencies](https://core.telegram.org/bots/payments#supported-currencies).
+        currency: String,
+        /// Price breakdown, a JSON-serialized list of components.
+        /// (e.g. product price, tax, discount, delivery cost, delivery tax, bonus, etc.)
+        prices: Vec<LabeledPrice>,
+        /// The maximum accepted amount for tips in the smallest units of the currency (integer, **not** float/double).
+        /// For example, for a maximum tip of `US$ 1.45` pass `max_tip_amount = 145`.
+        /// See the exp parameter in [currencies.json](https://core.telegram.org/bots/payments/currencies.json),
+        /// it shows the number of digits past the decimal point for each currency (2 for the majority of currencies). Defaults to 0
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_tip_amount: Option<i32>,
+        /// A JSON-serialized array of suggested amounts of tips in the smallest units of the currency (integer, **not** float/double).
+        /// At most 4 suggested tip amounts can be specified.
+        /// The suggested tip amounts must be positive, passed in a strictly increased order and must not exceed *max_tip_amount*.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        suggested_tip_amounts: Option<Vec<i32>>,
+        /// Unique deep-linking parameter.
+        /// If left empty, **forwarded copies** of the sent message will have a *Pay* button, allowing multiple users to pay directly from the forwarded message, using the same invoice.
+        /// If non-empty, forwarded copies of the sent message will have a *URL* button with a deep link to the bot (instead of a *Pay* button), with the value used as the start parameter
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start_parameter: Option<String>,
+        /// A JSON-serialized data about the invoice, which will be shared with the payment provider.
+        /// A detailed description of required fields should be provided by the payment provider.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        provider_data: Option<String>,
+        /// URL of the product photo for the invoice. Can be a photo of the goods or a marketing image for a service.
+        /// People like it better when they see what they are paying for.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        photo_url: Option<String>,
+        /// Photo size.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        photo_size: Option<u32>,
+        /// Photo width.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        photo_width: Option<u32>,
+        /// Photo height.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        photo_height: Option<u32>,
+        /// Pass `true`, if you require the user's full name to complete the order.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        need_name: Option<bool>,
+        /// Pass `true`, if you require the user's phone number to complete the order.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        need_phone_number: Option<bool>,
+        /// Pass `true`, if you require the user's email address to complete the order.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        need_email: Option<bool>,
+        /// Pass `true`, if you require the user's shipping address to complete the order
+        #[serde(skip_serializing_if = "Option::is_none")]
+        need_shipping_address: Option<bool>,
+        /// Pass `true`, if user's phone number should be sent to provider
+        #[serde(skip_serializing_if = "Option::is_none")]
+        send_phone_number_to_provider: Option<bool>,
+        /// Pass `true`, if user's email address should be sent to provider
+        #[serde(skip_serializing_if = "Option::is_none")]
+        send_email_to_provider: Option<bool>,
+        /// Pass `true`, if the final price depends on the shipping method
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_flexible: Option<bool>,
+        /// Sends the message [silently](https://telegram.org/blog/channels-2-0#silent-messages).
+        /// Users will receive a notification with no sound.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        disable_notification: Option<bool>,
+        /// If the message is a reply, ID of the original message.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reply_to_message_id: Option<i64>,
+        /// Pass `true`, if the message should be sent even if the specified replied-to message is not found.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        allow_sending_without_reply: Option<bool>,
+        /// A JSON-serialized object for an [inline keyboard](https://core.telegram.org/bots#inline-keyboards-and-on-the-fly-updating).
+        /// If empty, one 'Pay `total price`' button will be shown.
+        /// If not empty, the first button must be a Pay button.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reply_markup: Option<InlineKeyboardMarkup>,
+    },
+}
+
+impl InputMessageContent {
+    /// Creates the content of a text message.
+    pub fn text(message_text: impl Into<String>) -> Self {
+        Self::Text {
+            message_text: message_text.into(),
+            parse_mode: None,
+            entities: None,
+            disable_web_page_preview: None,
+        }
+    }
+
+    /// Creates the content of a location message.
+    pub fn location(latitude: f32, longitude: f32) -> Self {
+        Self::Location {
+            latitude,
+            longitude,
+            horizontal_accuracy: None,
+            live_period: None,
+            heading: None,
+            proximity_alert_radius: None,
+        }
+    }
+
+    /// Creates the content of a venue message.
+    pub fn venue(
+        latitude: f32,
+        longitude: f32,
+        title: impl Into<String>,
+        address: impl Into<String>,
+    ) -> Self {
+        Self::Venue {
+            latitude,
+            longitude,
+            title: title.into(),
+            address: address.into(),
+            foursquare_id: None,
+            foursquare_type: None,
+            google_place_id: None,
+            google_place_type: None,
+        }
+    }
+
+    /// Creates the content of a contact message.
+    pub fn contact(phone_number: impl Into<String>, first_name: impl Into<String>) -> Self {
+        Self::Contact {
+            phone_number: phone_number.into(),
+            first_name: first_name.into(),
+            last_name: None,
+            vcard: None,
+        }
+    }
+
+    /// Sets the mode for parsing entities in the message text.
+    ///
+    /// Has no effect on contents other than text.
+    pub fn with_parse_mode(mut self, mode: ParseMode) -> Self {
+        if let Self::Text { parse_mode, .. } = &mut self {
+            *parse_mode = Some(mode);
+        }
+        self
+    }
+
+    /// Sets the special entities that appear in the message text.
+    ///
+    /// Has no effect on contents other than text.
+    pub fn with_entities(mut self, message_entities: Vec<MessageEntity>) -> Self {
+        if let Self::Text { entities, .. } = &mut self {
+            *entities = Some(message_entities);
+        }
+        self
+    }
+
+    /// Disables link previews for links in the message text.
+    ///
+    /// Has no effect on contents other than text.
+    pub fn disable_web_page_preview(mut self) -> Self {
+        if let Self::Text {
+            disable_web_page_preview,
+            ..
+        } = &mut self
+        {
+            *disable_web_page_preview = Some(true);
+        }
+        self
+    }
+}
+
+/// Sends answers to an inline query.
+///
+/// On success, `true` is returned.
+///
+/// No more than 50 results per query are allowed.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#answerinlinequery)
+#[derive(Clone, Serialize)]
+pub struct AnswerInlineQuery {
+    /// Unique identifier for the answered query.
+    pub inline_query_id: String,
+    /// A JSON-serialized array of results for the inline query.
+    ///
+    /// No more than [`MAX_INLINE_QUERY_RESULTS`] results per query are allowed.
+    pub results: Vec<InlineQueryResult>,
+    /// The maximum amount of time in seconds that the result of the inline query may be cached on the server.
+    /// Defaults to 300.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_time: Option<u32>,
+    /// Pass `true`, if results may be cached on the server side only for the user that sent the query.
+    /// By default, results may be returned to any user who sends the same query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_personal: Option<bool>,
+    /// Pass the offset that a client should send in the next query with the same text to receive more results.
+    /// Pass an empty string if there are no more results or if you don't support pagination.
+    /// Offset length can't exceed 64 bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<String>,
+    /// If passed, clients will display a button with specified text that switches the user to a private chat with the bot and sends the bot a start message with the parameter switch_pm_parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub switch_pm_text: Option<String>,
+    /// [Deep-linking](https://core.telegram.org/bots#deep-linking) parameter for the /start message sent to the bot when user presses the switch button.
+    /// 1-64 characters, only `A-Z`, `a-z`, `0-9`, `_` and `-` are allowed.
+    ///
+    /// *Example:* An inline bot that sends YouTube videos can ask the user to connect the bot to their YouTube account to adapt search results accordingly.
+    /// To do this, it displays a 'Connect your YouTube account' button above the results, or even before showing any.
+    /// The user presses the button, switches to a private chat with the bot and, in doing so, passes a start parameter that instructs the bot to return an oauth link.
+    /// Once done, the bot can offer a [*switch_inline*](https://core.telegram.org/bots/api#inlinekeyboardmarkup) button
+    /// so that the user can easily return to the chat where they wanted to use the bot's inline capabilities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub switch_pm_parameter: Option<String>,
+}
+
+impl AnswerInlineQuery {
+    /// Creates a new [`AnswerInlineQuery`] request that answers to the given query and with given results.
+    pub fn new(query_id: impl Into<String>, results: Vec<InlineQueryResult>) -> Self {
+        Self {
+            inline_query_id: query_id.into(),
+            results,
+            cache_time: None,
+            is_personal: None,
+            next_offset: None,
+            switch_pm_text: None,
+            switch_pm_parameter: None,
+        }
+    }
+    /// Creates a new [`AnswerInlineQuery`] request, keeping only the first [`MAX_INLINE_QUERY_RESULTS`] results.
+    pub fn new_capped(query_id: impl Into<String>, mut results: Vec<InlineQueryResult>) -> Self {
+        results.truncate(MAX_INLINE_QUERY_RESULTS);
+        Self::new(query_id, results)
+    }
+    /// Adds a result.
+    ///
+    /// Returns `false` without adding if there are already [`MAX_INLINE_QUERY_RESULTS`] results,
+    /// or there is a result with the same identifier, which would make Telegram drop the result silently.
+    pub fn push_result(&mut self, result: InlineQueryResult) -> bool {
+        if self.is_full() || self.results.iter().any(|other| other.id == result.id) {
+            false
+        } else {
+            self.results.push(result);
+            true
+        }
+    }
+    /// Checks that all result identifiers are 1-64 bytes long and unique.
+    pub fn validate_ids(&self) -> Result<(), ResultIdError> {
+        let mut ids = std::collections::HashSet::new();
+        for result in &self.results {
+            if !InlineQueryResult::is_valid_id(&result.id) {
+                return Err(ResultIdError::InvalidLength(result.id.clone()));
+            }
+            if !ids.insert(result.id.as_str()) {
+                return Err(ResultIdError::Duplicate(result.id.clone()));
+            }
+        }
+        Ok(())
+    }
+    /// `true` if no more results can be added.
+    pub fn is_full(&self) -> bool {
+        self.results.len() >= MAX_INLINE_QUERY_RESULTS
+    }
+    /// Sets cache time.
+    pub fn with_cache_time(self, cache_time: u32) -> Self {
+        Self {
+            cache_time: Some(cache_time),
+            ..self
+        }
+    }
+    /// Sets the results to be cached on the server side.
+    pub fn personal(self) -> Self {
+        Self {
+            is_personal: Some(true),
+            ..self
+        }
+    }
+    /// Sets next offset string.
+    pub fn with_next_offset(self, offset: impl Into<String>) -> Self {
+        Self {
+            next_offset: Some(offset.into()),
+            ..self
+        }
+    }
+    /// Sets switch pm text.
+    pub fn with_switch_pm_text(self, text: impl Into<String>) -> Self {
+        Self {
+            switch_pm_text: Some(text.into()),
+            ..self
+        }
+    }
+    // Sets switch pm parameter.
+    pub fn with_switch_pm_parameter(self, param: impl Into<String>) -> Self {
+        Self {
+            switch_pm_parameter: Some(param.into()),
+            ..self
+        }
+    }
+}
+
+/// Error of invalid result identifiers in [`AnswerInlineQuery`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResultIdError {
+    /// The identifier is empty or longer than 64 bytes.
+    InvalidLength(String),
+    /// The identifier is used by more than one result.
+    Duplicate(String),
+}
+
+impl std::fmt::Display for ResultIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength(id) => write!(f, "result id must be 1-64 bytes long: {:?}", id),
+            Self::Duplicate(id) => write!(f, "duplicate result id: {:?}", id),
+        }
+    }
+}
+
+impl std::error::Error for ResultIdError {}
+
+telegram_method!(AnswerInlineQuery => "answerInlineQuery", bool, json);
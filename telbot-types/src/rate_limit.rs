@@ -0,0 +1,280 @@
+//! Throttling of requests to stay within the rate limits of Telegram.
+//!
+//! Telegram allows bots to send about 30 messages per second in total,
+//! 1 message per second to the same chat, and 20 messages per minute to the same group,
+//! and answers requests beyond the limits with `429 Too Many Requests`.
+//! [`RateLimiter`] schedules requests so that they are sent no faster than the limits,
+//! and [`RateLimitedApi`] wraps the API requester of any backend implementing [`TelegramClient`]
+//! to wait for the schedule.
+
+use std::collections::{BTreeSet, HashMap};
+use std::future::{ready, Future, Ready};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::chat::ChatId;
+use crate::{FileMethod, JsonMethod, TelegramClient, TelegramMethod};
+
+/// Number of chats remembered before the chats which are not waiting are forgotten.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Schedules requests to keep them within the global limit and the limits of each chat.
+///
+/// Each call to [`RateLimiter::reserve`] takes the earliest time slot allowed by the limits,
+/// so requests to the same chat are sent in the order they are reserved.
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use telbot_types::rate_limit::RateLimiter;
+/// let mut limiter = RateLimiter::new();
+/// let now = Instant::now();
+/// assert_eq!(limiter.reserve_at(Some(&1234.into()), now), Duration::ZERO);
+/// // another message to the same private chat waits a second
+/// assert_eq!(limiter.reserve_at(Some(&1234.into()), now), Duration::from_secs(1));
+/// // a message to another chat only waits for the global limit
+/// assert!(limiter.reserve_at(Some(&5678.into()), now) < Duration::from_millis(100));
+/// // messages to groups are limited to 20 per minute
+/// limiter.reserve_at(Some(&(-100).into()), now);
+/// assert!(limiter.reserve_at(Some(&(-100).into()), now) >= Duration::from_secs(3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    global_interval: Duration,
+    chat_interval: Duration,
+    group_interval: Duration,
+    global_slots: BTreeSet<Instant>,
+    next_chat: HashMap<String, Instant>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self {
+            global_interval: Duration::from_secs(1) / 30,
+            chat_interval: Duration::from_secs(1),
+            group_interval: Duration::from_secs(3),
+            global_slots: BTreeSet::new(),
+            next_chat: HashMap::new(),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter with the default limits of Telegram:
+    /// 30 requests per second in total, 1 per second for each private chat and 20 per minute for each group or channel.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of requests per second in total.
+    pub fn with_global_limit(self, per_second: u32) -> Self {
+        Self {
+            global_interval: Duration::from_secs(1) / per_second.max(1),
+            ..self
+        }
+    }
+
+    /// Sets the minimum interval between requests to the same private chat.
+    pub fn with_chat_interval(self, interval: Duration) -> Self {
+        Self {
+            chat_interval: interval,
+            ..self
+        }
+    }
+
+    /// Sets the minimum interval between requests to the same group or channel.
+    pub fn with_group_interval(self, interval: Duration) -> Self {
+        Self {
+            group_interval: interval,
+            ..self
+        }
+    }
+
+    /// Reserves a time slot for the request, returning how long to wait before sending it.
+    ///
+    /// The chat is taken from [`TelegramMethod::chat_id`].
+    /// Requests without the chat are only subject to the global limit.
+    pub fn reserve<M: TelegramMethod>(&mut self, method: &M) -> Duration {
+        self.reserve_at(method.chat_id().as_ref(), Instant::now())
+    }
+
+    /// Reserves a time slot for a request to the chat at `now`, returning how long to wait before sending it.
+    pub fn reserve_at(&mut self, chat_id: Option<&ChatId>, now: Instant) -> Duration {
+        let chat = chat_id.map(|chat_id| match chat_id {
+            ChatId::Id(id) if *id > 0 => (id.to_string(), self.chat_interval),
+            ChatId::Id(id) => (id.to_string(), self.group_interval),
+            ChatId::Username(username) => (username.to_lowercase(), self.group_interval),
+        });
+        let ready = match &chat {
+            Some((key, _)) => match self.next_chat.get(key) {
+                Some(next) if *next > now => *next,
+                _ => now,
+            },
+            None => now,
+        };
+        let at = self.reserve_global_slot(ready, now);
+        if let Some((key, interval)) = chat {
+            if self.next_chat.len() >= PRUNE_THRESHOLD {
+                self.next_chat.retain(|_, next| *next > now);
+            }
+            self.next_chat.insert(key, at + interval);
+        }
+        at - now
+    }
+
+    /// Takes the earliest slot from `ready` which is at least the global interval apart from the other slots.
+    ///
+    /// A request waiting for its chat does not delay requests to other chats,
+    /// which take the free slots before it.
+    fn reserve_global_slot(&mut self, ready: Instant, now: Instant) -> Instant {
+        let interval = self.global_interval;
+        if let Some(expired) = now.checked_sub(interval) {
+            self.global_slots = self.global_slots.split_off(&expired);
+        }
+        let mut at = ready;
+        let from = ready.checked_sub(interval).unwrap_or(ready);
+        for slot in self.global_slots.range(from..) {
+            if *slot >= at + interval {
+                break;
+            }
+            if *slot + interval > at {
+                at = *slot + interval;
+            }
+        }
+        self.global_slots.insert(at);
+        at
+    }
+}
+
+/// Wraps an API requester to throttle requests with a [`RateLimiter`].
+///
+/// Requests are sent with [`TelegramClient`], and wait for their time slots before being sent.
+/// The time slot of a request is reserved when it is called, so requests to the same chat are sent in that order.
+/// The wrapper can be shared between threads or tasks, which then wait for the same schedule.
+///
+/// By default the current thread is blocked while waiting, which suits synchronous backends like `telbot-ureq`.
+/// Async backends should measure and wait with the timer of their runtime, set with [`RateLimitedApi::with_timer`]:
+///
+/// ```no_run
+/// # use std::time::{Duration, Instant};
+/// # use telbot_types::message::SendMessage;
+/// # use telbot_types::rate_limit::RateLimitedApi;
+/// # use telbot_types::TelegramClient;
+/// # async fn sleep(_: Duration) {}
+/// # async fn run<Api: TelegramClient>(api: Api) -> Result<(), Api::Error> {
+/// // e.g. with_timer(|| tokio::time::Instant::now().into_std(), tokio::time::sleep)
+/// let api = RateLimitedApi::new(api).with_timer(Instant::now, sleep);
+/// for chat_id in [1, 2, 3] {
+///     api.execute_json(&SendMessage::new(chat_id, "Hello")).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct RateLimitedApi<A, S = fn(Duration) -> Ready<()>, C = fn() -> Instant> {
+    api: A,
+    limiter: Mutex<RateLimiter>,
+    now: C,
+    sleep: S,
+}
+
+impl<A> RateLimitedApi<A> {
+    /// Wraps the API requester with the default limits of Telegram, blocking the current thread while waiting.
+    pub fn new(api: A) -> Self {
+        Self {
+            api,
+            limiter: Mutex::new(RateLimiter::new()),
+            now: Instant::now,
+            sleep: blocking_sleep,
+        }
+    }
+}
+
+impl<A, S, C> RateLimitedApi<A, S, C> {
+    /// Sets the rate limiter.
+    pub fn with_limiter(self, limiter: RateLimiter) -> Self {
+        Self {
+            limiter: Mutex::new(limiter),
+            ..self
+        }
+    }
+
+    /// Sets the clock and the function waiting for the given duration,
+    /// like `|| tokio::time::Instant::now().into_std()` and `tokio::time::sleep`.
+    ///
+    /// Both should come from the same timer, so that the schedule follows the time of the runtime
+    /// even if it is paused or mocked.
+    pub fn with_timer<T, F, N>(self, now: N, sleep: T) -> RateLimitedApi<A, T, N>
+    where
+        N: Fn() -> Instant,
+        T: Fn(Duration) -> F,
+        F: Future<Output = ()>,
+    {
+        RateLimitedApi {
+            api: self.api,
+            limiter: self.limiter,
+            now,
+            sleep,
+        }
+    }
+
+    /// Gets the wrapped API requester, to send requests without throttling.
+    pub fn inner(&self) -> &A {
+        &self.api
+    }
+
+    /// Unwraps the API requester.
+    pub fn into_inner(self) -> A {
+        self.api
+    }
+
+    fn reserve<M: TelegramMethod>(&self, method: &M) -> Duration
+    where
+        C: Fn() -> Instant,
+    {
+        self.limiter
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .reserve_at(method.chat_id().as_ref(), (self.now)())
+    }
+}
+
+impl<A, S, F, C> TelegramClient for RateLimitedApi<A, S, C>
+where
+    A: TelegramClient,
+    S: Fn(Duration) -> F,
+    F: Future<Output = ()>,
+    C: Fn() -> Instant,
+{
+    type Error = A::Error;
+
+    fn execute_json<M: JsonMethod>(
+        &self,
+        method: &M,
+    ) -> impl Future<Output = Result<M::Response, Self::Error>> {
+        let delay = self.reserve(method);
+        async move {
+            if !delay.is_zero() {
+                (self.sleep)(delay).await;
+            }
+            self.api.execute_json(method).await
+        }
+    }
+
+    fn execute_file<M: FileMethod>(
+        &self,
+        method: &M,
+    ) -> impl Future<Output = Result<M::Response, Self::Error>> {
+        let delay = self.reserve(method);
+        async move {
+            if !delay.is_zero() {
+                (self.sleep)(delay).await;
+            }
+            self.api.execute_file(method).await
+        }
+    }
+}
+
+fn blocking_sleep(delay: Duration) -> Ready<()> {
+    thread::sleep(delay);
+    ready(())
+}
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chat::{Chat, ChatId};
+use crate::telegram_method;
+use crate::user::User;
+
+/// Type of a reaction.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#reactiontype)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReactionType {
+    /// A reaction based on a predefined emoji.
+    Emoji {
+        /// Reaction emoji.
+        emoji: String,
+    },
+    /// A reaction based on a custom emoji.
+    CustomEmoji {
+        /// Custom emoji identifier.
+        custom_emoji_id: String,
+    },
+    /// A paid reaction, sent with Telegram Stars.
+    Paid,
+}
+
+impl ReactionType {
+    /// Creates a reaction based on the predefined emoji.
+    pub fn emoji(emoji: impl Into<String>) -> Self {
+        Self::Emoji {
+            emoji: emoji.into(),
+        }
+    }
+
+    /// Creates a reaction based on the custom emoji.
+    pub fn custom_emoji(custom_emoji_id: impl Into<String>) -> Self {
+        Self::CustomEmoji {
+            custom_emoji_id: custom_emoji_id.into(),
+        }
+    }
+
+    /// Gets the emoji of the reaction, if any.
+    pub fn as_emoji(&self) -> Option<&str> {
+        match self {
+            Self::Emoji { emoji } => Some(emoji),
+            _ => None,
+        }
+    }
+
+    /// Gets the custom emoji identifier of the reaction, if any.
+    pub fn custom_emoji_id(&self) -> Option<&str> {
+        match self {
+            Self::CustomEmoji { custom_emoji_id } => Some(custom_emoji_id),
+            _ => None,
+        }
+    }
+
+    /// `true` if the reaction is paid.
+    pub fn is_paid(&self) -> bool {
+        matches!(self, Self::Paid)
+    }
+}
+
+/// A reaction added to a message along with the number of times it was added.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#reactioncount)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReactionCount {
+    /// Type of the reaction.
+    #[serde(rename = "type")]
+    pub kind: ReactionType,
+    /// Number of times the reaction was added.
+    pub total_count: u32,
+}
+
+/// A change of a reaction on a message performed by a user.
+///
+/// The bot must be an administrator in the chat and must explicitly specify `message_reaction`
+/// in the list of `allowed_updates` to receive these updates.
+/// The update isn't received for reactions set by bots.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#messagereactionupdated)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageReactionUpdated {
+    /// The chat containing the message the user reacted to.
+    pub chat: Chat,
+    /// Unique identifier of the message inside the chat.
+    pub message_id: i64,
+    /// The user that changed the reaction, if the user isn't anonymous.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+    /// The chat on behalf of which the reaction was changed, if the user is anonymous.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_chat: Option<Chat>,
+    /// Date of the change in Unix time.
+    pub date: u64,
+    /// Previous list of reaction types that were set by the user.
+    pub old_reaction: Vec<ReactionType>,
+    /// New list of reaction types that have been set by the user.
+    pub new_reaction: Vec<ReactionType>,
+}
+
+impl MessageReactionUpdated {
+    /// Gets the reactions which the user has newly set.
+    pub fn added(&self) -> impl Iterator<Item = &ReactionType> {
+        self.new_reaction
+            .iter()
+            .filter(move |reaction| !self.old_reaction.contains(reaction))
+    }
+
+    /// Gets the reactions which the user has removed.
+    pub fn removed(&self) -> impl Iterator<Item = &ReactionType> {
+        self.old_reaction
+            .iter()
+            .filter(move |reaction| !self.new_reaction.contains(reaction))
+    }
+}
+
+/// Reaction changes on a message with anonymous reactions.
+///
+/// The bot must be an administrator in the chat and must explicitly specify `message_reaction_count`
+/// in the list of `allowed_updates` to receive these updates.
+/// The updates are grouped and can be sent with delay up to a few minutes.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#messagereactioncountupdated)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageReactionCountUpdated {
+    /// The chat containing the message.
+    pub chat: Chat,
+    /// Unique message identifier inside the chat.
+    pub message_id: i64,
+    /// Date of the change in Unix time.
+    pub date: u64,
+    /// List of reactions that are present on the message.
+    pub reactions: Vec<ReactionCount>,
+}
+
+/// Changes the chosen reactions on a message.
+///
+/// Service messages can't be reacted to.
+/// Automatically forwarded messages from a channel to its discussion group have the same available reactions as messages in the channel.
+/// Bots can't use paid reactions.
+///
+/// Returns `true` on success.
+///
+/// ```
+/// # use telbot_types::reaction::{ReactionType, SetMessageReaction};
+/// let request = SetMessageReaction::new(1234, 42).with_reaction(ReactionType::emoji("👍"));
+/// assert_eq!(
+///     serde_json::to_string(&request).unwrap(),
+///     r#"{"chat_id":1234,"message_id":42,"reaction":[{"type":"emoji","emoji":"👍"}]}"#
+/// );
+/// ```
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#setmessagereaction)
+#[derive(Clone, Serialize)]
+pub struct SetMessageReaction {
+    /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`).
+    pub chat_id: ChatId,
+    /// Identifier of the target message.
+    /// If the message belongs to a media group, the reaction is set to the first non-deleted message in the group instead.
+    pub message_id: i64,
+    /// A JSON-serialized list of reaction types to set on the message.
+    /// Currently, as non-premium users, bots can set up to one reaction per message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reaction: Option<Vec<ReactionType>>,
+    /// Pass `true` to set the reaction with a big animation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_big: Option<bool>,
+}
+
+impl SetMessageReaction {
+    /// Creates a new [`SetMessageReaction`] request which will remove the reactions of the bot,
+    /// unless a reaction is added.
+    pub fn new(chat_id: impl Into<ChatId>, message_id: i64) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            message_id,
+            reaction: None,
+            is_big: None,
+        }
+    }
+
+    /// Adds a reaction to set on the message.
+    pub fn with_reaction(mut self, reaction: ReactionType) -> Self {
+        self.reaction.get_or_insert_with(Vec::new).push(reaction);
+        self
+    }
+
+    /// Sets the reaction with a big animation.
+    pub fn is_big(self) -> Self {
+        Self {
+            is_big: Some(true),
+            ..self
+        }
+    }
+}
+
+telegram_method!(SetMessageReaction => "setMessageReaction", bool, json, chat_id);
@@ -0,0 +1,222 @@
+//! Polls held with reactions on a message.
+//!
+//! Instead of sending a native poll, a bot can post a message and count the reactions users put on it.
+//! [`ReactionPoll`] follows the reactions of each user from `message_reaction` updates,
+//! so that a user changing or removing a reaction is counted once, with the latest choice.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::message::Message;
+use crate::reaction::{MessageReactionUpdated, ReactionCount, ReactionType};
+use crate::update::{Update, UpdateKind};
+
+/// A poll counting some reactions on a message until the window closes.
+///
+/// The bot must be an administrator in the chat and must specify `message_reaction`
+/// in the list of allowed updates to receive the reactions of users.
+/// Reactions of anonymous administrators are counted by the chat they act on behalf of,
+/// but reactions in channels are only reported as counts and are not counted.
+///
+/// The window is checked with the dates of the updates, which are set by Telegram,
+/// so the poll does not depend on when the updates are handled.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use telbot_types::message::Message;
+/// # use telbot_types::reaction::{MessageReactionUpdated, ReactionType};
+/// # use telbot_types::reaction_poll::ReactionPoll;
+/// # let message: Message = serde_json::from_str(
+/// #     r#"{"message_id": 42, "date": 100, "chat": {"id": -100, "type": "group", "title": "Kiwi"}, "text": "Lunch?"}"#,
+/// # ).unwrap();
+/// # let reaction: MessageReactionUpdated = serde_json::from_str(r#"{
+/// #     "chat": {"id": -100, "type": "group", "title": "Kiwi"}, "message_id": 42,
+/// #     "user": {"id": 1, "is_bot": false, "first_name": "Kiwi"}, "date": 110,
+/// #     "old_reaction": [], "new_reaction": [{"type": "emoji", "emoji": "👍"}]
+/// # }"#).unwrap();
+/// let mut poll = ReactionPoll::new(
+///     &message,
+///     [ReactionType::emoji("👍"), ReactionType::emoji("👎")],
+///     Duration::from_secs(60),
+/// );
+/// assert!(poll.record(&reaction));
+/// let results = poll.results();
+/// assert_eq!(results[0].total_count, 1);
+/// assert_eq!(results[1].total_count, 0);
+/// assert!(poll.is_closed_at(160));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReactionPoll {
+    chat_id: i64,
+    message_id: i64,
+    options: Vec<ReactionType>,
+    opens_at: u64,
+    closes_at: u64,
+    /// Reactions among the options of each voter, by user identifier or chat identifier.
+    votes: HashMap<i64, Vec<ReactionType>>,
+}
+
+impl ReactionPoll {
+    /// Creates a new poll on the message, counting the reactions for `window` after it was sent.
+    pub fn new(
+        message: &Message,
+        options: impl IntoIterator<Item = ReactionType>,
+        window: Duration,
+    ) -> Self {
+        Self {
+            chat_id: message.chat.id,
+            message_id: message.message_id,
+            options: options.into_iter().collect(),
+            opens_at: message.date,
+            closes_at: message.date.saturating_add(window.as_secs()),
+            votes: HashMap::new(),
+        }
+    }
+
+    /// Gets the reactions which are counted.
+    pub fn options(&self) -> &[ReactionType] {
+        &self.options
+    }
+
+    /// Gets the Unix time at which the poll closes.
+    pub fn closes_at(&self) -> u64 {
+        self.closes_at
+    }
+
+    /// `true` if the poll is closed at the Unix time.
+    pub fn is_closed_at(&self, now: u64) -> bool {
+        now >= self.closes_at
+    }
+
+    /// Counts the reactions of the user, replacing the ones counted before.
+    ///
+    /// Returns `false` if the change is not on the message or not within the window.
+    pub fn record(&mut self, reaction: &MessageReactionUpdated) -> bool {
+        if reaction.chat.id != self.chat_id
+            || reaction.message_id != self.message_id
+            || reaction.date < self.opens_at
+            || self.is_closed_at(reaction.date)
+        {
+            return false;
+        }
+        let voter = match (&reaction.user, &reaction.actor_chat) {
+            (Some(user), _) => user.id,
+            (None, Some(chat)) => chat.id,
+            (None, None) => return false,
+        };
+        let chosen: Vec<_> = reaction
+            .new_reaction
+            .iter()
+            .filter(|reaction| self.options.contains(reaction))
+            .cloned()
+            .collect();
+        if chosen.is_empty() {
+            self.votes.remove(&voter);
+        } else {
+            self.votes.insert(voter, chosen);
+        }
+        true
+    }
+
+    /// Counts the reactions of the update, if it is a `message_reaction` update.
+    pub fn record_update(&mut self, update: &Update) -> bool {
+        match &update.kind {
+            UpdateKind::MessageReaction { message_reaction } => self.record(message_reaction),
+            _ => false,
+        }
+    }
+
+    /// Gets the number of users who chose at least one of the options.
+    pub fn voter_count(&self) -> usize {
+        self.votes.len()
+    }
+
+    /// Gets the number of users who chose each option, in the order of the options.
+    pub fn results(&self) -> Vec<ReactionCount> {
+        self.options
+            .iter()
+            .map(|option| ReactionCount {
+                kind: option.clone(),
+                total_count: self
+                    .votes
+                    .values()
+                    .filter(|chosen| chosen.contains(option))
+                    .count() as u32,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll() -> ReactionPoll {
+        let message: Message = serde_json::from_str(
+            r#"{"message_id": 42, "date": 100, "chat": {"id": -100, "type": "group", "title": "Kiwi"}, "text": "Lunch?"}"#,
+        )
+        .unwrap();
+        ReactionPoll::new(
+            &message,
+            vec![ReactionType::emoji("👍"), ReactionType::emoji("👎")],
+            Duration::from_secs(60),
+        )
+    }
+
+    fn reaction(user_id: i64, date: u64, new_reaction: &[&str]) -> MessageReactionUpdated {
+        let new_reaction: Vec<_> = new_reaction
+            .iter()
+            .map(|emoji| ReactionType::emoji(*emoji))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "chat": {"id": -100, "type": "group", "title": "Kiwi"},
+            "message_id": 42,
+            "user": {"id": user_id, "is_bot": false, "first_name": "Kiwi"},
+            "date": date,
+            "old_reaction": [],
+            "new_reaction": new_reaction,
+        }))
+        .unwrap()
+    }
+
+    fn counts(poll: &ReactionPoll) -> Vec<u32> {
+        poll.results()
+            .iter()
+            .map(|count| count.total_count)
+            .collect()
+    }
+
+    #[test]
+    fn counts_latest_reactions_of_each_user() {
+        let mut poll = poll();
+        assert!(poll.record(&reaction(1, 110, &["👍"])));
+        assert!(poll.record(&reaction(2, 110, &["👍", "🔥"])));
+        assert!(poll.record(&reaction(1, 120, &["👎"])));
+        assert_eq!(counts(&poll), [1, 1]);
+        assert_eq!(poll.voter_count(), 2);
+
+        assert!(poll.record(&reaction(2, 130, &["🔥"])));
+        assert_eq!(counts(&poll), [0, 1]);
+        assert_eq!(poll.voter_count(), 1);
+    }
+
+    #[test]
+    fn ignores_reactions_outside_window() {
+        let mut poll = poll();
+        assert!(!poll.record(&reaction(1, 99, &["👍"])));
+        assert!(poll.record(&reaction(2, 159, &["👍"])));
+        assert!(!poll.record(&reaction(3, 160, &["👍"])));
+        assert_eq!(counts(&poll), [1, 0]);
+        assert!(!poll.is_closed_at(159));
+        assert!(poll.is_closed_at(160));
+    }
+
+    #[test]
+    fn ignores_reactions_on_other_messages() {
+        let mut poll = poll();
+        let mut other = reaction(1, 110, &["👍"]);
+        other.message_id = 43;
+        assert!(!poll.record(&other));
+        assert_eq!(counts(&poll), [0, 0]);
+    }
+}
@@ -0,0 +1,210 @@
+//! Resolution of usernames to identifiers without extra API calls.
+//!
+//! Bots accepting `@username` input need the identifier of the user or chat to call most methods,
+//! but the Bot API can only resolve usernames of public chats with [`GetChat`](crate::chat::GetChat).
+//! [`Resolver`] remembers the usernames observed in updates and API responses instead.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::chat::{Chat, ChatId, Username};
+use crate::message::Message;
+use crate::ttl_map::TtlMap;
+use crate::update::{Update, UpdateKind};
+use crate::user::User;
+
+struct Entry {
+    id: i64,
+    username: String,
+}
+
+/// Caches username-identifier mappings of users and chats.
+///
+/// Usernames are compared case-insensitively, with or without the leading `@` or a `t.me` link prefix.
+/// A mapping expires `ttl` after it was last observed, since usernames can be changed or taken over.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use telbot_types::resolver::Resolver;
+/// let mut resolver = Resolver::new(Duration::from_secs(7 * 24 * 60 * 60));
+/// // for each update:
+/// // resolver.record_update(&update);
+/// resolver.record(1234, "Telbot");
+/// assert_eq!(resolver.resolve("@telbot"), Some(1234));
+/// assert_eq!(resolver.username(1234), Some("Telbot"));
+/// ```
+pub struct Resolver {
+    by_username: TtlMap<String, Entry>,
+    by_id: HashMap<i64, String>,
+    record_handler: Option<RecordHandler>,
+}
+
+type RecordHandler = Box<dyn FnMut(&str, i64) + Send>;
+
+fn normalize(username: &str) -> String {
+    match Username::parse(username) {
+        Ok(username) => username.into(),
+        Err(_) => username.trim_start_matches('@').to_lowercase(),
+    }
+}
+
+impl Resolver {
+    /// Creates a new resolver which forgets mappings `ttl` after they were last observed.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            by_username: TtlMap::new(ttl),
+            by_id: HashMap::new(),
+            record_handler: None,
+        }
+    }
+
+    /// Sets a handler which is called with the username and the identifier of each new or changed mapping.
+    ///
+    /// Useful for persisting mappings, which can be loaded again with [`Resolver::record`].
+    pub fn on_record(self, handler: impl FnMut(&str, i64) + Send + 'static) -> Self {
+        Self {
+            record_handler: Some(Box::new(handler)),
+            ..self
+        }
+    }
+
+    /// Records that the user or chat with the identifier has the username.
+    pub fn record(&mut self, id: i64, username: &str) {
+        self.record_at(id, username, Instant::now())
+    }
+
+    /// Records that the user or chat with the identifier has the username at `now`.
+    pub fn record_at(&mut self, id: i64, username: &str, now: Instant) {
+        let username = username.trim_start_matches('@');
+        let key = normalize(username);
+        let entry = Entry {
+            id,
+            username: username.to_string(),
+        };
+        if let Some(old) = self.by_username.peek(&key) {
+            if old.id == id && old.username == username {
+                self.by_username.insert_at(key, entry, now);
+                return;
+            }
+        }
+
+        // Both the identifier and the username may have been mapped to something else.
+        if let Some(old_key) = self.by_id.remove(&id) {
+            self.by_username.remove(&old_key);
+        }
+        if let Some(old) = self.by_username.remove(&key) {
+            self.by_id.remove(&old.id);
+        }
+        self.by_id.insert(id, key.clone());
+        self.by_username.insert_at(key, entry, now);
+        if let Some(handler) = &mut self.record_handler {
+            handler(username, id);
+        }
+    }
+
+    /// Records the username of a user, if any.
+    pub fn record_user(&mut self, user: &User) {
+        if let Some(username) = &user.username {
+            self.record(user.id, username);
+        }
+    }
+
+    /// Records the username of a chat, if any, e.g. from the response of [`GetChat`](crate::chat::GetChat).
+    pub fn record_chat(&mut self, chat: &Chat) {
+        if let Some(username) = &chat.username {
+            self.record(chat.id, username);
+        }
+    }
+
+    /// Records the usernames of the users and chats involved in a message.
+    pub fn record_message(&mut self, message: &Message) {
+        self.record_chat(&message.chat);
+        let users = [&message.from, &message.forward_from, &message.via_bot];
+        for user in users.iter().copied().flatten() {
+            self.record_user(user);
+        }
+        let chats = [&message.sender_chat, &message.forward_from_chat];
+        for chat in chats.iter().copied().flatten() {
+            self.record_chat(chat);
+        }
+    }
+
+    /// Records the usernames of the users and chats involved in an update.
+    pub fn record_update(&mut self, update: &Update) {
+        match &update.kind {
+            UpdateKind::Message { message: m }
+            | UpdateKind::EditedMessage { edited_message: m }
+            | UpdateKind::ChannelPost { channel_post: m }
+            | UpdateKind::EditedChannelPost {
+                edited_channel_post: m,
+            } => self.record_message(m),
+            UpdateKind::InlineQuery { inline_query } => self.record_user(&inline_query.from),
+            UpdateKind::CallbackQuery { callback_query } => {
+                self.record_user(&callback_query.from);
+                if let Some(message) = &callback_query.message {
+                    self.record_message(message);
+                }
+            }
+            UpdateKind::PollAnswer { poll_answer } => self.record_user(&poll_answer.user),
+            UpdateKind::MyChatMemberUpdated { my_chat_member: u }
+            | UpdateKind::ChatMemberUpdated { chat_member: u } => {
+                self.record_chat(&u.chat);
+                self.record_user(&u.from);
+                self.record_user(u.new_chat_member.user());
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolves a username, with or without the leading `@`, to the identifier.
+    pub fn resolve(&self, username: &str) -> Option<i64> {
+        self.resolve_at(username, Instant::now())
+    }
+
+    /// Resolves a username to the identifier at `now`.
+    pub fn resolve_at(&self, username: &str, now: Instant) -> Option<i64> {
+        self.by_username
+            .get_at(&normalize(username), now)
+            .map(|entry| entry.id)
+    }
+
+    /// Resolves a chat identifier given as a username to the numeric identifier.
+    pub fn resolve_chat_id(&self, chat_id: &ChatId) -> Option<i64> {
+        match chat_id {
+            ChatId::Id(id) => Some(*id),
+            ChatId::Username(username) => self.resolve(username),
+        }
+    }
+
+    /// Gets the last observed username of the user or chat with the identifier, without the leading `@`.
+    ///
+    /// The mapping may be already expired if [`Resolver::purge_expired`] is not called.
+    pub fn username(&self, id: i64) -> Option<&str> {
+        let key = self.by_id.get(&id)?;
+        self.by_username
+            .peek(key)
+            .map(|entry| entry.username.as_str())
+    }
+
+    /// Forgets mappings whose TTL has passed.
+    pub fn purge_expired(&mut self) {
+        self.purge_expired_at(Instant::now())
+    }
+
+    /// Forgets mappings whose TTL has passed at `now`.
+    pub fn purge_expired_at(&mut self, now: Instant) {
+        self.by_username.purge_expired_at(now);
+        let by_username = &self.by_username;
+        self.by_id.retain(|_, key| by_username.peek(key).is_some());
+    }
+
+    /// Gets the number of remembered mappings.
+    pub fn len(&self) -> usize {
+        self.by_username.len()
+    }
+
+    /// `true` if no mapping is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.by_username.is_empty()
+    }
+}
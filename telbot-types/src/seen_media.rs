@@ -0,0 +1,113 @@
+//! Deduplication of received media by their unique identifiers.
+//!
+//! [`FileUniqueId`] of a file is the same across chats and bots,
+//! so it can be used to detect reposts of the same media, e.g. for anti-spam bots.
+
+use std::time::{Duration, Instant};
+
+use crate::file::FileUniqueId;
+use crate::message::Message;
+use crate::ttl_map::TtlMap;
+
+/// Where and when a media was first seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sighting {
+    /// Identifier of the chat the media was seen in.
+    pub chat_id: i64,
+    /// Identifier of the message containing the media.
+    pub message_id: i64,
+    /// Time when the media was seen.
+    pub seen_at: Instant,
+}
+
+/// Remembers media for a while to detect duplicates.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use telbot_types::seen_media::SeenMedia;
+/// let mut seen = SeenMedia::new(Duration::from_secs(24 * 60 * 60));
+/// // for each received message:
+/// // if let Some(original) = seen.record_message(&message) { /* repost of `original` */ }
+/// seen.purge_expired();
+/// ```
+pub struct SeenMedia {
+    sightings: TtlMap<FileUniqueId, Sighting>,
+}
+
+impl SeenMedia {
+    /// Creates a new store which forgets media `ttl` after they are first seen.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            sightings: TtlMap::new(ttl),
+        }
+    }
+
+    /// Records the media of a message.
+    ///
+    /// Returns the first sighting if the same media was seen within the TTL,
+    /// or `None` if the media is new or the message has no media.
+    pub fn record_message(&mut self, message: &Message) -> Option<Sighting> {
+        let id = message.kind.file_unique_id()?;
+        self.record(id, message.chat.id, message.message_id)
+    }
+
+    /// Records a media with the given unique identifier.
+    ///
+    /// Returns the first sighting if the same media was seen within the TTL.
+    pub fn record(
+        &mut self,
+        id: impl Into<FileUniqueId>,
+        chat_id: i64,
+        message_id: i64,
+    ) -> Option<Sighting> {
+        self.record_at(id, chat_id, message_id, Instant::now())
+    }
+
+    /// Records a media with the given unique identifier as if it were seen at `now`.
+    pub fn record_at(
+        &mut self,
+        id: impl Into<FileUniqueId>,
+        chat_id: i64,
+        message_id: i64,
+        now: Instant,
+    ) -> Option<Sighting> {
+        let id = id.into();
+        if let Some(first) = self.sightings.get_at(&id, now) {
+            return Some(*first);
+        }
+        let sighting = Sighting {
+            chat_id,
+            message_id,
+            seen_at: now,
+        };
+        self.sightings.insert_at(id, sighting, now);
+        None
+    }
+
+    /// Gets the first sighting of the media with the given unique identifier, if it is remembered.
+    ///
+    /// The sighting may be already expired if [`SeenMedia::purge_expired`] is not called.
+    pub fn get(&self, id: &FileUniqueId) -> Option<&Sighting> {
+        self.sightings.peek(id)
+    }
+
+    /// Forgets media whose TTL has passed.
+    pub fn purge_expired(&mut self) {
+        self.purge_expired_at(Instant::now())
+    }
+
+    /// Forgets media whose TTL has passed at `now`.
+    pub fn purge_expired_at(&mut self, now: Instant) {
+        self.sightings.purge_expired_at(now)
+    }
+
+    /// Gets the number of remembered media.
+    pub fn len(&self) -> usize {
+        self.sightings.len()
+    }
+
+    /// `true` if no media is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.sightings.is_empty()
+    }
+}
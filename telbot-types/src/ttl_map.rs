@@ -0,0 +1,186 @@
+//! A map whose entries expire some time after they were inserted.
+//!
+//! The caches of this crate, like [`AdminCache`](crate::admin_cache::AdminCache)
+//! or [`Resolver`](crate::resolver::Resolver), are built on [`TtlMap`],
+//! which can also be used to remember other data fetched from the API for a while.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// A map whose entries expire `ttl` after they were inserted.
+///
+/// Expired entries are not returned, but stay in memory until [`TtlMap::purge_expired`] is called.
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use telbot_types::ttl_map::TtlMap;
+/// let mut map = TtlMap::new(Duration::from_secs(60));
+/// let now = Instant::now();
+/// map.insert_at(-100, "group", now);
+/// assert_eq!(map.get_at(&-100, now + Duration::from_secs(30)), Some(&"group"));
+/// assert_eq!(map.get_at(&-100, now + Duration::from_secs(60)), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TtlMap<K, V> {
+    ttl: Duration,
+    entries: HashMap<K, (V, Instant)>,
+}
+
+impl<K: Eq + Hash, V> TtlMap<K, V> {
+    /// Creates a new map which forgets entries `ttl` after they were inserted.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Gets the time entries are kept for.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Inserts the value, returning the previous value of the key if any, even if it is expired.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert_at(key, value, Instant::now())
+    }
+
+    /// Inserts the value at `now`.
+    pub fn insert_at(&mut self, key: K, value: V, now: Instant) -> Option<V> {
+        self.entries
+            .insert(key, (value, now))
+            .map(|(value, _)| value)
+    }
+
+    /// Gets the value of the key, or `None` if it is not inserted or expired.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.get_at(key, Instant::now())
+    }
+
+    /// Gets the value of the key at `now`.
+    pub fn get_at(&self, key: &K, now: Instant) -> Option<&V> {
+        let ttl = self.ttl;
+        self.entries
+            .get(key)
+            .filter(|(_, at)| is_alive(*at, now, ttl))
+            .map(|(value, _)| value)
+    }
+
+    /// Gets the value of the key to modify it, or `None` if it is not inserted or expired.
+    ///
+    /// Modifying the value does not extend its lifetime.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut_at(key, Instant::now())
+    }
+
+    /// Gets the value of the key to modify it at `now`.
+    pub fn get_mut_at(&mut self, key: &K, now: Instant) -> Option<&mut V> {
+        let ttl = self.ttl;
+        self.entries
+            .get_mut(key)
+            .filter(|(_, at)| is_alive(*at, now, ttl))
+            .map(|(value, _)| value)
+    }
+
+    /// Gets the value of the key even if it is expired, as long as it is not purged.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|(value, _)| value)
+    }
+
+    /// Removes the key, returning its value if any, even if it is expired.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.entries.remove(key).map(|(value, _)| value)
+    }
+
+    /// Forgets entries whose TTL has passed.
+    pub fn purge_expired(&mut self) {
+        self.purge_expired_at(Instant::now())
+    }
+
+    /// Forgets entries whose TTL has passed at `now`.
+    pub fn purge_expired_at(&mut self, now: Instant) {
+        let ttl = self.ttl;
+        self.entries.retain(|_, (_, at)| is_alive(*at, now, ttl));
+    }
+
+    /// Gets the number of entries, including expired ones which are not purged yet.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// `true` if there is no entry, including expired ones which are not purged yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+fn is_alive(inserted_at: Instant, now: Instant, ttl: Duration) -> bool {
+    now.saturating_duration_since(inserted_at) < ttl
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let mut map = TtlMap::new(TTL);
+        let now = Instant::now();
+        assert_eq!(map.insert_at(1, "a", now), None);
+        assert_eq!(map.get_at(&1, now), Some(&"a"));
+        assert_eq!(
+            map.get_at(&1, now + TTL - Duration::from_millis(1)),
+            Some(&"a")
+        );
+        assert_eq!(map.get_at(&1, now + TTL), None);
+        assert_eq!(map.get_at(&2, now), None);
+    }
+
+    #[test]
+    fn entries_inserted_after_now_are_alive() {
+        let mut map = TtlMap::new(TTL);
+        let now = Instant::now();
+        map.insert_at(1, "a", now + Duration::from_secs(1));
+        assert_eq!(map.get_at(&1, now), Some(&"a"));
+    }
+
+    #[test]
+    fn insert_again_extends_lifetime() {
+        let mut map = TtlMap::new(TTL);
+        let now = Instant::now();
+        map.insert_at(1, "a", now);
+        assert_eq!(map.insert_at(1, "b", now + TTL), Some("a"));
+        assert_eq!(map.get_at(&1, now + TTL), Some(&"b"));
+    }
+
+    #[test]
+    fn get_mut_keeps_lifetime() {
+        let mut map = TtlMap::new(TTL);
+        let now = Instant::now();
+        map.insert_at(1, 10, now);
+        *map.get_mut_at(&1, now + TTL / 2).unwrap() += 1;
+        assert_eq!(map.get_at(&1, now + TTL / 2), Some(&11));
+        assert_eq!(map.get_mut_at(&1, now + TTL), None);
+    }
+
+    #[test]
+    fn expired_entries_stay_until_purged() {
+        let mut map = TtlMap::new(TTL);
+        let now = Instant::now();
+        map.insert_at(1, "a", now);
+        map.insert_at(2, "b", now + TTL);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.peek(&1), Some(&"a"));
+
+        map.purge_expired_at(now + TTL);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.peek(&1), None);
+        assert_eq!(map.get_at(&2, now + TTL), Some(&"b"));
+
+        assert_eq!(map.remove(&2), Some("b"));
+        assert!(map.is_empty());
+    }
+}
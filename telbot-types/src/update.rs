@@ -1,17 +1,19 @@
 use serde::{Deserialize, Serialize};
 
-use crate::chat::ChatMemberUpdated;
+use crate::chat::{Chat, ChatJoinRequest, ChatMemberUpdated};
 use crate::message::{Message, Poll, PollAnswer};
 use crate::payment::{PreCheckoutQuery, ShippingQuery};
 use crate::query::{CallbackQuery, ChosenInlineResult, InlineQuery};
-use crate::{JsonMethod, TelegramMethod};
+use crate::reaction::{MessageReactionCountUpdated, MessageReactionUpdated};
+use crate::telegram_method;
+use crate::user::User;
 
 /// An incoming update.
 ///
 /// At most **one** of the optional parameters can be present in any given update.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#update)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Update {
     /// The update's unique identifier.
     /// Update identifiers start from a certain positive number and increase sequentially.
@@ -26,7 +28,7 @@ pub struct Update {
 }
 
 /// Type of update.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum UpdateKind {
     /// New incoming message of any kind — text, photo, sticker, etc.
@@ -67,9 +69,46 @@ pub enum UpdateKind {
     /// The bot must be an administrator in the chat and must explicitly specify “chat_member”
     /// in the list of *allowed_updates* to receive these updates.
     ChatMemberUpdated { chat_member: ChatMemberUpdated },
+    /// A request to join the chat has been sent.
+    /// The bot must have the *can_invite_users* administrator right in the chat to receive these updates.
+    ChatJoinRequest { chat_join_request: ChatJoinRequest },
+    /// A reaction to a message was changed by a user.
+    /// The bot must be an administrator in the chat and must explicitly specify “message_reaction”
+    /// in the list of *allowed_updates* to receive these updates.
+    MessageReaction {
+        message_reaction: MessageReactionUpdated,
+    },
+    /// Reactions to a message with anonymous reactions were changed.
+    /// The bot must be an administrator in the chat and must explicitly specify “message_reaction_count”
+    /// in the list of *allowed_updates* to receive these updates.
+    MessageReactionCount {
+        message_reaction_count: MessageReactionCountUpdated,
+    },
 }
 
 impl UpdateKind {
+    /// Gets the type of the update, to be compared with `allowed_updates`.
+    pub fn allowed_update(&self) -> AllowedUpdate {
+        match self {
+            Self::Message { .. } => AllowedUpdate::Message,
+            Self::EditedMessage { .. } => AllowedUpdate::EditedMessage,
+            Self::ChannelPost { .. } => AllowedUpdate::ChannelPost,
+            Self::EditedChannelPost { .. } => AllowedUpdate::EditedChannelPost,
+            Self::InlineQuery { .. } => AllowedUpdate::InlineQuery,
+            Self::ChosenInlineResult { .. } => AllowedUpdate::ChosenInlineResult,
+            Self::CallbackQuery { .. } => AllowedUpdate::CallbackQuery,
+            Self::ShippingQuery { .. } => AllowedUpdate::ShippingQuery,
+            Self::PreCheckoutQuery { .. } => AllowedUpdate::PreCheckoutQuery,
+            Self::Poll { .. } => AllowedUpdate::Poll,
+            Self::PollAnswer { .. } => AllowedUpdate::PollAnswer,
+            Self::MyChatMemberUpdated { .. } => AllowedUpdate::MyChatMember,
+            Self::ChatMemberUpdated { .. } => AllowedUpdate::ChatMember,
+            Self::ChatJoinRequest { .. } => AllowedUpdate::ChatJoinRequest,
+            Self::MessageReaction { .. } => AllowedUpdate::MessageReaction,
+            Self::MessageReactionCount { .. } => AllowedUpdate::MessageReactionCount,
+        }
+    }
+
     /// Gets the message associated with this update, if any.
     pub fn message(&self) -> Option<&Message> {
         match self {
@@ -104,6 +143,60 @@ impl UpdateKind {
         }
     }
 
+    /// Gets the message, edited message, channel post or edited channel post associated with this update, if any.
+    pub fn any_message(&self) -> Option<&Message> {
+        match self {
+            Self::Message { message }
+            | Self::EditedMessage {
+                edited_message: message,
+            }
+            | Self::ChannelPost {
+                channel_post: message,
+            }
+            | Self::EditedChannelPost {
+                edited_channel_post: message,
+            } => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Gets the chat the update happened in, if any.
+    ///
+    /// For callback queries, this is the chat of the message with the button, if it is not an inline message.
+    pub fn effective_chat(&self) -> Option<&Chat> {
+        match self {
+            Self::CallbackQuery { callback_query } => {
+                callback_query.message.as_ref().map(|message| &message.chat)
+            }
+            Self::MyChatMemberUpdated { my_chat_member: u }
+            | Self::ChatMemberUpdated { chat_member: u } => Some(&u.chat),
+            Self::ChatJoinRequest { chat_join_request } => Some(&chat_join_request.chat),
+            Self::MessageReaction { message_reaction } => Some(&message_reaction.chat),
+            Self::MessageReactionCount {
+                message_reaction_count,
+            } => Some(&message_reaction_count.chat),
+            _ => self.any_message().map(|message| &message.chat),
+        }
+    }
+
+    /// Gets the user who caused the update, if any.
+    ///
+    /// Messages sent on behalf of chats, like channel posts, have no user.
+    pub fn effective_user(&self) -> Option<&User> {
+        match self {
+            Self::InlineQuery { inline_query } => Some(&inline_query.from),
+            Self::CallbackQuery { callback_query } => Some(&callback_query.from),
+            Self::ShippingQuery { shipping_query } => Some(&shipping_query.from),
+            Self::PreCheckoutQuery { pre_checkout_query } => Some(&pre_checkout_query.from),
+            Self::PollAnswer { poll_answer } => Some(&poll_answer.user),
+            Self::MyChatMemberUpdated { my_chat_member: u }
+            | Self::ChatMemberUpdated { chat_member: u } => Some(&u.from),
+            Self::ChatJoinRequest { chat_join_request } => Some(&chat_join_request.from),
+            Self::MessageReaction { message_reaction } => message_reaction.user.as_ref(),
+            _ => self.any_message()?.from.as_ref(),
+        }
+    }
+
     /// Gets the inline query associated with this update, if any.
     pub fn inline_query(&self) -> Option<&InlineQuery> {
         match self {
@@ -178,6 +271,32 @@ impl UpdateKind {
         }
     }
 
+    /// Gets the chat join request associated with this update, if any.
+    pub fn chat_join_request(&self) -> Option<&ChatJoinRequest> {
+        match self {
+            Self::ChatJoinRequest { chat_join_request } => Some(chat_join_request),
+            _ => None,
+        }
+    }
+
+    /// Gets the reaction change associated with this update, if any.
+    pub fn message_reaction(&self) -> Option<&MessageReactionUpdated> {
+        match self {
+            Self::MessageReaction { message_reaction } => Some(message_reaction),
+            _ => None,
+        }
+    }
+
+    /// Gets the anonymous reaction change associated with this update, if any.
+    pub fn message_reaction_count(&self) -> Option<&MessageReactionCountUpdated> {
+        match self {
+            Self::MessageReactionCount {
+                message_reaction_count,
+            } => Some(message_reaction_count),
+            _ => None,
+        }
+    }
+
     /// `true` if it is a message update.
     pub fn is_message(&self) -> bool {
         matches!(self, Self::Message { .. })
@@ -242,6 +361,181 @@ impl UpdateKind {
     pub fn is_chat_member_updated(&self) -> bool {
         matches!(self, Self::ChatMemberUpdated { .. })
     }
+
+    /// `true` if it is a chat join request update.
+    pub fn is_chat_join_request(&self) -> bool {
+        matches!(self, Self::ChatJoinRequest { .. })
+    }
+
+    /// `true` if it is a message reaction update.
+    pub fn is_message_reaction(&self) -> bool {
+        matches!(self, Self::MessageReaction { .. })
+    }
+
+    /// `true` if it is a message reaction count update.
+    pub fn is_message_reaction_count(&self) -> bool {
+        matches!(self, Self::MessageReactionCount { .. })
+    }
+}
+
+/// Type of updates to receive, given in `allowed_updates` of [`GetUpdates`] and
+/// [`SetWebhook`](crate::webhook::SetWebhook).
+///
+/// Serialized as the name of the update field, like `"message"` or `"chat_member"`.
+///
+/// ```
+/// # use telbot_types::update::{AllowedUpdate, GetUpdates};
+/// let get_updates = GetUpdates::new()
+///     .with_allowed_update(AllowedUpdate::Message)
+///     .with_allowed_update(AllowedUpdate::ChatMember);
+/// assert_eq!(
+///     serde_json::to_string(&get_updates).unwrap(),
+///     r#"{"allowed_updates":["message","chat_member"]}"#
+/// );
+/// assert_eq!(AllowedUpdate::from("callback_query"), AllowedUpdate::CallbackQuery);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum AllowedUpdate {
+    /// New messages.
+    Message,
+    /// Edited messages.
+    EditedMessage,
+    /// New channel posts.
+    ChannelPost,
+    /// Edited channel posts.
+    EditedChannelPost,
+    /// Changes of reactions to messages by users.
+    MessageReaction,
+    /// Changes of anonymous reactions to messages.
+    MessageReactionCount,
+    /// Inline queries.
+    InlineQuery,
+    /// Results of inline queries chosen by users.
+    ChosenInlineResult,
+    /// Callback queries.
+    CallbackQuery,
+    /// Shipping queries.
+    ShippingQuery,
+    /// Pre-checkout queries.
+    PreCheckoutQuery,
+    /// Changes of poll states.
+    Poll,
+    /// Changes of answers in non-anonymous polls.
+    PollAnswer,
+    /// Changes of the bot's own member status.
+    MyChatMember,
+    /// Changes of other members' status, which are not received by default.
+    ChatMember,
+    /// Requests to join chats.
+    ChatJoinRequest,
+    /// Added or changed chat boosts.
+    ChatBoost,
+    /// Removed chat boosts.
+    RemovedChatBoost,
+    /// Update type unknown to this library.
+    Other(String),
+}
+
+impl AllowedUpdate {
+    /// All update types known to this library, including those not received by default like `chat_member`.
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Message,
+            Self::EditedMessage,
+            Self::ChannelPost,
+            Self::EditedChannelPost,
+            Self::MessageReaction,
+            Self::MessageReactionCount,
+            Self::InlineQuery,
+            Self::ChosenInlineResult,
+            Self::CallbackQuery,
+            Self::ShippingQuery,
+            Self::PreCheckoutQuery,
+            Self::Poll,
+            Self::PollAnswer,
+            Self::MyChatMember,
+            Self::ChatMember,
+            Self::ChatJoinRequest,
+            Self::ChatBoost,
+            Self::RemovedChatBoost,
+        ]
+    }
+
+    /// Gets the name of the update type, like `"message"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Message => "message",
+            Self::EditedMessage => "edited_message",
+            Self::ChannelPost => "channel_post",
+            Self::EditedChannelPost => "edited_channel_post",
+            Self::MessageReaction => "message_reaction",
+            Self::MessageReactionCount => "message_reaction_count",
+            Self::InlineQuery => "inline_query",
+            Self::ChosenInlineResult => "chosen_inline_result",
+            Self::CallbackQuery => "callback_query",
+            Self::ShippingQuery => "shipping_query",
+            Self::PreCheckoutQuery => "pre_checkout_query",
+            Self::Poll => "poll",
+            Self::PollAnswer => "poll_answer",
+            Self::MyChatMember => "my_chat_member",
+            Self::ChatMember => "chat_member",
+            Self::ChatJoinRequest => "chat_join_request",
+            Self::ChatBoost => "chat_boost",
+            Self::RemovedChatBoost => "removed_chat_boost",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for AllowedUpdate {
+    fn from(name: &str) -> Self {
+        match name {
+            "message" => Self::Message,
+            "edited_message" => Self::EditedMessage,
+            "channel_post" => Self::ChannelPost,
+            "edited_channel_post" => Self::EditedChannelPost,
+            "message_reaction" => Self::MessageReaction,
+            "message_reaction_count" => Self::MessageReactionCount,
+            "inline_query" => Self::InlineQuery,
+            "chosen_inline_result" => Self::ChosenInlineResult,
+            "callback_query" => Self::CallbackQuery,
+            "shipping_query" => Self::ShippingQuery,
+            "pre_checkout_query" => Self::PreCheckoutQuery,
+            "poll" => Self::Poll,
+            "poll_answer" => Self::PollAnswer,
+            "my_chat_member" => Self::MyChatMember,
+            "chat_member" => Self::ChatMember,
+            "chat_join_request" => Self::ChatJoinRequest,
+            "chat_boost" => Self::ChatBoost,
+            "removed_chat_boost" => Self::RemovedChatBoost,
+            _ => Self::Other(name.to_string()),
+        }
+    }
+}
+
+impl From<String> for AllowedUpdate {
+    fn from(name: String) -> Self {
+        match Self::from(name.as_str()) {
+            Self::Other(_) => Self::Other(name),
+            known => known,
+        }
+    }
+}
+
+impl From<AllowedUpdate> for String {
+    fn from(update: AllowedUpdate) -> Self {
+        match update {
+            AllowedUpdate::Other(name) => name,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for AllowedUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Receives incoming updates using long polling ([wiki](https://en.wikipedia.org/wiki/Push_technology#Long_polling)).
@@ -275,7 +569,7 @@ pub struct GetUpdates {
     /// Please note that this parameter doesn't affect updates created before the call to the getUpdates,
     /// so unwanted updates may be received for a short period of time.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub allowed_updates: Option<Vec<String>>,
+    pub allowed_updates: Option<Vec<AllowedUpdate>>,
 }
 
 impl GetUpdates {
@@ -310,29 +604,18 @@ impl GetUpdates {
         }
     }
     /// Sets allowed updates.
-    pub fn with_allowed_updates(self, updates: Vec<String>) -> Self {
+    pub fn with_allowed_updates(self, updates: Vec<AllowedUpdate>) -> Self {
         Self {
             allowed_updates: Some(updates),
             ..self
         }
     }
     /// Adds one allowed update.
-    pub fn with_allowed_update(mut self, update: impl Into<String>) -> Self {
+    pub fn with_allowed_update(mut self, update: impl Into<AllowedUpdate>) -> Self {
         let updates = self.allowed_updates.get_or_insert_with(Default::default);
         updates.push(update.into());
-        Self {
-            allowed_updates: self.allowed_updates,
-            ..self
-        }
-    }
-}
-
-impl TelegramMethod for GetUpdates {
-    type Response = Vec<Update>;
-
-    fn name() -> &'static str {
-        "getUpdates"
+        self
     }
 }
 
-impl JsonMethod for GetUpdates {}
+telegram_method!(GetUpdates => "getUpdates", Vec<Update>, json);
@@ -6,7 +6,16 @@ use crate::chat::{
     UnbanChatMember,
 };
 use crate::file::PhotoSize;
-use crate::{JsonMethod, TelegramMethod};
+use crate::markup::ParseMode;
+use crate::telegram_method;
+
+/// Identifier of the Telegram service account,
+/// which appears as the sender of messages automatically forwarded from a linked channel.
+pub const TELEGRAM_USER_ID: i64 = 777000;
+
+/// Identifier of `@GroupAnonymousBot`,
+/// which appears as the sender of messages from anonymous group administrators.
+pub const GROUP_ANONYMOUS_BOT_ID: i64 = 1087968824;
 
 /// A Telegram user or bot.
 ///
@@ -43,6 +52,48 @@ pub struct User {
 }
 
 impl User {
+    /// Gets the full name of this user, joining first name and last name.
+    pub fn full_name(&self) -> String {
+        match &self.last_name {
+            Some(last_name) => format!("{} {}", self.first_name, last_name),
+            None => self.first_name.clone(),
+        }
+    }
+
+    /// Formats a mention of this user in [`ParseMode::MarkdownV2`].
+    ///
+    /// Mentions by `@username` if the user has one,
+    /// since `tg://user?id=` links only work for users whose privacy settings allow it.
+    /// Otherwise, links the escaped full name to the user's identifier.
+    pub fn mention_markdown_v2(&self) -> String {
+        let mode = ParseMode::MarkdownV2;
+        match &self.username {
+            Some(username) => format!("@{}", mode.escape(username)),
+            None => format!(
+                "[{}](tg://user?id={})",
+                mode.escape(self.full_name()),
+                self.id
+            ),
+        }
+    }
+
+    /// Formats a mention of this user in [`ParseMode::HTML`].
+    ///
+    /// Mentions by `@username` if the user has one,
+    /// since `tg://user?id=` links only work for users whose privacy settings allow it.
+    /// Otherwise, links the escaped full name to the user's identifier.
+    pub fn mention_html(&self) -> String {
+        let mode = ParseMode::HTML;
+        match &self.username {
+            Some(username) => format!("@{}", mode.escape(username)),
+            None => format!(
+                "<a href=\"tg://user?id={}\">{}</a>",
+                self.id,
+                mode.escape(self.full_name())
+            ),
+        }
+    }
+
     /// Creates a new [`GetUserProfilePhotos`] request that gets the profile photos of this user.
     pub fn get_profile_photos(&self) -> GetUserProfilePhotos {
         GetUserProfilePhotos::new(self.id)
@@ -98,7 +149,7 @@ impl User {
 }
 
 /// A user's profile pictures.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserProfilePhotos {
     /// Total number of profile pictures the target user has.
     pub total_count: usize,
@@ -148,12 +199,4 @@ impl GetUserProfilePhotos {
     }
 }
 
-impl TelegramMethod for GetUserProfilePhotos {
-    type Response = UserProfilePhotos;
-
-    fn name() -> &'static str {
-        "getUserProfilePhotos"
-    }
-}
-
-impl JsonMethod for GetUserProfilePhotos {}
+telegram_method!(GetUserProfilePhotos => "getUserProfilePhotos", UserProfilePhotos, json);
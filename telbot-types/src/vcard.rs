@@ -0,0 +1,399 @@
+//! Parsing and building [vCard](https://en.wikipedia.org/wiki/VCard) attached to contacts.
+//!
+//! Only the properties commonly used by Telegram clients are supported;
+//! other properties are ignored while parsing.
+
+use std::fmt;
+
+/// A structured vCard.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VCard {
+    /// Formatted name. (`FN`)
+    pub full_name: Option<String>,
+    /// Given name. (`N`)
+    pub first_name: Option<String>,
+    /// Family name. (`N`)
+    pub last_name: Option<String>,
+    /// Phone numbers. (`TEL`)
+    pub phones: Vec<VCardPhone>,
+    /// Email addresses. (`EMAIL`)
+    pub emails: Vec<String>,
+    /// Organization name. (`ORG`)
+    pub organization: Option<String>,
+    /// Job title. (`TITLE`)
+    pub title: Option<String>,
+    /// Web page addresses. (`URL`)
+    pub urls: Vec<String>,
+    /// Free-form note. (`NOTE`)
+    pub note: Option<String>,
+}
+
+/// A phone number in vCard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VCardPhone {
+    /// Phone type such as `CELL`, `HOME` or `WORK`, if specified.
+    pub kind: Option<String>,
+    /// Phone number.
+    pub number: String,
+}
+
+/// Error that can occur while parsing vCard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VCardError {
+    /// `BEGIN:VCARD` line is missing.
+    MissingBegin,
+    /// `END:VCARD` line is missing.
+    MissingEnd,
+    /// A line is not in the form of `NAME:VALUE`.
+    InvalidLine(String),
+}
+
+impl fmt::Display for VCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingBegin => write!(f, "missing BEGIN:VCARD"),
+            Self::MissingEnd => write!(f, "missing END:VCARD"),
+            Self::InvalidLine(line) => write!(f, "invalid vCard line: {}", line),
+        }
+    }
+}
+
+impl std::error::Error for VCardError {}
+
+impl VCard {
+    /// Creates a new vCard with the given name.
+    pub fn new(first_name: impl Into<String>) -> Self {
+        let first_name = first_name.into();
+        Self {
+            full_name: Some(first_name.clone()),
+            first_name: Some(first_name),
+            ..Default::default()
+        }
+    }
+
+    /// Sets last name.
+    pub fn with_last_name(self, last_name: impl Into<String>) -> Self {
+        let last_name = last_name.into();
+        let full_name = match &self.first_name {
+            Some(first_name) => format!("{} {}", first_name, last_name),
+            None => last_name.clone(),
+        };
+        Self {
+            full_name: Some(full_name),
+            last_name: Some(last_name),
+            ..self
+        }
+    }
+
+    /// Adds a phone number.
+    pub fn with_phone(mut self, kind: Option<&str>, number: impl Into<String>) -> Self {
+        self.phones.push(VCardPhone {
+            kind: kind.map(str::to_string),
+            number: number.into(),
+        });
+        self
+    }
+
+    /// Adds an email address.
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.emails.push(email.into());
+        self
+    }
+
+    /// Sets organization name.
+    pub fn with_organization(self, organization: impl Into<String>) -> Self {
+        Self {
+            organization: Some(organization.into()),
+            ..self
+        }
+    }
+
+    /// Sets job title.
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Adds a web page address.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.urls.push(url.into());
+        self
+    }
+
+    /// Sets note.
+    pub fn with_note(self, note: impl Into<String>) -> Self {
+        Self {
+            note: Some(note.into()),
+            ..self
+        }
+    }
+
+    /// Parses vCard text.
+    pub fn parse(text: &str) -> Result<Self, VCardError> {
+        let mut lines: Vec<String> = vec![];
+        for line in text.lines() {
+            let line = line.trim_end_matches('\r');
+            match (
+                line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')),
+                lines.last_mut(),
+            ) {
+                (Some(continuation), Some(last)) => last.push_str(continuation),
+                _ if line.is_empty() => {}
+                _ => lines.push(line.to_string()),
+            }
+        }
+
+        let mut lines = lines.into_iter();
+        match lines.next() {
+            Some(line) if line.eq_ignore_ascii_case("BEGIN:VCARD") => {}
+            _ => return Err(VCardError::MissingBegin),
+        }
+
+        let mut vcard = Self::default();
+        for line in lines {
+            if line.eq_ignore_ascii_case("END:VCARD") {
+                return Ok(vcard);
+            }
+            let (head, value) =
+                split_unescaped(&line, ':').ok_or_else(|| VCardError::InvalidLine(line.clone()))?;
+            let mut params = split_params(head).into_iter();
+            let name = params.next().unwrap_or_default();
+            // Strip group prefix like `item1.TEL`.
+            let name = name
+                .rsplit('.')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_uppercase();
+            match name.as_str() {
+                "FN" => vcard.full_name = Some(unescape(value)),
+                "N" => {
+                    let mut parts = split_components(value).into_iter().map(unescape);
+                    vcard.last_name = parts.next().filter(|part| !part.is_empty());
+                    vcard.first_name = parts.next().filter(|part| !part.is_empty());
+                }
+                "TEL" => vcard.phones.push(VCardPhone {
+                    kind: params
+                        .filter_map(|param| {
+                            let param = param.to_ascii_uppercase();
+                            match param.strip_prefix("TYPE=") {
+                                Some(kind) => Some(kind.trim_matches('"').to_string()),
+                                None if !param.contains('=') => Some(param),
+                                None => None,
+                            }
+                        })
+                        .find(|kind| kind != "PREF" && kind != "VOICE"),
+                    number: unescape(value),
+                }),
+                "EMAIL" => vcard.emails.push(unescape(value)),
+                "ORG" => {
+                    vcard.organization = split_components(value).first().map(|name| unescape(name))
+                }
+                "TITLE" => vcard.title = Some(unescape(value)),
+                "URL" => vcard.urls.push(unescape(value)),
+                "NOTE" => vcard.note = Some(unescape(value)),
+                _ => {}
+            }
+        }
+        Err(VCardError::MissingEnd)
+    }
+}
+
+impl fmt::Display for VCard {
+    /// Formats vCard in version 3.0.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BEGIN:VCARD\r\nVERSION:3.0\r\n")?;
+        let full_name =
+            self.full_name
+                .clone()
+                .unwrap_or_else(|| match (&self.first_name, &self.last_name) {
+                    (Some(first), Some(last)) => format!("{} {}", first, last),
+                    (Some(name), None) | (None, Some(name)) => name.clone(),
+                    (None, None) => String::new(),
+                });
+        write!(f, "FN:{}\r\n", escape(&full_name))?;
+        write!(
+            f,
+            "N:{};{};;;\r\n",
+            escape(self.last_name.as_deref().unwrap_or_default()),
+            escape(self.first_name.as_deref().unwrap_or_default())
+        )?;
+        for phone in &self.phones {
+            match &phone.kind {
+                Some(kind) => write!(
+                    f,
+                    "TEL;TYPE={}:{}\r\n",
+                    escape_param(kind),
+                    escape(&phone.number)
+                )?,
+                None => write!(f, "TEL:{}\r\n", escape(&phone.number))?,
+            }
+        }
+        for email in &self.emails {
+            write!(f, "EMAIL:{}\r\n", escape(email))?;
+        }
+        if let Some(organization) = &self.organization {
+            write!(f, "ORG:{}\r\n", escape(organization))?;
+        }
+        if let Some(title) = &self.title {
+            write!(f, "TITLE:{}\r\n", escape(title))?;
+        }
+        for url in &self.urls {
+            write!(f, "URL:{}\r\n", escape(url))?;
+        }
+        if let Some(note) = &self.note {
+            write!(f, "NOTE:{}\r\n", escape(note))?;
+        }
+        write!(f, "END:VCARD")
+    }
+}
+
+impl From<VCard> for String {
+    fn from(vcard: VCard) -> Self {
+        vcard.to_string()
+    }
+}
+
+fn split_unescaped(line: &str, delimiter: char) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => return Some((&line[..index], &line[index + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits parameters of a property at semicolons which are not in quotes.
+fn split_params(head: &str) -> Vec<&str> {
+    let mut params = vec![];
+    let mut rest = head;
+    while let Some((param, next)) = split_unescaped(rest, ';') {
+        params.push(param);
+        rest = next;
+    }
+    params.push(rest);
+    params
+}
+
+/// Splits a structured value like `N` or `ORG` at semicolons which are not escaped.
+fn split_components(value: &str) -> Vec<&str> {
+    let mut components = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ';' => {
+                components.push(&value[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    components.push(&value[start..]);
+    components
+}
+
+/// Escapes a parameter value, which is quoted if it contains delimiters and cannot contain quotes or line breaks.
+fn escape_param(value: &str) -> String {
+    let value: String = value
+        .chars()
+        .filter(|c| !matches!(c, '"' | '\r' | '\n'))
+        .collect();
+    if value.contains(&[';', ':', ','][..]) {
+        format!("\"{}\"", value)
+    } else {
+        value
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | ',' | ';' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => unescaped.push('\n'),
+                Some(c) => unescaped.push(c),
+                None => unescaped.push('\\'),
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_and_organization_keep_escaped_semicolons() {
+        let vcard = VCard::parse(
+            "BEGIN:VCARD\r\nN:Doe\\;Smith;John;;;\r\nORG:Foo\\; Bar;Sales\r\nEND:VCARD",
+        )
+        .unwrap();
+        assert_eq!(vcard.last_name.as_deref(), Some("Doe;Smith"));
+        assert_eq!(vcard.first_name.as_deref(), Some("John"));
+        assert_eq!(vcard.organization.as_deref(), Some("Foo; Bar"));
+    }
+
+    #[test]
+    fn escaped_values_survive_round_trip() {
+        let vcard = VCard::new("John;Jr")
+            .with_last_name("Doe, Smith")
+            .with_organization("Foo; Bar")
+            .with_note("line\nbreak");
+        assert_eq!(VCard::parse(&vcard.to_string()).unwrap(), vcard);
+    }
+
+    #[test]
+    fn phone_type_is_escaped() {
+        let vcard = VCard::new("John").with_phone(Some("WORK;X:\"Y\""), "+1234");
+        let text = vcard.to_string();
+        assert!(text.contains("TEL;TYPE=\"WORK;X:Y\":+1234\r\n"));
+        let parsed = VCard::parse(&text).unwrap();
+        assert_eq!(parsed.phones[0].kind.as_deref(), Some("WORK;X:Y"));
+        assert_eq!(parsed.phones[0].number, "+1234");
+
+        let vcard = VCard::new("John").with_phone(Some("CELL\r\nEND:VCARD"), "+1234");
+        let parsed = VCard::parse(&vcard.to_string()).unwrap();
+        assert_eq!(parsed.phones.len(), 1);
+    }
+
+    #[test]
+    fn url_is_escaped() {
+        let vcard =
+            VCard::new("John").with_url("https://example.com/a,b\r\nEMAIL:evil@example.com");
+        let text = vcard.to_string();
+        assert!(text.contains("URL:https://example.com/a\\,b\\nEMAIL:evil@example.com\r\n"));
+        let parsed = VCard::parse(&text).unwrap();
+        assert!(parsed.emails.is_empty());
+        assert_eq!(
+            parsed.urls,
+            ["https://example.com/a,b\nEMAIL:evil@example.com"]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Types, requests, and validation of [Web Apps](https://core.telegram.org/bots/webapps), also known as Mini Apps.
+//!
+//! Web Apps are opened from [`WebAppInfo`] buttons, and send [`WebAppData`] back to the bot,
+//! or answer the inline query of the Web App with [`AnswerWebAppQuery`].
+//! Requests from a Web App to its backend carry the `initData` string signed with the bot token,
+//! which must be verified with [`WebAppInitData::verify`] (requires the `webapp` feature) before trusting it.
+
+#[cfg(feature = "webapp")]
+use std::collections::BTreeMap;
+#[cfg(feature = "webapp")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "webapp")]
+use hmac_sha256::HMAC;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "webapp")]
+use crate::{decode_hex, percent_decode};
+
+#[cfg(feature = "inline")]
+mod requests;
+#[cfg(feature = "inline")]
+pub use requests::*;
+
+/// Information about a [Web App](https://core.telegram.org/bots/webapps).
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#webappinfo)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebAppInfo {
+    /// An HTTPS URL of a Web App to be opened with additional data as specified in
+    /// [Initializing Web Apps](https://core.telegram.org/bots/webapps#initializing-mini-apps).
+    pub url: String,
+}
+
+impl WebAppInfo {
+    /// Creates a new [`WebAppInfo`] which will open the URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+/// Data sent from a [Web App](https://core.telegram.org/bots/webapps) to the bot.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#webappdata)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebAppData {
+    /// The data. Be aware that a bad client can send arbitrary data in this field.
+    pub data: String,
+    /// Text of the `web_app` keyboard button from which the Web App was opened.
+    /// Be aware that a bad client can send arbitrary data in this field.
+    pub button_text: String,
+}
+
+/// Information about an inline message sent by a [Web App](https://core.telegram.org/bots/webapps) on behalf of a user.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#sentwebappmessage)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentWebAppMessage {
+    /// Identifier of the sent inline message.
+    /// Available only if there is an inline keyboard attached to the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline_message_id: Option<String>,
+}
+
+/// A user in the `initData` of a Web App.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/webapps#webappuser)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebAppUser {
+    /// Unique identifier for the user or bot.
+    pub id: i64,
+    /// `true`, if the user is a bot. Returned in the `receiver` field only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_bot: Option<bool>,
+    /// First name of the user or bot.
+    pub first_name: String,
+    /// Last name of the user or bot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    /// Username of the user or bot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// [IETF language tag](https://en.wikipedia.org/wiki/IETF_language_tag) of the user's language.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language_code: Option<String>,
+    /// `true`, if the user is a Telegram Premium user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_premium: Option<bool>,
+    /// `true`, if the user allowed the bot to message them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allows_write_to_pm: Option<bool>,
+    /// URL of the user's profile photo.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_url: Option<String>,
+}
+
+/// A chat in the `initData` of a Web App.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/webapps#webappchat)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebAppChat {
+    /// Unique identifier for the chat.
+    pub id: i64,
+    /// Type of the chat, one of `group`, `supergroup` or `channel`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Title of the chat.
+    pub title: String,
+    /// Username of the chat.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// URL of the chat's photo.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_url: Option<String>,
+}
+
+/// Verified `initData` passed to a Web App.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/webapps#webappinitdata)
+#[cfg(feature = "webapp")]
+#[derive(Debug, Clone)]
+pub struct WebAppInitData {
+    /// A unique identifier for the Web App session, required for sending messages via [`AnswerWebAppQuery`].
+    pub query_id: Option<String>,
+    /// Data of the current user.
+    pub user: Option<WebAppUser>,
+    /// Data of the chat partner of the current user in the chat where the bot was launched via the attachment menu.
+    pub receiver: Option<WebAppUser>,
+    /// Data of the chat where the bot was launched via the attachment menu.
+    pub chat: Option<WebAppChat>,
+    /// Type of the chat from which the Web App was opened.
+    pub chat_type: Option<String>,
+    /// Global identifier, uniquely corresponding to the chat from which the Web App was opened.
+    pub chat_instance: Option<String>,
+    /// The value of the `startattach` or `startapp` parameter passed via link.
+    pub start_param: Option<String>,
+    /// Time in seconds, after which a message can be sent via [`AnswerWebAppQuery`].
+    pub can_send_after: Option<u64>,
+    /// Unix time when the form was opened.
+    pub auth_date: u64,
+}
+
+/// Error that can occur while verifying `initData`.
+#[cfg(feature = "webapp")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitDataError {
+    /// A required field is missing.
+    MissingField(&'static str),
+    /// A field has a malformed value.
+    InvalidField(&'static str),
+    /// The hash does not match the data, i.e. the data is not sent by Telegram.
+    HashMismatch,
+    /// The data is older than the allowed age.
+    Expired,
+}
+
+#[cfg(feature = "webapp")]
+impl std::fmt::Display for InitDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing field `{}`", field),
+            Self::InvalidField(field) => write!(f, "invalid field `{}`", field),
+            Self::HashMismatch => write!(f, "hash mismatch"),
+            Self::Expired => write!(f, "init data expired"),
+        }
+    }
+}
+
+#[cfg(feature = "webapp")]
+impl std::error::Error for InitDataError {}
+
+#[cfg(feature = "webapp")]
+impl WebAppInitData {
+    /// Verifies the `initData` query string, like `query_id=...&user=...&auth_date=...&hash=...`, with the bot token.
+    ///
+    /// Data authorized more than `max_age` ago is rejected to prevent replaying leaked data.
+    ///
+    /// ```
+    /// # use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    /// # use telbot_types::webapp::{InitDataError, WebAppInitData};
+    /// # let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    /// # let token = "1234:TOKEN";
+    /// # let user = r#"{"id":1234,"first_name":"Kiwi"}"#;
+    /// # let data_check_string = format!("auth_date={}\nquery_id=AAE\nuser={}", now, user);
+    /// # let secret = hmac_sha256::HMAC::mac(token, "WebAppData");
+    /// # let hash: String = hmac_sha256::HMAC::mac(data_check_string, secret).iter().map(|b| format!("{:02x}", b)).collect();
+    /// # let init_data = format!(
+    /// #     "query_id=AAE&user=%7B%22id%22%3A1234%2C%22first_name%22%3A%22Kiwi%22%7D&auth_date={}&hash={}",
+    /// #     now, hash,
+    /// # );
+    /// // `init_data` is `Telegram.WebApp.initData` sent by the Web App
+    /// let data = WebAppInitData::verify(&init_data, token, Duration::from_secs(60 * 60))?;
+    /// assert_eq!(data.user.unwrap().id, 1234);
+    /// assert_eq!(data.query_id.as_deref(), Some("AAE"));
+    /// # Ok::<(), InitDataError>(())
+    /// ```
+    pub fn verify(init_data: &str, token: &str, max_age: Duration) -> Result<Self, InitDataError> {
+        Self::verify_at(init_data, token, max_age, SystemTime::now())
+    }
+
+    /// Verifies the `initData` query string, regarding the current time as `now`.
+    pub fn verify_at(
+        init_data: &str,
+        token: &str,
+        max_age: Duration,
+        now: SystemTime,
+    ) -> Result<Self, InitDataError> {
+        let mut fields: BTreeMap<String, String> = init_data
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut pair = pair.splitn(2, '=');
+                let key = percent_decode(pair.next().unwrap_or(""));
+                let value = percent_decode(pair.next().unwrap_or(""));
+                (key, value)
+            })
+            .collect();
+        let hash = fields
+            .remove("hash")
+            .ok_or(InitDataError::MissingField("hash"))?;
+        let hash = decode_hex(&hash).ok_or(InitDataError::InvalidField("hash"))?;
+
+        // Fields are sorted by key, since they are in a BTreeMap.
+        let data_check_string = fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let secret = HMAC::mac(token, "WebAppData");
+        if !HMAC::verify(data_check_string, secret, &hash) {
+            return Err(InitDataError::HashMismatch);
+        }
+
+        let auth_date: u64 = fields
+            .get("auth_date")
+            .ok_or(InitDataError::MissingField("auth_date"))?
+            .parse()
+            .map_err(|_| InitDataError::InvalidField("auth_date"))?;
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        if now.saturating_sub(auth_date) > max_age.as_secs() {
+            return Err(InitDataError::Expired);
+        }
+
+        fn parse_json<T: serde::de::DeserializeOwned>(
+            fields: &BTreeMap<String, String>,
+            key: &'static str,
+        ) -> Result<Option<T>, InitDataError> {
+            fields
+                .get(key)
+                .map(|value| serde_json::from_str(value))
+                .transpose()
+                .map_err(|_| InitDataError::InvalidField(key))
+        }
+
+        Ok(Self {
+            user: parse_json(&fields, "user")?,
+            receiver: parse_json(&fields, "receiver")?,
+            chat: parse_json(&fields, "chat")?,
+            can_send_after: fields
+                .get("can_send_after")
+                .map(|value| value.parse())
+                .transpose()
+                .map_err(|_| InitDataError::InvalidField("can_send_after"))?,
+            query_id: fields.remove("query_id"),
+            chat_type: fields.remove("chat_type"),
+            chat_instance: fields.remove("chat_instance"),
+            start_param: fields.remove("start_param"),
+            auth_date,
+        })
+    }
+}
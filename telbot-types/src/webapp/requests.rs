@@ -0,0 +1,31 @@
+//! Requests of Web Apps sent in inline mode, enabled by the `inline` feature.
+
+use super::*;
+use crate::query::InlineQueryResult;
+use crate::telegram_method;
+
+/// Sets the result of an interaction with a [Web App](https://core.telegram.org/bots/webapps)
+/// and sends a corresponding message on behalf of the user to the chat from which the query originated.
+///
+/// On success, a [`SentWebAppMessage`] object is returned.
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#answerwebappquery)
+#[derive(Clone, Serialize)]
+pub struct AnswerWebAppQuery {
+    /// Unique identifier for the query to be answered.
+    pub web_app_query_id: String,
+    /// An object describing the message to be sent.
+    pub result: InlineQueryResult,
+}
+
+impl AnswerWebAppQuery {
+    /// Creates a new [`AnswerWebAppQuery`] request which will send the result on behalf of the user.
+    pub fn new(web_app_query_id: impl Into<String>, result: InlineQueryResult) -> Self {
+        Self {
+            web_app_query_id: web_app_query_id.into(),
+            result,
+        }
+    }
+}
+
+telegram_method!(AnswerWebAppQuery => "answerWebAppQuery", SentWebAppMessage, json);
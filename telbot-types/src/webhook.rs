@@ -1,14 +1,17 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 
 use crate::file::InputFile;
-use crate::{FileMethod, JsonMethod, TelegramMethod};
+use crate::update::{AllowedUpdate, Update};
+use crate::{telegram_method, FileMethod, JsonMethod};
 
 /// Contains information about the current status of a webhook.
 ///
 /// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/api#webhookinfo)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookInfo {
     /// Webhook URL, may be empty if webhook is not set up.
     pub url: String,
@@ -17,16 +20,158 @@ pub struct WebhookInfo {
     /// Number of updates awaiting delivery.
     pub pending_update_count: u32,
     /// Currently used webhook IP address.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ip_address: Option<String>,
     /// Unix time for the most recent error that happened when trying to deliver an update via webhook.
+    ///
+    /// 0 if no error has happened.
+    #[serde(default)]
     pub last_error_date: u64,
     /// Error message in human-readable format for the most recent error that happened when trying to deliver an update via webhook.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_error_message: Option<String>,
     /// Maximum allowed number of simultaneous HTTPS connections to the webhook for update delivery.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_connections: Option<u32>,
     /// A list of update types the bot is subscribed to.
     /// Defaults to all update types except chat_member.
-    pub allowed_updates: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_updates: Option<Vec<AllowedUpdate>>,
+}
+
+impl WebhookInfo {
+    /// Checks if updates are delivered to the webhook, e.g. for periodic checks and alerting.
+    ///
+    /// ```
+    /// # use telbot_types::webhook::{WebhookHealth, WebhookHealthThresholds, WebhookInfo};
+    /// let info: WebhookInfo = serde_json::from_str(r#"{
+    ///     "url": "https://example.com/webhook", "has_custom_certificate": false,
+    ///     "pending_update_count": 42, "last_error_date": 1000,
+    ///     "last_error_message": "Wrong response from the webhook: 502 Bad Gateway"
+    /// }"#).unwrap();
+    /// let thresholds = WebhookHealthThresholds::default();
+    /// assert!(matches!(info.health_at(&thresholds, 1060), WebhookHealth::Failing { .. }));
+    /// // The error is old enough, but too many updates are waiting.
+    /// let thresholds = thresholds.with_max_pending_updates(10);
+    /// assert!(matches!(info.health_at(&thresholds, 100_000), WebhookHealth::Backlogged { .. }));
+    /// ```
+    pub fn health(&self, thresholds: &WebhookHealthThresholds) -> WebhookHealth {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        self.health_at(thresholds, now)
+    }
+
+    /// Checks if updates are delivered to the webhook at `now` in Unix time.
+    pub fn health_at(&self, thresholds: &WebhookHealthThresholds, now: u64) -> WebhookHealth {
+        if self.url.is_empty() {
+            return WebhookHealth::NotSet;
+        }
+        let is_recent_error = self.last_error_date != 0
+            && now.saturating_sub(self.last_error_date) <= thresholds.error_window.as_secs();
+        if is_recent_error {
+            WebhookHealth::Failing {
+                last_error_date: self.last_error_date,
+                last_error_message: self.last_error_message.clone().unwrap_or_default(),
+                pending_update_count: self.pending_update_count,
+            }
+        } else if self.pending_update_count > thresholds.max_pending_updates {
+            WebhookHealth::Backlogged {
+                pending_update_count: self.pending_update_count,
+            }
+        } else {
+            WebhookHealth::Healthy
+        }
+    }
+}
+
+/// Thresholds of [`WebhookInfo::health`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebhookHealthThresholds {
+    /// Maximum number of updates awaiting delivery regarded as healthy. Defaults to 100.
+    pub max_pending_updates: u32,
+    /// How long a delivery error is regarded as recent. Defaults to 10 minutes.
+    pub error_window: Duration,
+}
+
+impl Default for WebhookHealthThresholds {
+    fn default() -> Self {
+        Self {
+            max_pending_updates: 100,
+            error_window: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+impl WebhookHealthThresholds {
+    /// Sets the maximum number of updates awaiting delivery regarded as healthy.
+    pub fn with_max_pending_updates(self, max_pending_updates: u32) -> Self {
+        Self {
+            max_pending_updates,
+            ..self
+        }
+    }
+
+    /// Sets how long a delivery error is regarded as recent.
+    pub fn with_error_window(self, error_window: Duration) -> Self {
+        Self {
+            error_window,
+            ..self
+        }
+    }
+}
+
+/// Health of a webhook, returned by [`WebhookInfo::health`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookHealth {
+    /// Updates are delivered.
+    Healthy,
+    /// No webhook is set, e.g. the bot is using long polling or the webhook was removed.
+    NotSet,
+    /// Telegram failed to deliver an update recently.
+    Failing {
+        /// Unix time of the most recent delivery error.
+        last_error_date: u64,
+        /// Message of the most recent delivery error.
+        last_error_message: String,
+        /// Number of updates awaiting delivery.
+        pending_update_count: u32,
+    },
+    /// No error happened recently, but too many updates are awaiting delivery,
+    /// e.g. the webhook is too slow to respond.
+    Backlogged {
+        /// Number of updates awaiting delivery.
+        pending_update_count: u32,
+    },
+}
+
+impl WebhookHealth {
+    /// `true` if updates are delivered to the webhook.
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Self::Healthy)
+    }
+}
+
+impl fmt::Display for WebhookHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Healthy => write!(f, "webhook is healthy"),
+            Self::NotSet => write!(f, "webhook is not set"),
+            Self::Failing {
+                last_error_date,
+                last_error_message,
+                pending_update_count,
+            } => write!(
+                f,
+                "webhook failed at {}: {} ({} pending updates)",
+                last_error_date, last_error_message, pending_update_count
+            ),
+            Self::Backlogged {
+                pending_update_count,
+            } => write!(f, "webhook has {} pending updates", pending_update_count),
+        }
+    }
 }
 
 /// Specifies a url and receive incoming updates via an outgoing webhook.
@@ -65,10 +210,15 @@ pub struct SetWebhook {
     /// Please note that this parameter doesn't affect updates created before the call to the getUpdates,
     /// so unwanted updates may be received for a short period of time.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub allowed_updates: Option<Vec<String>>,
+    pub allowed_updates: Option<Vec<AllowedUpdate>>,
     /// Pass `true` to drop all pending updates.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub drop_pending_updates: Option<bool>,
+    /// A secret token to be sent in the [`SECRET_TOKEN_HEADER`] header in every webhook request, 1-256 characters.
+    /// Only characters `A-Z`, `a-z`, `0-9`, `_` and `-` are allowed.
+    /// The header is useful to ensure that the request comes from a webhook set by you.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret_token: Option<String>,
 }
 
 impl SetWebhook {
@@ -81,6 +231,7 @@ impl SetWebhook {
             max_connections: None,
             allowed_updates: None,
             drop_pending_updates: None,
+            secret_token: None,
         }
     }
     /// Creates a new [`SetWebhook`] request that removes the previous webhook url.
@@ -92,6 +243,7 @@ impl SetWebhook {
             max_connections: None,
             allowed_updates: None,
             drop_pending_updates: None,
+            secret_token: None,
         }
     }
     /// Sets custom certificate for the webhook.
@@ -115,6 +267,19 @@ impl SetWebhook {
             ..self
         }
     }
+    /// Sets allowed updates.
+    pub fn with_allowed_updates(self, updates: Vec<AllowedUpdate>) -> Self {
+        Self {
+            allowed_updates: Some(updates),
+            ..self
+        }
+    }
+    /// Adds one allowed update.
+    pub fn with_allowed_update(mut self, update: impl Into<AllowedUpdate>) -> Self {
+        let updates = self.allowed_updates.get_or_insert_with(Default::default);
+        updates.push(update.into());
+        self
+    }
     /// Drops all pending updates,
     pub fn drop_pending_updates(self) -> Self {
         Self {
@@ -122,16 +287,17 @@ impl SetWebhook {
             ..self
         }
     }
-}
-
-impl TelegramMethod for SetWebhook {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "setWebhook"
+    /// Sets the secret token to be sent in the [`SECRET_TOKEN_HEADER`] header in every webhook request.
+    pub fn with_secret_token(self, secret_token: impl Into<String>) -> Self {
+        Self {
+            secret_token: Some(secret_token.into()),
+            ..self
+        }
     }
 }
 
+telegram_method!(SetWebhook => "setWebhook", bool);
+
 impl FileMethod for SetWebhook {
     fn files(&self) -> Option<std::collections::HashMap<&str, &InputFile>> {
         self.certificate.as_ref().map(|file| {
@@ -150,6 +316,7 @@ impl FileMethod for SetWebhook {
 #[derive(Clone, Serialize)]
 pub struct DeleteWebhook {
     /// Pass `true` to drop all pending updates.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub drop_pending_updates: Option<bool>,
 }
 
@@ -169,15 +336,7 @@ impl DeleteWebhook {
     }
 }
 
-impl TelegramMethod for DeleteWebhook {
-    type Response = bool;
-
-    fn name() -> &'static str {
-        "deleteWebhook"
-    }
-}
-
-impl JsonMethod for DeleteWebhook {}
+telegram_method!(DeleteWebhook => "deleteWebhook", bool, json);
 
 /// Gets current webhook status. Requires no parameters.
 ///
@@ -188,12 +347,213 @@ impl JsonMethod for DeleteWebhook {}
 #[derive(Clone, Serialize)]
 pub struct GetWebhookInfo;
 
-impl TelegramMethod for GetWebhookInfo {
-    type Response = WebhookInfo;
+telegram_method!(GetWebhookInfo => "getWebhookInfo", WebhookInfo, json);
 
-    fn name() -> &'static str {
-        "getWebhookInfo"
+/// Name of the header in which Telegram sends the secret token set with [`SetWebhook::with_secret_token`].
+pub const SECRET_TOKEN_HEADER: &str = "X-Telegram-Bot-Api-Secret-Token";
+
+/// Checks if the value of the [`SECRET_TOKEN_HEADER`] header matches the secret token.
+///
+/// Compares in constant time with respect to the contents, so that the token cannot be guessed by timing.
+///
+/// ```
+/// # use telbot_types::webhook::verify_secret_token;
+/// assert!(verify_secret_token(Some("s3cr3t"), "s3cr3t"));
+/// assert!(!verify_secret_token(Some("guess"), "s3cr3t"));
+/// assert!(!verify_secret_token(None, "s3cr3t"));
+/// ```
+pub fn verify_secret_token(header: Option<&str>, secret_token: &str) -> bool {
+    match header {
+        Some(header) if header.len() == secret_token.len() => {
+            header
+                .bytes()
+                .zip(secret_token.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+        }
+        _ => false,
     }
 }
 
-impl JsonMethod for GetWebhookInfo {}
+/// Limits of webhook request bodies, to reject abusive payloads before deserializing them.
+///
+/// Webhook endpoints are public, so anyone can send requests to them;
+/// updates from Telegram are far below the default limits of 1 MiB and 64 levels of nesting.
+///
+/// ```
+/// # use telbot_types::webhook::{UpdateGuard, UpdateRejection};
+/// let guard = UpdateGuard::new().with_max_depth(8);
+/// let update = guard.parse(br#"{"update_id": 1, "message": {"message_id": 1, "date": 0, "chat": {"id": 1, "type": "private"}, "text": "hi"}}"#);
+/// assert!(update.is_ok());
+/// let nested = "[".repeat(100);
+/// assert!(matches!(guard.parse(nested.as_bytes()), Err(UpdateRejection::TooDeep { limit: 8 })));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateGuard {
+    max_body_size: usize,
+    max_depth: usize,
+}
+
+impl Default for UpdateGuard {
+    fn default() -> Self {
+        Self {
+            max_body_size: 1 << 20,
+            max_depth: 64,
+        }
+    }
+}
+
+impl UpdateGuard {
+    /// Creates a new [`UpdateGuard`] with the default limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum size of request bodies in bytes.
+    pub fn with_max_body_size(self, max_body_size: usize) -> Self {
+        Self {
+            max_body_size,
+            ..self
+        }
+    }
+
+    /// Sets the maximum nesting depth of JSON arrays and objects.
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Gets the maximum size of request bodies in bytes.
+    pub fn max_body_size(&self) -> usize {
+        self.max_body_size
+    }
+
+    /// Checks the size of the body, e.g. from `Content-Length` or while receiving the body.
+    pub fn check_size(&self, size: usize) -> Result<(), UpdateRejection> {
+        if size > self.max_body_size {
+            Err(UpdateRejection::TooLarge {
+                limit: self.max_body_size,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks the limits and deserializes the body into an update.
+    pub fn parse(&self, body: &[u8]) -> Result<Update, UpdateRejection> {
+        self.check_size(body.len())?;
+        self.check_depth(body)?;
+        serde_json::from_slice(body).map_err(UpdateRejection::Malformed)
+    }
+
+    fn check_depth(&self, body: &[u8]) -> Result<(), UpdateRejection> {
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        for &byte in body {
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'"' => in_string = true,
+                b'[' | b'{' => {
+                    depth += 1;
+                    if depth > self.max_depth {
+                        return Err(UpdateRejection::TooDeep {
+                            limit: self.max_depth,
+                        });
+                    }
+                }
+                b']' | b'}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reason why a webhook request body is rejected by [`UpdateGuard`].
+#[derive(Debug)]
+pub enum UpdateRejection {
+    /// The body is larger than the limit in bytes.
+    TooLarge {
+        /// The maximum size of bodies.
+        limit: usize,
+    },
+    /// JSON arrays and objects in the body are nested deeper than the limit.
+    TooDeep {
+        /// The maximum nesting depth.
+        limit: usize,
+    },
+    /// The body is not an update.
+    Malformed(serde_json::Error),
+}
+
+impl fmt::Display for UpdateRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { limit } => write!(f, "body is larger than {} bytes", limit),
+            Self::TooDeep { limit } => write!(f, "body is nested deeper than {} levels", limit),
+            Self::Malformed(error) => write!(f, "body is not an update: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for UpdateRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Malformed(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// A method sent in the response to a webhook request.
+///
+/// Telegram performs the method without returning its result, which saves a round trip.
+/// Serializes to the parameters of the method with its name in the `method` field.
+///
+/// ```
+/// # use telbot_types::message::SendMessage;
+/// # use telbot_types::webhook::WebhookReply;
+/// let reply = WebhookReply::new(&SendMessage::new(1234, "Hello!")).unwrap();
+/// assert_eq!(
+///     serde_json::to_value(&reply).unwrap(),
+///     serde_json::json!({"method": "sendMessage", "chat_id": 1234, "text": "Hello!"})
+/// );
+/// ```
+///
+/// [*Documentation on Telegram API Docs*](https://core.telegram.org/bots/faq#how-can-i-make-requests-in-response-to-updates)
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookReply {
+    method: &'static str,
+    #[serde(flatten)]
+    parameters: serde_json::Value,
+}
+
+impl WebhookReply {
+    /// Create a new webhook reply performing the given method.
+    ///
+    /// The method is serialized right away, so the reply does not borrow it.
+    pub fn new<Method: JsonMethod>(method: &Method) -> serde_json::Result<Self> {
+        Ok(Self {
+            method: Method::name(),
+            parameters: serde_json::to_value(method)?,
+        })
+    }
+
+    /// Gets the name of the method, like `sendMessage`.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// Gets the parameters of the method.
+    pub fn parameters(&self) -> &serde_json::Value {
+        &self.parameters
+    }
+}
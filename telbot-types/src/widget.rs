@@ -0,0 +1,355 @@
+//! Reusable inline keyboard widgets.
+//!
+//! Each widget encodes its state into the callback data of its buttons, prefixed with a namespace chosen by the bot,
+//! so that handlers can tell which widget a callback query belongs to.
+//! Pass [`CallbackQuery::data`](crate::query::CallbackQuery::data) to `parse` of the widget to get a typed event.
+//!
+//! Since callback data is limited to 64 bytes, the prefix should be at most 40 bytes long.
+
+use crate::markup::{InlineKeyboardButton, InlineKeyboardButtonKind, InlineKeyboardMarkup};
+
+fn button(text: impl Into<String>, data: String) -> InlineKeyboardButton {
+    InlineKeyboardButton {
+        text: text.into(),
+        kind: InlineKeyboardButtonKind::Callback {
+            callback_data: data,
+        },
+    }
+}
+
+fn strip_prefix<'a>(prefix: &str, data: &'a str) -> Option<&'a str> {
+    data.strip_prefix(prefix)?.strip_prefix(':')
+}
+
+/// A yes/no confirmation.
+///
+/// ```
+/// # use telbot_types::widget::Confirm;
+/// let confirm = Confirm::new("del");
+/// let keyboard = confirm.keyboard("Delete", "Cancel");
+/// assert_eq!(confirm.parse("del:y"), Some(true));
+/// assert_eq!(confirm.parse("other:y"), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Confirm {
+    prefix: String,
+}
+
+impl Confirm {
+    /// Creates a new confirmation with the given callback data prefix.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    /// Creates a keyboard with the given labels of yes and no buttons.
+    pub fn keyboard(&self, yes: impl Into<String>, no: impl Into<String>) -> InlineKeyboardMarkup {
+        InlineKeyboardMarkup {
+            inline_keyboard: vec![vec![
+                button(yes, format!("{}:y", self.prefix)),
+                button(no, format!("{}:n", self.prefix)),
+            ]],
+        }
+    }
+
+    /// Parses callback data, returning whether the user confirmed.
+    ///
+    /// Returns `None` if the data does not belong to this widget.
+    pub fn parse(&self, data: &str) -> Option<bool> {
+        match strip_prefix(&self.prefix, data)? {
+            "y" => Some(true),
+            "n" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// A calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// Year.
+    pub year: i32,
+    /// Month, 1-12.
+    pub month: u32,
+    /// Day of the month, starting from 1.
+    pub day: u32,
+}
+
+impl Date {
+    /// Creates a new date, returning `None` if it does not exist.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day) {
+            Some(Self { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// Gets the day of the week, where 0 is Monday and 6 is Sunday.
+    pub fn weekday(&self) -> u32 {
+        // Days since 1970-01-01, which is Thursday; see http://howardhinnant.github.io/date_algorithms.html
+        let year = if self.month <= 2 {
+            self.year as i64 - 1
+        } else {
+            self.year as i64
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146097 + day_of_era - 719468;
+        (days + 3).rem_euclid(7) as u32
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// An event from [`DatePicker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePickerEvent {
+    /// A date is selected.
+    Selected(Date),
+    /// The user moved to another month.
+    /// Edit the reply markup of the message with [`DatePicker::keyboard`] of the month.
+    Navigate {
+        /// Year to show.
+        year: i32,
+        /// Month to show.
+        month: u32,
+    },
+    /// A button without action, such as a title or a blank, is pressed.
+    Ignore,
+}
+
+/// A month calendar for picking a date.
+#[derive(Debug, Clone)]
+pub struct DatePicker {
+    prefix: String,
+    weekday_labels: [String; 7],
+}
+
+impl DatePicker {
+    /// Creates a new date picker with the given callback data prefix.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            weekday_labels: ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"].map(str::to_string),
+        }
+    }
+
+    /// Sets labels of the days of the week, starting from Monday.
+    pub fn with_weekday_labels(self, labels: [&str; 7]) -> Self {
+        Self {
+            weekday_labels: labels.map(str::to_string),
+            ..self
+        }
+    }
+
+    /// Creates a keyboard showing the given month.
+    pub fn keyboard(&self, year: i32, month: u32) -> InlineKeyboardMarkup {
+        let month = month.clamp(1, 12);
+        let ignore = || format!("{}:i", self.prefix);
+        let (prev_year, prev_month) = if month == 1 {
+            (year - 1, 12)
+        } else {
+            (year, month - 1)
+        };
+        let (next_year, next_month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+
+        let mut rows = vec![
+            vec![
+                button(
+                    "‹",
+                    format!("{}:m:{}:{}", self.prefix, prev_year, prev_month),
+                ),
+                button(format!("{}-{:02}", year, month), ignore()),
+                button(
+                    "›",
+                    format!("{}:m:{}:{}", self.prefix, next_year, next_month),
+                ),
+            ],
+            self.weekday_labels
+                .iter()
+                .map(|label| button(label.as_str(), ignore()))
+                .collect(),
+        ];
+
+        let first_weekday = Date {
+            year,
+            month,
+            day: 1,
+        }
+        .weekday();
+        let mut week = (0..first_weekday)
+            .map(|_| button(" ", ignore()))
+            .collect::<Vec<_>>();
+        for day in 1..=days_in_month(year, month) {
+            week.push(button(
+                day.to_string(),
+                format!("{}:d:{}:{}:{}", self.prefix, year, month, day),
+            ));
+            if week.len() == 7 {
+                rows.push(std::mem::take(&mut week));
+            }
+        }
+        if !week.is_empty() {
+            while week.len() < 7 {
+                week.push(button(" ", ignore()));
+            }
+            rows.push(week);
+        }
+
+        InlineKeyboardMarkup {
+            inline_keyboard: rows,
+        }
+    }
+
+    /// Parses callback data.
+    ///
+    /// Returns `None` if the data does not belong to this widget.
+    pub fn parse(&self, data: &str) -> Option<DatePickerEvent> {
+        let mut parts = strip_prefix(&self.prefix, data)?.split(':');
+        let event = match parts.next()? {
+            "i" => DatePickerEvent::Ignore,
+            "m" => {
+                let year = parts.next()?.parse().ok()?;
+                let month = parts.next()?.parse().ok()?;
+                Date::new(year, month, 1)?;
+                DatePickerEvent::Navigate { year, month }
+            }
+            "d" => {
+                let year = parts.next()?.parse().ok()?;
+                let month = parts.next()?.parse().ok()?;
+                let day = parts.next()?.parse().ok()?;
+                DatePickerEvent::Selected(Date::new(year, month, day)?)
+            }
+            _ => return None,
+        };
+        match parts.next() {
+            Some(_) => None,
+            None => Some(event),
+        }
+    }
+}
+
+/// A time of day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    /// Hour, 0-23.
+    pub hour: u32,
+    /// Minute, 0-59.
+    pub minute: u32,
+}
+
+/// An event from [`TimePicker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimePickerEvent {
+    /// An hour is selected.
+    /// Edit the reply markup of the message with [`TimePicker::minute_keyboard`] of the hour.
+    Hour(u32),
+    /// A time is selected.
+    Selected(Time),
+    /// The user went back to hour selection.
+    /// Edit the reply markup of the message with [`TimePicker::hour_keyboard`].
+    Back,
+}
+
+/// A two-step picker for a time of day, selecting an hour and then a minute.
+#[derive(Debug, Clone)]
+pub struct TimePicker {
+    prefix: String,
+    minute_step: u32,
+}
+
+impl TimePicker {
+    /// Creates a new time picker with the given callback data prefix and the default minute step of 15.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            minute_step: 15,
+        }
+    }
+
+    /// Sets the interval between selectable minutes, 1-60.
+    pub fn with_minute_step(self, minute_step: u32) -> Self {
+        Self {
+            minute_step: minute_step.clamp(1, 60),
+            ..self
+        }
+    }
+
+    /// Creates a keyboard for selecting an hour.
+    pub fn hour_keyboard(&self) -> InlineKeyboardMarkup {
+        let hours = (0..24)
+            .map(|hour| {
+                button(
+                    format!("{:02}", hour),
+                    format!("{}:h:{}", self.prefix, hour),
+                )
+            })
+            .collect::<Vec<_>>();
+        InlineKeyboardMarkup {
+            inline_keyboard: hours.chunks(6).map(<[_]>::to_vec).collect(),
+        }
+    }
+
+    /// Creates a keyboard for selecting a minute of the given hour.
+    pub fn minute_keyboard(&self, hour: u32) -> InlineKeyboardMarkup {
+        let minutes = (0..60)
+            .step_by(self.minute_step as usize)
+            .map(|minute| {
+                button(
+                    format!("{:02}:{:02}", hour, minute),
+                    format!("{}:t:{}:{}", self.prefix, hour, minute),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut rows = minutes.chunks(4).map(<[_]>::to_vec).collect::<Vec<_>>();
+        rows.push(vec![button("‹", format!("{}:b", self.prefix))]);
+        InlineKeyboardMarkup {
+            inline_keyboard: rows,
+        }
+    }
+
+    /// Parses callback data.
+    ///
+    /// Returns `None` if the data does not belong to this widget.
+    pub fn parse(&self, data: &str) -> Option<TimePickerEvent> {
+        let mut parts = strip_prefix(&self.prefix, data)?.split(':');
+        let event = match parts.next()? {
+            "b" => TimePickerEvent::Back,
+            "h" => {
+                let hour = parts.next()?.parse().ok().filter(|&hour| hour < 24)?;
+                TimePickerEvent::Hour(hour)
+            }
+            "t" => {
+                let hour = parts.next()?.parse().ok().filter(|&hour| hour < 24)?;
+                let minute = parts.next()?.parse().ok().filter(|&minute| minute < 60)?;
+                TimePickerEvent::Selected(Time { hour, minute })
+            }
+            _ => return None,
+        };
+        match parts.next() {
+            Some(_) => None,
+            None => Some(event),
+        }
+    }
+}
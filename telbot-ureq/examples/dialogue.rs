@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::env;
+
+use telbot_ureq::polling::Polling;
+use telbot_ureq::Api;
+
+/// The step of the registration dialogue of each chat.
+enum Dialogue {
+    Start,
+    ReceiveName,
+    ReceiveAge { name: String },
+}
+
+impl Dialogue {
+    /// Handles a text message, returning the reply and the next step.
+    fn next(self, text: &str) -> (String, Dialogue) {
+        match self {
+            Dialogue::Start => ("What is your name?".to_string(), Dialogue::ReceiveName),
+            Dialogue::ReceiveName => (
+                format!("Nice to meet you, {}. How old are you?", text),
+                Dialogue::ReceiveAge {
+                    name: text.to_string(),
+                },
+            ),
+            Dialogue::ReceiveAge { name } => match text.parse::<u8>() {
+                Ok(age) => (
+                    format!("Registered {}, aged {}.", name, age),
+                    Dialogue::Start,
+                ),
+                Err(_) => (
+                    "Please send a number.".to_string(),
+                    Dialogue::ReceiveAge { name },
+                ),
+            },
+        }
+    }
+}
+
+fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+    let mut dialogues: HashMap<i64, Dialogue> = HashMap::new();
+
+    for update in Polling::new(&api) {
+        let update = update.unwrap();
+        let message = match update.kind.message() {
+            Some(message) => message,
+            None => continue,
+        };
+        let text = match message.kind.text() {
+            Some(text) => text,
+            None => continue,
+        };
+
+        let dialogue = if text.starts_with("/cancel") {
+            dialogues.remove(&message.chat.id);
+            api.send_json(&message.reply_text("Cancelled.")).unwrap();
+            continue;
+        } else {
+            dialogues
+                .remove(&message.chat.id)
+                .unwrap_or(Dialogue::Start)
+        };
+        let (reply, next) = dialogue.next(text);
+        if !matches!(next, Dialogue::Start) {
+            dialogues.insert(message.chat.id, next);
+        }
+        api.send_json(&message.reply_text(reply)).unwrap();
+    }
+}
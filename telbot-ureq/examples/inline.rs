@@ -0,0 +1,51 @@
+use std::env;
+
+use telbot_ureq::polling::Polling;
+use telbot_ureq::types::query::{InlineQueryResultKind, InputMessageContent};
+use telbot_ureq::Api;
+
+const FRUITS: &[(&str, &str)] = &[
+    ("Apple", "🍎"),
+    ("Banana", "🍌"),
+    ("Cherry", "🍒"),
+    ("Grape", "🍇"),
+    ("Kiwi", "🥝"),
+    ("Lemon", "🍋"),
+    ("Peach", "🍑"),
+    ("Strawberry", "🍓"),
+];
+
+fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+
+    // Enable the inline mode with /setinline on @BotFather first.
+    for update in Polling::new(&api) {
+        let update = update.unwrap();
+        if let Some(query) = update.kind.inline_query() {
+            let keyword = query.query.to_lowercase();
+            let matches: Vec<_> = FRUITS
+                .iter()
+                .filter(|(name, _)| name.to_lowercase().contains(&keyword))
+                .collect();
+            // Answer 5 results at a time, loading more as the user scrolls.
+            let answer = query.answer_page(5, |start, count| {
+                matches
+                    .iter()
+                    .skip(start)
+                    .take(count)
+                    .map(|(name, emoji)| {
+                        InlineQueryResultKind::article(*name, InputMessageContent::text(*emoji))
+                            .with_description(format!("Send {}", emoji))
+                            .with_stable_id(name)
+                    })
+                    .collect()
+            });
+            // Ignore queries whose offset was not set by this bot.
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(_) => continue,
+            };
+            api.send_json(&answer.with_cache_time(60)).unwrap();
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::env;
+
+use telbot_ureq::polling::Polling;
+use telbot_ureq::types::file::{GetFile, InputFile};
+use telbot_ureq::Api;
+
+fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+
+    // Send photos back as documents, and documents back as they are.
+    for update in Polling::new(&api) {
+        let update = update.unwrap();
+        let message = match update.kind.message() {
+            Some(message) => message,
+            None => continue,
+        };
+
+        let (file_id, name, mime) = if let Some(sizes) = message.kind.photo() {
+            // the last size is the largest one
+            let largest = sizes.last().unwrap();
+            (
+                largest.file_id.clone(),
+                "photo.jpg".to_string(),
+                "image/jpeg".to_string(),
+            )
+        } else if let Some(document) = message.kind.document() {
+            (
+                document.file_id.clone(),
+                document
+                    .file_name
+                    .clone()
+                    .unwrap_or_else(|| "document".to_string()),
+                document
+                    .mime_type
+                    .clone()
+                    .unwrap_or_else(|| "application/octet-stream".to_string()),
+            )
+        } else {
+            continue;
+        };
+
+        // Download the file from Telegram servers...
+        let file = api.send_json(&GetFile::new(file_id)).unwrap();
+        let data = api.download_file(&file).unwrap();
+
+        // ...and upload it again.
+        let document = InputFile { name, data, mime };
+        api.send_file(&message.reply_document(document)).unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+use std::env;
+
+use telbot_ureq::callback::CallbackAnswer;
+use telbot_ureq::polling::Polling;
+use telbot_ureq::types::markup::{
+    InlineKeyboardButtonKind, InlineKeyboardMarkup, InlineKeyboardRow,
+};
+use telbot_ureq::Api;
+
+fn button(text: &str, data: &str) -> InlineKeyboardRow {
+    InlineKeyboardRow::new_emplace(
+        text,
+        InlineKeyboardButtonKind::Callback {
+            callback_data: data.to_string(),
+        },
+    )
+}
+
+fn main_menu() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new_with_row(button("Drinks", "menu:drinks"))
+        .with_row(button("Desserts", "menu:desserts"))
+}
+
+fn sub_menu(items: &[&str]) -> InlineKeyboardMarkup {
+    let mut markup = InlineKeyboardMarkup::new_with_row(button("« Back", "menu:main"));
+    for item in items {
+        markup = markup.with_row(button(item, &format!("order:{}", item)));
+    }
+    markup
+}
+
+fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+
+    for update in Polling::new(&api) {
+        let update = update.unwrap();
+        if let Some(message) = update.kind.message() {
+            if matches!(message.kind.text(), Some(text) if text.starts_with("/menu")) {
+                let request = message.reply_text("What would you like?");
+                api.send_json(&request.with_reply_markup(main_menu()))
+                    .unwrap();
+            }
+        } else if let Some(query) = update.kind.callback_query() {
+            // The query is answered with an empty answer when the guard is dropped.
+            let answer = CallbackAnswer::new(&api, query);
+            let (message, data) = match (&query.message, &query.data) {
+                (Some(message), Some(data)) => (message, data.as_str()),
+                _ => continue,
+            };
+            let edit = match data {
+                "menu:main" => message
+                    .edit_text("What would you like?")
+                    .with_reply_markup(main_menu()),
+                "menu:drinks" => message
+                    .edit_text("Drinks")
+                    .with_reply_markup(sub_menu(&["Coffee", "Tea", "Juice"])),
+                "menu:desserts" => message
+                    .edit_text("Desserts")
+                    .with_reply_markup(sub_menu(&["Cake", "Ice cream"])),
+                _ => {
+                    if let Some(item) = data.strip_prefix("order:") {
+                        let text = format!("One {} coming up!", item);
+                        answer.answer(&query.answer_text(text)).unwrap();
+                    }
+                    continue;
+                }
+            };
+            api.send_json(&edit).unwrap();
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::env;
+
+use telbot_ureq::polling::Polling;
+use telbot_ureq::types::payment::{LabeledPrice, SendInvoice};
+use telbot_ureq::Api;
+
+fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+    // A test provider token from @BotFather, so that no real money is charged.
+    // See https://core.telegram.org/bots/payments#testing-payments
+    let provider_token = env::var("PROVIDER_TOKEN").unwrap();
+
+    for update in Polling::new(&api) {
+        let update = update.unwrap();
+        if let Some(message) = update.kind.message() {
+            if let Some(payment) = message.kind.successful_payment() {
+                let text = format!(
+                    "Thank you! Your order {} is on the way.",
+                    payment.invoice_payload
+                );
+                api.send_json(&message.reply_text(text)).unwrap();
+            } else if matches!(message.kind.text(), Some(text) if text.starts_with("/buy")) {
+                let invoice = SendInvoice::new(
+                    message.chat.id,
+                    "Kiwi box",
+                    "A box of twelve fresh kiwis.",
+                    format!("kiwi-{}", message.message_id),
+                    &provider_token,
+                    "USD",
+                    vec![
+                        LabeledPrice::new("Kiwi box", 1200),
+                        LabeledPrice::new("Delivery", 300),
+                    ],
+                )
+                .with_tips(500, vec![100, 200, 500])
+                .need_name();
+                api.send_json(&invoice).unwrap();
+            }
+        } else if let Some(query) = update.kind.pre_checkout_query() {
+            // Check the stock or validate the order here; Telegram waits at most 10 seconds.
+            let answer = if query.invoice_payload.starts_with("kiwi-") {
+                query.answer_ok()
+            } else {
+                query.answer_error("Sorry, this item is sold out.")
+            };
+            api.send_json(&answer).unwrap();
+        }
+    }
+}
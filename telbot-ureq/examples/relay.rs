@@ -0,0 +1,24 @@
+use std::env;
+use std::io::{self, Write};
+
+use telbot_ureq::relay::Relay;
+use telbot_ureq::types::update::Update;
+use telbot_ureq::Api;
+
+fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+
+    // Publish each update as a line of JSON.
+    // Pipe the output into a queue client (e.g. `nats pub`) to feed worker processes.
+    // With the `nats` feature, `telbot_ureq::nats::NatsSink` publishes to a NATS server directly.
+    let stdout = io::stdout();
+    let sink = |_: &Update, payload: &[u8]| -> io::Result<()> {
+        let mut stdout = stdout.lock();
+        stdout.write_all(payload)?;
+        stdout.write_all(b"\n")?;
+        stdout.flush()
+    };
+
+    let error = Relay::new(&api, sink).run();
+    panic!("{:?}", error);
+}
@@ -0,0 +1,65 @@
+use std::env;
+use std::io::Read;
+
+use telbot_ureq::types::webhook::{
+    verify_secret_token, SetWebhook, UpdateGuard, UpdateRejection, SECRET_TOKEN_HEADER,
+};
+use telbot_ureq::Api;
+use tiny_http::{Response, Server};
+
+fn main() {
+    let api = Api::new(env::var("BOT_TOKEN").unwrap());
+    // The public HTTPS URL which forwards to this server, e.g. `https://example.com/telegram`
+    let url = env::var("WEBHOOK_URL").unwrap();
+    let secret_token = env::var("WEBHOOK_SECRET").unwrap();
+
+    api.send_file(&SetWebhook::new(url).with_secret_token(&secret_token))
+        .unwrap();
+
+    let guard = UpdateGuard::new();
+    let server = Server::http("0.0.0.0:8080").unwrap();
+    for mut request in server.incoming_requests() {
+        // Reject requests not sent by Telegram.
+        let header = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv(SECRET_TOKEN_HEADER))
+            .map(|header| header.value.to_string());
+        if !verify_secret_token(header.as_deref(), &secret_token) {
+            request.respond(Response::empty(401)).unwrap();
+            continue;
+        }
+
+        // Read at most one byte more than the limit, so that larger bodies are rejected.
+        let mut body = vec![];
+        let limit = guard.max_body_size() as u64 + 1;
+        if request
+            .as_reader()
+            .take(limit)
+            .read_to_end(&mut body)
+            .is_err()
+        {
+            request.respond(Response::empty(400)).unwrap();
+            continue;
+        }
+        let update = match guard.parse(&body) {
+            Ok(update) => update,
+            Err(rejection) => {
+                let status = match rejection {
+                    UpdateRejection::TooLarge { .. } => 413,
+                    _ => 400,
+                };
+                request.respond(Response::empty(status)).unwrap();
+                continue;
+            }
+        };
+
+        if let Some(message) = update.kind.message() {
+            if let Some(text) = message.kind.text() {
+                api.send_json(&message.reply_text(text)).unwrap();
+            }
+        }
+        // Respond after handling, so that Telegram sends the update again if the bot crashes.
+        request.respond(Response::empty(200)).unwrap();
+    }
+}
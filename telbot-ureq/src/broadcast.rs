@@ -0,0 +1,82 @@
+use telbot_types::JsonMethod;
+
+use crate::{Api, Error};
+
+/// Sends a request to many chats, e.g. to subscribers of a mailing list.
+///
+/// Chats which can no longer receive messages, because the user blocked the bot or deleted their account,
+/// are reported to the handler set by [`Broadcaster::on_blocked`] so that they can be pruned.
+///
+/// ```no_run
+/// # use telbot_ureq::Api;
+/// # use telbot_ureq::broadcast::Broadcaster;
+/// # use telbot_ureq::types::message::SendMessageRef;
+/// # let api = Api::new("TOKEN");
+/// let mut subscribers = vec![1, 2, 3];
+/// let text = String::from("Hello");
+/// let report = Broadcaster::new(&api)
+///     .on_blocked(|chat_id| println!("{} is unsubscribed", chat_id))
+///     .send(subscribers.clone(), |chat_id| SendMessageRef::new(chat_id, &text));
+/// subscribers.retain(|chat_id| !report.blocked.contains(chat_id));
+/// ```
+pub struct Broadcaster<'a> {
+    api: &'a Api,
+    blocked_handler: Option<Box<dyn FnMut(i64) + 'a>>,
+}
+
+/// Result of a broadcast.
+#[derive(Debug, Default)]
+pub struct BroadcastReport {
+    /// Number of chats the request was successfully sent to.
+    pub delivered: usize,
+    /// Chats which can no longer receive messages.
+    pub blocked: Vec<i64>,
+    /// Chats the request failed to be sent to for other reasons, with the errors.
+    pub failed: Vec<(i64, Error)>,
+}
+
+impl<'a> Broadcaster<'a> {
+    /// Create a new Broadcaster object.
+    pub fn new(api: &'a Api) -> Self {
+        Self {
+            api,
+            blocked_handler: None,
+        }
+    }
+
+    /// Sets a handler which is called with the identifier of each chat which can no longer receive messages.
+    pub fn on_blocked(self, handler: impl FnMut(i64) + 'a) -> Self {
+        Self {
+            blocked_handler: Some(Box::new(handler)),
+            ..self
+        }
+    }
+
+    /// Sends the request created for each chat in order.
+    ///
+    /// Errors do not stop the broadcast; they are collected in the report instead.
+    pub fn send<M: JsonMethod>(
+        &mut self,
+        chat_ids: impl IntoIterator<Item = i64>,
+        mut method: impl FnMut(i64) -> M,
+    ) -> BroadcastReport {
+        let mut report = BroadcastReport::default();
+        for chat_id in chat_ids {
+            match self.api.send_json(&method(chat_id)) {
+                Ok(_) => report.delivered += 1,
+                Err(error) if is_unreachable_chat(&error) => {
+                    if let Some(handler) = &mut self.blocked_handler {
+                        handler(chat_id);
+                    }
+                    report.blocked.push(chat_id);
+                }
+                Err(error) => report.failed.push((chat_id, error)),
+            }
+        }
+        report
+    }
+}
+
+fn is_unreachable_chat(error: &Error) -> bool {
+    matches!(error.api_error_kind(), Some(kind) if kind.is_unreachable_chat())
+}
@@ -0,0 +1,43 @@
+use telbot_types::query::{AnswerCallbackQuery, CallbackQuery};
+
+use crate::{Api, Result};
+
+/// Answers a callback query automatically if it was not answered explicitly.
+///
+/// Telegram clients display a progress bar until the callback query is answered.
+/// Create this guard before handling a callback query;
+/// when the guard is dropped without [`CallbackAnswer::answer`] being called,
+/// an empty answer is sent so the progress bar does not hang.
+pub struct CallbackAnswer<'a> {
+    api: &'a Api,
+    query_id: Option<String>,
+}
+
+impl<'a> CallbackAnswer<'a> {
+    /// Creates a new guard for the given callback query.
+    pub fn new(api: &'a Api, query: &CallbackQuery) -> Self {
+        Self {
+            api,
+            query_id: Some(query.id.clone()),
+        }
+    }
+
+    /// Answers the query explicitly, disabling the automatic answer.
+    pub fn answer(mut self, answer: &AnswerCallbackQuery) -> Result<bool> {
+        self.query_id = None;
+        self.api.send_json(answer)
+    }
+
+    /// Disables the automatic answer, e.g. when the query was answered elsewhere.
+    pub fn dismiss(mut self) {
+        self.query_id = None;
+    }
+}
+
+impl Drop for CallbackAnswer<'_> {
+    fn drop(&mut self) {
+        if let Some(query_id) = self.query_id.take() {
+            let _ = self.api.send_json(&AnswerCallbackQuery::new(query_id));
+        }
+    }
+}
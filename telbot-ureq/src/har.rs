@@ -0,0 +1,282 @@
+//! Capture of requests and responses in [HAR](http://www.softwareishard.com/blog/har-12-spec/) format.
+//!
+//! Enable with [`Api::with_har_capture`](crate::Api::with_har_capture)
+//! and attach the output of [`Api::har`](crate::Api::har) when reporting interoperability issues.
+//! The bot token is replaced with `<token>` in URLs, and file contents are omitted.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+/// A HAR document.
+#[derive(Debug, Clone, Serialize)]
+pub struct Har {
+    /// The captured log.
+    pub log: HarLog,
+}
+
+/// Log of captured requests.
+#[derive(Debug, Clone, Serialize)]
+pub struct HarLog {
+    /// Version of HAR format.
+    pub version: &'static str,
+    /// Application which created the log.
+    pub creator: HarCreator,
+    /// Captured requests in order of their start.
+    pub entries: Vec<HarEntry>,
+}
+
+/// Application which created the log.
+#[derive(Debug, Clone, Serialize)]
+pub struct HarCreator {
+    /// Name of the application.
+    pub name: &'static str,
+    /// Version of the application.
+    pub version: &'static str,
+}
+
+/// A captured request and its response.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    /// Start time of the request in ISO 8601 format.
+    pub started_date_time: String,
+    /// Elapsed time of the request in milliseconds.
+    pub time: f64,
+    /// The request.
+    pub request: HarRequest,
+    /// The response.
+    pub response: HarResponse,
+    /// Unused; always empty.
+    pub cache: HarCache,
+    /// Detailed timing; only `wait` is measured.
+    pub timings: HarTimings,
+}
+
+/// A captured request.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    /// HTTP method.
+    pub method: &'static str,
+    /// Requested URL with the bot token redacted.
+    pub url: String,
+    /// HTTP version.
+    pub http_version: &'static str,
+    /// Unused; always empty.
+    pub cookies: Vec<HarHeader>,
+    /// Request headers.
+    pub headers: Vec<HarHeader>,
+    /// Unused; always empty.
+    pub query_string: Vec<HarHeader>,
+    /// Request body, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarContent>,
+    /// Unknown; always -1.
+    pub headers_size: i64,
+    /// Size of the request body in bytes, or -1 if unknown.
+    pub body_size: i64,
+}
+
+/// A captured response.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    /// HTTP status code, or 0 if no response was received.
+    pub status: u16,
+    /// HTTP status text.
+    pub status_text: String,
+    /// HTTP version.
+    pub http_version: &'static str,
+    /// Unused; always empty.
+    pub cookies: Vec<HarHeader>,
+    /// Response headers.
+    pub headers: Vec<HarHeader>,
+    /// Response body.
+    pub content: HarContent,
+    /// Unused; always empty.
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: &'static str,
+    /// Unknown; always -1.
+    pub headers_size: i64,
+    /// Size of the response body in bytes.
+    pub body_size: i64,
+}
+
+/// A name-value pair.
+#[derive(Debug, Clone, Serialize)]
+pub struct HarHeader {
+    /// Name of the header.
+    pub name: String,
+    /// Value of the header.
+    pub value: String,
+}
+
+/// A request or response body.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    /// Size of the body in bytes.
+    pub size: i64,
+    /// MIME type of the body.
+    pub mime_type: String,
+    /// The body as text, omitted for files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Cache information, which is always empty.
+#[derive(Debug, Clone, Serialize)]
+pub struct HarCache {}
+
+/// Timing information in milliseconds.
+#[derive(Debug, Clone, Serialize)]
+pub struct HarTimings {
+    /// Time to send the request; always 0.
+    pub send: f64,
+    /// Time from sending the request to receiving the whole response.
+    pub wait: f64,
+    /// Time to receive the response; always 0.
+    pub receive: f64,
+}
+
+/// Shared storage of captured entries.
+#[derive(Clone, Default)]
+pub(crate) struct HarRecorder {
+    entries: Arc<Mutex<Vec<HarEntry>>>,
+}
+
+impl HarRecorder {
+    pub(crate) fn start(&self, url: String, request: Option<HarContent>) -> PendingEntry {
+        PendingEntry {
+            recorder: self.clone(),
+            started: SystemTime::now(),
+            instant: Instant::now(),
+            method: if request.is_some() { "POST" } else { "GET" },
+            url,
+            request,
+        }
+    }
+
+    pub(crate) fn har(&self) -> Har {
+        Har {
+            log: HarLog {
+                version: "1.2",
+                creator: HarCreator {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                },
+                entries: self.entries.lock().unwrap().clone(),
+            },
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+/// A request waiting for its response.
+pub(crate) struct PendingEntry {
+    recorder: HarRecorder,
+    started: SystemTime,
+    instant: Instant,
+    method: &'static str,
+    url: String,
+    request: Option<HarContent>,
+}
+
+impl PendingEntry {
+    pub(crate) fn finish(self, status: u16, status_text: &str, mime_type: &str, body: &[u8]) {
+        // Responses of GET requests are files, so their contents are omitted.
+        let response = HarContent {
+            size: body.len() as i64,
+            mime_type: mime_type.to_string(),
+            text: self
+                .request
+                .as_ref()
+                .map(|_| String::from_utf8_lossy(body).into_owned()),
+        };
+        let time = self.instant.elapsed().as_secs_f64() * 1000.0;
+        let request_headers = self
+            .request
+            .iter()
+            .map(|content| header("Content-Type", &content.mime_type))
+            .collect();
+        let entry = HarEntry {
+            started_date_time: format_time(self.started),
+            time,
+            request: HarRequest {
+                method: self.method,
+                url: self.url,
+                http_version: "HTTP/1.1",
+                cookies: vec![],
+                headers: request_headers,
+                query_string: vec![],
+                body_size: self.request.as_ref().map_or(0, |content| content.size),
+                post_data: self.request,
+                headers_size: -1,
+            },
+            response: HarResponse {
+                status,
+                status_text: status_text.to_string(),
+                http_version: "HTTP/1.1",
+                cookies: vec![],
+                headers: vec![header("Content-Type", &response.mime_type)],
+                body_size: response.size,
+                content: response,
+                redirect_url: "",
+                headers_size: -1,
+            },
+            cache: HarCache {},
+            timings: HarTimings {
+                send: 0.0,
+                wait: time,
+                receive: 0.0,
+            },
+        };
+        self.recorder.entries.lock().unwrap().push(entry);
+    }
+}
+
+fn header(name: &str, value: &str) -> HarHeader {
+    HarHeader {
+        name: name.to_string(),
+        value: value.to_string(),
+    }
+}
+
+/// Formats time in ISO 8601 format in UTC.
+fn format_time(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = ((secs / 86400) as i64, secs % 86400);
+
+    // Converts days since epoch to civil date; see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z / 146097;
+    let day_of_era = z - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
@@ -1,24 +1,200 @@
+pub mod broadcast;
+pub mod callback;
+#[cfg(feature = "har")]
+pub mod har;
+#[cfg(feature = "nats")]
+pub mod nats;
 pub mod polling;
+pub mod progress;
+pub mod relay;
+pub mod self_check;
 
-use std::io;
+use std::future::{ready, Future};
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
 
 use multipart::client::lazy::Multipart;
+use serde::de::DeserializeOwned;
 pub use telbot_types as types;
-use telbot_types::{ApiResponse, FileMethod, JsonMethod, TelegramError};
-use types::TelegramMethod;
+use telbot_types::admin_cache::AdminCache;
+use telbot_types::bot::{BotToken, GetMe};
+use telbot_types::chat::{ChatPhoto, GetChat, GetChatAdministrators};
+use telbot_types::file::{DownloadCheck, File, GetFile, IntegrityError};
+use telbot_types::form::{to_form_fields, FormValue};
+use telbot_types::pin_tracker::{PinTracker, PinnedMessage};
+use telbot_types::user::User;
+use telbot_types::{
+    ApiErrorKind, ApiResponse, FileMethod, JsonMethod, RequestOptions, RetryPolicy, TelegramClient,
+    TelegramError,
+};
 use ureq::Response;
 
 #[derive(Clone)]
 pub struct Api {
     base_url: String,
+    file_base_url: String,
+    default_headers: Vec<(String, String)>,
+    agent: ureq::Agent,
+    bot_id: Option<i64>,
+    me: Arc<Mutex<Option<User>>>,
+    retry: Option<RetryPolicy>,
+    #[cfg(feature = "har")]
+    har: Option<har::HarRecorder>,
 }
 
 impl Api {
-    pub fn new(token: impl AsRef<str>) -> Self {
+    pub fn new(token: impl Into<BotToken>) -> Self {
+        Self::new_with_server(token, "https://api.telegram.org")
+    }
+
+    /// Creates a new API requester which sends requests to the server, like `http://localhost:8081`,
+    /// e.g. a [local Bot API server](https://github.com/tdlib/telegram-bot-api).
+    pub fn new_with_server(token: impl Into<BotToken>, server: &str) -> Self {
+        let token = token.into();
+        let server = server.trim_end_matches('/');
+        Self {
+            base_url: format!("{}/bot{}/", server, token.as_str()),
+            file_base_url: format!("{}/file/bot{}/", server, token.as_str()),
+            default_headers: vec![],
+            agent: ureq::Agent::new(),
+            bot_id: token.bot_id(),
+            me: Arc::default(),
+            retry: None,
+            #[cfg(feature = "har")]
+            har: None,
+        }
+    }
+
+    /// Gets the identifier of the bot, which is a part of the token.
+    pub fn bot_id(&self) -> Option<i64> {
+        self.bot_id
+    }
+
+    /// Sets HTTP headers sent with every request, including file downloads.
+    pub fn with_default_headers<K: Into<String>, V: Into<String>>(
+        self,
+        headers: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
         Self {
-            base_url: format!("https://api.telegram.org/bot{}/", token.as_ref()),
+            default_headers: headers
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+            ..self
         }
     }
+
+    /// Sets the agent used to send requests, e.g. to configure proxies or TLS.
+    pub fn with_agent(self, agent: ureq::Agent) -> Self {
+        Self { agent, ..self }
+    }
+
+    /// Retries requests which failed with flood control or transient transport errors, following the policy.
+    ///
+    /// The thread sleeps while waiting for the next retry.
+    pub fn with_retry(self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: Some(policy),
+            ..self
+        }
+    }
+
+    /// Sets the timeouts for reading and writing each chunk of requests and responses.
+    ///
+    /// The read timeout should be longer than the long polling timeout of [`polling::Polling`].
+    /// A new agent is created, so settings of the agent set with [`Api::with_agent`] are discarded.
+    pub fn with_timeouts(self, read: Duration, write: Duration) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout_read(read)
+            .timeout_write(write)
+            .build();
+        self.with_agent(agent)
+    }
+
+    fn request(&self, method: &str, url: &str, options: &RequestOptions) -> ureq::Request {
+        let mut request = self.agent.request(method, url);
+        for (name, value) in self.default_headers.iter().chain(&options.headers) {
+            request = request.set(name, value);
+        }
+        request
+    }
+}
+
+#[cfg(feature = "har")]
+impl Api {
+    /// Starts capturing requests and responses, which can be exported with [`Api::har`].
+    ///
+    /// Clones of the returned requester share the same capture.
+    pub fn with_har_capture(self) -> Self {
+        Self {
+            har: Some(Default::default()),
+            ..self
+        }
+    }
+
+    /// Gets captured requests and responses, if capturing is enabled.
+    pub fn har(&self) -> Option<har::Har> {
+        self.har.as_ref().map(har::HarRecorder::har)
+    }
+
+    /// Clears captured requests and responses.
+    pub fn clear_har(&self) {
+        if let Some(har) = &self.har {
+            har.clear();
+        }
+    }
+
+    fn capture(&self, url: &str, request: Option<(&str, &dyn Fn() -> String)>) -> Capture {
+        let har = self.har.as_ref()?;
+        let url = url
+            .replace(&self.base_url, "https://api.telegram.org/bot<token>/")
+            .replace(
+                &self.file_base_url,
+                "https://api.telegram.org/file/bot<token>/",
+            );
+        let request = request.map(|(mime_type, text)| {
+            let text = text();
+            har::HarContent {
+                size: text.len() as i64,
+                mime_type: mime_type.to_string(),
+                text: Some(text),
+            }
+        });
+        Some(har.start(url, request))
+    }
+
+    fn finish_capture(
+        capture: Capture,
+        status: u16,
+        status_text: &str,
+        mime_type: &str,
+        body: &[u8],
+    ) {
+        if let Some(capture) = capture {
+            capture.finish(status, status_text, mime_type, body);
+        }
+    }
+}
+
+/// A request being captured, if any.
+#[cfg(feature = "har")]
+type Capture = Option<har::PendingEntry>;
+#[cfg(not(feature = "har"))]
+type Capture = ();
+
+#[cfg(not(feature = "har"))]
+impl Api {
+    fn capture(&self, _url: &str, _request: Option<(&str, &dyn Fn() -> String)>) -> Capture {}
+
+    fn finish_capture(
+        _capture: Capture,
+        _status: u16,
+        _status_text: &str,
+        _mime_type: &str,
+        _body: &[u8],
+    ) {
+    }
 }
 
 #[derive(Debug)]
@@ -27,6 +203,7 @@ pub enum Error {
     Ureq(ureq::Transport),
     Serde(serde_json::Error),
     Io(std::io::Error),
+    Integrity(IntegrityError),
 }
 
 impl From<serde_json::Error> for Error {
@@ -43,57 +220,365 @@ impl From<std::io::Error> for Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+impl Error {
+    /// Gets the kind of the error returned by Telegram, if any.
+    pub fn api_error_kind(&self) -> Option<ApiErrorKind> {
+        match self {
+            Self::TelegramError(error) => Some(error.kind()),
+            _ => None,
+        }
+    }
+}
+
 impl Api {
     /// Send a JSON-serializable API request
     pub fn send_json<Method: JsonMethod>(&self, method: &Method) -> Result<Method::Response> {
+        self.send_json_as(method, &RequestOptions::default())
+    }
+
+    /// Send a JSON-serializable API request with extra headers and query parameters.
+    pub fn send_json_with<Method: JsonMethod>(
+        &self,
+        method: &Method,
+        options: &RequestOptions,
+    ) -> Result<Method::Response> {
+        self.send_json_as(method, options)
+    }
+
+    /// Send a JSON-serializable API request, returning the result as raw JSON.
+    ///
+    /// Useful for inspecting responses which fail to deserialize.
+    pub fn send_json_raw<Method: JsonMethod>(&self, method: &Method) -> Result<serde_json::Value> {
+        self.send_json_as(method, &RequestOptions::default())
+    }
+
+    fn send_json_as<Method: JsonMethod, T: DeserializeOwned>(
+        &self,
+        method: &Method,
+        options: &RequestOptions,
+    ) -> Result<T> {
         let value = serde_json::to_value(method)?;
-        let response = ureq::post(&format!("{}{}", self.base_url, Method::name())).send_json(value);
-        Self::parse_response::<Method>(response)
+        let url = options.url_with_query(&format!("{}{}", self.base_url, Method::name()));
+        self.retrying(|| {
+            let capture = self.capture(&url, Some(("application/json", &|| value.to_string())));
+            let response = self.request("POST", &url, options).send_json(value.clone());
+            Self::parse_response(response, capture)
+        })
+    }
+
+    fn retrying<T>(&self, mut send: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut retries = 0;
+        loop {
+            let result = send();
+            let policy = match &self.retry {
+                Some(policy) => policy,
+                None => return result,
+            };
+            let delay = match &result {
+                Err(Error::TelegramError(error)) => policy.delay_for(retries, error),
+                Err(Error::Ureq(error)) if is_transient(error) => policy.delay(retries, None),
+                _ => None,
+            };
+            match delay {
+                Some(delay) => std::thread::sleep(delay),
+                None => return result,
+            }
+            retries += 1;
+        }
     }
 
     /// Send a JSON-serializable API request with files.
     pub fn send_file<Method: FileMethod>(&self, method: &Method) -> Result<Method::Response> {
-        let value = serde_json::to_value(method)?;
-        let files = method.files();
-        let mut multipart = Multipart::new();
-        for (key, value) in value.as_object().unwrap().iter() {
-            if let Some(file) = files.as_ref().and_then(|map| map.get(key.as_str())) {
-                multipart.add_stream(
-                    key,
-                    &file.data[..],
-                    Some(&file.name),
-                    Some(file.mime.parse().unwrap()),
-                );
-            } else if let Some(text) = value.as_str() {
-                multipart.add_text(key, text);
-            } else {
-                multipart.add_text(key, value.to_string());
+        self.send_file_with(method, &RequestOptions::default())
+    }
+
+    /// Send a JSON-serializable API request with files, extra headers and query parameters.
+    pub fn send_file_with<Method: FileMethod>(
+        &self,
+        method: &Method,
+        options: &RequestOptions,
+    ) -> Result<Method::Response> {
+        let fields = to_form_fields(method)?;
+        let multipart = |with_data: bool| {
+            let mut multipart = Multipart::new();
+            for (key, value) in &fields {
+                match value {
+                    FormValue::Text(text) => {
+                        multipart.add_text(key.as_str(), text.as_str());
+                    }
+                    FormValue::File(file) => {
+                        let data = if with_data { &file.data[..] } else { &[] };
+                        multipart.add_stream(
+                            key.as_str(),
+                            data,
+                            Some(file.name.as_str()),
+                            Some(file.mime.parse().unwrap()),
+                        );
+                    }
+                }
             }
-        }
+            multipart
+        };
 
-        let prepared = multipart.prepare().map_err(Into::<io::Error>::into)?;
-        let response = ureq::post(&format!("{}{}", self.base_url, Method::name()))
+        let url = options.url_with_query(&format!("{}{}", self.base_url, Method::name()));
+        let data_len: usize = fields
+            .iter()
+            .filter_map(|(_, value)| value.as_file())
+            .map(|file| file.data.len())
+            .sum();
+        // Files are replaced with their names and sizes to keep the capture small.
+        let summary = || {
+            let summary: serde_json::Map<String, serde_json::Value> = fields
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        FormValue::Text(text) => text.clone(),
+                        FormValue::File(file) => {
+                            format!("<{}, {} bytes>", file.name, file.data.len())
+                        }
+                    };
+                    (key.clone(), value.into())
+                })
+                .collect();
+            serde_json::Value::Object(summary).to_string()
+        };
+        self.retrying(|| self.send_multipart(&url, options, &multipart, data_len, &summary))
+    }
+
+    fn send_multipart<'a, T: DeserializeOwned>(
+        &self,
+        url: &str,
+        options: &RequestOptions,
+        multipart: &impl Fn(bool) -> Multipart<'a, 'a>,
+        data_len: usize,
+        summary: &dyn Fn() -> String,
+    ) -> Result<T> {
+        let capture = self.capture(url, Some(("multipart/form-data", summary)));
+        // Streams are not sized, so the length of the body is computed from the body without file data,
+        // which differs only in the file data since boundaries have the same length.
+        let mut without_data = multipart(false)
+            .prepare()
+            .map_err(Into::<io::Error>::into)?;
+        let content_length = io::copy(&mut without_data, &mut io::sink())? + data_len as u64;
+        let prepared = multipart(true).prepare().map_err(Into::<io::Error>::into)?;
+        let response = self
+            .request("POST", url, options)
             .set(
                 "Content-Type",
                 &format!("multipart/form-data; boundary={}", prepared.boundary()),
             )
+            .set("Content-Length", &content_length.to_string())
             .send(prepared);
-        Self::parse_response::<Method>(response)
+        Self::parse_response(response, capture)
+    }
+
+    /// Gets the bot itself, sending [`GetMe`] only the first time.
+    ///
+    /// The bot is cached in this requester and its clones,
+    /// e.g. to ignore commands addressed to other bots with its username.
+    pub fn me(&self) -> Result<User> {
+        let mut me = self.me.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(user) = &*me {
+            return Ok(user.clone());
+        }
+        let user = self.send_json(&GetMe)?;
+        *me = Some(user.clone());
+        Ok(user)
+    }
+
+    /// Checks if the user is an administrator of the chat,
+    /// fetching the administrators only if they are not cached or expired.
+    pub fn is_chat_admin(
+        &self,
+        cache: &mut AdminCache,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<bool> {
+        if let Some(is_admin) = cache.is_admin(chat_id, user_id) {
+            return Ok(is_admin);
+        }
+        let admins = self.send_json(&GetChatAdministrators::new(chat_id))?;
+        let is_admin = admins.iter().any(|admin| admin.user().id == user_id);
+        cache.insert(chat_id, admins);
+        Ok(is_admin)
     }
 
-    fn parse_response<Method: TelegramMethod>(
+    /// Unpins the known pinned messages of the chat matching the predicate, returning the number of unpinned messages.
+    ///
+    /// The most recent pinned message is fetched with [`GetChat`] first, so that it is known to the tracker.
+    /// Messages which turn out to be no longer pinned are forgotten without failing.
+    pub fn unpin_messages_where(
+        &self,
+        tracker: &mut PinTracker,
+        chat_id: i64,
+        predicate: impl FnMut(&PinnedMessage) -> bool,
+    ) -> Result<usize> {
+        tracker.record_chat(&self.send_json(&GetChat::new(chat_id))?);
+        let mut unpinned = 0;
+        for request in tracker.unpin_where(chat_id, predicate) {
+            match self.send_json(&request) {
+                Ok(_) => unpinned += 1,
+                Err(Error::TelegramError(error))
+                    if error.kind() == ApiErrorKind::MessageToUnpinNotFound => {}
+                Err(error) => return Err(error),
+            }
+            if let Some(message_id) = request.message_id {
+                tracker.record_unpinned(chat_id, message_id);
+            }
+        }
+        Ok(unpinned)
+    }
+
+    /// Download the small (160x160) chat photo.
+    pub fn download_chat_photo_small(&self, photo: &ChatPhoto) -> Result<Vec<u8>> {
+        self.download(&photo.get_small())
+    }
+
+    /// Download the big (640x640) chat photo.
+    pub fn download_chat_photo_big(&self, photo: &ChatPhoto) -> Result<Vec<u8>> {
+        self.download(&photo.get_big())
+    }
+
+    /// Downloads the file.
+    ///
+    /// If the path of the file is unknown, e.g. the file is from a message, it is requested with [`GetFile`] first.
+    /// Bots can download files of up to 20 MB in size.
+    pub fn download_file(&self, file: &File) -> Result<Vec<u8>> {
+        let url = self.file_url(file)?;
+        let capture = self.capture(&url, None);
+        let (status, data) = Self::read_response(
+            self.request("GET", &url, &RequestOptions::default()).call(),
+            capture,
+        )?;
+        if status >= 400 {
+            return Err(Error::TelegramError(serde_json::from_slice(&data)?));
+        }
+        Ok(data)
+    }
+
+    /// Downloads the file into the writer without keeping the whole file in memory,
+    /// returning the number of bytes written.
+    ///
+    /// The contents are not captured in HAR.
+    pub fn download_file_to(&self, file: &File, writer: &mut impl Write) -> Result<u64> {
+        let url = self.file_url(file)?;
+        let capture = self.capture(&url, None);
+        match self.request("GET", &url, &RequestOptions::default()).call() {
+            Ok(response) => {
+                let status_text = response.status_text().to_string();
+                let content_type = response.content_type().to_string();
+                Self::finish_capture(capture, response.status(), &status_text, &content_type, &[]);
+                Ok(io::copy(&mut response.into_reader(), writer)?)
+            }
+            Err(error) => {
+                let (_, data) = Self::read_response(Err(error), capture)?;
+                Err(Error::TelegramError(serde_json::from_slice(&data)?))
+            }
+        }
+    }
+
+    /// Downloads the file and verifies the content, e.g. to detect truncated downloads.
+    pub fn download_file_checked(&self, file: &File, check: &DownloadCheck) -> Result<Vec<u8>> {
+        let data = self.download_file(file)?;
+        check.verify(file, &data).map_err(Error::Integrity)?;
+        Ok(data)
+    }
+
+    fn download(&self, get_file: &GetFile) -> Result<Vec<u8>> {
+        let file = self.send_json(get_file)?;
+        self.download_file(&file)
+    }
+
+    fn file_url(&self, file: &File) -> Result<String> {
+        let file_path = match &file.file_path {
+            Some(file_path) => file_path.clone(),
+            None => self
+                .send_json(&GetFile::new(&file.file_id))?
+                .file_path
+                .ok_or_else(|| {
+                    Error::TelegramError(TelegramError {
+                        error_code: 0,
+                        description: "File is not available for download".to_string(),
+                        parameters: None,
+                    })
+                })?,
+        };
+        Ok(format!("{}{}", self.file_base_url, file_path))
+    }
+
+    fn read_response(
         response: std::result::Result<Response, ureq::Error>,
-    ) -> Result<Method::Response> {
+        capture: Capture,
+    ) -> Result<(u16, Vec<u8>)> {
         let response = match response {
             Ok(response) => response,
             Err(ureq::Error::Status(_, response)) => response,
-            Err(ureq::Error::Transport(e)) => return Err(Error::Ureq(e)),
+            Err(ureq::Error::Transport(e)) => {
+                // Only the kind is recorded, since the message contains the URL with the token.
+                Self::finish_capture(capture, 0, &e.kind().to_string(), "", &[]);
+                return Err(Error::Ureq(e));
+            }
         };
 
-        let tg_response: ApiResponse<_> = response.into_json()?;
+        let status = response.status();
+        let status_text = response.status_text().to_string();
+        let content_type = response.content_type().to_string();
+        let mut body = vec![];
+        response.into_reader().read_to_end(&mut body)?;
+        Self::finish_capture(capture, status, &status_text, &content_type, &body);
+        Ok((status, body))
+    }
+
+    fn parse_response<T: DeserializeOwned>(
+        response: std::result::Result<Response, ureq::Error>,
+        capture: Capture,
+    ) -> Result<T> {
+        let (_, body) = Self::read_response(response, capture)?;
+        let tg_response: ApiResponse<_> = from_json_body(body)?;
         match tg_response {
             ApiResponse::Ok { result } => Ok(result),
             ApiResponse::Err(error) => Err(Error::TelegramError(error)),
         }
     }
 }
+
+/// Lets bot logic written against [`TelegramClient`] send requests with this requester.
+///
+/// The returned futures are ready, since requests are sent before they are returned.
+/// With the `rate-limit` feature, the requester can be throttled by wrapping it
+/// with [`RateLimitedApi`](telbot_types::rate_limit::RateLimitedApi).
+impl TelegramClient for Api {
+    type Error = Error;
+
+    fn execute_json<M: JsonMethod>(&self, method: &M) -> impl Future<Output = Result<M::Response>> {
+        ready(self.send_json(method))
+    }
+
+    fn execute_file<M: FileMethod>(&self, method: &M) -> impl Future<Output = Result<M::Response>> {
+        ready(self.send_file(method))
+    }
+}
+
+/// Checks if the request may succeed when sent again.
+fn is_transient(error: &ureq::Transport) -> bool {
+    matches!(
+        error.kind(),
+        ureq::ErrorKind::Dns | ureq::ErrorKind::ConnectionFailed | ureq::ErrorKind::Io
+    )
+}
+
+/// Deserializes JSON with simd-json, which rewrites escaped strings in the buffer,
+/// falling back to serde_json on failure if the buffer is left untouched.
+#[cfg(feature = "simd-json")]
+fn from_json_body<T: DeserializeOwned>(mut body: Vec<u8>) -> serde_json::Result<T> {
+    match simd_json::serde::from_slice(&mut body) {
+        Ok(value) => Ok(value),
+        Err(_) if !body.contains(&b'\\') => serde_json::from_slice(&body),
+        Err(e) => Err(serde::de::Error::custom(e)),
+    }
+}
+
+#[cfg(not(feature = "simd-json"))]
+fn from_json_body<T: DeserializeOwned>(body: Vec<u8>) -> serde_json::Result<T> {
+    serde_json::from_slice(&body)
+}
@@ -0,0 +1,90 @@
+//! Publishing of relayed updates to a [NATS](https://nats.io) server.
+//!
+//! [`NatsSink`] speaks the core NATS protocol over TCP, so it needs no client library.
+//! It connects in verbose mode, in which the server acknowledges each message with `+OK`,
+//! and an update is acknowledged to the [`Relay`](crate::relay::Relay) only after that.
+//!
+//! Other queues, such as AMQP brokers, need a client library and are not provided;
+//! implement [`UpdateSink`] with the client of your choice.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+use telbot_types::update::Update;
+
+use crate::relay::UpdateSink;
+
+/// Publishes updates to a subject of a NATS server.
+///
+/// Authentication and TLS are not supported; run the relay next to the server or through a tunnel.
+///
+/// ```no_run
+/// # use telbot_ureq::nats::NatsSink;
+/// # use telbot_ureq::relay::Relay;
+/// # use telbot_ureq::Api;
+/// # let api = Api::new("1234:TOKEN");
+/// let sink = NatsSink::connect("127.0.0.1:4222", "telegram.updates").unwrap();
+/// let error = Relay::new(&api, sink).run();
+/// ```
+pub struct NatsSink {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    subject: String,
+}
+
+impl NatsSink {
+    /// Connects to the server, publishing to the subject, like `telegram.updates`.
+    pub fn connect(address: impl ToSocketAddrs, subject: impl Into<String>) -> io::Result<Self> {
+        let writer = TcpStream::connect(address)?;
+        let mut sink = Self {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+            subject: subject.into(),
+        };
+        let info = sink.read_line()?;
+        if !info.starts_with("INFO ") {
+            return Err(protocol_error(&info));
+        }
+        sink.writer
+            .write_all(b"CONNECT {\"verbose\":true,\"pedantic\":false}\r\n")?;
+        sink.wait_ok()?;
+        Ok(sink)
+    }
+
+    fn read_line(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(line.trim_end().to_string())
+    }
+
+    /// Waits for the acknowledgement of the last operation, answering pings in between.
+    fn wait_ok(&mut self) -> io::Result<()> {
+        loop {
+            let line = self.read_line()?;
+            match line.as_str() {
+                "+OK" => return Ok(()),
+                "PING" => self.writer.write_all(b"PONG\r\n")?,
+                line if line.starts_with("INFO ") || line == "PONG" => {}
+                line => return Err(protocol_error(line)),
+            }
+        }
+    }
+}
+
+impl UpdateSink for NatsSink {
+    type Error = io::Error;
+
+    fn publish(&mut self, _: &Update, payload: &[u8]) -> io::Result<()> {
+        let mut message = format!("PUB {} {}\r\n", self.subject, payload.len()).into_bytes();
+        message.extend_from_slice(payload);
+        message.extend_from_slice(b"\r\n");
+        self.writer.write_all(&message)?;
+        self.wait_ok()
+    }
+}
+
+fn protocol_error(line: &str) -> io::Error {
+    io::Error::other(format!("unexpected reply from NATS server: {}", line))
+}
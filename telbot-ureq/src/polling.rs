@@ -1,12 +1,68 @@
-use telbot_types::update::{GetUpdates, Update};
+use std::collections::VecDeque;
 
-use crate::{Api, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use telbot_types::update::{AllowedUpdate, GetUpdates, Update};
+use telbot_types::webhook::GetWebhookInfo;
 
+use crate::{Api, Error, Result};
+
+/// Receives updates with long polling.
+///
+/// Updates are yielded in ascending order of [`Update::update_id`],
+/// and each update is yielded at most once during the lifetime of the poller.
+/// Telegram keeps unconfirmed updates only for 24 hours,
+/// so updates may be lost if the poller is not running for a long time.
+/// Use [`Polling::on_gap`] to get notified when such loss is detected.
+///
+/// Each update is deserialized separately;
+/// an update failing to deserialize is yielded as an error and skipped afterwards.
+///
+/// Telegram remembers the list of allowed update types from the last request which specified it,
+/// so a bot may silently miss updates such as `chat_member` after its configuration changes.
+/// Use [`Polling::with_allowed_updates`] to send the list on startup,
+/// and [`Polling::effective_allowed_updates`] to check the setting in effect.
+///
+/// Messages sent by the bot itself, or sent via the bot in inline mode, are skipped
+/// so that echo-style bots do not reply to themselves in a loop.
+/// Use [`Polling::with_own_messages`] to receive them.
 pub struct Polling<'a> {
     api: &'a Api,
     offset: u32,
     timeout: u32,
-    queue: Vec<Update>,
+    allowed_updates: Option<Vec<AllowedUpdate>>,
+    queue: VecDeque<Value>,
+    last_update_id: Option<u32>,
+    gap_handler: Option<Box<dyn FnMut(UpdateGap) + 'a>>,
+    raw_handler: Option<RawUpdateHandler<'a>>,
+    skip_own_messages: bool,
+}
+
+type RawUpdateHandler<'a> =
+    Box<dyn FnMut(&Value, std::result::Result<&Update, &serde_json::Error>) + 'a>;
+
+/// A gap between two consecutive update identifiers.
+///
+/// Update identifiers increase sequentially,
+/// so a gap means that some updates were dropped before being delivered,
+/// e.g. after the bot was down for more than 24 hours.
+///
+/// Note that if there are no new updates for at least a week,
+/// the identifier of the next update is chosen randomly.
+/// It is reported as a gap only if it happens to be greater than the last identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateGap {
+    /// Identifier of the last update received before the gap.
+    pub last_update_id: u32,
+    /// Identifier of the first update received after the gap.
+    pub next_update_id: u32,
+}
+
+impl UpdateGap {
+    /// Gets the number of updates that were not received.
+    pub fn missed(&self) -> u32 {
+        self.next_update_id - self.last_update_id - 1
+    }
 }
 
 impl<'a> Polling<'a> {
@@ -18,33 +74,154 @@ impl<'a> Polling<'a> {
             api,
             offset: 0,
             timeout: DEFAULT_TIMEOUT,
-            queue: vec![],
+            allowed_updates: None,
+            queue: VecDeque::new(),
+            last_update_id: None,
+            gap_handler: None,
+            raw_handler: None,
+            skip_own_messages: true,
+        }
+    }
+
+    /// Create a new Polling object resuming after the last update handled before a restart,
+    /// e.g. one saved in a database.
+    ///
+    /// Updates up to `last_update_id` are confirmed with the first request and never yielded,
+    /// and a gap between it and the first update received is reported to [`Polling::on_gap`].
+    pub fn resume(api: &'a Api, last_update_id: u32) -> Self {
+        Self {
+            offset: last_update_id.saturating_add(1),
+            last_update_id: Some(last_update_id),
+            ..Self::new(api)
+        }
+    }
+
+    /// Sets the list of update types to receive, such as [`AllowedUpdate::Message`] or `"chat_member"`.
+    ///
+    /// The list is sent with the first request, overriding the setting remembered by Telegram.
+    /// An empty list means all update types except `chat_member`.
+    pub fn with_allowed_updates<T: Into<AllowedUpdate>>(
+        self,
+        updates: impl IntoIterator<Item = T>,
+    ) -> Self {
+        Self {
+            allowed_updates: Some(updates.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    /// Gets the list of update types the bot is currently subscribed to, as reported by Telegram.
+    ///
+    /// Returns `None` if the default setting, all update types except `chat_member`, is in effect.
+    pub fn effective_allowed_updates(&self) -> Result<Option<Vec<AllowedUpdate>>> {
+        let info = self.api.send_json(&GetWebhookInfo)?;
+        Ok(info.allowed_updates.filter(|updates| !updates.is_empty()))
+    }
+
+    /// Yields messages sent by the bot itself, or sent via the bot in inline mode, which are skipped by default.
+    pub fn with_own_messages(self) -> Self {
+        Self {
+            skip_own_messages: false,
+            ..self
+        }
+    }
+
+    /// Sets a handler which is called when a gap in update identifiers is detected.
+    ///
+    /// The handler is called before the first update after the gap is yielded,
+    /// so the bot can run its reconciliation logic before processing it.
+    pub fn on_gap(self, handler: impl FnMut(UpdateGap) + 'a) -> Self {
+        Self {
+            gap_handler: Some(Box::new(handler)),
+            ..self
+        }
+    }
+
+    /// Sets a handler which is called with the raw JSON of every update and its deserialization result.
+    ///
+    /// Useful for capturing payloads that fail to deserialize, e.g. to attach them to bug reports.
+    pub fn on_raw_update(
+        self,
+        handler: impl FnMut(&Value, std::result::Result<&Update, &serde_json::Error>) + 'a,
+    ) -> Self {
+        Self {
+            raw_handler: Some(Box::new(handler)),
+            ..self
         }
     }
 }
 
-impl Iterator for Polling<'_> {
-    type Item = Result<Update>;
+fn update_id(update: &Value) -> u32 {
+    update["update_id"].as_u64().unwrap_or_default() as u32
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
+impl Polling<'_> {
+    fn is_own_message(&self, update: &Update) -> bool {
+        match (self.api.bot_id(), update.kind.any_message()) {
+            (Some(bot_id), Some(message)) => self.skip_own_messages && message.is_sent_by(bot_id),
+            _ => false,
+        }
+    }
+
+    fn next_update(&mut self) -> Option<Result<Update>> {
         while self.queue.is_empty() {
-            let updates = self.api.send_json(
-                &GetUpdates::new()
-                    .with_offset(self.offset as i32)
-                    .with_timeout(self.timeout),
-            );
+            let mut get_updates = GetUpdates::new()
+                .with_offset(self.offset as i32)
+                .with_timeout(self.timeout);
+            if let Some(allowed_updates) = &self.allowed_updates {
+                get_updates = get_updates.with_allowed_updates(allowed_updates.clone());
+            }
+            let updates = self.api.send_json_raw(&get_updates);
+            let updates = match updates {
+                Ok(updates) => Vec::<Value>::deserialize(updates).map_err(Error::Serde),
+                Err(e) => Err(e),
+            };
             match updates {
-                Ok(update) => {
-                    self.queue = update;
-                    self.offset = self
-                        .queue
+                Ok(mut updates) => {
+                    // Telegram remembers the list once a request succeeds.
+                    self.allowed_updates = None;
+                    updates.sort_by_key(update_id);
+                    self.offset = updates
                         .iter()
-                        .map(|update| update.update_id + 1)
+                        .map(|update| update_id(update) + 1)
                         .fold(self.offset, std::cmp::max);
+                    self.queue = updates.into();
                 }
                 Err(e) => return Some(Result::Err(e)),
             }
         }
-        self.queue.pop().map(Result::Ok)
+
+        let raw = self.queue.pop_front()?;
+        let update_id = update_id(&raw);
+        if let Some(last_update_id) = self.last_update_id {
+            if update_id > last_update_id + 1 {
+                if let Some(handler) = &mut self.gap_handler {
+                    handler(UpdateGap {
+                        last_update_id,
+                        next_update_id: update_id,
+                    });
+                }
+            }
+        }
+        self.last_update_id = Some(update_id);
+
+        let update = Update::deserialize(&raw);
+        if let Some(handler) = &mut self.raw_handler {
+            handler(&raw, update.as_ref());
+        }
+        Some(update.map_err(Error::Serde))
+    }
+}
+
+impl Iterator for Polling<'_> {
+    type Item = Result<Update>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_update()? {
+                Ok(update) if self.is_own_message(&update) => continue,
+                update => return Some(update),
+            }
+        }
     }
 }
@@ -0,0 +1,158 @@
+use std::time::{Duration, Instant};
+
+use telbot_types::chat::ChatId;
+use telbot_types::message::{EditMessageText, SendMessage};
+use telbot_types::ApiErrorKind;
+
+use crate::{Api, Error, Result};
+
+/// A message showing the progress of a long task, edited as the task proceeds.
+///
+/// Edits are throttled to avoid hitting rate limits, so updates arriving too early are skipped.
+/// Unchanged text, flood waits, and deletion of the message by the user are handled internally.
+///
+/// ```no_run
+/// # use telbot_ureq::Api;
+/// # use telbot_ureq::progress::ProgressMessage;
+/// # fn main() -> telbot_ureq::Result<()> {
+/// # let api = Api::new("TOKEN");
+/// let mut progress = ProgressMessage::send(&api, 1234, "Downloading...")?;
+/// for percent in 0..=100 {
+///     // do some work
+///     progress.update_percent("Downloading...", percent)?;
+/// }
+/// progress.finish("Downloaded!")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ProgressMessage<'a> {
+    api: &'a Api,
+    chat_id: i64,
+    message_id: i64,
+    text: String,
+    interval: Duration,
+    next_edit: Instant,
+    flood_until: Option<Instant>,
+    is_deleted: bool,
+}
+
+enum EditResult {
+    Edited,
+    Flooded,
+    Deleted,
+}
+
+impl<'a> ProgressMessage<'a> {
+    /// Sends a new progress message with the initial text.
+    ///
+    /// The message is edited at most once every 3 seconds by default.
+    pub fn send(api: &'a Api, chat_id: impl Into<ChatId>, text: impl Into<String>) -> Result<Self> {
+        const DEFAULT_INTERVAL: Duration = Duration::from_secs(3);
+
+        let text = text.into();
+        let message = api.send_json(&SendMessage::new(chat_id, text.clone()))?;
+        Ok(Self {
+            api,
+            chat_id: message.chat.id,
+            message_id: message.message_id,
+            text,
+            interval: DEFAULT_INTERVAL,
+            next_edit: Instant::now() + DEFAULT_INTERVAL,
+            flood_until: None,
+            is_deleted: false,
+        })
+    }
+
+    /// Sets the minimum interval between edits.
+    pub fn with_interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// Gets the identifier of the message.
+    pub fn message_id(&self) -> i64 {
+        self.message_id
+    }
+
+    /// `true` if the user deleted the message.
+    ///
+    /// Further updates are ignored after the deletion.
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    /// Edits the message with the text, unless the last edit was too recent.
+    ///
+    /// Returns `true` if the message now shows the text.
+    pub fn update(&mut self, text: impl Into<String>) -> Result<bool> {
+        let now = Instant::now();
+        let is_flooded = matches!(self.flood_until, Some(until) if now < until);
+        if self.is_deleted || is_flooded || now < self.next_edit {
+            return Ok(false);
+        }
+        Ok(matches!(self.edit(text.into())?, EditResult::Edited))
+    }
+
+    /// Edits the message with the label and a progress bar of the percentage.
+    pub fn update_percent(&mut self, label: &str, percent: u32) -> Result<bool> {
+        let percent = percent.min(100);
+        let filled = (percent / 10) as usize;
+        self.update(format!(
+            "{}\n{}{} {}%",
+            label,
+            "█".repeat(filled),
+            "░".repeat(10 - filled),
+            percent
+        ))
+    }
+
+    /// Edits the message with the final text, waiting for flood waits if needed.
+    ///
+    /// If the user deleted the message, the text is sent as a new message.
+    pub fn finish(mut self, text: impl Into<String>) -> Result<()> {
+        let text = text.into();
+        loop {
+            if self.is_deleted {
+                self.api.send_json(&SendMessage::new(self.chat_id, text))?;
+                return Ok(());
+            }
+            if let Some(until) = self.flood_until.take() {
+                let now = Instant::now();
+                if now < until {
+                    std::thread::sleep(until - now);
+                }
+            }
+            if let EditResult::Edited = self.edit(text.clone())? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn edit(&mut self, text: String) -> Result<EditResult> {
+        if text == self.text {
+            return Ok(EditResult::Edited);
+        }
+        let now = Instant::now();
+        let edit = EditMessageText::new(self.chat_id, self.message_id, text.clone());
+        let kind = match self.api.send_json(&edit) {
+            Ok(_) => None,
+            Err(Error::TelegramError(error)) => Some((error.kind(), error)),
+            Err(error) => return Err(error),
+        };
+        match kind {
+            None | Some((ApiErrorKind::MessageNotModified, _)) => {
+                self.text = text;
+                self.next_edit = now + self.interval;
+                Ok(EditResult::Edited)
+            }
+            Some((ApiErrorKind::TooManyRequests, error)) => {
+                self.flood_until = Some(now + error.retry_after().unwrap_or(self.interval));
+                Ok(EditResult::Flooded)
+            }
+            Some((ApiErrorKind::MessageToEditNotFound, _)) => {
+                self.is_deleted = true;
+                Ok(EditResult::Deleted)
+            }
+            Some((_, error)) => Err(Error::TelegramError(error)),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use serde::Deserialize;
+use serde_json::Value;
+use telbot_types::update::{GetUpdates, Update};
+
+use crate::{Api, Error};
+
+/// A destination for serialized updates, such as a message queue.
+pub trait UpdateSink {
+    type Error;
+
+    /// Publishes an update with its JSON as received from Telegram.
+    ///
+    /// Returning `Ok` acknowledges the update,
+    /// which is then confirmed to Telegram and never relayed again.
+    fn publish(&mut self, update: &Update, payload: &[u8]) -> Result<(), Self::Error>;
+}
+
+impl<F, E> UpdateSink for F
+where
+    F: FnMut(&Update, &[u8]) -> Result<(), E>,
+{
+    type Error = E;
+
+    fn publish(&mut self, update: &Update, payload: &[u8]) -> Result<(), Self::Error> {
+        self(update, payload)
+    }
+}
+
+/// An error occurred while relaying updates.
+#[derive(Debug)]
+pub enum RelayError<E> {
+    /// Failed to receive updates.
+    Api(Error),
+    /// The sink rejected an update.
+    Sink(E),
+}
+
+fn update_id(update: &Value) -> u32 {
+    update["update_id"].as_u64().unwrap_or_default() as u32
+}
+
+/// Receives updates with long polling and publishes them to an [`UpdateSink`].
+///
+/// An update is confirmed to Telegram only after the sink has acknowledged it,
+/// so rejected updates are received again on the next poll (at-least-once delivery).
+///
+/// Updates failing to deserialize are not published, but skipped so that they do not block the others.
+/// Use [`Relay::on_malformed`] to capture them.
+pub struct Relay<'a, S> {
+    api: &'a Api,
+    sink: S,
+    offset: u32,
+    timeout: u32,
+    malformed_handler: Option<MalformedUpdateHandler<'a>>,
+}
+
+type MalformedUpdateHandler<'a> = Box<dyn FnMut(&Value, &serde_json::Error) + 'a>;
+
+impl<'a, S: UpdateSink> Relay<'a, S> {
+    /// Create a new Relay object with default timeout 1s.
+    pub fn new(api: &'a Api, sink: S) -> Self {
+        const DEFAULT_TIMEOUT: u32 = 1;
+
+        Self {
+            api,
+            sink,
+            offset: 0,
+            timeout: DEFAULT_TIMEOUT,
+            malformed_handler: None,
+        }
+    }
+
+    /// Sets long polling timeout in seconds.
+    pub fn with_timeout(self, timeout: u32) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Sets a handler which is called with the raw JSON of every update failing to deserialize,
+    /// before the update is skipped.
+    pub fn on_malformed(self, handler: impl FnMut(&Value, &serde_json::Error) + 'a) -> Self {
+        Self {
+            malformed_handler: Some(Box::new(handler)),
+            ..self
+        }
+    }
+
+    /// Polls once and publishes received updates in order.
+    ///
+    /// Returns the number of acknowledged updates, not counting skipped ones.
+    /// Publishing stops at the first update rejected by the sink.
+    pub fn relay_once(&mut self) -> Result<usize, RelayError<S::Error>> {
+        let updates = self
+            .api
+            .send_json_raw(
+                &GetUpdates::new()
+                    .with_offset(self.offset as i32)
+                    .with_timeout(self.timeout),
+            )
+            .map_err(RelayError::Api)?;
+        let mut updates =
+            Vec::<Value>::deserialize(updates).map_err(|e| RelayError::Api(Error::Serde(e)))?;
+        updates.sort_by_key(update_id);
+
+        let mut acknowledged = 0;
+        for raw in updates {
+            match Update::deserialize(&raw) {
+                Ok(update) => {
+                    let payload =
+                        serde_json::to_vec(&raw).map_err(|e| RelayError::Api(Error::Serde(e)))?;
+                    self.sink
+                        .publish(&update, &payload)
+                        .map_err(RelayError::Sink)?;
+                    acknowledged += 1;
+                }
+                Err(e) => {
+                    if let Some(handler) = &mut self.malformed_handler {
+                        handler(&raw, &e);
+                    }
+                }
+            }
+            self.offset = self.offset.max(update_id(&raw) + 1);
+        }
+        Ok(acknowledged)
+    }
+
+    /// Relays updates until an error occurs.
+    pub fn run(&mut self) -> RelayError<S::Error> {
+        loop {
+            if let Err(e) = self.relay_once() {
+                return e;
+            }
+        }
+    }
+}
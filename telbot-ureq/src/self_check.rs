@@ -0,0 +1,232 @@
+//! Diagnostics to run once on deployment.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use telbot_types::bot::{GetMe, GetMyCommands};
+use telbot_types::user::User;
+use telbot_types::webhook::{GetWebhookInfo, WebhookInfo};
+use telbot_types::{RequestOptions, TelegramMethod};
+
+use crate::{Api, Error, Result};
+
+/// Maximum difference between the local clock and the server clock regarded as healthy.
+///
+/// Timestamps signed by Telegram, e.g. `auth_date` of WebApp data, are usually checked against a freshness window,
+/// so a large clock skew makes valid data look expired or not-yet-valid.
+pub const MAX_CLOCK_SKEW: i64 = 30;
+
+/// How the bot receives updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateMode {
+    /// Updates are fetched with [`GetUpdates`](telbot_types::update::GetUpdates).
+    Polling,
+    /// Updates are sent to the URL set with [`SetWebhook`](telbot_types::webhook::SetWebhook).
+    Webhook,
+}
+
+/// Result of [`Api::self_check`].
+#[derive(Debug)]
+pub struct SelfCheckReport {
+    /// The bot itself, returned by [`GetMe`].
+    pub bot: Result<User>,
+    /// Round-trip time of [`GetMe`].
+    pub latency: Duration,
+    /// Seconds the server clock is ahead of the local clock, negative if behind.
+    ///
+    /// `None` if the server did not respond with a `Date` header.
+    pub clock_skew: Option<i64>,
+    /// Current webhook status, returned by [`GetWebhookInfo`].
+    pub webhook_info: Result<WebhookInfo>,
+    /// Result of calling [`GetMyCommands`], which has no side effects.
+    pub method_call: Result<()>,
+}
+
+/// A problem found by [`Api::self_check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelfCheckProblem {
+    /// The token was rejected by Telegram.
+    InvalidToken,
+    /// Telegram could not be reached, e.g. because of DNS or firewall issues.
+    Unreachable,
+    /// A method call failed.
+    MethodFailed {
+        method: &'static str,
+        description: String,
+    },
+    /// A webhook is set but polling is expected, or vice versa.
+    ModeMismatch {
+        expected: UpdateMode,
+        actual: UpdateMode,
+    },
+    /// The most recent delivery to the webhook failed.
+    WebhookDeliveryFailed {
+        /// Unix time of the failure.
+        date: u64,
+        message: String,
+    },
+    /// The local clock differs from the server clock by more than [`MAX_CLOCK_SKEW`] seconds.
+    ClockSkew(i64),
+}
+
+impl SelfCheckReport {
+    /// Gets how the bot currently receives updates, if the webhook status is known.
+    pub fn mode(&self) -> Option<UpdateMode> {
+        match &self.webhook_info {
+            Ok(info) if info.url.is_empty() => Some(UpdateMode::Polling),
+            Ok(_) => Some(UpdateMode::Webhook),
+            Err(_) => None,
+        }
+    }
+
+    /// Lists the problems found, given how the bot is expected to receive updates.
+    pub fn problems(&self, expected: UpdateMode) -> Vec<SelfCheckProblem> {
+        let mut problems = vec![];
+        let errors = [
+            (GetMe::name(), self.bot.as_ref().err()),
+            (GetWebhookInfo::name(), self.webhook_info.as_ref().err()),
+            (GetMyCommands::name(), self.method_call.as_ref().err()),
+        ];
+        for &(method, error) in errors.iter() {
+            let problem = match error {
+                None => continue,
+                Some(Error::TelegramError(error)) if is_token_rejected(error.error_code) => {
+                    SelfCheckProblem::InvalidToken
+                }
+                Some(Error::Ureq(_)) => SelfCheckProblem::Unreachable,
+                Some(error) => SelfCheckProblem::MethodFailed {
+                    method,
+                    description: describe(error),
+                },
+            };
+            // The same cause usually makes every method fail.
+            if !problems.contains(&problem) {
+                problems.push(problem);
+            }
+        }
+
+        if let Some(actual) = self.mode() {
+            if actual != expected {
+                problems.push(SelfCheckProblem::ModeMismatch { expected, actual });
+            }
+        }
+        if let Ok(info) = &self.webhook_info {
+            if let Some(message) = &info.last_error_message {
+                problems.push(SelfCheckProblem::WebhookDeliveryFailed {
+                    date: info.last_error_date,
+                    message: message.clone(),
+                });
+            }
+        }
+        if let Some(skew) = self.clock_skew {
+            if skew.abs() > MAX_CLOCK_SKEW {
+                problems.push(SelfCheckProblem::ClockSkew(skew));
+            }
+        }
+        problems
+    }
+
+    /// `true` if no problem is found.
+    pub fn is_healthy(&self, expected: UpdateMode) -> bool {
+        self.problems(expected).is_empty()
+    }
+}
+
+fn is_token_rejected(error_code: i64) -> bool {
+    // Telegram responds with 404 for malformed tokens and 401 for revoked ones.
+    error_code == 401 || error_code == 404
+}
+
+fn describe(error: &Error) -> String {
+    match error {
+        Error::TelegramError(error) => error.description.clone(),
+        Error::Ureq(error) => error.kind().to_string(),
+        Error::Serde(error) => error.to_string(),
+        Error::Io(error) => error.to_string(),
+        Error::Integrity(error) => error.to_string(),
+    }
+}
+
+impl Api {
+    /// Checks the token, the connectivity, the webhook status, and the clock skew.
+    ///
+    /// Intended to be run once on deployment, since it sends several requests.
+    ///
+    /// ```no_run
+    /// # use telbot_ureq::Api;
+    /// # use telbot_ureq::self_check::UpdateMode;
+    /// # let api = Api::new("TOKEN");
+    /// let report = api.self_check();
+    /// for problem in report.problems(UpdateMode::Polling) {
+    ///     eprintln!("{:?}", problem);
+    /// }
+    /// ```
+    pub fn self_check(&self) -> SelfCheckReport {
+        let started_at = Instant::now();
+        let sent_at = SystemTime::now();
+        let (bot, server_date) = self.get_me_with_date();
+        let latency = started_at.elapsed();
+        let clock_skew = server_date.map(|server_date| {
+            // The server time is compared with the midpoint of the round trip.
+            let local = sent_at + latency / 2;
+            let local = match local.duration_since(UNIX_EPOCH) {
+                Ok(since_epoch) => since_epoch.as_secs() as i64,
+                Err(before_epoch) => -(before_epoch.duration().as_secs() as i64),
+            };
+            server_date - local
+        });
+
+        SelfCheckReport {
+            bot,
+            latency,
+            clock_skew,
+            webhook_info: self.send_json(&GetWebhookInfo),
+            method_call: self.send_json(&GetMyCommands::new()).map(|_| ()),
+        }
+    }
+
+    /// Sends [`GetMe`], also returning the `Date` header as Unix time.
+    fn get_me_with_date(&self) -> (Result<User>, Option<i64>) {
+        let value = match serde_json::to_value(&GetMe) {
+            Ok(value) => value,
+            Err(error) => return (Err(error.into()), None),
+        };
+        let url = format!("{}{}", self.base_url, GetMe::name());
+        let capture = self.capture(&url, Some(("application/json", &|| value.to_string())));
+        let response = self
+            .request("POST", &url, &RequestOptions::default())
+            .send_json(value);
+        let date = match &response {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => {
+                response.header("Date").and_then(parse_http_date)
+            }
+            Err(ureq::Error::Transport(_)) => None,
+        };
+        (Self::parse_response(response, capture), date)
+    }
+}
+
+/// Parses a date like `Sun, 06 Nov 1994 08:49:37 GMT` into Unix time.
+fn parse_http_date(date: &str) -> Option<i64> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let mut parts = date.split_whitespace().skip(1);
+    let day: i64 = parts.next()?.parse().ok()?;
+    let month_name = parts.next()?;
+    let month = MONTHS.iter().position(|name| *name == month_name)? as i64 + 1;
+    let year: i64 = parts.next()?.parse().ok()?;
+    let mut time = parts.next()?.split(':');
+    let hour: i64 = time.next()?.parse().ok()?;
+    let minute: i64 = time.next()?.parse().ok()?;
+    let second: i64 = time.next()?.parse().ok()?;
+
+    // See http://howardhinnant.github.io/date_algorithms.html
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+    Some(days * 86400 + hour * 3600 + minute * 60 + second)
+}
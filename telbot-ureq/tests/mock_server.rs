@@ -0,0 +1,390 @@
+//! Checks the requests sent by the ureq backend against a local mock server.
+
+use std::thread::{self, JoinHandle};
+
+use serde_json::{json, Value};
+use telbot_ureq::polling::{Polling, UpdateGap};
+use telbot_ureq::relay::Relay;
+use telbot_ureq::types::file::{
+    DownloadCheck, File, InputFile, InputFileVariant, InputMedia, IntegrityError,
+};
+use telbot_ureq::types::markup::{MessageEntity, MessageEntityKind};
+use telbot_ureq::types::message::{
+    EditInlineMessageMedia, SendDocument, SendMediaGroup, SendMessage,
+};
+use telbot_ureq::{Api, Error};
+use tiny_http::{Header, Response, Server};
+
+const TOKEN: &str = "1234:TOKEN";
+
+const MESSAGE: &str = r#"{"ok":true,"result":{"message_id":1,"date":0,"chat":{"id":1,"type":"private"},"text":"hi"}}"#;
+
+const ME: &str =
+    r#"{"ok":true,"result":{"id":1234,"is_bot":true,"first_name":"Kiwi","username":"KiwiBot"}}"#;
+
+struct Captured {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Captured {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Serves a single request with the response, returning the API connected to the server.
+fn serve_once(status: u16, response: &'static str) -> (Api, JoinHandle<Captured>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let handle = thread::spawn(move || {
+        let mut request = server.recv().unwrap();
+        let mut body = vec![];
+        request.as_reader().read_to_end(&mut body).unwrap();
+        let captured = Captured {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers: request
+                .headers()
+                .iter()
+                .map(|header| (header.field.to_string(), header.value.to_string()))
+                .collect(),
+            body,
+        };
+        let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+        let response = Response::from_string(response)
+            .with_status_code(status)
+            .with_header(content_type);
+        request.respond(response).unwrap();
+        captured
+    });
+    (Api::new_with_server(TOKEN, &address), handle)
+}
+
+/// Splits a multipart body into pairs of part headers and contents.
+fn multipart_parts(body: &[u8], boundary: &str) -> Vec<(String, Vec<u8>)> {
+    let delimiter = format!("--{}", boundary);
+    let body = String::from_utf8_lossy(body);
+    body.split(delimiter.as_str())
+        .filter_map(|part| {
+            let part = part.strip_prefix("\r\n")?;
+            let (headers, content) = part.split_at(part.find("\r\n\r\n")?);
+            let content = content[4..].strip_suffix("\r\n").unwrap_or(&content[4..]);
+            Some((headers.to_string(), content.as_bytes().to_vec()))
+        })
+        .collect()
+}
+
+fn part<'a>(parts: &'a [(String, Vec<u8>)], name: &str) -> &'a (String, Vec<u8>) {
+    let disposition = format!("name=\"{}\"", name);
+    parts
+        .iter()
+        .find(|(headers, _)| headers.contains(&disposition))
+        .unwrap_or_else(|| panic!("no part named {}", name))
+}
+
+#[test]
+fn send_json_posts_json_body() {
+    let (api, server) = serve_once(200, MESSAGE);
+    let message = api.send_json(&SendMessage::new(1, "hi")).unwrap();
+    let request = server.join().unwrap();
+
+    assert_eq!(message.kind.text(), Some("hi"));
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.url, format!("/bot{}/sendMessage", TOKEN));
+    assert_eq!(request.header("Content-Type"), Some("application/json"));
+    let body: Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body, json!({"chat_id": 1, "text": "hi"}));
+}
+
+#[test]
+fn send_file_posts_multipart_body() {
+    let (api, server) = serve_once(200, MESSAGE);
+    let document = InputFile {
+        name: "report.txt".to_string(),
+        data: b"hello, world".to_vec(),
+        mime: "text/plain".to_string(),
+    };
+    let send = SendDocument::new(1, document).with_caption("*monthly* report");
+    api.send_file(&send).unwrap();
+    let request = server.join().unwrap();
+
+    assert_eq!(request.url, format!("/bot{}/sendDocument", TOKEN));
+    let content_type = request.header("Content-Type").unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap()
+        .trim_matches('"');
+    let content_length = request.header("Content-Length").unwrap();
+    assert_eq!(content_length, request.body.len().to_string());
+
+    let parts = multipart_parts(&request.body, boundary);
+    assert_eq!(part(&parts, "chat_id").1, b"1");
+    // Strings are sent as is, not as JSON strings with quotes.
+    assert_eq!(part(&parts, "caption").1, b"*monthly* report");
+    let (headers, data) = part(&parts, "document");
+    assert!(headers.contains("filename=\"report.txt\""));
+    assert!(headers.contains("text/plain"));
+    assert_eq!(data, b"hello, world");
+}
+
+#[test]
+fn structured_fields_are_sent_as_json() {
+    let (api, server) = serve_once(200, MESSAGE);
+    let document = InputFile {
+        name: "report.txt".to_string(),
+        data: b"hello, world".to_vec(),
+        mime: "text/plain".to_string(),
+    };
+    let send = SendDocument::new(1, document)
+        .with_caption("the \"monthly\" report")
+        .with_entity(MessageEntity {
+            kind: MessageEntityKind::Bold,
+            offset: 4,
+            length: 9,
+        });
+    api.send_file(&send).unwrap();
+    let request = server.join().unwrap();
+
+    let content_type = request.header("Content-Type").unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap()
+        .trim_matches('"');
+    let parts = multipart_parts(&request.body, boundary);
+    assert_eq!(part(&parts, "caption").1, b"the \"monthly\" report");
+    let entities: Value = serde_json::from_slice(&part(&parts, "caption_entities").1).unwrap();
+    assert_eq!(
+        entities,
+        json!([{"type": "bold", "offset": 4, "length": 9}])
+    );
+}
+
+#[test]
+fn media_group_attaches_new_files() {
+    let (api, server) = serve_once(200, r#"{"ok":true,"result":[]}"#);
+    let photo = |media: InputFileVariant| InputMedia::Photo {
+        media,
+        caption: None,
+        parse_mode: None,
+        caption_entities: None,
+        show_caption_above_media: None,
+    };
+    let upload = InputFile {
+        name: "cat.jpg".to_string(),
+        data: b"meow".to_vec(),
+        mime: "image/jpeg".to_string(),
+    };
+    let send = SendMediaGroup::new(1)
+        .with_media(photo("AgACAgIAAxkBAAIB".into()))
+        .with_media(photo(upload.into()));
+    api.send_file(&send).unwrap();
+    let request = server.join().unwrap();
+
+    let content_type = request.header("Content-Type").unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap()
+        .trim_matches('"');
+    let parts = multipart_parts(&request.body, boundary);
+    let media: Value = serde_json::from_slice(&part(&parts, "media").1).unwrap();
+    assert_eq!(
+        media,
+        json!([
+            {"type": "photo", "media": "AgACAgIAAxkBAAIB"},
+            {"type": "photo", "media": "attach://media1"},
+        ])
+    );
+    let (headers, data) = part(&parts, "media1");
+    assert!(headers.contains("filename=\"cat.jpg\""));
+    assert_eq!(data, b"meow");
+}
+
+#[test]
+fn edited_inline_media_attaches_new_files() {
+    let (api, server) = serve_once(200, r#"{"ok":true,"result":true}"#);
+    let upload = InputFile {
+        name: "cat.jpg".to_string(),
+        data: b"meow".to_vec(),
+        mime: "image/jpeg".to_string(),
+    };
+    let media = InputMedia::Photo {
+        media: upload.into(),
+        caption: None,
+        parse_mode: None,
+        caption_entities: None,
+        show_caption_above_media: None,
+    };
+    assert!(api
+        .send_file(&EditInlineMessageMedia::new("inline", media))
+        .unwrap());
+    let request = server.join().unwrap();
+
+    let content_type = request.header("Content-Type").unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .unwrap()
+        .trim_matches('"');
+    let parts = multipart_parts(&request.body, boundary);
+    let media: Value = serde_json::from_slice(&part(&parts, "media").1).unwrap();
+    assert_eq!(media, json!({"type": "photo", "media": "attach://media0"}));
+    assert_eq!(part(&parts, "media0").1, b"meow");
+}
+
+#[test]
+fn resumed_polling_confirms_handled_updates() {
+    let (api, server) = serve_once(
+        200,
+        r#"{"ok":true,"result":[{"update_id":13,"message":{"message_id":1,"date":0,"chat":{"id":1,"type":"private"},"text":"hi"}}]}"#,
+    );
+    let mut gaps = vec![];
+    let update = Polling::resume(&api, 10)
+        .on_gap(|gap| gaps.push(gap))
+        .next()
+        .unwrap()
+        .unwrap();
+    let request = server.join().unwrap();
+
+    assert_eq!(update.update_id, 13);
+    let body: Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["offset"], 11);
+    assert_eq!(
+        gaps,
+        [UpdateGap {
+            last_update_id: 10,
+            next_update_id: 13
+        }]
+    );
+}
+
+#[test]
+fn relay_skips_malformed_updates() {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let handle = thread::spawn(move || {
+        let responses = [
+            r#"{"ok":true,"result":[
+                {"update_id":5,"message":{"message_id":2,"date":0,"chat":{"id":1,"type":"private"},"text":"bye"}},
+                {"update_id":4,"message":{"message_id":"broken"}},
+                {"update_id":3,"message":{"message_id":1,"date":0,"chat":{"id":1,"type":"private"},"text":"hi","future_field":1}}
+            ]}"#,
+            r#"{"ok":true,"result":[]}"#,
+        ];
+        let mut bodies = vec![];
+        for response in responses {
+            let mut request = server.recv().unwrap();
+            let mut body = vec![];
+            request.as_reader().read_to_end(&mut body).unwrap();
+            bodies.push(serde_json::from_slice::<Value>(&body).unwrap());
+            let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+            request
+                .respond(Response::from_string(response).with_header(content_type))
+                .unwrap();
+        }
+        bodies
+    });
+
+    let api = Api::new_with_server(TOKEN, &address);
+    let mut payloads = vec![];
+    let mut malformed = vec![];
+    let mut relay = Relay::new(&api, |_: &_, payload: &[u8]| {
+        payloads.push(serde_json::from_slice::<Value>(payload).unwrap());
+        Ok::<_, ()>(())
+    })
+    .on_malformed(|raw, _| malformed.push(raw["update_id"].clone()));
+    assert_eq!(relay.relay_once().unwrap(), 2);
+    assert_eq!(relay.relay_once().unwrap(), 0);
+    drop(relay);
+    let bodies = handle.join().unwrap();
+
+    assert_eq!(payloads[0]["update_id"], 3);
+    assert_eq!(payloads[0]["message"]["future_field"], 1);
+    assert_eq!(payloads[1]["update_id"], 5);
+    assert_eq!(malformed, [4]);
+    assert_eq!(bodies[1]["offset"], 6);
+}
+
+#[test]
+fn telegram_error_is_mapped() {
+    let (api, server) = serve_once(
+        400,
+        r#"{"ok":false,"error_code":400,"description":"Bad Request: chat not found"}"#,
+    );
+    let result = api.send_json(&SendMessage::new(1, "hi"));
+    server.join().unwrap();
+
+    match result {
+        Err(Error::TelegramError(error)) => {
+            assert_eq!(error.error_code, 400);
+            assert_eq!(error.description, "Bad Request: chat not found");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn malformed_response_is_mapped() {
+    let (api, server) = serve_once(502, "<html>Bad Gateway</html>");
+    let result = api.send_json(&SendMessage::new(1, "hi"));
+    server.join().unwrap();
+
+    assert!(matches!(result, Err(Error::Serde(_))));
+}
+
+#[test]
+fn download_file_fetches_file_path() {
+    let (api, server) = serve_once(200, "file contents");
+    let file = File {
+        file_id: "id".to_string(),
+        file_unique_id: "unique".to_string(),
+        file_size: Some(13),
+        file_path: Some("documents/file_0.txt".to_string()),
+    };
+    let data = api.download_file(&file).unwrap();
+    let request = server.join().unwrap();
+
+    assert_eq!(data, b"file contents");
+    assert_eq!(request.method, "GET");
+    assert_eq!(
+        request.url,
+        format!("/file/bot{}/documents/file_0.txt", TOKEN)
+    );
+}
+
+#[test]
+fn truncated_download_is_detected() {
+    let (api, server) = serve_once(200, "file cont");
+    let file = File {
+        file_id: "id".to_string(),
+        file_unique_id: "unique".to_string(),
+        file_size: Some(13),
+        file_path: Some("documents/file_0.txt".to_string()),
+    };
+    let result = api.download_file_checked(&file, &DownloadCheck::new());
+    server.join().unwrap();
+
+    match result {
+        Err(Error::Integrity(IntegrityError::SizeMismatch { expected, actual })) => {
+            assert_eq!((expected, actual), (13, 9));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn me_is_fetched_once() {
+    let (api, server) = serve_once(200, ME);
+    let me = api.me().unwrap();
+    let request = server.join().unwrap();
+    // the server is gone, so another request would fail
+    let cached = api.clone().me().unwrap();
+
+    assert_eq!(request.url, format!("/bot{}/getMe", TOKEN));
+    assert_eq!(me.username.as_deref(), Some("KiwiBot"));
+    assert_eq!(cached.id, me.id);
+}
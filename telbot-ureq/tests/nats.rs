@@ -0,0 +1,62 @@
+//! Checks relaying updates to a fake NATS server.
+#![cfg(feature = "nats")]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use telbot_ureq::nats::NatsSink;
+use telbot_ureq::relay::Relay;
+use telbot_ureq::Api;
+use tiny_http::{Header, Response, Server};
+
+const UPDATES: &str = r#"{"ok":true,"result":[{"update_id":7,"message":{"message_id":1,"date":0,"chat":{"id":1,"type":"private"},"text":"hi"}}]}"#;
+
+#[test]
+fn relay_publishes_updates_to_nats() {
+    let telegram = Server::http("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", telegram.server_addr().to_ip().unwrap());
+    thread::spawn(move || {
+        let request = telegram.recv().unwrap();
+        let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+        request
+            .respond(Response::from_string(UPDATES).with_header(content_type))
+            .unwrap();
+    });
+
+    let nats = TcpListener::bind("127.0.0.1:0").unwrap();
+    let nats_address = nats.local_addr().unwrap();
+    let server = thread::spawn(move || {
+        let (mut stream, _) = nats.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        stream
+            .write_all(b"INFO {\"max_payload\":1048576}\r\n")
+            .unwrap();
+        reader.read_line(&mut line).unwrap();
+        assert!(line.starts_with("CONNECT {\"verbose\":true"));
+        stream.write_all(b"PING\r\n+OK\r\n").unwrap();
+
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "PONG\r\n");
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        let header: Vec<_> = line.split_whitespace().collect();
+        assert_eq!(header[..2], ["PUB", "telegram.updates"]);
+        let mut payload = vec![0; header[2].parse::<usize>().unwrap() + 2];
+        reader.read_exact(&mut payload).unwrap();
+        stream.write_all(b"+OK\r\n").unwrap();
+        payload.truncate(payload.len() - 2);
+        String::from_utf8(payload).unwrap()
+    });
+
+    let api = Api::new_with_server("1234:TOKEN", &address);
+    let sink = NatsSink::connect(nats_address, "telegram.updates").unwrap();
+    assert_eq!(Relay::new(&api, sink).relay_once().unwrap(), 1);
+
+    let payload = server.join().unwrap();
+    assert!(payload.contains(r#""update_id":7"#));
+    assert!(payload.contains(r#""text":"hi""#));
+    assert!(!payload.contains("null"));
+}